@@ -0,0 +1,5 @@
+-- Add scheduled publishing support to posts
+ALTER TABLE posts ADD COLUMN publish_at TIMESTAMPTZ;
+
+-- Index for the scheduled publishing sweep
+CREATE INDEX idx_posts_publish_at ON posts(publish_at) WHERE published = false AND publish_at IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Add soft-delete support to posts
+ALTER TABLE posts ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Most queries only look at posts that haven't been deleted
+CREATE INDEX idx_posts_deleted_at ON posts(deleted_at) WHERE deleted_at IS NULL;
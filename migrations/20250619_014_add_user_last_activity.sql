@@ -0,0 +1,2 @@
+-- Track authenticated activity for inactivity-based session timeouts
+ALTER TABLE users ADD COLUMN last_activity_at TIMESTAMPTZ;
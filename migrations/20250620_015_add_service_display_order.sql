@@ -0,0 +1,13 @@
+-- Let owners control the order services are shown in
+ALTER TABLE services ADD COLUMN display_order INTEGER NOT NULL DEFAULT 0;
+
+-- Keep the current newest-first order for existing services
+UPDATE services s
+SET display_order = ordered.position
+FROM (
+    SELECT id, ROW_NUMBER() OVER (ORDER BY created_at DESC) AS position
+    FROM services
+) ordered
+WHERE s.id = ordered.id;
+
+CREATE INDEX idx_services_display_order ON services(display_order);
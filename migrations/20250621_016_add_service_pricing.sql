@@ -0,0 +1,5 @@
+-- Optional pricing for service listings; amounts are stored in minor units (e.g. cents)
+ALTER TABLE services
+    ADD COLUMN price_amount BIGINT CHECK (price_amount >= 0),
+    ADD COLUMN price_currency VARCHAR(3),
+    ADD COLUMN price_type VARCHAR(20) CHECK (price_type IN ('fixed', 'hourly', 'starting_at'));
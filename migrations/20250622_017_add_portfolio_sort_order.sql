@@ -0,0 +1,13 @@
+-- Let owners curate the order portfolio projects are shown in
+ALTER TABLE portfolio_projects ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+
+-- Keep the current featured-then-newest order for existing projects
+UPDATE portfolio_projects p
+SET sort_order = ordered.position
+FROM (
+    SELECT id, ROW_NUMBER() OVER (ORDER BY featured DESC, created_at DESC) AS position
+    FROM portfolio_projects
+) ordered
+WHERE p.id = ordered.id;
+
+CREATE INDEX idx_portfolio_sort_order ON portfolio_projects(sort_order);
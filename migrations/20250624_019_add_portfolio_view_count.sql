@@ -0,0 +1,2 @@
+-- Track how often public project pages are viewed
+ALTER TABLE portfolio_projects ADD COLUMN view_count INTEGER NOT NULL DEFAULT 0;
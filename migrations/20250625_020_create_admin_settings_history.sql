@@ -0,0 +1,13 @@
+-- Previous values of admin settings, written on every update so changes can be rolled back
+CREATE TABLE admin_settings_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    setting_key VARCHAR(100) NOT NULL,
+    setting_value JSONB NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+CREATE INDEX idx_admin_settings_history_key_created
+    ON admin_settings_history (setting_key, created_at DESC);
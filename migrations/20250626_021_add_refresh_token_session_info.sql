@@ -0,0 +1,5 @@
+-- Each refresh token is a login session; record where it was issued and when it was last used
+ALTER TABLE refresh_tokens
+    ADD COLUMN ip_address VARCHAR(45),
+    ADD COLUMN user_agent TEXT,
+    ADD COLUMN last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
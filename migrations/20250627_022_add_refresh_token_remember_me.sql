@@ -0,0 +1,2 @@
+-- Remembered sessions keep their longer lifetime each time the refresh token is rotated
+ALTER TABLE refresh_tokens ADD COLUMN remember_me BOOLEAN NOT NULL DEFAULT FALSE;
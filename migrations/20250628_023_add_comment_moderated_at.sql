@@ -0,0 +1,4 @@
+-- When a moderator last changed the status, so automatic cleanup can leave those comments alone
+ALTER TABLE comments ADD COLUMN moderated_at TIMESTAMPTZ;
+
+UPDATE comments SET moderated_at = updated_at WHERE status <> 'pending';
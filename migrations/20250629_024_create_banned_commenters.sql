@@ -0,0 +1,14 @@
+-- Commenters blocked by email or IP; a NULL expires_at makes the ban permanent
+CREATE TABLE banned_commenters (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    email VARCHAR(255),
+    ip_address INET,
+    reason TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (email IS NOT NULL OR ip_address IS NOT NULL)
+);
+
+CREATE INDEX idx_banned_commenters_email ON banned_commenters(LOWER(email));
+CREATE INDEX idx_banned_commenters_ip_address ON banned_commenters(ip_address);
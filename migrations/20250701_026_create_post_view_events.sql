@@ -0,0 +1,10 @@
+-- One row per counted (deduplicated) post view, for daily trend charts.
+-- posts.view_count stays the cumulative total.
+CREATE TABLE post_view_events (
+    id BIGSERIAL PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    viewed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ip_hash VARCHAR(64)
+);
+
+CREATE INDEX idx_post_view_events_post_id_viewed_at ON post_view_events(post_id, viewed_at);
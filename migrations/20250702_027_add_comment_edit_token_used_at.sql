@@ -0,0 +1,2 @@
+-- Edit tokens are one-time: set when a commenter's edit goes through, so the token can't be replayed
+ALTER TABLE comments ADD COLUMN edit_token_used_at TIMESTAMPTZ;
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use clap::{Args, Parser, Subcommand};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::{
+    database::seeder::{DatabaseSeeder, SeedSummary},
+    models::user::{CreateUserRequest, User},
+    repositories::{user_repository::UserRepositoryTrait, AuditLogRepository, UserRepository},
+    services::audit_log_service::{AuditLogService, AuditLogServiceTrait},
+    utils::{
+        errors::{validation_error_summary, AppError},
+        password::{validate_strength, PasswordPolicy, PasswordService},
+    },
+};
+
+/// Portfolio backend server and admin operations
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Starts the server when left out
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create an admin user
+    CreateAdmin(CreateAdminArgs),
+    /// Seed sample data into empty tables
+    Seed(SeedArgs),
+    /// Delete audit logs older than the given number of days
+    CleanupLogs(CleanupLogsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateAdminArgs {
+    #[arg(long)]
+    pub username: String,
+    #[arg(long)]
+    pub email: String,
+    #[arg(long)]
+    pub full_name: String,
+    /// Read from ADMIN_PASSWORD so it stays out of shell history
+    #[arg(long, env = "ADMIN_PASSWORD", hide_env_values = true)]
+    pub password: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SeedArgs {
+    /// Log what would be inserted without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Delete all existing data first (requires SEED_FRESH_CONFIRM=yes)
+    #[arg(long)]
+    pub fresh: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CleanupLogsArgs {
+    /// Audit logs older than this are deleted (at least 30)
+    #[arg(long, value_parser = clap::value_parser!(i32).range(30..))]
+    pub days: i32,
+}
+
+/// Runs a single admin command against the database
+pub async fn run(command: Command, pool: &PgPool) -> Result<(), AppError> {
+    match command {
+        Command::CreateAdmin(args) => {
+            let user = create_admin(pool, args).await?;
+            println!("✅ Created admin {} ({})", user.username, user.id);
+        }
+        Command::Seed(args) => {
+            let dry_run = args.dry_run;
+            let summary = seed(pool, args).await?;
+            if dry_run {
+                println!(
+                    "🔍 Dry run, nothing was written. Would have seeded {:?}",
+                    summary
+                );
+            } else {
+                println!("✅ Seeding finished: {:?}", summary);
+            }
+        }
+        Command::CleanupLogs(args) => {
+            let deleted = cleanup_logs(pool, args).await?;
+            println!("🧹 Deleted {} audit logs", deleted);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn create_admin(pool: &PgPool, args: CreateAdminArgs) -> Result<User, AppError> {
+    let request = CreateUserRequest {
+        username: args.username,
+        email: args.email.trim().to_lowercase(),
+        password: args.password,
+        full_name: args.full_name,
+        phone: None,
+    };
+    request
+        .validate()
+        .map_err(|e| AppError::Validation(validation_error_summary(&e)))?;
+
+    // Admin accounts always get the strict default policy, whatever the settings say
+    validate_strength(&request.password, &PasswordPolicy::default())?;
+    let password_hash = PasswordService::new().hash_password(&request.password)?;
+
+    UserRepository::new(pool.clone())
+        .create(request, password_hash)
+        .await
+}
+
+pub async fn seed(pool: &PgPool, args: SeedArgs) -> Result<SeedSummary, AppError> {
+    let seeder = DatabaseSeeder::new(pool.clone()).with_dry_run(args.dry_run);
+    if args.fresh {
+        seeder.seed_fresh().await
+    } else {
+        seeder.seed_if_empty().await
+    }
+}
+
+pub async fn cleanup_logs(pool: &PgPool, args: CleanupLogsArgs) -> Result<u64, AppError> {
+    let audit_log_service = AuditLogService::new(
+        Arc::new(AuditLogRepository::new(pool.clone())),
+        None,
+        None,
+        None,
+    );
+
+    Ok(audit_log_service.delete_old_logs(args.days).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn admin_args(username: &str, password: &str) -> CreateAdminArgs {
+        CreateAdminArgs {
+            username: username.to_string(),
+            email: format!("{}@Example.com", username),
+            full_name: "Site Admin".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_no_subcommand_starts_the_server() {
+        assert!(Cli::try_parse_from(["portfolio-backend"])
+            .unwrap()
+            .command
+            .is_none());
+
+        let cli =
+            Cli::try_parse_from(["portfolio-backend", "cleanup-logs", "--days", "90"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::CleanupLogs(CleanupLogsArgs { days: 90 }))
+        ));
+
+        // Same floor as the cleanup endpoint
+        assert!(Cli::try_parse_from(["portfolio-backend", "cleanup-logs", "--days", "7"]).is_err());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_create_admin(pool: PgPool) {
+        let user = create_admin(&pool, admin_args("operator", "Correct-Horse-9"))
+            .await
+            .unwrap();
+
+        assert_eq!(user.role, "admin");
+        assert_eq!(user.email, "operator@example.com");
+        assert!(PasswordService::new()
+            .verify_password("Correct-Horse-9", &user.password_hash)
+            .unwrap());
+
+        let duplicate = create_admin(&pool, admin_args("operator", "Correct-Horse-9")).await;
+        assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_create_admin_rejects_weak_password(pool: PgPool) {
+        let result = create_admin(&pool, admin_args("operator", "password")).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(users, 0);
+    }
+}
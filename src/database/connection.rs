@@ -1,18 +1,44 @@
+use super::query_timing::set_slow_query_threshold;
 use crate::utils::{config::DatabaseConfig, errors::AppError};
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::{postgres::PgPoolOptions, Executor, PgPool};
 use std::time::Duration;
 
 pub async fn create_pool(database_url: &str, config: &DatabaseConfig) -> Result<PgPool, AppError> {
+    let statement_timeout_ms = config.statement_timeout_ms;
+
     let pool = PgPoolOptions::new()
         .max_connections(config.max_connections)
         .min_connections(config.min_connections)
-        .acquire_timeout(Duration::from_secs(config.connect_timeout))
-        .idle_timeout(Duration::from_secs(config.idle_timeout))
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_seconds))
+        .idle_timeout(Duration::from_secs(config.idle_timeout_seconds))
+        .max_lifetime(Duration::from_secs(config.max_lifetime_seconds))
         .test_before_acquire(true)
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                // Applied per session so one runaway query can't hold a connection indefinitely
+                if statement_timeout_ms > 0 {
+                    conn.execute(
+                        format!("SET statement_timeout = {}", statement_timeout_ms).as_str(),
+                    )
+                    .await?;
+                }
+                Ok(())
+            })
+        })
         .connect(database_url)
         .await?;
 
-    tracing::info!("Database connection pool created successfully");
+    set_slow_query_threshold(config.slow_query_threshold_ms);
+
+    tracing::info!(
+        "Database connection pool created (max: {}, min: {}, acquire timeout: {}s, idle timeout: {}s, max lifetime: {}s, statement timeout: {}ms)",
+        config.max_connections,
+        config.min_connections,
+        config.acquire_timeout_seconds,
+        config.idle_timeout_seconds,
+        config.max_lifetime_seconds,
+        config.statement_timeout_ms
+    );
 
     Ok(pool)
 }
@@ -24,3 +50,34 @@ pub async fn run_migrations(pool: &PgPool) -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Whether every bundled migration has been applied successfully
+pub async fn migrations_applied(pool: &PgPool) -> Result<bool, AppError> {
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = true")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(sqlx::migrate!("./migrations")
+        .iter()
+        .all(|migration| applied.contains(&migration.version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_statement_timeout_maps_to_database_timeout(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        conn.execute("SET statement_timeout = 50").await.unwrap();
+
+        let err = sqlx::query("SELECT pg_sleep(1)")
+            .execute(&mut *conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(AppError::from(err), AppError::DatabaseTimeout(_)));
+    }
+}
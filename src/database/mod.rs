@@ -1,2 +1,3 @@
 pub mod connection;
+pub mod query_timing;
 pub mod seeder;
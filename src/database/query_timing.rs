@@ -0,0 +1,29 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+// Set from config when the pool is created; 0 turns the warning off
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(500);
+
+pub fn set_slow_query_threshold(threshold_ms: u64) {
+    SLOW_QUERY_THRESHOLD_MS.store(threshold_ms, Ordering::Relaxed);
+}
+
+/// Awaits a query and logs a warning when it runs longer than the slow-query threshold
+pub async fn timed<F: Future>(name: &str, query: F) -> F::Output {
+    let started = Instant::now();
+    let output = query.await;
+
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let threshold_ms = SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed);
+    if threshold_ms > 0 && elapsed_ms >= threshold_ms {
+        tracing::warn!(
+            "Slow query '{}' took {}ms (threshold {}ms)",
+            name,
+            elapsed_ms,
+            threshold_ms
+        );
+    }
+
+    output
+}
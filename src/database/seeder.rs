@@ -2,75 +2,181 @@ use anyhow::Result;
 use argon2::password_hash::{rand_core::OsRng, SaltString};
 use argon2::{Argon2, PasswordHasher};
 use chrono::{NaiveDate, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::utils::errors::AppError;
+use crate::utils::password::{validate_strength, PasswordPolicy};
+
+/// `seed_fresh` only runs when this variable is set to `yes`
+pub const SEED_FRESH_CONFIRM_VAR: &str = "SEED_FRESH_CONFIRM";
+
+/// Rows inserted by a seeding run, or that would be inserted on a dry run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SeedSummary {
+    pub users: usize,
+    pub posts: usize,
+    pub portfolio_projects: usize,
+    pub services: usize,
+    pub comments: usize,
+    pub audit_logs: usize,
+}
 
 pub struct DatabaseSeeder {
     pool: PgPool,
+    dry_run: bool,
 }
 
 impl DatabaseSeeder {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            dry_run: false,
+        }
+    }
+
+    /// Seed inside a transaction that is rolled back instead of committed,
+    /// so the summary shows what would be inserted without writing anything
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Seeds each table only while it has no rows, so it is safe to run on every start.
+    /// Comments and audit logs point at the sample posts and users, so they are only
+    /// added in the run that seeds those.
+    pub async fn seed_if_empty(&self) -> Result<SeedSummary, AppError> {
+        tracing::info!("🌱 Seeding empty tables...");
+
+        let mut tx = self.pool.begin().await?;
+        let mut summary = SeedSummary::default();
+
+        let user_ids = if is_empty(&mut tx, "users").await? {
+            self.seed_users(&mut tx).await?
+        } else {
+            Vec::new()
+        };
+        summary.users = user_ids.len();
+
+        let post_ids = if is_empty(&mut tx, "posts").await? {
+            // Existing posts keep their author; new sample posts go to the first admin
+            let author_id = match user_ids.first() {
+                Some(id) => Some(*id),
+                None => {
+                    sqlx::query_scalar(
+                        "SELECT id FROM users WHERE role = 'admin' ORDER BY created_at LIMIT 1",
+                    )
+                    .fetch_optional(&mut *tx)
+                    .await?
+                }
+            };
+            self.seed_posts(&mut tx, author_id).await?
+        } else {
+            Vec::new()
+        };
+        summary.posts = post_ids.len();
+
+        if is_empty(&mut tx, "portfolio_projects").await? {
+            summary.portfolio_projects = self.seed_portfolio_projects(&mut tx).await?.len();
+        }
+
+        if is_empty(&mut tx, "services").await? {
+            summary.services = self.seed_services(&mut tx).await?.len();
+        }
+
+        if !post_ids.is_empty() && is_empty(&mut tx, "comments").await? {
+            summary.comments = self.seed_comments(&mut tx, &post_ids).await?;
+        }
+
+        if !user_ids.is_empty() && is_empty(&mut tx, "audit_logs").await? {
+            summary.audit_logs = self.seed_audit_logs(&mut tx, &user_ids).await?;
+        }
+
+        self.finish(tx, summary).await
     }
 
-    pub async fn seed_all(&self) -> Result<(), AppError> {
-        tracing::info!("🌱 Starting database seeding...");
+    /// Deletes all existing data and seeds from scratch. Refuses to run unless
+    /// `SEED_FRESH_CONFIRM=yes` is set.
+    pub async fn seed_fresh(&self) -> Result<SeedSummary, AppError> {
+        if std::env::var(SEED_FRESH_CONFIRM_VAR).as_deref() != Ok("yes") {
+            return Err(AppError::Validation(format!(
+                "Fresh seeding deletes all existing data; set {}=yes to confirm",
+                SEED_FRESH_CONFIRM_VAR
+            )));
+        }
+
+        tracing::info!("🌱 Starting fresh database seeding...");
+
+        let mut tx = self.pool.begin().await?;
 
         // Clear existing data (in reverse order due to foreign keys)
-        self.clear_data().await?;
+        self.clear_data(&mut tx).await?;
 
         // Seed data in dependency order
-        let user_ids = self.seed_users().await?;
-        let post_ids = self.seed_posts(&user_ids).await?;
-        let portfolio_ids = self.seed_portfolio_projects().await?;
-        let service_ids = self.seed_services().await?;
-        self.seed_comments(&post_ids, &user_ids).await?;
-        self.seed_audit_logs(&user_ids).await?;
-
-        tracing::info!("✅ Database seeding completed successfully!");
-        tracing::info!(
-            "📊 Seeded {} users, {} posts, {} portfolio projects, {} services",
-            user_ids.len(),
-            post_ids.len(),
-            portfolio_ids.len(),
-            service_ids.len()
-        );
+        let user_ids = self.seed_users(&mut tx).await?;
+        let post_ids = self.seed_posts(&mut tx, user_ids.first().copied()).await?;
+        let summary = SeedSummary {
+            users: user_ids.len(),
+            posts: post_ids.len(),
+            portfolio_projects: self.seed_portfolio_projects(&mut tx).await?.len(),
+            services: self.seed_services(&mut tx).await?.len(),
+            comments: self.seed_comments(&mut tx, &post_ids).await?,
+            audit_logs: self.seed_audit_logs(&mut tx, &user_ids).await?,
+        };
+
+        self.finish(tx, summary).await
+    }
 
-        Ok(())
+    async fn finish(
+        &self,
+        tx: Transaction<'static, Postgres>,
+        summary: SeedSummary,
+    ) -> Result<SeedSummary, AppError> {
+        if self.dry_run {
+            tx.rollback().await?;
+            tracing::info!(
+                "🔍 Dry run, nothing was written. Would have seeded {:?}",
+                summary
+            );
+        } else {
+            tx.commit().await?;
+            tracing::info!("✅ Database seeding completed: {:?}", summary);
+        }
+
+        Ok(summary)
     }
 
-    async fn clear_data(&self) -> Result<(), AppError> {
+    async fn clear_data(&self, conn: &mut PgConnection) -> Result<(), AppError> {
         tracing::info!("🧹 Clearing existing data...");
 
         // Clear in reverse dependency order
-        sqlx::query("DELETE FROM audit_logs")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("DELETE FROM comments")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("DELETE FROM services")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("DELETE FROM portfolio_projects")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("DELETE FROM posts").execute(&self.pool).await?;
-        sqlx::query("DELETE FROM users").execute(&self.pool).await?;
+        for table in [
+            "audit_logs",
+            "comments",
+            "services",
+            "portfolio_projects",
+            "posts",
+            "users",
+        ] {
+            sqlx::query(&format!("DELETE FROM {}", table))
+                .execute(&mut *conn)
+                .await?;
+        }
 
         Ok(())
     }
 
-    async fn seed_users(&self) -> Result<Vec<Uuid>, AppError> {
+    async fn seed_users(&self, conn: &mut PgConnection) -> Result<Vec<Uuid>, AppError> {
         tracing::info!("👥 Seeding users...");
 
+        // Seed accounts must satisfy the default password policy
+        let seed_password = "Password123!";
+        validate_strength(seed_password, &PasswordPolicy::default())?;
+
         let argon2 = Argon2::default();
         let salt = SaltString::generate(&mut OsRng);
         let password_hash = argon2
-            .hash_password(b"password123", &salt)
+            .hash_password(seed_password.as_bytes(), &salt)
             .map_err(|e| AppError::Internal(format!("Failed to hash password: {}", e)))?
             .to_string();
 
@@ -94,7 +200,7 @@ impl DatabaseSeeder {
         .bind(true)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         user_ids.push(admin_id);
 
@@ -116,7 +222,7 @@ impl DatabaseSeeder {
         .bind(true)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         user_ids.push(john_id);
 
@@ -137,18 +243,21 @@ impl DatabaseSeeder {
         .bind(true)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         user_ids.push(jane_id);
 
         Ok(user_ids)
     }
 
-    async fn seed_posts(&self, user_ids: &[Uuid]) -> Result<Vec<Uuid>, AppError> {
+    async fn seed_posts(
+        &self,
+        conn: &mut PgConnection,
+        author_id: Option<Uuid>,
+    ) -> Result<Vec<Uuid>, AppError> {
         tracing::info!("📝 Seeding blog posts...");
 
         let mut post_ids = Vec::new();
-        let author_id = user_ids[0]; // Admin user as author
 
         // Post 1: Getting Started with Rust
         let post1_id = Uuid::new_v4();
@@ -179,7 +288,7 @@ impl DatabaseSeeder {
         .bind(author_id)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         post_ids.push(post1_id);
 
@@ -213,14 +322,17 @@ impl DatabaseSeeder {
         .bind(author_id)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         post_ids.push(post2_id);
 
         Ok(post_ids)
     }
 
-    async fn seed_portfolio_projects(&self) -> Result<Vec<Uuid>, AppError> {
+    async fn seed_portfolio_projects(
+        &self,
+        conn: &mut PgConnection,
+    ) -> Result<Vec<Uuid>, AppError> {
         tracing::info!("💼 Seeding portfolio projects...");
 
         let mut project_ids = Vec::new();
@@ -303,7 +415,7 @@ One of the main challenges was implementing real-time inventory updates across m
         .bind(Some("Acme Corp"))
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         project_ids.push(project1_id);
 
@@ -454,14 +566,14 @@ Deployed using Docker containers with:
         .bind(Some("Tech Startup"))
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         project_ids.push(project2_id);
 
         Ok(project_ids)
     }
 
-    async fn seed_services(&self) -> Result<Vec<Uuid>, AppError> {
+    async fn seed_services(&self, conn: &mut PgConnection) -> Result<Vec<Uuid>, AppError> {
         tracing::info!("🛠️ Seeding services...");
 
         let mut service_ids = Vec::new();
@@ -476,8 +588,8 @@ Deployed using Docker containers with:
         ];
         sqlx::query(
             r#"
-            INSERT INTO services (id, title, description, features, category, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO services (id, title, description, features, category, active, display_order, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#
         )
         .bind(service1_id)
@@ -486,9 +598,10 @@ Deployed using Docker containers with:
         .bind(&features1)
         .bind("Web Development")
         .bind(true)
+        .bind(1)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         service_ids.push(service1_id);
 
@@ -501,8 +614,8 @@ Deployed using Docker containers with:
         ];
         sqlx::query(
             r#"
-            INSERT INTO services (id, title, description, features, category, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO services (id, title, description, features, category, active, display_order, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#
         )
         .bind(service2_id)
@@ -511,16 +624,21 @@ Deployed using Docker containers with:
         .bind(&features2)
         .bind("Backend Development")
         .bind(true)
+        .bind(2)
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         service_ids.push(service2_id);
 
         Ok(service_ids)
     }
 
-    async fn seed_comments(&self, post_ids: &[Uuid], _user_ids: &[Uuid]) -> Result<(), AppError> {
+    async fn seed_comments(
+        &self,
+        conn: &mut PgConnection,
+        post_ids: &[Uuid],
+    ) -> Result<usize, AppError> {
         tracing::info!("💬 Seeding comments...");
 
         // Comment 1
@@ -543,7 +661,7 @@ Deployed using Docker containers with:
         .bind(Some("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)"))
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         // Comment 2 (reply to comment 1)
@@ -566,13 +684,17 @@ Deployed using Docker containers with:
         .bind(Some("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)"))
         .bind(Utc::now())
         .bind(Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
-        Ok(())
+        Ok(2)
     }
 
-    async fn seed_audit_logs(&self, user_ids: &[Uuid]) -> Result<(), AppError> {
+    async fn seed_audit_logs(
+        &self,
+        conn: &mut PgConnection,
+        user_ids: &[Uuid],
+    ) -> Result<usize, AppError> {
         tracing::info!("📋 Seeding audit logs...");
 
         let audit_logs = vec![
@@ -582,6 +704,7 @@ Deployed using Docker containers with:
             (user_ids[0], "create", "portfolio_projects"),
         ];
 
+        let count = audit_logs.len();
         for (user_id, action, resource_type) in audit_logs {
             sqlx::query(
                 r#"
@@ -596,10 +719,84 @@ Deployed using Docker containers with:
             .bind("192.168.1.100")
             .bind("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)")
             .bind(Utc::now())
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
         }
 
-        Ok(())
+        Ok(count)
+    }
+}
+
+async fn is_empty(conn: &mut PgConnection, table: &str) -> Result<bool, AppError> {
+    let exists: bool = sqlx::query_scalar(&format!("SELECT EXISTS (SELECT 1 FROM {})", table))
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(!exists)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn count(pool: &PgPool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_seed_if_empty_does_not_duplicate_rows(pool: PgPool) {
+        let seeder = DatabaseSeeder::new(pool.clone());
+
+        let first = seeder.seed_if_empty().await.unwrap();
+        assert_eq!(first.users, 3);
+        assert_eq!(first.comments, 2);
+        let posts = count(&pool, "posts").await;
+
+        let second = seeder.seed_if_empty().await.unwrap();
+        assert_eq!(second, SeedSummary::default());
+        assert_eq!(count(&pool, "users").await, 3);
+        assert_eq!(count(&pool, "posts").await, posts);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_seed_if_empty_only_fills_empty_tables(pool: PgPool) {
+        DatabaseSeeder::new(pool.clone())
+            .seed_if_empty()
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM services")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let summary = DatabaseSeeder::new(pool.clone())
+            .seed_if_empty()
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            SeedSummary {
+                services: 2,
+                ..SeedSummary::default()
+            }
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_dry_run_writes_nothing(pool: PgPool) {
+        let summary = DatabaseSeeder::new(pool.clone())
+            .with_dry_run(true)
+            .seed_if_empty()
+            .await
+            .unwrap();
+
+        assert_eq!(summary.users, 3);
+        assert_eq!(count(&pool, "users").await, 0);
+        assert_eq!(count(&pool, "posts").await, 0);
     }
 }
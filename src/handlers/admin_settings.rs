@@ -1,57 +1,39 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{ConnectInfo, Path, Query, State},
+    http::HeaderMap,
     response::Json,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tracing::info;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
+    middleware::maintenance::MaintenanceCache,
     middleware::rate_limiter::{BlockedIpInfo, RedisRateLimiter},
     models::admin_settings::{
-        FeatureSettings, FilesSettings, GeneralSettings, NotificationSettings, SecuritySettings,
-        SocialMediaLinks, UpdateSettingsRequest,
+        FeatureSettings, GeneralSettings, NotificationSettings, PublicSettings, SecuritySettings,
+        UpdateSettingsRequest,
     },
+    models::audit_log::{AuditAction, ResourceType},
     services::admin_settings_service::AdminSettingsServiceTrait,
+    services::audit_log_service::AuditLogServiceTrait,
     services::auth_service::Claims,
+    services::notification_tester::NotificationTester,
     utils::errors::AppError,
+    utils::request::{get_accept_languages, get_client_ip, get_user_agent},
 };
 
-// Public response structures (different from internal models for security)
-#[derive(Debug, Serialize)]
-pub struct PublicSiteSettings {
-    pub site_name: String,
-    pub site_description: String,
-    pub maintenance_mode: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub maintenance_message: Option<String>,
-    pub photo_profile: Option<String>,
-    pub social_media_links: SocialMediaLinks,
-    pub files: FilesSettings,
-}
-
-#[derive(Debug, Serialize)]
-pub struct PublicFeatureSettings {
-    pub portfolio_enabled: bool,
-    pub services_enabled: bool,
-    pub blog_enabled: bool,
-    pub contact_form_enabled: bool,
-    pub comments_enabled: bool,
-}
-
-#[derive(Debug, Serialize)]
-pub struct PublicSettingsResponse {
-    pub site: PublicSiteSettings,
-    pub features: PublicFeatureSettings,
-}
-
 #[derive(Clone)]
 pub struct AdminSettingsState {
     pub admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
     pub rate_limiter: Option<Arc<RedisRateLimiter>>,
+    pub notification_tester: Arc<NotificationTester>,
+    // Refreshed right after maintenance changes instead of on the next background tick
+    pub maintenance_cache: MaintenanceCache,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -63,6 +45,13 @@ pub struct BlockIpRequest {
     pub permanent: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SetMaintenanceModeRequest {
+    pub enabled: bool,
+    // Replaces the default maintenance message when given
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SecurityQuery {
     pub page: Option<u32>,
@@ -70,6 +59,48 @@ pub struct SecurityQuery {
     pub status: Option<String>, // "active", "expired", "all"
 }
 
+// Record who changed the settings and where the request came from
+async fn log_settings_update(
+    state: &AdminSettingsState,
+    claims: &Claims,
+    user_id: Uuid,
+    details: &str,
+    client_ip: String,
+    user_agent: Option<String>,
+) {
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            &AuditAction::SettingsUpdated.to_string(),
+            &ResourceType::Settings.to_string(),
+            None,
+            None,
+            Some(details.to_string()),
+            None,
+            None,
+            true,
+            None,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log settings update: {}", e);
+    }
+}
+
+async fn refresh_maintenance_cache(state: &AdminSettingsState) {
+    if let Err(e) = state
+        .maintenance_cache
+        .refresh(state.admin_settings_service.as_ref())
+        .await
+    {
+        tracing::warn!("Failed to refresh maintenance mode: {}", e);
+    }
+}
+
 // GET /api/v1/admin/settings
 pub async fn get_settings(
     State(state): State<AdminSettingsState>,
@@ -79,7 +110,7 @@ pub async fn get_settings(
     let settings = state.admin_settings_service.get_all_settings().await?;
 
     info!("get_settings: Successfully fetched admin settings");
-    Ok(Json(json!(settings)))
+    Ok(Json(json!(settings.masked())))
 }
 
 // GET /api/v1/admin/settings/:key
@@ -95,13 +126,15 @@ pub async fn get_setting(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Setting '{}' not found", key)))?;
 
-    Ok(Json(json!(setting)))
+    Ok(Json(json!(setting.masked())))
 }
 
 // PUT /api/v1/admin/settings
 pub async fn update_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateSettingsRequest>,
 ) -> Result<Json<Value>, AppError> {
     info!(
@@ -116,11 +149,22 @@ pub async fn update_settings(
         .admin_settings_service
         .update_settings(payload, Some(user_id))
         .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "Admin settings updated",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
 
     info!("update_settings: Successfully updated admin settings");
     Ok(Json(json!({
         "message": "Settings updated successfully",
-        "settings": updated_settings
+        "settings": updated_settings.masked()
     })))
 }
 
@@ -128,6 +172,8 @@ pub async fn update_settings(
 pub async fn update_general_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<GeneralSettings>,
 ) -> Result<Json<Value>, AppError> {
     info!(
@@ -142,11 +188,22 @@ pub async fn update_general_settings(
         .admin_settings_service
         .update_general_settings(payload, Some(user_id))
         .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "General settings updated",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
 
     info!("update_general_settings: Successfully updated general settings");
     Ok(Json(json!({
         "message": "General settings updated successfully",
-        "settings": updated_settings
+        "settings": updated_settings.masked()
     })))
 }
 
@@ -154,6 +211,8 @@ pub async fn update_general_settings(
 pub async fn update_feature_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FeatureSettings>,
 ) -> Result<Json<Value>, AppError> {
     info!(
@@ -169,10 +228,20 @@ pub async fn update_feature_settings(
         .update_feature_settings(payload, Some(user_id))
         .await?;
 
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "Feature settings updated",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
+
     info!("update_feature_settings: Successfully updated feature settings");
     Ok(Json(json!({
         "message": "Feature settings updated successfully",
-        "settings": updated_settings
+        "settings": updated_settings.masked()
     })))
 }
 
@@ -180,6 +249,8 @@ pub async fn update_feature_settings(
 pub async fn update_notification_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<NotificationSettings>,
 ) -> Result<Json<Value>, AppError> {
     info!(
@@ -195,10 +266,20 @@ pub async fn update_notification_settings(
         .update_notification_settings(payload, Some(user_id))
         .await?;
 
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "Notification settings updated",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
+
     info!("update_notification_settings: Successfully updated notification settings");
     Ok(Json(json!({
         "message": "Notification settings updated successfully",
-        "settings": updated_settings
+        "settings": updated_settings.masked()
     })))
 }
 
@@ -206,6 +287,8 @@ pub async fn update_notification_settings(
 pub async fn update_security_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<SecuritySettings>,
 ) -> Result<Json<Value>, AppError> {
     info!(
@@ -221,10 +304,20 @@ pub async fn update_security_settings(
         .update_security_settings(payload, Some(user_id))
         .await?;
 
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "Security settings updated",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
+
     info!("update_security_settings: Successfully updated security settings");
     Ok(Json(json!({
         "message": "Security settings updated successfully",
-        "settings": updated_settings
+        "settings": updated_settings.masked()
     })))
 }
 
@@ -232,6 +325,8 @@ pub async fn update_security_settings(
 pub async fn reset_settings(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
 ) -> Result<Json<Value>, AppError> {
     info!(
         "reset_settings: Resetting all settings to defaults for user: {}",
@@ -245,11 +340,103 @@ pub async fn reset_settings(
         .admin_settings_service
         .reset_to_defaults(Some(user_id))
         .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        "All settings reset to defaults",
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
 
     info!("reset_settings: Successfully reset all settings to defaults");
     Ok(Json(json!({
         "message": "All settings have been reset to defaults",
-        "settings": default_settings
+        "settings": default_settings.masked()
+    })))
+}
+
+// POST /api/v1/admin/settings/notifications/test
+pub async fn test_notification_settings(
+    State(state): State<AdminSettingsState>,
+    claims: Claims,
+) -> Result<Json<Value>, AppError> {
+    info!(
+        "test_notification_settings: Testing notification channels for user: {}",
+        claims.sub
+    );
+
+    let settings = state.admin_settings_service.get_all_settings().await?;
+    let report = state
+        .notification_tester
+        .test_channels(&settings.notifications)
+        .await;
+
+    Ok(Json(json!(report)))
+}
+
+// GET /api/v1/admin/settings/history/:key
+pub async fn get_setting_history(
+    State(state): State<AdminSettingsState>,
+    _claims: Claims,
+    Path(key): Path<String>,
+) -> Result<Json<Value>, AppError> {
+    let history = state
+        .admin_settings_service
+        .get_setting_history(&key)
+        .await?
+        .into_iter()
+        .map(|entry| entry.masked())
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({
+        "key": key,
+        "history": history,
+        "total": history.len()
+    })))
+}
+
+// POST /api/v1/admin/settings/history/:key/:history_id/rollback
+pub async fn rollback_setting(
+    State(state): State<AdminSettingsState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path((key, history_id)): Path<(String, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    info!(
+        "rollback_setting: Rolling back setting '{}' to {} for user: {}",
+        key, history_id, claims.sub
+    );
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let setting = state
+        .admin_settings_service
+        .rollback_setting(&key, history_id, Some(user_id))
+        .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        &format!(
+            "Setting '{}' rolled back to history entry {}",
+            key, history_id
+        ),
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
+
+    Ok(Json(json!({
+        "message": format!("Setting '{}' rolled back successfully", key),
+        "setting": setting.masked()
     })))
 }
 
@@ -272,6 +459,7 @@ pub async fn is_feature_enabled(
 // GET /api/v1/admin/settings/maintenance-mode
 pub async fn get_maintenance_mode(
     State(state): State<AdminSettingsState>,
+    headers: HeaderMap,
 ) -> Result<Json<Value>, AppError> {
     let maintenance_mode = state.admin_settings_service.is_maintenance_mode().await?;
 
@@ -279,7 +467,7 @@ pub async fn get_maintenance_mode(
         Some(
             state
                 .admin_settings_service
-                .get_maintenance_message()
+                .get_maintenance_message(&get_accept_languages(&headers))
                 .await?,
         )
     } else {
@@ -292,10 +480,65 @@ pub async fn get_maintenance_mode(
     })))
 }
 
+// PUT /api/v1/admin/settings/maintenance-mode
+pub async fn set_maintenance_mode(
+    State(state): State<AdminSettingsState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<SetMaintenanceModeRequest>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    // Only the maintenance fields change; the rest of the general settings are kept
+    let mut general = state
+        .admin_settings_service
+        .get_all_settings()
+        .await?
+        .general;
+    general.maintenance_mode = payload.enabled;
+    if let Some(message) = payload.message {
+        general.maintenance_message = message;
+    }
+
+    let updated_settings = state
+        .admin_settings_service
+        .update_general_settings(general, Some(user_id))
+        .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        if payload.enabled {
+            "Maintenance mode enabled"
+        } else {
+            "Maintenance mode disabled"
+        },
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
+
+    info!(
+        "set_maintenance_mode: Maintenance mode set to {}",
+        payload.enabled
+    );
+    Ok(Json(json!({
+        "message": "Maintenance mode updated successfully",
+        "maintenance_mode": updated_settings.general.maintenance_mode,
+        "maintenance_message": updated_settings.general.maintenance_message
+    })))
+}
+
 // PUT /api/v1/admin/settings/:key
 pub async fn update_setting(
     State(state): State<AdminSettingsState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(key): Path<String>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, AppError> {
@@ -311,11 +554,22 @@ pub async fn update_setting(
         .admin_settings_service
         .update_setting(&key, payload, Some(user_id))
         .await?;
+    refresh_maintenance_cache(&state).await;
+
+    log_settings_update(
+        &state,
+        &claims,
+        user_id,
+        &format!("Setting '{}' updated", key),
+        get_client_ip(&headers, Some(&addr)),
+        get_user_agent(&headers),
+    )
+    .await;
 
     info!("update_setting: Successfully updated setting '{}'", key);
     Ok(Json(json!({
         "message": format!("Setting '{}' updated successfully", key),
-        "setting": updated_setting
+        "setting": updated_setting.masked()
     })))
 }
 
@@ -377,9 +631,7 @@ pub async fn block_ip(
     Json(request): Json<BlockIpRequest>,
 ) -> Result<Json<Value>, AppError> {
     // Validate request
-    request
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    request.validate()?;
 
     if let Some(ref rate_limiter) = state.rate_limiter {
         let permanent = request.permanent.unwrap_or(false);
@@ -477,35 +729,14 @@ pub async fn get_security_stats(
 // GET /api/v1/settings/public - Public endpoint for safe settings (no auth required)
 pub async fn get_public_settings(
     State(state): State<AdminSettingsState>,
-) -> Result<Json<PublicSettingsResponse>, AppError> {
+    headers: HeaderMap,
+) -> Result<Json<PublicSettings>, AppError> {
     info!("get_public_settings: Fetching public settings");
 
     let settings = state.admin_settings_service.get_all_settings().await?;
 
-    // Only expose safe, non-sensitive settings
-    let public_response = PublicSettingsResponse {
-        site: PublicSiteSettings {
-            site_name: settings.general.site_name,
-            site_description: settings.general.site_description,
-            maintenance_mode: settings.general.maintenance_mode,
-            maintenance_message: if settings.general.maintenance_mode {
-                Some(settings.general.maintenance_message)
-            } else {
-                None
-            },
-            photo_profile: settings.general.photo_profile,
-            social_media_links: settings.general.social_media_links,
-            files: settings.general.files,
-        },
-        features: PublicFeatureSettings {
-            portfolio_enabled: settings.features.portfolio_enabled,
-            services_enabled: settings.features.services_enabled,
-            blog_enabled: settings.features.blog_enabled,
-            contact_form_enabled: settings.features.contact_form_enabled,
-            comments_enabled: settings.features.comments_enabled,
-        },
-    };
+    let public_settings = PublicSettings::new(settings, &get_accept_languages(&headers));
 
     info!("get_public_settings: Successfully fetched public settings");
-    Ok(Json(public_response))
+    Ok(Json(public_settings))
 }
@@ -0,0 +1,21 @@
+use axum::extract::State;
+use std::sync::Arc;
+
+use crate::{
+    models::analytics::AnalyticsSummary,
+    services::analytics_service::AnalyticsServiceTrait,
+    utils::{errors::AppError, response::ApiResponse},
+};
+
+#[derive(Clone)]
+pub struct AnalyticsState {
+    pub analytics_service: Arc<dyn AnalyticsServiceTrait>,
+}
+
+// GET /api/v1/admin/analytics/summary
+pub async fn get_summary(
+    State(state): State<AnalyticsState>,
+) -> Result<ApiResponse<AnalyticsSummary>, AppError> {
+    let summary = state.analytics_service.get_summary().await?;
+    Ok(ApiResponse::ok(summary))
+}
@@ -0,0 +1,131 @@
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::HeaderMap,
+    response::Json,
+};
+use serde_json::{json, Value};
+use std::{net::SocketAddr, sync::Arc};
+use uuid::Uuid;
+
+use crate::{
+    models::api_key::CreateApiKeyRequest,
+    services::{
+        api_key_service::ApiKeyServiceTrait, audit_log_service::AuditLogServiceTrait,
+        auth_service::Claims,
+    },
+    utils::{
+        errors::AppError,
+        request::{get_client_ip, get_user_agent},
+    },
+};
+
+#[derive(Clone)]
+pub struct ApiKeyState {
+    pub api_key_service: Arc<dyn ApiKeyServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
+}
+
+// GET /api/v1/auth/api-keys
+pub async fn list_api_keys(
+    State(state): State<ApiKeyState>,
+    claims: Claims,
+) -> Result<Json<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let api_keys = state.api_key_service.list(user_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": api_keys
+    })))
+}
+
+// POST /api/v1/auth/api-keys
+pub async fn create_api_key(
+    State(state): State<ApiKeyState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let created = state.api_key_service.create(user_id, payload).await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            "api_key_created",
+            "api_key",
+            Some(created.api_key.id),
+            Some(created.api_key.name.clone()),
+            Some(format!("Scopes: {}", created.api_key.scopes.join(", "))),
+            None,
+            None,
+            true,
+            None,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log API key creation: {}", e);
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "data": created,
+        "message": "API key created. Copy it now, it won't be shown again"
+    })))
+}
+
+// DELETE /api/v1/auth/api-keys/:id
+pub async fn revoke_api_key(
+    State(state): State<ApiKeyState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    state.api_key_service.revoke(user_id, id).await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            "api_key_revoked",
+            "api_key",
+            Some(id),
+            None,
+            None,
+            None,
+            None,
+            true,
+            None,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log API key revocation: {}", e);
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "API key revoked successfully"
+    })))
+}
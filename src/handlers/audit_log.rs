@@ -9,7 +9,7 @@ use tracing::info;
 use uuid::Uuid;
 
 use crate::{
-    models::audit_log::{AuditLogFilters, CreateAuditLogRequest},
+    models::audit_log::{AuditLogFilters, CreateAuditLogRequest, ResourceType},
     services::audit_log_service::AuditLogServiceTrait,
     utils::errors::AppError,
 };
@@ -103,8 +103,15 @@ pub async fn get_audit_logs_by_user(
 // GET /api/v1/admin/audit-logs/resource/:resource_type/:resource_id
 pub async fn get_audit_logs_by_resource(
     State(state): State<AuditLogState>,
-    Path((resource_type, resource_id)): Path<(String, Uuid)>,
+    Path((resource_type, resource_id)): Path<(String, String)>,
 ) -> Result<Json<Value>, AppError> {
+    let resource_type = resource_type
+        .parse::<ResourceType>()
+        .map_err(AppError::BadRequest)?
+        .to_string();
+    let resource_id = Uuid::parse_str(&resource_id)
+        .map_err(|_| AppError::BadRequest("Invalid resource ID".to_string()))?;
+
     let logs = state
         .audit_log_service
         .get_by_resource(resource_type.clone(), resource_id)
@@ -1,19 +1,36 @@
 use axum::{
-    extract::{ConnectInfo, State},
+    extract::{ConnectInfo, Path, State},
     http::{header::SET_COOKIE, HeaderMap},
-    response::Json,
+    response::{IntoResponse, Json},
 };
 use serde_json::json;
 use std::{net::SocketAddr, sync::Arc};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::middleware::rate_limiter::{
-    check_and_auto_block_ip, clear_auth_rate_limit, record_auth_failure, RedisRateLimiter,
+    check_and_auto_block_ip, clear_account_lock, clear_auth_rate_limit, get_account_lock,
+    record_account_failure, record_auth_failure, RedisRateLimiter,
+};
+use crate::models::user::{
+    ChangePasswordRequest, ForgotPasswordRequest, LoginRequest, RefreshTokenRequest,
+    ResetPasswordRequest, SessionClient, UpdateProfileRequest,
 };
-use crate::models::user::{ChangePasswordRequest, LoginRequest, UpdateProfileRequest};
 use crate::services::audit_log_service::AuditLogServiceTrait;
 use crate::services::auth_service::{AuthService, Claims};
+use crate::utils::config::CookieConfig;
+use crate::utils::cookie::{build_cookie, clear_cookie};
 use crate::utils::errors::AppError;
+use crate::utils::request::{get_client_ip, get_user_agent};
+
+const ACCESS_TOKEN_COOKIE: &str = "admin_token";
+// Scoped to the auth endpoints so the refresh token isn't sent with every API request
+const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+const REFRESH_TOKEN_PATH: &str = "/api/v1/auth";
+// Clients that can't store cookies send `X-Auth-Mode: token` to get the tokens in the body
+const AUTH_MODE_HEADER: &str = "x-auth-mode";
+// ...and present the refresh token here or in the JSON body instead of the cookie
+const REFRESH_TOKEN_HEADER: &str = "x-refresh-token";
 
 // State struct to hold auth service, audit log service, and rate limiter
 #[derive(Clone)]
@@ -21,6 +38,7 @@ pub struct AuthState {
     pub auth_service: AuthService,
     pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
     pub rate_limiter: Option<Arc<RedisRateLimiter>>,
+    pub cookie_config: CookieConfig,
 }
 
 pub async fn login(
@@ -30,6 +48,7 @@ pub async fn login(
     Json(request): Json<LoginRequest>,
 ) -> Result<axum::response::Response, AppError> {
     let username = request.username.clone();
+    let remember_me = request.remember_me.unwrap_or(false);
     let client_ip = get_client_ip(&headers, Some(&addr));
     let user_agent = get_user_agent(&headers);
 
@@ -37,21 +56,31 @@ pub async fn login(
     if let Some(ref limiter) = state.rate_limiter {
         let blocked_key = format!("blocked_ip:{}", client_ip);
         if let Ok(mut conn) = limiter.get_connection().await {
-            let is_blocked: Option<String> = redis::cmd("GET")
+            // TTL is -2 for a missing key and -1 for a permanent block
+            let ttl: i64 = redis::cmd("TTL")
                 .arg(&blocked_key)
                 .query_async(&mut conn)
                 .await
-                .unwrap_or(None);
+                .unwrap_or(-2);
 
-            if is_blocked.is_some() {
+            if ttl != -2 {
                 return Err(AppError::TooManyRequests {
                     message: "Your IP address has been blocked due to suspicious activity. Please contact support if you believe this is an error.".to_string(),
-                    retry_after: None,
+                    retry_after: u64::try_from(ttl).ok().filter(|seconds| *seconds > 0),
                 });
             }
         }
     }
 
+    // Check whether the account itself is locked, whichever IP the attempt comes from
+    if let Some(ref limiter) = state.rate_limiter {
+        match get_account_lock(limiter, &username).await {
+            Ok(Some(retry_after)) => return Err(account_locked_error(retry_after)),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Account lock check failed: {}", e),
+        }
+    }
+
     // Check rate limiting before authentication
     if let Some(ref limiter) = state.rate_limiter {
         match limiter
@@ -75,13 +104,21 @@ pub async fn login(
         }
     }
 
-    match state.auth_service.authenticate_user(request).await {
+    let client = SessionClient {
+        ip_address: Some(client_ip.clone()),
+        user_agent: user_agent.clone(),
+    };
+
+    match state.auth_service.authenticate_user(request, &client).await {
         Ok(response) => {
             // Clear rate limiting on successful authentication
             if let Some(ref limiter) = state.rate_limiter {
                 if let Err(e) = clear_auth_rate_limit(limiter, &client_ip, &username).await {
                     tracing::warn!("Failed to clear auth rate limit: {}", e);
                 }
+                if let Err(e) = clear_account_lock(limiter, &username).await {
+                    tracing::warn!("Failed to clear account lock: {}", e);
+                }
             }
 
             // Log successful login
@@ -93,8 +130,13 @@ pub async fn login(
                     "login",
                     true,
                     Some(format!(
-                        "Successful login for user: {}",
-                        response.user.username
+                        "Successful login for user: {} ({} session)",
+                        response.user.username,
+                        if remember_me {
+                            "remembered"
+                        } else {
+                            "standard"
+                        }
                     )),
                     None,
                     Some(client_ip.clone()),
@@ -105,21 +147,30 @@ pub async fn login(
                 eprintln!("Failed to log successful login: {}", e);
             }
 
-            // Create secure httpOnly cookie for the token
-            let cookie_value = format!(
-                "admin_token={}; HttpOnly; Secure; SameSite=Strict; Path=/; Max-Age={}",
-                response.token,
-                24 * 60 * 60 // 24 hours in seconds
+            // Create httpOnly cookies for the tokens
+            let cookie_value =
+                access_token_cookie(&state.cookie_config, &response.token, response.expires_at);
+            let refresh_cookie = refresh_token_cookie(
+                &state.cookie_config,
+                &response.refresh_token,
+                response.refresh_expires_at,
             );
 
-            // Build response with cookie
+            // Build response with cookie. The tokens stay out of the body unless the client
+            // opts in, since anything in the body is readable by scripts on the page.
+            let mut data = json!({
+                "user": response.user,
+                "expires_at": response.expires_at,
+            });
+            if wants_token_in_body(&headers) {
+                data["token"] = json!(response.token);
+                data["refresh_token"] = json!(response.refresh_token);
+                data["refresh_expires_at"] = json!(response.refresh_expires_at);
+            }
+
             let json_response = Json(json!({
                 "success": true,
-                "data": {
-                    "user": response.user,
-                    "expires_at": response.expires_at,
-                    // Don't send token in response body for security
-                }
+                "data": data
             }));
 
             let mut response = axum::response::Response::new(
@@ -134,12 +185,28 @@ pub async fn login(
             response
                 .headers_mut()
                 .insert(SET_COOKIE, cookie_value.parse().unwrap());
+            response
+                .headers_mut()
+                .append(SET_COOKIE, refresh_cookie.parse().unwrap());
 
             Ok(response)
         }
         Err(e) => {
+            let mut locked_for = None;
+
             // Record failed attempt and check for auto-blocking
             if let Some(ref limiter) = state.rate_limiter {
+                let (max_attempts, lockout_seconds) =
+                    state.auth_service.account_lockout_policy().await;
+                match record_account_failure(limiter, &username, max_attempts, lockout_seconds)
+                    .await
+                {
+                    Ok(lock) => locked_for = lock,
+                    Err(redis_err) => {
+                        tracing::warn!("Failed to record account failure: {}", redis_err)
+                    }
+                }
+
                 if let Err(redis_err) = record_auth_failure(limiter, &client_ip, &username).await {
                     tracing::warn!("Failed to record auth failure: {}", redis_err);
                 } else {
@@ -168,6 +235,11 @@ pub async fn login(
                 eprintln!("Failed to log failed login: {}", log_err);
             }
 
+            // The attempt that reaches the limit already reports the lock
+            if let Some(retry_after) = locked_for {
+                return Err(account_locked_error(retry_after));
+            }
+
             Err(e)
         }
     }
@@ -175,8 +247,23 @@ pub async fn login(
 
 pub async fn logout(
     State(state): State<AuthState>,
+    headers: HeaderMap,
     claims: Claims,
 ) -> Result<axum::response::Response, AppError> {
+    // Deny the access token itself, so a copy of it stops working right away
+    state.auth_service.revoke_access_token(&claims).await;
+
+    // Revoke the refresh token so it can't mint new access tokens
+    if let Some(refresh_token) = get_cookie(&headers, REFRESH_TOKEN_COOKIE) {
+        if let Err(e) = state
+            .auth_service
+            .revoke_refresh_token(&refresh_token)
+            .await
+        {
+            tracing::warn!("Failed to revoke refresh token on logout: {}", e);
+        }
+    }
+
     // Log logout
     if let Err(e) = state
         .audit_log_service
@@ -195,9 +282,6 @@ pub async fn logout(
         eprintln!("Failed to log logout: {}", e);
     }
 
-    // Clear the cookie by setting it to expire
-    let clear_cookie = "admin_token=; HttpOnly; Secure; SameSite=Strict; Path=/; Max-Age=0";
-
     let json_response = Json(json!({
         "success": true,
         "message": "Successfully logged out"
@@ -211,44 +295,199 @@ pub async fn logout(
         "application/json".parse().unwrap(),
     );
 
-    response
-        .headers_mut()
-        .insert(SET_COOKIE, clear_cookie.parse().unwrap());
+    // Clear the cookies by setting them to expire
+    append_clear_session_cookies(&mut response, &state.cookie_config);
 
     Ok(response)
 }
 
-// Helper function to extract client IP
-fn get_client_ip(headers: &HeaderMap, addr: Option<&SocketAddr>) -> String {
-    // Priority: X-Forwarded-For > X-Real-IP > actual connection IP > fallback to unknown
-    if let Some(forwarded) = headers.get("x-forwarded-for") {
-        if let Ok(forwarded_str) = forwarded.to_str() {
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return first_ip.trim().to_string();
-            }
+// POST /api/v1/auth/forgot-password
+pub async fn forgot_password(
+    State(state): State<AuthState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<ForgotPasswordRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    request.validate()?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let user = state
+        .auth_service
+        .request_password_reset(&request.email)
+        .await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_auth_event(
+            user.as_ref().map(|u| u.id),
+            user.as_ref().map(|u| u.username.clone()),
+            "password_reset_requested",
+            user.is_some(),
+            Some(format!(
+                "Password reset requested for email: {}",
+                request.email
+            )),
+            user.is_none()
+                .then(|| "No account with this email".to_string()),
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log password reset request: {}", e);
+    }
+
+    // Same response whether or not the email exists to prevent user enumeration
+    Ok(Json(json!({
+        "success": true,
+        "message": "If an account with that email exists, a password reset link has been sent"
+    })))
+}
+
+// POST /api/v1/auth/reset-password
+pub async fn reset_password(
+    State(state): State<AuthState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<ResetPasswordRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    request.validate()?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let result = state
+        .auth_service
+        .reset_password(&request.token, &request.new_password)
+        .await;
+
+    let log_result = match &result {
+        Ok(user) => {
+            state
+                .audit_log_service
+                .log_auth_event(
+                    Some(user.id),
+                    Some(user.username.clone()),
+                    "password_reset",
+                    true,
+                    Some(format!("Password reset for user: {}", user.username)),
+                    None,
+                    Some(client_ip),
+                    user_agent,
+                )
+                .await
+        }
+        Err(e) => {
+            state
+                .audit_log_service
+                .log_auth_event(
+                    None,
+                    None,
+                    "password_reset",
+                    false,
+                    Some("Password reset attempt failed".to_string()),
+                    Some(e.to_string()),
+                    Some(client_ip),
+                    user_agent,
+                )
+                .await
         }
+    };
+
+    if let Err(e) = log_result {
+        tracing::warn!("Failed to log password reset: {}", e);
     }
 
-    if let Some(real_ip) = headers.get("x-real-ip") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            return ip_str.to_string();
+    let user = result?;
+
+    // Proving ownership through the reset link lifts any lockout straight away
+    if let Some(ref limiter) = state.rate_limiter {
+        if let Err(e) = clear_account_lock(limiter, &user.username).await {
+            tracing::warn!("Failed to clear account lock: {}", e);
         }
     }
 
-    // Use actual connection IP if available
-    if let Some(socket_addr) = addr {
-        return socket_addr.ip().to_string();
+    Ok(Json(json!({
+        "success": true,
+        "message": "Password has been reset successfully"
+    })))
+}
+
+fn account_locked_error(retry_after: u64) -> AppError {
+    AppError::TooManyRequests {
+        message: format!(
+            "Account temporarily locked due to too many failed login attempts. Try again in {} minute(s).",
+            retry_after.div_ceil(60)
+        ),
+        retry_after: Some(retry_after),
     }
+}
 
-    "unknown".to_string()
+// Helper function to check whether the client asked for the tokens in the response body
+fn wants_token_in_body(headers: &HeaderMap) -> bool {
+    headers
+        .get(AUTH_MODE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|mode| mode.trim().eq_ignore_ascii_case("token"))
+}
+
+// Helper function to build the access token cookie
+fn access_token_cookie(
+    config: &CookieConfig,
+    token: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let max_age = config
+        .max_age
+        .unwrap_or_else(|| (expires_at - chrono::Utc::now()).num_seconds());
+    build_cookie(config, ACCESS_TOKEN_COOKIE, token, "/", max_age)
+}
+
+// Helper function to build the refresh token cookie
+fn refresh_token_cookie(
+    config: &CookieConfig,
+    token: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> String {
+    build_cookie(
+        config,
+        REFRESH_TOKEN_COOKIE,
+        token,
+        REFRESH_TOKEN_PATH,
+        (expires_at - chrono::Utc::now()).num_seconds(),
+    )
+}
+
+// Helper function to expire both auth cookies on a response
+fn append_clear_session_cookies(response: &mut axum::response::Response, config: &CookieConfig) {
+    let clear_access_cookie = clear_cookie(config, ACCESS_TOKEN_COOKIE, "/");
+    let clear_refresh_cookie = clear_cookie(config, REFRESH_TOKEN_COOKIE, REFRESH_TOKEN_PATH);
+
+    response
+        .headers_mut()
+        .append(SET_COOKIE, clear_access_cookie.parse().unwrap());
+    response
+        .headers_mut()
+        .append(SET_COOKIE, clear_refresh_cookie.parse().unwrap());
 }
 
-// Helper function to extract user agent
-fn get_user_agent(headers: &HeaderMap) -> Option<String> {
+// Helper function to read a cookie value from the request headers
+fn get_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
     headers
-        .get("user-agent")
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string())
+        .get(axum::http::header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|cookies| {
+            cookies.split(';').find_map(|cookie| {
+                cookie
+                    .trim()
+                    .strip_prefix(name)
+                    .and_then(|rest| rest.strip_prefix('='))
+                    .map(|value| value.to_string())
+            })
+        })
+        .filter(|value| !value.is_empty())
 }
 
 pub async fn me(
@@ -268,22 +507,189 @@ pub async fn me(
     })))
 }
 
+// POST /api/v1/auth/refresh
 pub async fn refresh_token(
     State(state): State<AuthState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Option<Json<RefreshTokenRequest>>,
+) -> Result<axum::response::Response, AppError> {
+    let from_cookie = get_cookie(&headers, REFRESH_TOKEN_COOKIE);
+    let cookie_client = from_cookie.is_some();
+    let refresh_token = from_cookie
+        .or_else(|| {
+            headers
+                .get(REFRESH_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim().to_string())
+        })
+        .or_else(|| body.and_then(|Json(body)| body.refresh_token))
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AppError::Unauthorized("Missing refresh token".to_string()))?;
+
+    let client = SessionClient {
+        ip_address: Some(get_client_ip(&headers, Some(&addr))),
+        user_agent: get_user_agent(&headers),
+    };
+
+    // Rotates the refresh token; the one presented here stops working
+    let login = state
+        .auth_service
+        .refresh_token(&refresh_token, &client)
+        .await?;
+
+    let access_cookie = access_token_cookie(&state.cookie_config, &login.token, login.expires_at);
+    let refresh_cookie = refresh_token_cookie(
+        &state.cookie_config,
+        &login.refresh_token,
+        login.refresh_expires_at,
+    );
+
+    // The presented token is now spent, so clients without the cookie need the new one back
+    let mut data = json!({
+        "token": login.token,
+        "expires_at": login.expires_at,
+        "user": login.user
+    });
+    if !cookie_client || wants_token_in_body(&headers) {
+        data["refresh_token"] = json!(login.refresh_token);
+        data["refresh_expires_at"] = json!(login.refresh_expires_at);
+    }
+
+    let json_response = Json(json!({
+        "success": true,
+        "data": data
+    }));
+
+    let mut response =
+        axum::response::Response::new(serde_json::to_string(&json_response.0).unwrap().into());
+
+    response.headers_mut().insert(
+        axum::http::header::CONTENT_TYPE,
+        "application/json".parse().unwrap(),
+    );
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, access_cookie.parse().unwrap());
+    response
+        .headers_mut()
+        .append(SET_COOKIE, refresh_cookie.parse().unwrap());
+
+    Ok(response)
+}
+
+// GET /api/v1/auth/sessions
+pub async fn list_sessions(
+    State(state): State<AuthState>,
+    headers: HeaderMap,
     claims: Claims,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
 
-    let user = state.auth_service.get_user_by_id(user_id).await?;
-    let (token, expires_at) = state.auth_service.generate_token(&user)?;
+    // The refresh token cookie identifies which session is making the request
+    let current = get_cookie(&headers, REFRESH_TOKEN_COOKIE);
+    let sessions = state
+        .auth_service
+        .list_sessions(user_id, current.as_deref())
+        .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": sessions
+    })))
+}
+
+// DELETE /api/v1/auth/sessions/:id
+pub async fn revoke_session(
+    State(state): State<AuthState>,
+    Path(session_id): Path<Uuid>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    claims: Claims,
+) -> Result<axum::response::Response, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
+
+    let current = get_cookie(&headers, REFRESH_TOKEN_COOKIE);
+    let was_current = state
+        .auth_service
+        .revoke_session(user_id, session_id, current.as_deref())
+        .await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_auth_event(
+            Some(user_id),
+            Some(claims.username.clone()),
+            "session_revoked",
+            true,
+            Some(format!("Revoked session {}", session_id)),
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log session revocation: {}", e);
+    }
+
+    let mut response = Json(json!({
+        "success": true,
+        "message": "Session revoked"
+    }))
+    .into_response();
+
+    // Revoking the session in use logs this client out too
+    if was_current {
+        append_clear_session_cookies(&mut response, &state.cookie_config);
+    }
+
+    Ok(response)
+}
+
+// DELETE /api/v1/auth/sessions
+pub async fn revoke_other_sessions(
+    State(state): State<AuthState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
+
+    // Without the refresh token cookie there is no way to tell which session to keep
+    let current = get_cookie(&headers, REFRESH_TOKEN_COOKIE).ok_or_else(|| {
+        AppError::BadRequest("Current session could not be identified".to_string())
+    })?;
+
+    let revoked = state
+        .auth_service
+        .revoke_other_sessions(user_id, &current)
+        .await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_auth_event(
+            Some(user_id),
+            Some(claims.username.clone()),
+            "sessions_revoked",
+            true,
+            Some(format!("Revoked {} other session(s)", revoked)),
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log session revocation: {}", e);
+    }
 
     Ok(Json(json!({
         "success": true,
         "data": {
-            "token": token,
-            "expires_at": expires_at,
-            "user": crate::models::user::UserResponse::from(user)
+            "revoked": revoked
         }
     })))
 }
@@ -291,11 +697,16 @@ pub async fn refresh_token(
 pub async fn update_profile(
     State(state): State<AuthState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(request): Json<UpdateProfileRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
 
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
     let updated_user = state.auth_service.update_profile(user_id, request).await?;
 
     // Log profile update
@@ -313,6 +724,8 @@ pub async fn update_profile(
             None,
             true,
             None,
+            Some(client_ip),
+            user_agent,
         )
         .await
     {
@@ -331,13 +744,20 @@ pub async fn update_profile(
 pub async fn change_password(
     State(state): State<AuthState>,
     claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(request): Json<ChangePasswordRequest>,
 ) -> Result<axum::response::Response, AppError> {
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
 
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
     match state.auth_service.change_password(user_id, request).await {
         Ok(_) => {
+            state.auth_service.revoke_access_token(&claims).await;
+
             // Log successful password change
             if let Err(e) = state
                 .audit_log_service
@@ -355,6 +775,8 @@ pub async fn change_password(
                     None,
                     true,
                     None,
+                    Some(client_ip),
+                    user_agent,
                 )
                 .await
             {
@@ -362,7 +784,7 @@ pub async fn change_password(
             }
 
             // Clear the authentication cookie for security
-            let clear_cookie = "admin_token=; HttpOnly; Secure; SameSite=Strict; Path=/; Max-Age=0";
+            let clear_access_cookie = clear_cookie(&state.cookie_config, ACCESS_TOKEN_COOKIE, "/");
 
             let json_response = Json(json!({
                 "success": true,
@@ -382,7 +804,7 @@ pub async fn change_password(
             // Clear the authentication cookie
             response
                 .headers_mut()
-                .insert(SET_COOKIE, clear_cookie.parse().unwrap());
+                .insert(SET_COOKIE, clear_access_cookie.parse().unwrap());
 
             Ok(response)
         }
@@ -402,6 +824,8 @@ pub async fn change_password(
                     None,
                     false,
                     Some(e.to_string()),
+                    Some(client_ip),
+                    user_agent,
                 )
                 .await
             {
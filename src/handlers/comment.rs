@@ -9,8 +9,11 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    models::comment::{CommentQuery, CreateCommentRequest, UpdateCommentStatusRequest},
-    services::comment_service::CommentServiceTrait,
+    models::comment::{
+        CommentQuery, CommentStatsQuery, CreateBanRequest, CreateCommentRequest,
+        EditCommentRequest, UpdateCommentStatusRequest,
+    },
+    services::{auth_service::Claims, comment_service::CommentServiceTrait},
     utils::errors::AppError,
 };
 
@@ -50,9 +53,7 @@ pub async fn create_comment(
     Json(payload): Json<CreateCommentRequest>,
 ) -> Result<(StatusCode, Json<Value>), AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
 
     // Extract IP address and User-Agent
     let ip_address = Some(addr.ip().to_string());
@@ -65,16 +66,35 @@ pub async fn create_comment(
         .comment_service
         .create_comment(payload, ip_address, user_agent)
         .await?;
+    let edit_token = state.comment_service.create_edit_token(&comment);
 
     Ok((
         StatusCode::CREATED,
         Json(json!({
             "message": "Comment submitted successfully and is pending moderation",
-            "comment": comment
+            "comment": comment,
+            "edit_token": edit_token
         })),
     ))
 }
 
+// PUT /api/v1/comments/:id/edit
+pub async fn edit_comment(
+    State(state): State<CommentState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<EditCommentRequest>,
+) -> Result<Json<Value>, AppError> {
+    // Validate the request
+    payload.validate()?;
+
+    let comment = state.comment_service.edit_comment(id, payload).await?;
+
+    Ok(Json(json!({
+        "message": "Comment updated successfully",
+        "comment": comment
+    })))
+}
+
 // PUT /api/v1/comments/:id/status
 pub async fn update_comment_status(
     State(state): State<CommentState>,
@@ -82,9 +102,7 @@ pub async fn update_comment_status(
     Json(payload): Json<UpdateCommentStatusRequest>,
 ) -> Result<Json<Value>, AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
 
     let comment = state
         .comment_service
@@ -133,6 +151,20 @@ pub async fn get_comments_by_post(
     })))
 }
 
+// GET /api/v1/comments/post/:post_id/threaded
+pub async fn get_threaded_comments(
+    State(state): State<CommentState>,
+    Path(post_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let threads = state.comment_service.get_threaded_comments(post_id).await?;
+
+    Ok(Json(json!({
+        "comments": threads,
+        "post_id": post_id,
+        "total": threads.len()
+    })))
+}
+
 // GET /api/v1/comments/:id/replies
 pub async fn get_comment_replies(
     State(state): State<CommentState>,
@@ -165,6 +197,22 @@ pub async fn get_comment_stats(State(state): State<CommentState>) -> Result<Json
     Ok(Json(json!(stats)))
 }
 
+// GET /api/v1/comments/stats/timeseries?days=30
+pub async fn get_comment_stats_timeseries(
+    State(state): State<CommentState>,
+    Query(query): Query<CommentStatsQuery>,
+) -> Result<Json<Value>, AppError> {
+    let stats = state
+        .comment_service
+        .get_comment_stats_timeseries(query.days.unwrap_or(30))
+        .await?;
+
+    Ok(Json(json!({
+        "days": stats.len(),
+        "series": stats
+    })))
+}
+
 // PUT /api/v1/comments/bulk-status
 pub async fn bulk_update_comment_status(
     State(state): State<CommentState>,
@@ -229,3 +277,48 @@ pub async fn reject_comment(
         "comment_id": id
     })))
 }
+
+// GET /api/v1/comments/bans
+pub async fn list_bans(State(state): State<CommentState>) -> Result<Json<Value>, AppError> {
+    let bans = state.comment_service.list_bans().await?;
+
+    Ok(Json(json!({
+        "bans": bans,
+        "total": bans.len()
+    })))
+}
+
+// POST /api/v1/comments/bans
+pub async fn ban_commenter(
+    State(state): State<CommentState>,
+    claims: Claims,
+    Json(payload): Json<CreateBanRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    payload.validate()?;
+
+    let created_by = Uuid::parse_str(&claims.sub).ok();
+    let ban = state
+        .comment_service
+        .ban_commenter(payload, created_by)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "message": "Commenter banned successfully",
+            "ban": ban
+        })),
+    ))
+}
+
+// DELETE /api/v1/comments/bans/:id
+pub async fn unban_commenter(
+    State(state): State<CommentState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    state.comment_service.unban_commenter(id).await?;
+
+    Ok(Json(json!({
+        "message": "Ban removed successfully"
+    })))
+}
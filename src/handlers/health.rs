@@ -0,0 +1,116 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::{future::Future, sync::Arc, time::Duration};
+
+use crate::{database::connection::migrations_applied, middleware::rate_limiter::RedisRateLimiter};
+
+// A hung dependency shouldn't hang the probe
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct HealthState {
+    pub pool: PgPool,
+    pub rate_limiter: Option<Arc<RedisRateLimiter>>,
+}
+
+// GET /api/v1/health
+pub async fn health_check(State(state): State<HealthState>) -> (StatusCode, Json<Value>) {
+    let database = check(sqlx::query("SELECT 1").execute(&state.pool)).await;
+    let redis = match &state.rate_limiter {
+        Some(rate_limiter) => Some(check(rate_limiter.ping()).await),
+        None => None,
+    };
+
+    let healthy = database && redis.unwrap_or(true);
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(json!({
+            "status": if healthy { "healthy" } else { "unhealthy" },
+            "database": component_status(Some(database)),
+            "redis": component_status(redis),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "version": env!("CARGO_PKG_VERSION")
+        })),
+    )
+}
+
+// GET /livez
+// Liveness only says the process is serving requests; it never touches dependencies
+pub async fn liveness() -> Json<Value> {
+    Json(json!({
+        "status": "alive",
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "version": env!("CARGO_PKG_VERSION")
+    }))
+}
+
+// GET /readyz
+pub async fn readiness(State(state): State<HealthState>) -> (StatusCode, Json<Value>) {
+    let database = check(sqlx::query("SELECT 1").execute(&state.pool)).await;
+    let redis = match &state.rate_limiter {
+        Some(rate_limiter) => Some(check(rate_limiter.ping()).await),
+        None => None,
+    };
+    let migrations = if database {
+        match tokio::time::timeout(CHECK_TIMEOUT, migrations_applied(&state.pool)).await {
+            Ok(Ok(true)) => "ok",
+            Ok(Ok(false)) => "pending",
+            Ok(Err(e)) => {
+                tracing::warn!("Migration check failed: {}", e);
+                "down"
+            }
+            Err(_) => "down",
+        }
+    } else {
+        "down"
+    };
+
+    let ready = database && redis.unwrap_or(true) && migrations == "ok";
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(json!({
+            "ready": ready,
+            "components": {
+                "database": component_status(Some(database)),
+                "redis": component_status(redis),
+                "migrations": migrations
+            },
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+    )
+}
+
+async fn check<T, E: std::fmt::Display>(probe: impl Future<Output = Result<T, E>>) -> bool {
+    match tokio::time::timeout(CHECK_TIMEOUT, probe).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            tracing::warn!("Health check failed: {}", e);
+            false
+        }
+        Err(_) => {
+            tracing::warn!("Health check timed out after {:?}", CHECK_TIMEOUT);
+            false
+        }
+    }
+}
+
+fn component_status(status: Option<bool>) -> &'static str {
+    match status {
+        Some(true) => "ok",
+        Some(false) => "down",
+        None => "disabled",
+    }
+}
@@ -1,8 +1,15 @@
 pub mod admin_settings;
+pub mod analytics;
+pub mod api_key;
 pub mod audit_log;
 pub mod auth;
 pub mod comment;
+pub mod health;
 pub mod portfolio;
 pub mod post;
+pub mod search;
 pub mod service;
+pub mod sitemap;
+pub mod upload;
+pub mod user;
 pub mod user_notification;
@@ -1,120 +1,199 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{Json, Response},
 };
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
+    models::audit_log::{AuditAction, ResourceType},
     models::portfolio::{
-        CreatePortfolioProjectRequest, PortfolioProjectQuery, UpdatePortfolioProjectRequest,
+        BulkFeatureProjectsRequest, CreatePortfolioProjectRequest, PortfolioImportResponse,
+        PortfolioProject, PortfolioProjectQuery, PortfolioProjectRecord, PortfolioProjectsResponse,
+        PortfolioStats, ReorderPortfolioProjectsRequest, UpdatePortfolioProjectRequest,
+    },
+    services::{
+        audit_log_service::{audit_snapshots, AuditLogServiceTrait},
+        auth_service::Claims,
+        portfolio_service::PortfolioServiceTrait,
+    },
+    utils::{
+        errors::AppError,
+        etag::{conditional_response, weak_etag},
+        request::{get_client_ip, get_user_agent},
+        response::ApiResponse,
     },
-    services::portfolio_service::PortfolioServiceTrait,
-    utils::errors::AppError,
 };
 
 #[derive(Clone)]
 pub struct PortfolioState {
     pub portfolio_service: Arc<dyn PortfolioServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
 }
 
 // GET /api/v1/portfolio
 pub async fn get_all_projects(
     State(state): State<PortfolioState>,
     Query(query): Query<PortfolioProjectQuery>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<PortfolioProjectsResponse>, AppError> {
     let response = state.portfolio_service.get_all_projects(query).await?;
-    Ok(Json(json!(response)))
+    Ok(ApiResponse::ok(response))
 }
 
 // GET /api/v1/portfolio/:id
 pub async fn get_project(
     State(state): State<PortfolioState>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<Response, AppError> {
     let project = state
         .portfolio_service
         .get_project_by_id(id)
         .await?
         .ok_or_else(|| AppError::NotFound("Portfolio project not found".to_string()))?;
 
-    Ok(Json(json!(project)))
+    let etag = weak_etag(project.id, project.updated_at);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        ApiResponse::ok(project),
+    ))
 }
 
 // GET /api/v1/portfolio/slug/:slug
 pub async fn get_project_by_slug(
     State(state): State<PortfolioState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(slug): Path<String>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<Response, AppError> {
     let project = state
         .portfolio_service
         .get_project_by_slug(&slug)
         .await?
         .ok_or_else(|| AppError::NotFound("Portfolio project not found".to_string()))?;
 
-    Ok(Json(json!(project)))
+    // Inactive projects are hidden from the public site
+    if !project.active {
+        return Err(AppError::NotFound(
+            "Portfolio project not found".to_string(),
+        ));
+    }
+
+    // Record the view (deduplicated per client IP)
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let _ = state
+        .portfolio_service
+        .record_view(project.id, &client_ip)
+        .await;
+
+    let etag = weak_etag(project.id, project.updated_at);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        ApiResponse::ok(project),
+    ))
 }
 
 // POST /api/v1/portfolio
 pub async fn create_project(
     State(state): State<PortfolioState>,
     Json(payload): Json<CreatePortfolioProjectRequest>,
-) -> Result<(StatusCode, Json<Value>), AppError> {
+) -> Result<(StatusCode, ApiResponse<PortfolioProject>), AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
 
     let project = state.portfolio_service.create_project(payload).await?;
 
     Ok((
         StatusCode::CREATED,
-        Json(json!({
-            "message": "Portfolio project created successfully",
-            "project": project
-        })),
+        ApiResponse::with_message(project, "Portfolio project created successfully"),
     ))
 }
 
 // PUT /api/v1/portfolio/:id
 pub async fn update_project(
     State(state): State<PortfolioState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdatePortfolioProjectRequest>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<PortfolioProject>, AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let before = state
+        .portfolio_service
+        .get_project_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Portfolio project not found".to_string()))?;
 
     let project = state.portfolio_service.update_project(id, payload).await?;
 
-    Ok(Json(json!({
-        "message": "Portfolio project updated successfully",
-        "project": project
-    })))
+    let (old_values, new_values) = audit_snapshots(&before, &project);
+    if let Err(e) = state
+        .audit_log_service
+        .log_crud_operation(
+            Uuid::parse_str(&claims.sub).ok(),
+            Some(claims.username.clone()),
+            &AuditAction::PortfolioUpdated.to_string(),
+            &ResourceType::Portfolio.to_string(),
+            id,
+            Some(project.title.clone()),
+            old_values,
+            new_values,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log project update: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(
+        project,
+        "Portfolio project updated successfully",
+    ))
+}
+
+// PUT /api/v1/portfolio/reorder
+pub async fn reorder_projects(
+    State(state): State<PortfolioState>,
+    Json(payload): Json<ReorderPortfolioProjectsRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    payload.validate()?;
+
+    state.portfolio_service.reorder_projects(payload).await?;
+
+    Ok(ApiResponse::message(
+        "Portfolio projects reordered successfully",
+    ))
 }
 
 // DELETE /api/v1/portfolio/:id
 pub async fn delete_project(
     State(state): State<PortfolioState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     state.portfolio_service.delete_project(id).await?;
 
-    Ok(Json(json!({
-        "message": "Portfolio project deleted successfully"
-    })))
+    Ok(ApiResponse::message(
+        "Portfolio project deleted successfully",
+    ))
 }
 
 // GET /api/v1/portfolio/featured
 pub async fn get_featured_projects(
     State(state): State<PortfolioState>,
     Query(query): Query<serde_json::Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let limit = query
         .get("limit")
         .and_then(|v| v.as_u64())
@@ -122,7 +201,7 @@ pub async fn get_featured_projects(
 
     let projects = state.portfolio_service.get_featured_projects(limit).await?;
 
-    Ok(Json(json!({
+    Ok(ApiResponse::ok(json!({
         "projects": projects,
         "total": projects.len()
     })))
@@ -131,9 +210,121 @@ pub async fn get_featured_projects(
 // GET /api/v1/portfolio/stats
 pub async fn get_portfolio_stats(
     State(state): State<PortfolioState>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<PortfolioStats>, AppError> {
     let stats = state.portfolio_service.get_portfolio_statistics().await?;
-    Ok(Json(json!(stats)))
+    Ok(ApiResponse::ok(stats))
+}
+
+// PUT /api/v1/portfolio/bulk-featured
+pub async fn bulk_update_featured(
+    State(state): State<PortfolioState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<BulkFeatureProjectsRequest>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let ids = payload.ids.clone();
+    let featured = payload.featured;
+    let updated_count = state
+        .portfolio_service
+        .bulk_update_featured(payload)
+        .await?;
+
+    let action = if featured {
+        AuditAction::PortfolioFeatured
+    } else {
+        AuditAction::PortfolioUnfeatured
+    };
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            &action.to_string(),
+            &ResourceType::Portfolio.to_string(),
+            None,
+            None,
+            Some(format!(
+                "Bulk {} {} projects",
+                if featured { "featured" } else { "unfeatured" },
+                updated_count
+            )),
+            None,
+            Some(json!({ "ids": ids, "featured": featured })),
+            true,
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log bulk featured update: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(
+        json!({ "updated_count": updated_count }),
+        "Featured status updated successfully",
+    ))
+}
+
+// GET /api/v1/portfolio/export
+pub async fn export_projects(
+    State(state): State<PortfolioState>,
+) -> Result<Json<Vec<PortfolioProjectRecord>>, AppError> {
+    // A bare array, so the file can be posted back to /import unchanged
+    let records = state.portfolio_service.export_projects().await?;
+    Ok(Json(records))
+}
+
+// POST /api/v1/portfolio/import
+pub async fn import_projects(
+    State(state): State<PortfolioState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<Vec<Value>>,
+) -> Result<ApiResponse<PortfolioImportResponse>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let response = state.portfolio_service.import_projects(payload).await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            &AuditAction::PortfolioImported.to_string(),
+            &ResourceType::Portfolio.to_string(),
+            None,
+            None,
+            Some(format!(
+                "Imported projects: {} created, {} updated, {} skipped",
+                response.created, response.updated, response.skipped
+            )),
+            None,
+            Some(json!({
+                "created": response.created,
+                "updated": response.updated,
+                "skipped": response.skipped,
+            })),
+            true,
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log portfolio import: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(
+        response,
+        "Portfolio import completed",
+    ))
 }
 
 // PUT /api/v1/portfolio/:id/featured
@@ -141,7 +332,7 @@ pub async fn update_featured_status(
     State(state): State<PortfolioState>,
     Path(id): Path<Uuid>,
     Json(payload): Json<Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     let featured = payload
         .get("featured")
         .and_then(|v| v.as_bool())
@@ -152,7 +343,5 @@ pub async fn update_featured_status(
         .toggle_featured_status(id, featured)
         .await?;
 
-    Ok(Json(json!({
-        "message": "Featured status updated successfully"
-    })))
+    Ok(ApiResponse::message("Featured status updated successfully"))
 }
@@ -1,184 +1,510 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    body::Bytes,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tracing::{error, info};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    models::post::{CreatePostRequest, PostQuery, UpdatePostRequest},
-    services::blog_service::BlogServiceTrait,
-    utils::errors::AppError,
+    models::audit_log::{AuditAction, ResourceType},
+    models::post::{
+        BulkDeletePostsRequest, BulkFeaturePostsRequest, CreatePostRequest, Post,
+        PostImportDocument, PostImportQuery, PostImportResponse, PostPreviewLink,
+        PostPreviewLinkQuery, PostPreviewTokenQuery, PostQuery, PostResponse, PostSearchQuery,
+        PostStats, PostViewsQuery, PostsResponse, PublishedPostsQuery, TagQuery, UpdatePostRequest,
+    },
+    services::{
+        audit_log_service::{audit_snapshots, AuditLogServiceTrait},
+        auth_service::Claims,
+        blog_service::BlogServiceTrait,
+    },
+    utils::{
+        errors::AppError,
+        etag::{conditional_response, weak_etag},
+        frontmatter::read_markdown_archive,
+        request::{get_client_ip, get_user_agent},
+        response::ApiResponse,
+    },
 };
 
 #[derive(Clone)]
 pub struct PostState {
     pub blog_service: Arc<dyn BlogServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
 }
 
 // GET /api/v1/posts
 pub async fn get_all_posts(
     State(state): State<PostState>,
-    Query(query): Query<PostQuery>,
-) -> Result<Json<Value>, AppError> {
+    Query(mut query): Query<PostQuery>,
+) -> Result<ApiResponse<PostsResponse>, AppError> {
+    // Drafts are only listed through the admin route
+    query.published = Some(true);
+
     let response = state.blog_service.get_all_posts(query).await?;
-    Ok(Json(json!(response)))
+    Ok(ApiResponse::ok(response))
 }
 
 // GET /api/v1/posts/:id
 pub async fn get_post(
     State(state): State<PostState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+    Query(query): Query<PostPreviewTokenQuery>,
+) -> Result<Response, AppError> {
     let post = state
         .blog_service
         .get_post_by_id(id)
         .await?
         .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
 
-    // Increment view count
-    let _ = state.blog_service.increment_view_count(id).await;
-
-    Ok(Json(json!(post)))
+    public_post_response(&state, post, &headers, addr, query.preview_token.as_deref()).await
 }
 
 // GET /api/v1/posts/slug/:slug
 pub async fn get_post_by_slug(
     State(state): State<PostState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(slug): Path<String>,
-    Query(query): Query<serde_json::Value>,
-) -> Result<Json<Value>, AppError> {
+    Query(query): Query<PostPreviewTokenQuery>,
+) -> Result<Response, AppError> {
     let post = state
         .blog_service
         .get_post_by_slug(&slug)
         .await?
         .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
 
-    // Check if this is a preview request
-    let is_preview = query
-        .get("preview")
-        .and_then(|v| v.as_str())
-        .map(|s| s == "true")
-        .unwrap_or(false);
+    public_post_response(&state, post, &headers, addr, query.preview_token.as_deref()).await
+}
 
-    // If not in preview mode and post is not published, return 404
-    if !is_preview && !post.published {
-        return Err(AppError::NotFound("Post not found".to_string()));
-    }
+// Unpublished posts are only readable with a preview link signed for this post
+async fn public_post_response(
+    state: &PostState,
+    post: Post,
+    headers: &HeaderMap,
+    addr: SocketAddr,
+    preview_token: Option<&str>,
+) -> Result<Response, AppError> {
+    if !post.published {
+        let authorized = preview_token
+            .is_some_and(|token| state.blog_service.verify_preview_token(post.id, token));
+        if !authorized {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
 
-    // Only increment view count for published posts (not previews)
-    if post.published && !is_preview {
-        let _ = state.blog_service.increment_view_count(post.id).await;
+        return Ok(private_response(ApiResponse::ok(PostResponse::from(post))));
     }
 
-    Ok(Json(json!(post)))
+    let client_ip = get_client_ip(headers, Some(&addr));
+    let _ = state.blog_service.record_view(post.id, &client_ip).await;
+
+    let etag = weak_etag(post.id, post.updated_at);
+    Ok(conditional_response(
+        headers,
+        etag,
+        ApiResponse::ok(PostResponse::from(post)),
+    ))
+}
+
+// Drafts must never land in a shared cache
+fn private_response(response: impl IntoResponse) -> Response {
+    let mut response = response.into_response();
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("private, no-store"),
+    );
+    response
+}
+
+// GET /api/v1/admin/posts
+pub async fn get_all_posts_admin(
+    State(state): State<PostState>,
+    Query(query): Query<PostQuery>,
+) -> Result<ApiResponse<PostsResponse>, AppError> {
+    let response = state.blog_service.get_all_posts(query).await?;
+    Ok(ApiResponse::ok(response))
+}
+
+// GET /api/v1/admin/posts/:id
+pub async fn get_post_admin(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<PostResponse>, AppError> {
+    let post = state
+        .blog_service
+        .get_post_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    Ok(ApiResponse::ok(PostResponse::from(post)))
+}
+
+// POST /api/v1/posts/:id/preview-link
+pub async fn create_preview_link(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PostPreviewLinkQuery>,
+) -> Result<ApiResponse<PostPreviewLink>, AppError> {
+    let link = state
+        .blog_service
+        .create_preview_link(id, query.expires_in_hours)
+        .await?;
+    Ok(ApiResponse::with_message(link, "Preview link created"))
 }
 
 // POST /api/v1/posts
 pub async fn create_post(
     State(state): State<PostState>,
     Json(payload): Json<CreatePostRequest>,
-) -> Result<(StatusCode, Json<Value>), AppError> {
+) -> Result<(StatusCode, ApiResponse<Post>), AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
 
     let post = state.blog_service.create_post(payload).await?;
 
     Ok((
         StatusCode::CREATED,
-        Json(json!({
-            "message": "Post created successfully",
-            "post": post
-        })),
+        ApiResponse::with_message(post, "Post created successfully"),
     ))
 }
 
+// POST /api/v1/posts/import
+pub async fn import_posts(
+    State(state): State<PostState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<PostImportQuery>,
+    body: Bytes,
+) -> Result<ApiResponse<PostImportResponse>, AppError> {
+    // A zip of markdown files, otherwise a JSON array of posts
+    let is_zip = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("zip"));
+    let documents = if is_zip {
+        read_markdown_archive(&body)?
+            .into_iter()
+            .map(|(filename, contents)| PostImportDocument::Markdown { filename, contents })
+            .collect()
+    } else {
+        serde_json::from_slice::<Vec<Value>>(&body)
+            .map_err(|e| AppError::Validation(format!("Expected a JSON array of posts: {}", e)))?
+            .into_iter()
+            .map(PostImportDocument::Json)
+            .collect()
+    };
+
+    let response = state
+        .blog_service
+        .import_posts(documents, query.mode)
+        .await?;
+
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Uuid::parse_str(&claims.sub).ok(),
+            Some(claims.username.clone()),
+            &AuditAction::PostsImported.to_string(),
+            &ResourceType::Post.to_string(),
+            None,
+            None,
+            Some(format!(
+                "Imported posts: {} created, {} updated, {} skipped, {} failed",
+                response.created, response.updated, response.skipped, response.failed
+            )),
+            None,
+            Some(json!({
+                "mode": query.mode,
+                "created": response.created,
+                "updated": response.updated,
+                "skipped": response.skipped,
+                "failed": response.failed,
+            })),
+            true,
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log post import: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(response, "Post import completed"))
+}
+
 // PUT /api/v1/posts/:id
 pub async fn update_post(
     State(state): State<PostState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdatePostRequest>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Post>, AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let before = state
+        .blog_service
+        .get_post_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
 
     let post = state.blog_service.update_post(id, payload).await?;
 
-    Ok(Json(json!({
-        "message": "Post updated successfully",
-        "post": post
-    })))
+    let (old_values, new_values) = audit_snapshots(&before, &post);
+    if let Err(e) = state
+        .audit_log_service
+        .log_crud_operation(
+            Uuid::parse_str(&claims.sub).ok(),
+            Some(claims.username.clone()),
+            &AuditAction::PostUpdated.to_string(),
+            &ResourceType::Post.to_string(),
+            id,
+            Some(post.title.clone()),
+            old_values,
+            new_values,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log post update: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(post, "Post updated successfully"))
 }
 
 // DELETE /api/v1/posts/:id
 pub async fn delete_post(
     State(state): State<PostState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     state.blog_service.delete_post(id).await?;
 
-    Ok(Json(json!({
-        "message": "Post deleted successfully"
-    })))
+    Ok(ApiResponse::message("Post deleted successfully"))
+}
+
+// DELETE /api/v1/posts/bulk
+pub async fn bulk_delete_posts(
+    State(state): State<PostState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<BulkDeletePostsRequest>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let deleted = state.blog_service.bulk_delete_posts(payload).await?;
+
+    for (id, title) in &deleted {
+        if let Err(e) = state
+            .audit_log_service
+            .log_admin_action(
+                Some(user_id),
+                Some(claims.username.clone()),
+                "post_deleted",
+                "post",
+                Some(*id),
+                Some(title.clone()),
+                Some("Deleted as part of a bulk delete".to_string()),
+                None,
+                None,
+                true,
+                None,
+                Some(client_ip.clone()),
+                user_agent.clone(),
+            )
+            .await
+        {
+            tracing::warn!("Failed to log bulk post deletion: {}", e);
+        }
+    }
+
+    Ok(ApiResponse::with_message(
+        json!({
+            "deleted_count": deleted.len(),
+            "deleted_ids": deleted.iter().map(|(id, _)| id).collect::<Vec<_>>()
+        }),
+        "Posts deleted successfully",
+    ))
+}
+
+// PUT /api/v1/posts/bulk-featured
+pub async fn bulk_update_featured(
+    State(state): State<PostState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<BulkFeaturePostsRequest>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let ids = payload.ids.clone();
+    let featured = payload.featured;
+    let updated_count = state.blog_service.bulk_update_featured(payload).await?;
+
+    let action = if featured {
+        AuditAction::PostFeatured
+    } else {
+        AuditAction::PostUnfeatured
+    };
+    if let Err(e) = state
+        .audit_log_service
+        .log_admin_action(
+            Some(user_id),
+            Some(claims.username.clone()),
+            &action.to_string(),
+            &ResourceType::Post.to_string(),
+            None,
+            None,
+            Some(format!(
+                "Bulk {} {} posts",
+                if featured { "featured" } else { "unfeatured" },
+                updated_count
+            )),
+            None,
+            Some(json!({ "ids": ids, "featured": featured })),
+            true,
+            None,
+            Some(get_client_ip(&headers, Some(&addr))),
+            get_user_agent(&headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log bulk featured update: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(
+        json!({ "updated_count": updated_count }),
+        "Featured status updated successfully",
+    ))
+}
+
+// PUT /api/v1/posts/:id/restore
+pub async fn restore_post(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    state.blog_service.restore_post(id).await?;
+
+    Ok(ApiResponse::message("Post restored successfully"))
+}
+
+// DELETE /api/v1/posts/:id/purge
+pub async fn purge_post(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    state.blog_service.purge_post(id).await?;
+
+    Ok(ApiResponse::message("Post permanently deleted"))
 }
 
 // GET /api/v1/posts/published
 pub async fn get_published_posts(
     State(state): State<PostState>,
-    Query(query): Query<serde_json::Value>,
-) -> Result<Json<Value>, AppError> {
+    Query(query): Query<PublishedPostsQuery>,
+) -> Result<ApiResponse<PostsResponse>, AppError> {
     info!(
         "get_published_posts: Starting request with query: {:?}",
         query
     );
 
-    let limit = query
-        .get("limit")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
-
-    info!("get_published_posts: Parsed limit: {:?}", limit);
-
-    info!("get_published_posts: Calling blog_service.get_published_posts");
-    let posts = match state.blog_service.get_published_posts(limit).await {
-        Ok(posts) => {
-            info!(
-                "get_published_posts: Successfully fetched {} posts",
-                posts.len()
-            );
-            posts
-        }
+    let response = match state.blog_service.get_published_posts(query).await {
+        Ok(response) => response,
         Err(e) => {
             error!("get_published_posts: Error fetching posts: {:?}", e);
             return Err(e);
         }
     };
 
-    let response = json!({
+    info!(
+        "get_published_posts: Returning page {} of {} ({} published posts)",
+        response.page, response.total_pages, response.total
+    );
+    Ok(ApiResponse::ok(response))
+}
+
+// GET /api/v1/posts/:id/related
+pub async fn get_related_posts(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<serde_json::Value>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let limit = query
+        .get("limit")
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse::<u32>().ok());
+
+    let posts = state.blog_service.get_related_posts(id, limit).await?;
+    let posts: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+
+    Ok(ApiResponse::ok(json!({
         "posts": posts,
         "total": posts.len()
-    });
+    })))
+}
 
-    info!(
-        "get_published_posts: Returning response with {} posts",
-        posts.len()
-    );
-    Ok(Json(response))
+// GET /api/v1/admin/posts/:id/views
+pub async fn get_post_views(
+    State(state): State<PostState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PostViewsQuery>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let series = state
+        .blog_service
+        .get_view_timeseries(id, query.days.unwrap_or(30))
+        .await?;
+    let total: i64 = series.iter().map(|day| day.views).sum();
+
+    Ok(ApiResponse::ok(json!({
+        "post_id": id,
+        "days": series.len(),
+        "total": total,
+        "series": series
+    })))
+}
+
+// GET /api/v1/posts/search
+pub async fn search_posts(
+    State(state): State<PostState>,
+    Query(query): Query<PostSearchQuery>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let posts = state
+        .blog_service
+        .search_posts(&query.q, query.limit)
+        .await?;
+    let posts: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+
+    Ok(ApiResponse::ok(json!({
+        "posts": posts,
+        "total": posts.len()
+    })))
 }
 
 // GET /api/v1/posts/featured
 pub async fn get_featured_posts(
     State(state): State<PostState>,
     Query(query): Query<serde_json::Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let limit = query
         .get("limit")
         .and_then(|v| v.as_u64())
@@ -186,18 +512,43 @@ pub async fn get_featured_posts(
 
     let posts = state.blog_service.get_featured_posts(limit).await?;
 
-    Ok(Json(json!({
+    Ok(ApiResponse::ok(json!({
         "posts": posts,
         "total": posts.len()
     })))
 }
 
+// GET /api/v1/posts/tags
+pub async fn get_tags(
+    State(state): State<PostState>,
+    Query(query): Query<TagQuery>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let tags = state.blog_service.get_tag_counts(query.limit).await?;
+
+    Ok(ApiResponse::ok(json!({
+        "tags": tags,
+        "total": tags.len()
+    })))
+}
+
+// GET /api/v1/posts/categories
+pub async fn get_categories(
+    State(state): State<PostState>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let categories = state.blog_service.get_category_counts().await?;
+
+    Ok(ApiResponse::ok(json!({
+        "categories": categories,
+        "total": categories.len()
+    })))
+}
+
 // GET /api/v1/posts/category/:category
 pub async fn get_posts_by_category(
     State(state): State<PostState>,
     Path(category): Path<String>,
     Query(query): Query<serde_json::Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let limit = query
         .get("limit")
         .and_then(|v| v.as_u64())
@@ -208,7 +559,7 @@ pub async fn get_posts_by_category(
         .get_posts_by_category(&category, limit)
         .await?;
 
-    Ok(Json(json!({
+    Ok(ApiResponse::ok(json!({
         "posts": posts,
         "category": category,
         "total": posts.len()
@@ -219,7 +570,7 @@ pub async fn get_posts_by_category(
 pub async fn get_posts_by_tags(
     State(state): State<PostState>,
     Json(payload): Json<Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let tags = payload
         .get("tags")
         .and_then(|v| v.as_array())
@@ -239,7 +590,7 @@ pub async fn get_posts_by_tags(
         .get_posts_by_tags(tags.clone(), limit)
         .await?;
 
-    Ok(Json(json!({
+    Ok(ApiResponse::ok(json!({
         "posts": posts,
         "tags": tags,
         "total": posts.len()
@@ -247,9 +598,11 @@ pub async fn get_posts_by_tags(
 }
 
 // GET /api/v1/posts/stats
-pub async fn get_post_stats(State(state): State<PostState>) -> Result<Json<Value>, AppError> {
+pub async fn get_post_stats(
+    State(state): State<PostState>,
+) -> Result<ApiResponse<PostStats>, AppError> {
     let stats = state.blog_service.get_blog_statistics().await?;
-    Ok(Json(json!(stats)))
+    Ok(ApiResponse::ok(stats))
 }
 
 // PUT /api/v1/posts/:id/publish
@@ -257,7 +610,7 @@ pub async fn update_published_status(
     State(state): State<PostState>,
     Path(id): Path<Uuid>,
     Json(payload): Json<Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     let published = payload
         .get("published")
         .and_then(|v| v.as_bool())
@@ -269,7 +622,7 @@ pub async fn update_published_status(
         state.blog_service.unpublish_post(id).await?;
     }
 
-    Ok(Json(json!({
-        "message": "Published status updated successfully"
-    })))
+    Ok(ApiResponse::message(
+        "Published status updated successfully",
+    ))
 }
@@ -0,0 +1,35 @@
+use axum::extract::{Query, State};
+use std::sync::Arc;
+
+use crate::{
+    middleware::feature_flags::FeatureFlagCache,
+    models::search::{SearchQuery, SearchResponse, SearchResultKind},
+    services::search_service::SearchServiceTrait,
+    utils::{errors::AppError, response::ApiResponse},
+};
+
+#[derive(Clone)]
+pub struct SearchState {
+    pub search_service: Arc<dyn SearchServiceTrait>,
+    pub feature_flags: FeatureFlagCache,
+}
+
+// GET /api/v1/search
+pub async fn search(
+    State(state): State<SearchState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<ApiResponse<SearchResponse>, AppError> {
+    // Content from a disabled section stays hidden from search as well
+    let flags = state.feature_flags.get();
+    let kinds: Vec<SearchResultKind> = [
+        (SearchResultKind::Post, flags.blog_enabled),
+        (SearchResultKind::Portfolio, flags.portfolio_enabled),
+        (SearchResultKind::Service, flags.services_enabled),
+    ]
+    .into_iter()
+    .filter_map(|(kind, enabled)| enabled.then_some(kind))
+    .collect();
+
+    let response = state.search_service.search(query, &kinds).await?;
+    Ok(ApiResponse::ok(response))
+}
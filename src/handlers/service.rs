@@ -1,115 +1,175 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{Json, Response},
 };
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    models::service::{CreateServiceRequest, ServiceQuery, UpdateServiceRequest},
-    services::service_service::ServiceServiceTrait,
-    utils::errors::AppError,
+    models::audit_log::{AuditAction, ResourceType},
+    models::service::{
+        CreateServiceRequest, ReorderServicesRequest, ServiceQuery, ServiceResponse, ServiceStats,
+        ServicesResponse, UpdateServiceRequest,
+    },
+    services::{
+        audit_log_service::{audit_snapshots, AuditLogServiceTrait},
+        auth_service::Claims,
+        service_service::ServiceServiceTrait,
+    },
+    utils::{
+        errors::AppError,
+        etag::{conditional_response, weak_etag},
+        request::{get_client_ip, get_user_agent},
+        response::ApiResponse,
+    },
 };
 
 #[derive(Clone)]
 pub struct ServiceState {
     pub service_service: Arc<dyn ServiceServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
 }
 
 // GET /api/v1/services
 pub async fn get_all_services(
     State(state): State<ServiceState>,
     Query(query): Query<ServiceQuery>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<ServicesResponse>, AppError> {
     let response = state.service_service.get_all_services(query).await?;
-    Ok(Json(json!(response)))
+    Ok(ApiResponse::ok(response))
 }
 
 // GET /api/v1/services/:id
 pub async fn get_service(
     State(state): State<ServiceState>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<Response, AppError> {
     let service = state
         .service_service
         .get_service_by_id(id)
         .await?
         .ok_or_else(|| AppError::NotFound("Service not found".to_string()))?;
 
-    Ok(Json(json!(service)))
+    let etag = weak_etag(service.id, service.updated_at);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        ApiResponse::ok(ServiceResponse::from(service)),
+    ))
 }
 
 // POST /api/v1/services
 pub async fn create_service(
     State(state): State<ServiceState>,
     Json(payload): Json<CreateServiceRequest>,
-) -> Result<(StatusCode, Json<Value>), AppError> {
+) -> Result<(StatusCode, ApiResponse<ServiceResponse>), AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
 
     let service = state.service_service.create_service(payload).await?;
 
     Ok((
         StatusCode::CREATED,
-        Json(json!({
-            "message": "Service created successfully",
-            "service": service
-        })),
+        ApiResponse::with_message(
+            ServiceResponse::from(service),
+            "Service created successfully",
+        ),
     ))
 }
 
 // PUT /api/v1/services/:id
 pub async fn update_service(
     State(state): State<ServiceState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateServiceRequest>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<ServiceResponse>, AppError> {
     // Validate the request
-    payload
-        .validate()
-        .map_err(|e| AppError::Validation(e.to_string()))?;
+    payload.validate()?;
+
+    let client_ip = get_client_ip(&headers, Some(&addr));
+    let user_agent = get_user_agent(&headers);
+
+    let before = state
+        .service_service
+        .get_service_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Service not found".to_string()))?;
 
     let service = state.service_service.update_service(id, payload).await?;
 
-    Ok(Json(json!({
-        "message": "Service updated successfully",
-        "service": service
-    })))
+    let (old_values, new_values) = audit_snapshots(&before, &service);
+    if let Err(e) = state
+        .audit_log_service
+        .log_crud_operation(
+            Uuid::parse_str(&claims.sub).ok(),
+            Some(claims.username.clone()),
+            &AuditAction::ServiceUpdated.to_string(),
+            &ResourceType::Service.to_string(),
+            id,
+            Some(service.title.clone()),
+            old_values,
+            new_values,
+            Some(client_ip),
+            user_agent,
+        )
+        .await
+    {
+        tracing::warn!("Failed to log service update: {}", e);
+    }
+
+    Ok(ApiResponse::with_message(
+        ServiceResponse::from(service),
+        "Service updated successfully",
+    ))
+}
+
+// PUT /api/v1/services/reorder
+pub async fn reorder_services(
+    State(state): State<ServiceState>,
+    Json(payload): Json<ReorderServicesRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    payload.validate()?;
+
+    state.service_service.reorder_services(payload).await?;
+
+    Ok(ApiResponse::message("Services reordered successfully"))
 }
 
 // DELETE /api/v1/services/:id
 pub async fn delete_service(
     State(state): State<ServiceState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     state.service_service.delete_service(id).await?;
 
-    Ok(Json(json!({
-        "message": "Service deleted successfully"
-    })))
+    Ok(ApiResponse::message("Service deleted successfully"))
 }
 
 // GET /api/v1/services/active
 pub async fn get_active_services(
     State(state): State<ServiceState>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let services = state.service_service.get_active_services().await?;
 
-    Ok(Json(json!({
-        "services": services,
-        "total": services.len()
+    Ok(ApiResponse::ok(json!({
+        "total": services.len(),
+        "services": services.into_iter().map(ServiceResponse::from).collect::<Vec<_>>()
     })))
 }
 
 // GET /api/v1/services/stats
-pub async fn get_service_stats(State(state): State<ServiceState>) -> Result<Json<Value>, AppError> {
+pub async fn get_service_stats(
+    State(state): State<ServiceState>,
+) -> Result<ApiResponse<ServiceStats>, AppError> {
     let stats = state.service_service.get_service_statistics().await?;
-    Ok(Json(json!(stats)))
+    Ok(ApiResponse::ok(stats))
 }
 
 // PUT /api/v1/services/:id/status
@@ -117,7 +177,7 @@ pub async fn update_service_status(
     State(state): State<ServiceState>,
     Path(id): Path<Uuid>,
     Json(payload): Json<Value>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<()>, AppError> {
     let active = payload
         .get("active")
         .and_then(|v| v.as_bool())
@@ -128,22 +188,20 @@ pub async fn update_service_status(
         .toggle_service_status(id, active)
         .await?;
 
-    Ok(Json(json!({
-        "message": "Service status updated successfully"
-    })))
+    Ok(ApiResponse::message("Service status updated successfully"))
 }
 
 // GET /api/v1/services/category/:category
 pub async fn get_services_by_category(
     State(state): State<ServiceState>,
     Path(category): Path<String>,
-) -> Result<Json<Value>, AppError> {
+) -> Result<ApiResponse<Value>, AppError> {
     let services = state
         .service_service
         .get_services_by_category(&category)
         .await?;
 
-    Ok(Json(json!({
+    Ok(ApiResponse::ok(json!({
         "services": services,
         "category": category,
         "total": services.len()
@@ -0,0 +1,81 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::{
+    services::{
+        admin_settings_service::AdminSettingsServiceTrait, blog_service::BlogServiceTrait,
+        portfolio_service::PortfolioServiceTrait, service_service::ServiceServiceTrait,
+    },
+    utils::{errors::AppError, sitemap::SitemapBuilder},
+};
+
+#[derive(Clone)]
+pub struct SitemapState {
+    pub blog_service: Arc<dyn BlogServiceTrait>,
+    pub portfolio_service: Arc<dyn PortfolioServiceTrait>,
+    pub service_service: Arc<dyn ServiceServiceTrait>,
+    pub admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+}
+
+// GET /sitemap.xml
+pub async fn get_sitemap(State(state): State<SitemapState>) -> Result<Response, AppError> {
+    let settings = state
+        .admin_settings_service
+        .get_all_settings()
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to get settings: {}", e)))?;
+
+    let base_url = settings
+        .general
+        .site_url
+        .filter(|url| !url.trim().is_empty())
+        .ok_or_else(|| AppError::Internal("Site URL is not configured".to_string()))?;
+
+    let posts = state.blog_service.get_published_slugs().await?;
+    let projects = state.portfolio_service.get_active_slugs().await?;
+    let services = state.service_service.get_active_services().await?;
+
+    let mut sitemap = SitemapBuilder::new(&base_url);
+    sitemap
+        .add("/", None, "weekly", 1.0)
+        .add(
+            "blog",
+            posts.iter().map(|(_, updated_at)| *updated_at).max(),
+            "daily",
+            0.9,
+        )
+        .add("portfolio", None, "weekly", 0.8)
+        .add("services", None, "monthly", 0.8);
+
+    for (slug, updated_at) in &posts {
+        sitemap.add(&format!("blog/{}", slug), Some(*updated_at), "monthly", 0.7);
+    }
+
+    for (slug, updated_at) in &projects {
+        sitemap.add(
+            &format!("portfolio/{}", slug),
+            Some(*updated_at),
+            "monthly",
+            0.6,
+        );
+    }
+
+    for service in &services {
+        sitemap.add(
+            &format!("services/{}", service.id),
+            Some(service.updated_at),
+            "monthly",
+            0.5,
+        );
+    }
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        sitemap.build(),
+    )
+        .into_response())
+}
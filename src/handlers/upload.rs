@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Datelike, Utc};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    services::storage::StorageBackend,
+    utils::{config::BLOCKED_UPLOAD_TYPES, errors::AppError},
+};
+
+#[derive(Clone)]
+pub struct UploadState {
+    pub storage: Arc<dyn StorageBackend>,
+    pub max_size: usize,
+    pub allowed_types: Vec<String>,
+}
+
+fn extension_for(content_type: &str) -> &'static str {
+    match content_type {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        _ => "bin",
+    }
+}
+
+// POST /api/v1/uploads
+pub async fn upload_file(
+    State(state): State<UploadState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let content_type = field
+            .content_type()
+            .map(|ct| ct.to_ascii_lowercase())
+            .ok_or_else(|| AppError::Validation("File content type is required".to_string()))?;
+        if BLOCKED_UPLOAD_TYPES.contains(&content_type.as_str())
+            || !state.allowed_types.iter().any(|t| t == &content_type)
+        {
+            return Err(AppError::Validation(format!(
+                "File type {} is not allowed; allowed types: {}",
+                content_type,
+                state.allowed_types.join(", ")
+            )));
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .chunk()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?
+        {
+            if data.len() + chunk.len() > state.max_size {
+                return Err(AppError::Validation(format!(
+                    "File exceeds the maximum size of {} bytes",
+                    state.max_size
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+        if data.is_empty() {
+            return Err(AppError::Validation("File is empty".to_string()));
+        }
+
+        let now = Utc::now();
+        let key = format!(
+            "{:04}/{:02}/{}.{}",
+            now.year(),
+            now.month(),
+            Uuid::new_v4(),
+            extension_for(&content_type)
+        );
+        let url = state
+            .storage
+            .store(&key, &content_type, &data)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to store upload: {:#}", e)))?;
+
+        return Ok((
+            StatusCode::CREATED,
+            Json(json!({
+                "message": "File uploaded successfully",
+                "url": url,
+                "key": key,
+                "content_type": content_type,
+                "size": data.len()
+            })),
+        ));
+    }
+
+    Err(AppError::Validation(
+        "Missing \"file\" field in multipart body".to_string(),
+    ))
+}
+
+// DELETE /api/v1/uploads/*key
+pub async fn delete_file(
+    State(state): State<UploadState>,
+    Path(key): Path<String>,
+) -> Result<Json<Value>, AppError> {
+    if key
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "..")
+    {
+        return Err(AppError::BadRequest("Invalid upload key".to_string()));
+    }
+
+    state
+        .storage
+        .delete(&key)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to delete upload: {:#}", e)))?;
+
+    Ok(Json(json!({
+        "message": "File deleted successfully"
+    })))
+}
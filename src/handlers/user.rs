@@ -0,0 +1,136 @@
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::HeaderMap,
+    response::Json,
+};
+use std::{net::SocketAddr, sync::Arc};
+use uuid::Uuid;
+
+use crate::{
+    models::audit_log::{AuditAction, ResourceType},
+    models::user::{
+        UpdateUserRoleRequest, UpdateUserStatusRequest, User, UserQuery, UserResponse,
+        UsersResponse,
+    },
+    services::{
+        audit_log_service::{audit_snapshots, AuditLogServiceTrait},
+        auth_service::Claims,
+        user_service::UserServiceTrait,
+    },
+    utils::{
+        errors::AppError,
+        request::{get_client_ip, get_user_agent},
+        response::ApiResponse,
+    },
+};
+
+#[derive(Clone)]
+pub struct UserState {
+    pub user_service: Arc<dyn UserServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
+}
+
+async fn log_user_change(
+    state: &UserState,
+    claims: &Claims,
+    action: AuditAction,
+    before: &User,
+    after: &User,
+    headers: &HeaderMap,
+    addr: SocketAddr,
+) {
+    let (old_values, new_values) = audit_snapshots(before, after);
+    if let Err(e) = state
+        .audit_log_service
+        .log_crud_operation(
+            Uuid::parse_str(&claims.sub).ok(),
+            Some(claims.username.clone()),
+            &action.to_string(),
+            &ResourceType::User.to_string(),
+            after.id,
+            Some(after.username.clone()),
+            old_values,
+            new_values,
+            Some(get_client_ip(headers, Some(&addr))),
+            get_user_agent(headers),
+        )
+        .await
+    {
+        tracing::warn!("Failed to log user change: {}", e);
+    }
+}
+
+// GET /api/v1/admin/users
+pub async fn list_users(
+    State(state): State<UserState>,
+    Query(query): Query<UserQuery>,
+) -> Result<ApiResponse<UsersResponse>, AppError> {
+    let users = state.user_service.list_users(query).await?;
+    Ok(ApiResponse::ok(users))
+}
+
+// PUT /api/v1/admin/users/:id/status
+pub async fn update_user_status(
+    State(state): State<UserState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUserStatusRequest>,
+) -> Result<ApiResponse<UserResponse>, AppError> {
+    let before = state.user_service.get_user(id).await?;
+    let user = state
+        .user_service
+        .set_user_active(id, payload.is_active)
+        .await?;
+
+    if before.is_active != user.is_active {
+        let action = if user.is_active {
+            AuditAction::UserReactivated
+        } else {
+            AuditAction::UserDeactivated
+        };
+        log_user_change(&state, &claims, action, &before, &user, &headers, addr).await;
+    }
+
+    let message = if user.is_active {
+        "User reactivated"
+    } else {
+        "User deactivated"
+    };
+    Ok(ApiResponse::with_message(UserResponse::from(user), message))
+}
+
+// PUT /api/v1/admin/users/:id/role
+pub async fn update_user_role(
+    State(state): State<UserState>,
+    claims: Claims,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUserRoleRequest>,
+) -> Result<ApiResponse<UserResponse>, AppError> {
+    let before = state.user_service.get_user(id).await?;
+    let user = state
+        .user_service
+        .change_user_role(id, payload.role)
+        .await?;
+
+    if before.role != user.role {
+        log_user_change(
+            &state,
+            &claims,
+            AuditAction::UserRoleChanged,
+            &before,
+            &user,
+            &headers,
+            addr,
+        )
+        .await;
+    }
+
+    Ok(ApiResponse::with_message(
+        UserResponse::from(user),
+        "User role updated",
+    ))
+}
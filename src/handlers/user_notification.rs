@@ -1,25 +1,33 @@
 use axum::{
-    extract::{Query, State},
-    response::Json,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::{Json, Response},
 };
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tracing::info;
 use uuid::Uuid;
 
 use crate::{
     models::user_notification::{
-        MarkNotificationReadRequest, MarkNotificationsReadRequest,
-        UpdateNotificationPreferenceRequest,
+        MarkNotificationReadRequest, MarkNotificationsReadRequest, NotificationFilter,
+        NotificationWithReadStatus, UpdateNotificationPreferenceRequest,
+    },
+    services::{
+        auth_service::Claims, notification_hub::NotificationHub,
+        user_notification_service::UserNotificationServiceTrait,
     },
-    services::{auth_service::Claims, user_notification_service::UserNotificationServiceTrait},
     utils::errors::AppError,
 };
 
 #[derive(Clone)]
 pub struct UserNotificationState {
     pub user_notification_service: Arc<dyn UserNotificationServiceTrait>,
+    pub notification_hub: NotificationHub,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,6 +35,8 @@ pub struct NotificationQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub unread_only: Option<bool>,
+    pub action: Option<String>,
+    pub resource_type: Option<String>,
 }
 
 // GET /api/v1/user/notifications
@@ -45,7 +55,16 @@ pub async fn get_user_notifications(
 
     let response = state
         .user_notification_service
-        .get_user_notifications(user_id, query.limit, query.offset)
+        .get_user_notifications(
+            user_id,
+            query.limit,
+            query.offset,
+            NotificationFilter {
+                unread_only: query.unread_only.unwrap_or(false),
+                action: query.action,
+                resource_type: query.resource_type,
+            },
+        )
         .await?;
 
     info!(
@@ -184,6 +203,22 @@ pub async fn get_unread_count(
     })))
 }
 
+// GET /api/v1/user/notifications/summary
+pub async fn get_unread_summary(
+    State(state): State<UserNotificationState>,
+    claims: Claims,
+) -> Result<Json<Value>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    let summary = state
+        .user_notification_service
+        .get_unread_summary(user_id)
+        .await?;
+
+    Ok(Json(json!(summary)))
+}
+
 // GET /api/v1/user/notifications/preferences
 pub async fn get_notification_preferences(
     State(state): State<UserNotificationState>,
@@ -238,3 +273,79 @@ pub async fn update_notification_preference(
         "preference": preference
     })))
 }
+
+// How often an idle socket is pinged; a client that misses a whole interval is dropped
+const WS_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+// GET /api/v1/user/notifications/ws
+// WebSocket: pushes each new notification as JSON as it is created
+pub async fn notifications_ws(
+    State(state): State<UserNotificationState>,
+    claims: Claims,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Internal("Invalid user ID".to_string()))?;
+
+    info!("notifications_ws: User {} connected", user_id);
+
+    // Subscribe before the upgrade so nothing created during the handshake is missed
+    let receiver = state.notification_hub.subscribe(user_id);
+    Ok(ws.on_upgrade(move |socket| forward_notifications(socket, receiver, user_id)))
+}
+
+// Dropping the receiver on disconnect lets the hub clean up the user's channel
+async fn forward_notifications(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<NotificationWithReadStatus>,
+    user_id: Uuid,
+) {
+    let mut ping = tokio::time::interval(WS_PING_INTERVAL);
+    ping.reset();
+    let mut awaiting_pong = false;
+
+    loop {
+        tokio::select! {
+            notification = receiver.recv() => match notification {
+                Ok(notification) => {
+                    let payload = match serde_json::to_string(&notification) {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            tracing::warn!("notifications_ws: Failed to encode notification: {}", e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(payload)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "notifications_ws: User {} lagged, dropped {} notification(s)",
+                        user_id,
+                        skipped
+                    );
+                }
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Pong(_))) => awaiting_pong = false,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Client pings are answered by the socket itself; nothing else is expected
+                Some(Ok(_)) => {}
+            },
+            _ = ping.tick() => {
+                if awaiting_pong {
+                    tracing::warn!("notifications_ws: User {} missed a ping, closing", user_id);
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+                awaiting_pong = true;
+            }
+        }
+    }
+
+    info!("notifications_ws: User {} disconnected", user_id);
+}
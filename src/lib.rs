@@ -1,3 +1,4 @@
+pub mod cli;
 pub mod database;
 pub mod handlers;
 pub mod middleware;
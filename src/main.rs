@@ -1,52 +1,91 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, post, put},
     Router,
 };
-use std::{env, net::SocketAddr, sync::Arc};
+use clap::Parser;
+use std::{future::IntoFuture, net::SocketAddr, sync::Arc};
 use tokio::signal;
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
-use tower_http::{compression::CompressionLayer, trace::TraceLayer};
+use tower_http::{
+    compression::CompressionLayer, limit::RequestBodyLimitLayer, services::ServeDir,
+    timeout::TimeoutLayer, trace::TraceLayer,
+};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use portfolio_backend::{
+    cli::{self, Cli},
     database::{
         connection::{create_pool, run_migrations},
-        // seeder::DatabaseSeeder, // Removed unused import - seeding disabled to prevent data loss
+        seeder::DatabaseSeeder,
     },
     handlers::{
-        admin_settings, audit_log, auth, comment, portfolio, post, service, user_notification,
+        admin_settings, analytics, api_key, audit_log, auth, comment, health, portfolio, post,
+        search, service, sitemap, upload, user, user_notification,
     },
     middleware::{
-        auth::auth_middleware,
-        rate_limiter::RedisRateLimiter,
+        auth::{admin_only, auth_middleware},
+        feature_flags::{feature_flag_middleware, Feature, FeatureFlagCache},
+        maintenance::{maintenance_middleware, MaintenanceCache},
+        rate_limiter::{api_rate_limit_middleware, RedisRateLimiter},
         security::{
-            create_cors_layer, create_rate_limiter, logging_middleware, request_id_middleware,
+            create_cors_layer, create_rate_limiter, logging_middleware, no_store_middleware,
+            payload_too_large_middleware, proxy_headers_middleware, public_cache_control,
+            public_cache_middleware, request_id_middleware, request_timeout_middleware,
             security_headers_middleware,
         },
     },
     repositories::{
-        comment_repository::CommentRepository, portfolio_repository::PortfolioRepository,
-        post_repository::PostRepository, service_repository::ServiceRepository,
-        user_repository::UserRepository, AdminSettingsRepository, AuditLogRepository,
-        UserNotificationRepository,
+        analytics_repository::AnalyticsRepository,
+        api_key_repository::ApiKeyRepository,
+        comment_repository::CommentRepository,
+        password_reset_repository::PasswordResetRepository,
+        portfolio_repository::PortfolioRepository,
+        post_repository::{PostRepository, PostRepositoryTrait},
+        refresh_token_repository::RefreshTokenRepository,
+        search_repository::SearchRepository,
+        service_repository::ServiceRepository,
+        user_repository::UserRepository,
+        AdminSettingsRepository, AuditLogRepository, UserNotificationRepository,
     },
     services::{
-        admin_settings_service::{AdminSettingsService, AdminSettingsServiceTrait},
+        admin_settings_service::{
+            AdminSettingsService, AdminSettingsServiceTrait, CachedAdminSettingsService,
+            SETTINGS_CACHE_TTL,
+        },
+        analytics_service::{AnalyticsService, AnalyticsServiceTrait, ANALYTICS_CACHE_TTL},
+        api_key_service::{ApiKeyService, ApiKeyServiceTrait},
         audit_log_service::{AuditLogService, AuditLogServiceTrait},
         auth_service::AuthService,
         blog_service::{BlogService, BlogServiceTrait},
         comment_service::{CommentService, CommentServiceTrait},
+        email_notifier::{self, EmailNotifier},
+        email_service::{EmailService, EmailServiceTrait},
+        notification_hub::NotificationHub,
+        notification_tester::NotificationTester,
         portfolio_service::{PortfolioService, PortfolioServiceTrait},
+        search_service::{SearchService, SearchServiceTrait},
         service_service::{ServiceService, ServiceServiceTrait},
+        storage::build_storage_backend,
         user_notification_service::{UserNotificationService, UserNotificationServiceTrait},
+        user_service::{UserService, UserServiceTrait},
+        webhook_dispatcher::WebhookDispatcher,
+    },
+    utils::{
+        background::BackgroundTasks,
+        config::{AppConfig, StorageBackendKind},
+        logging::fmt_layer,
     },
-    utils::{config::AppConfig, errors::AppError},
 };
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Parsed before anything else so --help works without a config
+    let cli = Cli::parse();
+
     // Load configuration
     let (config, _secret_config) = AppConfig::from_yaml()?;
 
@@ -56,11 +95,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "portfolio_backend=debug,tower_http=debug".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(fmt_layer(config.logging.format, std::io::stdout))
         .init();
 
-    info!("Starting portfolio backend server...");
-
     // Create database connection pool
     let database_url = config.get_database_url()?;
     let pool = create_pool(database_url, &config.database).await?;
@@ -68,60 +105,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Run database migrations
     run_migrations(&pool).await?;
 
-    // DISABLED: Automatic seeding to prevent data loss
-    //
-    // WARNING: The automatic seeding was causing data loss on every restart
-    // because it drops all tables and re-seeds with sample data.
-    //
-    // To manually seed the database when needed, use:
-    // let seeder = DatabaseSeeder::new(pool.clone());
-    // seeder.seed_all().await?;
-    //
-    // Seed database in development
-    // if config.is_development() {
-    //     let seeder = DatabaseSeeder::new(pool.clone());
-    //     seeder.seed_all().await?;
-    //     info!("Database seeded successfully");
-    // }
+    // Admin subcommands run once and exit instead of starting the server
+    if let Some(command) = cli.command {
+        cli::run(command, &pool).await?;
+        return Ok(());
+    }
+
+    info!("Starting portfolio backend server...");
+
+    // Seed sample data in development; only empty tables are touched
+    if config.is_development() {
+        DatabaseSeeder::new(pool.clone()).seed_if_empty().await?;
+    }
 
     // Initialize repositories
     let user_repository = Arc::new(UserRepository::new(pool.clone()));
     let portfolio_repository = Arc::new(PortfolioRepository::new(pool.clone()));
     let service_repository = Arc::new(ServiceRepository::new(pool.clone()));
     let post_repository = Arc::new(PostRepository::new(pool.clone()));
+    let search_repository = Arc::new(SearchRepository::new(pool.clone()));
     let comment_repository = Arc::new(CommentRepository::new(pool.clone()));
+    let password_reset_repository = Arc::new(PasswordResetRepository::new(pool.clone()));
+    let refresh_token_repository = Arc::new(RefreshTokenRepository::new(pool.clone()));
+    let api_key_repository = Arc::new(ApiKeyRepository::new(pool.clone()));
     let audit_log_repository = Arc::new(AuditLogRepository::new(pool.clone()));
+    let analytics_repository = Arc::new(AnalyticsRepository::new(pool.clone()));
     let admin_settings_repository = Arc::new(AdminSettingsRepository::new(pool.clone()));
     let user_notification_repository: Arc<UserNotificationRepository> =
         Arc::new(UserNotificationRepository::new(pool.clone()));
 
-    // Safely initialize admin settings if they don't exist (won't overwrite existing data)
-    admin_settings_repository.ensure_settings_exist().await?;
-
-    // Initialize services
-    let auth_service = AuthService::new(
-        user_repository.clone(),
-        config.get_jwt_secret()?.to_string(),
-        config.auth.token_expiry,
-    );
+    // Jobs below stop between runs on shutdown rather than being killed mid-write
+    let background_tasks = BackgroundTasks::new();
 
-    let portfolio_service: Arc<dyn PortfolioServiceTrait> =
-        Arc::new(PortfolioService::new(portfolio_repository));
-    let service_service: Arc<dyn ServiceServiceTrait> =
-        Arc::new(ServiceService::new(service_repository));
-    let blog_service: Arc<dyn BlogServiceTrait> = Arc::new(BlogService::new(post_repository));
-    let audit_log_service: Arc<dyn AuditLogServiceTrait> =
-        Arc::new(AuditLogService::new(audit_log_repository));
-    let admin_settings_service: Arc<dyn AdminSettingsServiceTrait> =
-        Arc::new(AdminSettingsService::new(admin_settings_repository));
-    let comment_service: Arc<dyn CommentServiceTrait> = Arc::new(CommentService::new(
-        comment_repository,
-        admin_settings_service.clone(),
-    ));
-    let user_notification_service: Arc<dyn UserNotificationServiceTrait> =
-        Arc::new(UserNotificationService::new(user_notification_repository));
+    // Publish scheduled posts once their publish_at time has passed
+    spawn_scheduled_post_publisher(&background_tasks, post_repository.clone());
 
-    // CAPTCHA verifier and spam detector removed since contact form is no longer used
+    // Safely initialize admin settings if they don't exist (won't overwrite existing data)
+    admin_settings_repository.ensure_settings_exist().await?;
 
     // Initialize Redis rate limiter
     let rate_limiter = match config.get_redis_url() {
@@ -141,20 +161,170 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    // Initialize services
+    let admin_settings_service: Arc<dyn AdminSettingsServiceTrait> =
+        Arc::new(CachedAdminSettingsService::new(
+            Arc::new(AdminSettingsService::new(admin_settings_repository)),
+            SETTINGS_CACHE_TTL,
+        ));
+    let email_service: Arc<dyn EmailServiceTrait> =
+        Arc::new(EmailService::new(admin_settings_service.clone()));
+    let api_key_service: Arc<dyn ApiKeyServiceTrait> =
+        Arc::new(ApiKeyService::new(api_key_repository));
+
+    let user_service: Arc<dyn UserServiceTrait> = Arc::new(UserService::new(
+        user_repository.clone(),
+        refresh_token_repository.clone(),
+    ));
+
+    let auth_service = AuthService::new(
+        user_repository.clone(),
+        password_reset_repository,
+        refresh_token_repository,
+        api_key_service.clone(),
+        admin_settings_service.clone(),
+        email_service.clone(),
+        config.get_jwt_secret()?.to_string(),
+        config.auth.token_expiry,
+        config.auth.refresh_token_expiry,
+    )
+    .with_rate_limiter(rate_limiter.clone());
+
+    let portfolio_service: Arc<dyn PortfolioServiceTrait> = Arc::new(PortfolioService::new(
+        portfolio_repository,
+        rate_limiter.clone(),
+    ));
+    let service_service: Arc<dyn ServiceServiceTrait> =
+        Arc::new(ServiceService::new(service_repository));
+    let blog_service: Arc<dyn BlogServiceTrait> = Arc::new(BlogService::new(
+        post_repository,
+        rate_limiter.clone(),
+        config.get_jwt_secret()?.to_string(),
+    ));
+    let search_service: Arc<dyn SearchServiceTrait> =
+        Arc::new(SearchService::new(search_repository));
+    let analytics_service: Arc<dyn AnalyticsServiceTrait> = Arc::new(AnalyticsService::new(
+        analytics_repository,
+        ANALYTICS_CACHE_TTL,
+    ));
+    let notification_hub = NotificationHub::new();
+    let email_notifier = Arc::new(EmailNotifier::new(
+        user_notification_repository.clone(),
+        admin_settings_service.clone(),
+        email_service,
+    ));
+    let audit_log_service: Arc<dyn AuditLogServiceTrait> = Arc::new(AuditLogService::new(
+        audit_log_repository,
+        Some(Arc::new(WebhookDispatcher::new(
+            admin_settings_service.clone(),
+        ))),
+        Some(email_notifier.clone()),
+        Some(notification_hub.clone()),
+    ));
+    let comment_service: Arc<dyn CommentServiceTrait> = Arc::new(CommentService::new(
+        comment_repository,
+        admin_settings_service.clone(),
+        audit_log_service.clone(),
+        config.get_jwt_secret()?.to_string(),
+        config.security.feature_check_fail_open,
+    ));
+    let user_notification_service: Arc<dyn UserNotificationServiceTrait> =
+        Arc::new(UserNotificationService::new(user_notification_repository));
+
+    // CAPTCHA verifier and spam detector removed since contact form is no longer used
+
     // Initialize handler states (dependency injection)
-    let portfolio_state = portfolio::PortfolioState { portfolio_service };
-    let service_state = service::ServiceState { service_service };
-    let post_state = post::PostState { blog_service };
-    let comment_state = comment::CommentState { comment_service };
+    let sitemap_state = sitemap::SitemapState {
+        blog_service: blog_service.clone(),
+        portfolio_service: portfolio_service.clone(),
+        service_service: service_service.clone(),
+        admin_settings_service: admin_settings_service.clone(),
+    };
+    let upload_state = upload::UploadState {
+        storage: build_storage_backend(&config.file_upload)?,
+        max_size: config.file_upload.max_size,
+        allowed_types: config.file_upload.allowed_types.clone(),
+    };
+    let portfolio_state = portfolio::PortfolioState {
+        portfolio_service,
+        audit_log_service: audit_log_service.clone(),
+    };
+    let service_state = service::ServiceState {
+        service_service,
+        audit_log_service: audit_log_service.clone(),
+    };
+    let post_state = post::PostState {
+        blog_service,
+        audit_log_service: audit_log_service.clone(),
+    };
+    let comment_state = comment::CommentState {
+        comment_service: comment_service.clone(),
+    };
+    let analytics_state = analytics::AnalyticsState { analytics_service };
     let audit_log_state = audit_log::AuditLogState {
         audit_log_service: audit_log_service.clone(),
     };
+    // Keep the maintenance flag and feature toggles cached in memory for the public routes
+    let maintenance_cache = MaintenanceCache::new();
+    let feature_flag_cache =
+        FeatureFlagCache::new().with_fail_open(config.security.feature_check_fail_open);
+
     let admin_settings_state = admin_settings::AdminSettingsState {
         admin_settings_service: admin_settings_service.clone(),
+        audit_log_service: audit_log_service.clone(),
         rate_limiter: rate_limiter.clone(),
+        notification_tester: Arc::new(NotificationTester::default()),
+        maintenance_cache: maintenance_cache.clone(),
     };
     let user_notification_state = user_notification::UserNotificationState {
-        user_notification_service,
+        user_notification_service: user_notification_service.clone(),
+        notification_hub,
+    };
+    let api_key_state = api_key::ApiKeyState {
+        api_key_service,
+        audit_log_service: audit_log_service.clone(),
+    };
+    let user_state = user::UserState {
+        user_service,
+        audit_log_service: audit_log_service.clone(),
+    };
+
+    // Prune audit logs past the configured retention window once a day
+    spawn_audit_log_retention(
+        &background_tasks,
+        audit_log_service.clone(),
+        user_notification_service.clone(),
+        admin_settings_service.clone(),
+    );
+
+    // Reject or delete comments left pending past the configured expiry
+    spawn_pending_comment_expiry(&background_tasks, comment_service.clone());
+
+    // Email unread notification summaries once a day
+    let digest_send_time =
+        chrono::NaiveTime::parse_from_str(&config.notifications.digest_send_time, "%H:%M")
+            .map_err(|e| anyhow::anyhow!("Invalid digest_send_time: {}", e))?;
+    spawn_daily_digest(
+        &background_tasks,
+        email_notifier,
+        digest_send_time,
+        config.notifications.digest_dry_run,
+    );
+
+    spawn_settings_cache_refresher(
+        &background_tasks,
+        maintenance_cache.clone(),
+        feature_flag_cache.clone(),
+        admin_settings_service.clone(),
+    );
+
+    let search_state = search::SearchState {
+        search_service,
+        feature_flags: feature_flag_cache.clone(),
+    };
+    let health_state = health::HealthState {
+        pool: pool.clone(),
+        rate_limiter: rate_limiter.clone(),
     };
 
     // Create auth state with auth service, audit log service, and rate limiter
@@ -162,6 +332,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         auth_service: auth_service.clone(),
         audit_log_service,
         rate_limiter: rate_limiter.clone(),
+        cookie_config: config.auth.cookie.clone(),
     };
 
     // Build our application with routes
@@ -172,8 +343,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         post_state,
         comment_state,
         audit_log_state,
+        analytics_state,
         admin_settings_state,
         user_notification_state,
+        api_key_state,
+        user_state,
+        sitemap_state,
+        search_state,
+        upload_state,
+        health_state,
+        maintenance_cache,
+        feature_flag_cache,
         &config,
         rate_limiter,
     );
@@ -184,16 +364,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create server
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(
+    let shutdown = background_tasks.token();
+    let server = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .with_graceful_shutdown(shutdown_signal())
-    .await?;
+    .with_graceful_shutdown(shutdown_signal(shutdown.clone()));
+
+    // Open notification streams never finish on their own, so draining is bounded
+    tokio::select! {
+        result = server.into_future() => result?,
+        _ = async {
+            shutdown.cancelled().await;
+            tokio::time::sleep(SHUTDOWN_TIMEOUT).await;
+        } => tracing::warn!("Timed out waiting for in-flight requests to finish"),
+    }
+    info!("HTTP server stopped");
+
+    if !background_tasks.shutdown(SHUTDOWN_TIMEOUT).await {
+        tracing::warn!("Timed out waiting for background tasks to stop");
+    }
+
+    pool.close().await;
+    info!("Database pool closed");
 
     Ok(())
 }
 
+const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+const UPLOAD_BODY_OVERHEAD: usize = 64 * 1024;
+
 #[allow(clippy::too_many_arguments)]
 fn create_app(
     auth_state: auth::AuthState,
@@ -202,196 +403,422 @@ fn create_app(
     post_state: post::PostState,
     comment_state: comment::CommentState,
     audit_log_state: audit_log::AuditLogState,
+    analytics_state: analytics::AnalyticsState,
     admin_settings_state: admin_settings::AdminSettingsState,
     user_notification_state: user_notification::UserNotificationState,
+    api_key_state: api_key::ApiKeyState,
+    user_state: user::UserState,
+    sitemap_state: sitemap::SitemapState,
+    search_state: search::SearchState,
+    upload_state: upload::UploadState,
+    health_state: health::HealthState,
+    maintenance_cache: MaintenanceCache,
+    feature_flag_cache: FeatureFlagCache,
     config: &AppConfig,
-    _rate_limiter: Option<Arc<RedisRateLimiter>>,
+    rate_limiter: Option<Arc<RedisRateLimiter>>,
 ) -> Router {
     // Create CORS layer with configuration
     let cors = create_cors_layer(&config.security);
+    let public_cache = public_cache_control(config.server.public_cache_max_age_seconds);
 
     // Create protected routes that require authentication
     let protected_routes = Router::new()
         .route("/me", get(auth::me))
-        .route("/refresh", post(auth::refresh_token))
         .route("/profile", put(auth::update_profile))
         .route("/change-password", put(auth::change_password))
         .route("/logout", post(auth::logout))
+        .route(
+            "/sessions",
+            get(auth::list_sessions).delete(auth::revoke_other_sessions),
+        )
+        .route("/sessions/:id", delete(auth::revoke_session))
         .with_state(auth_state.clone())
         .route_layer(middleware::from_fn_with_state(
             auth_state.auth_service.clone(),
             auth_middleware,
         ));
 
-    // Create public routes
-    let public_routes = Router::new()
-        .route("/login", post(auth::login))
-        .route("/health", get(health_check))
-        .with_state(auth_state.clone());
-
-    // Portfolio routes (protected)
-    let portfolio_routes = Router::new()
+    // API key management routes (protected)
+    let api_key_routes = Router::new()
         .route(
             "/",
-            get(portfolio::get_all_projects).post(portfolio::create_project),
+            get(api_key::list_api_keys).post(api_key::create_api_key),
         )
-        .route(
-            "/:id",
-            get(portfolio::get_project)
-                .put(portfolio::update_project)
-                .delete(portfolio::delete_project),
-        )
-        .route("/featured", get(portfolio::get_featured_projects))
-        .route("/stats", get(portfolio::get_portfolio_stats))
-        .route("/:id/featured", put(portfolio::update_featured_status))
-        .with_state(portfolio_state.clone())
+        .route("/:id", delete(api_key::revoke_api_key))
+        .with_state(api_key_state)
         .route_layer(middleware::from_fn_with_state(
             auth_state.auth_service.clone(),
             auth_middleware,
         ));
 
+    // Create public routes
+    let public_routes = Router::new()
+        .route("/login", post(auth::login))
+        .route("/refresh", post(auth::refresh_token))
+        .route("/forgot-password", post(auth::forgot_password))
+        .route("/reset-password", post(auth::reset_password))
+        .with_state(auth_state.clone())
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
+        ))
+        .route(
+            "/health",
+            get(health::health_check).with_state(health_state.clone()),
+        );
+
+    // Portfolio routes (protected, admins only)
+    let portfolio_routes = admin_only(
+        Router::new()
+            .route(
+                "/",
+                get(portfolio::get_all_projects).post(portfolio::create_project),
+            )
+            .route(
+                "/:id",
+                get(portfolio::get_project)
+                    .put(portfolio::update_project)
+                    .delete(portfolio::delete_project),
+            )
+            .route("/featured", get(portfolio::get_featured_projects))
+            .route("/stats", get(portfolio::get_portfolio_stats))
+            .route("/reorder", put(portfolio::reorder_projects))
+            .route("/bulk-featured", put(portfolio::bulk_update_featured))
+            .route("/:id/featured", put(portfolio::update_featured_status))
+            .with_state(portfolio_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // Portfolio backup routes (protected, admins only); kept outside the request timeout
+    // with their own body limit, since a full export or import can be large and slow
+    let portfolio_transfer_routes = admin_only(
+        Router::new()
+            .route("/export", get(portfolio::export_projects))
+            .route("/import", post(portfolio::import_projects))
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(
+                config.server.max_import_body_size,
+            ))
+            .with_state(portfolio_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
     // Portfolio public routes (no authentication required)
     let portfolio_public_routes = Router::new()
         .route("/", get(portfolio::get_all_projects))
         .route("/:id", get(portfolio::get_project))
         .route("/slug/:slug", get(portfolio::get_project_by_slug))
         .route("/featured", get(portfolio::get_featured_projects))
-        .with_state(portfolio_state);
-
-    // Service routes (protected)
-    let service_routes = Router::new()
-        .route(
-            "/",
-            get(service::get_all_services).post(service::create_service),
-        )
-        .route(
-            "/:id",
-            get(service::get_service)
-                .put(service::update_service)
-                .delete(service::delete_service),
-        )
-        .route("/stats", get(service::get_service_stats))
-        .route("/:id/activate", put(service::update_service_status))
-        .with_state(service_state.clone())
+        .with_state(portfolio_state)
         .route_layer(middleware::from_fn_with_state(
-            auth_state.auth_service.clone(),
-            auth_middleware,
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            feature_flag_cache.gate(Feature::Portfolio),
+            feature_flag_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
         ));
 
+    // Service routes (protected, admins only)
+    let service_routes = admin_only(
+        Router::new()
+            .route(
+                "/",
+                get(service::get_all_services).post(service::create_service),
+            )
+            .route(
+                "/:id",
+                get(service::get_service)
+                    .put(service::update_service)
+                    .delete(service::delete_service),
+            )
+            .route("/stats", get(service::get_service_stats))
+            .route("/reorder", put(service::reorder_services))
+            .route("/:id/activate", put(service::update_service_status))
+            .with_state(service_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
     // Service public routes (no authentication required)
     let service_public_routes = Router::new()
         .route("/", get(service::get_all_services))
         .route("/active", get(service::get_active_services))
         .route("/:id", get(service::get_service))
-        .with_state(service_state);
-
-    // Post routes (protected for admin)
-    let post_protected_routes = Router::new()
-        .route("/", post(post::create_post))
-        .route("/:id", put(post::update_post).delete(post::delete_post))
-        .route("/:id/publish", put(post::update_published_status))
-        .route("/stats", get(post::get_post_stats))
-        .with_state(post_state.clone())
+        .with_state(service_state)
         .route_layer(middleware::from_fn_with_state(
-            auth_state.auth_service.clone(),
-            auth_middleware,
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            feature_flag_cache.gate(Feature::Services),
+            feature_flag_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
         ));
 
+    // Post routes (protected, admins only)
+    let post_protected_routes = admin_only(
+        Router::new()
+            .route("/", post(post::create_post))
+            .route("/:id", put(post::update_post).delete(post::delete_post))
+            .route("/bulk", delete(post::bulk_delete_posts))
+            .route("/bulk-featured", put(post::bulk_update_featured))
+            .route("/:id/publish", put(post::update_published_status))
+            .route("/:id/preview-link", post(post::create_preview_link))
+            .route("/:id/restore", put(post::restore_post))
+            .route("/:id/purge", delete(post::purge_post))
+            .route("/stats", get(post::get_post_stats))
+            .with_state(post_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // Post import route (protected, admins only); kept outside the request timeout with
+    // its own body limit, since a whole blog's archive can be large and slow to write
+    let post_import_routes = admin_only(
+        Router::new()
+            .route("/import", post(post::import_posts))
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(
+                config.server.max_import_body_size,
+            ))
+            .with_state(post_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
     // Post public routes (no authentication required)
     let post_public_routes = Router::new()
         .route("/", get(post::get_all_posts))
         .route("/:id", get(post::get_post))
+        .route("/:id/related", get(post::get_related_posts))
         .route("/slug/:slug", get(post::get_post_by_slug))
+        .route(
+            "/search",
+            get(post::search_posts).route_layer(middleware::from_fn_with_state(
+                feature_flag_cache.gate(Feature::Search),
+                feature_flag_middleware,
+            )),
+        )
         .route("/published", get(post::get_published_posts))
         .route("/featured", get(post::get_featured_posts))
-        .route("/categories", get(post::get_all_posts))
-        .with_state(post_state);
+        .route("/tags", get(post::get_tags))
+        .route("/categories", get(post::get_categories))
+        .with_state(post_state.clone())
+        .route_layer(middleware::from_fn_with_state(
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            feature_flag_cache.gate(Feature::Blog),
+            feature_flag_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
+        ));
 
-    // Comment routes (protected for admin)
-    let comment_protected_routes = Router::new()
-        .route("/", get(comment::get_all_comments))
-        .route(
-            "/:id",
-            get(comment::get_comment).delete(comment::delete_comment),
-        )
-        .route("/:id/status", put(comment::update_comment_status))
-        .route("/:id/approve", put(comment::approve_comment))
-        .route("/:id/reject", put(comment::reject_comment))
-        .route("/pending", get(comment::get_pending_comments))
-        .route("/bulk-status", put(comment::bulk_update_comment_status))
-        .route("/stats", get(comment::get_comment_stats))
-        .with_state(comment_state.clone())
+    // Search route (public, spans posts, portfolio and services)
+    let search_routes = Router::new()
+        .route("/", get(search::search))
+        .with_state(search_state)
         .route_layer(middleware::from_fn_with_state(
-            auth_state.auth_service.clone(),
-            auth_middleware,
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            feature_flag_cache.gate(Feature::Search),
+            feature_flag_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
         ));
 
+    // Post analytics routes (protected)
+    let post_admin_routes = admin_only(
+        Router::new()
+            .route("/", get(post::get_all_posts_admin))
+            .route("/:id", get(post::get_post_admin))
+            .route("/:id/views", get(post::get_post_views))
+            .with_state(post_state),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // Comment moderation routes (protected, admins only)
+    let comment_protected_routes = admin_only(
+        Router::new()
+            .route("/", get(comment::get_all_comments))
+            .route(
+                "/:id",
+                get(comment::get_comment).delete(comment::delete_comment),
+            )
+            .route("/:id/status", put(comment::update_comment_status))
+            .route("/:id/approve", put(comment::approve_comment))
+            .route("/:id/reject", put(comment::reject_comment))
+            .route("/pending", get(comment::get_pending_comments))
+            .route("/bulk-status", put(comment::bulk_update_comment_status))
+            .route("/stats", get(comment::get_comment_stats))
+            .route(
+                "/stats/timeseries",
+                get(comment::get_comment_stats_timeseries),
+            )
+            .route(
+                "/bans",
+                get(comment::list_bans).post(comment::ban_commenter),
+            )
+            .route("/bans/:id", delete(comment::unban_commenter))
+            .with_state(comment_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
     // Comment public routes (no authentication required)
     let comment_public_routes = Router::new()
         .route("/post/:post_id", get(comment::get_comments_by_post))
-        .route("/", post(comment::create_comment))
-        .with_state(comment_state);
-
-    // Audit log routes (protected)
-    let audit_log_routes = Router::new()
         .route(
-            "/",
-            get(audit_log::get_audit_logs)
-                .post(audit_log::create_audit_log)
-                .delete(audit_log::delete_all_audit_logs),
+            "/post/:post_id/threaded",
+            get(comment::get_threaded_comments),
         )
-        .route("/:id", get(audit_log::get_audit_log))
-        .route("/recent", get(audit_log::get_recent_audit_logs))
-        .route("/stats", get(audit_log::get_audit_log_stats))
-        .with_state(audit_log_state)
+        .route("/", post(comment::create_comment))
+        .route("/:id/edit", put(comment::edit_comment))
+        .with_state(comment_state)
         .route_layer(middleware::from_fn_with_state(
-            auth_state.auth_service.clone(),
-            auth_middleware,
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
         ));
 
+    // Audit log routes (protected)
+    let audit_log_routes = admin_only(
+        Router::new()
+            .route(
+                "/",
+                get(audit_log::get_audit_logs)
+                    .post(audit_log::create_audit_log)
+                    .delete(audit_log::delete_all_audit_logs),
+            )
+            .route("/:id", get(audit_log::get_audit_log))
+            .route("/recent", get(audit_log::get_recent_audit_logs))
+            .route("/stats", get(audit_log::get_audit_log_stats))
+            .route(
+                "/resource/:resource_type/:resource_id",
+                get(audit_log::get_audit_logs_by_resource),
+            )
+            .with_state(audit_log_state),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // User management routes (protected, admins only)
+    let user_routes = admin_only(
+        Router::new()
+            .route("/", get(user::list_users))
+            .route("/:id/status", put(user::update_user_status))
+            .route("/:id/role", put(user::update_user_role))
+            .with_state(user_state),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // Analytics routes (protected)
+    let analytics_routes = admin_only(
+        Router::new()
+            .route("/summary", get(analytics::get_summary))
+            .with_state(analytics_state),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
     // Admin settings routes (protected)
-    let admin_settings_routes = Router::new()
-        .route(
-            "/",
-            get(admin_settings::get_settings).put(admin_settings::update_settings),
-        )
-        .route("/features", get(admin_settings::get_settings))
-        .route("/features", put(admin_settings::update_feature_settings))
-        .route("/security", get(admin_settings::get_settings))
-        .route("/security", put(admin_settings::update_security_settings))
-        .route(
-            "/security/blocked-ips",
-            get(admin_settings::get_blocked_ips),
-        )
-        .route("/security/block-ip", post(admin_settings::block_ip))
-        .route(
-            "/security/blocked-ips/:ip",
-            delete(admin_settings::unblock_ip),
-        )
-        .route("/security/stats", get(admin_settings::get_security_stats))
-        .route("/reset", post(admin_settings::reset_settings))
-        .route(
-            "/features/:feature/enabled",
-            get(admin_settings::is_feature_enabled),
-        )
-        .route(
-            "/maintenance-mode",
-            get(admin_settings::get_maintenance_mode),
-        )
-        .route(
-            "/maintenance-mode",
-            put(admin_settings::get_maintenance_mode),
-        )
-        .with_state(admin_settings_state.clone())
-        .route_layer(middleware::from_fn_with_state(
-            auth_state.auth_service.clone(),
-            auth_middleware,
-        ));
+    let admin_settings_routes = admin_only(
+        Router::new()
+            .route(
+                "/",
+                get(admin_settings::get_settings).put(admin_settings::update_settings),
+            )
+            .route("/features", get(admin_settings::get_settings))
+            .route("/features", put(admin_settings::update_feature_settings))
+            .route("/security", get(admin_settings::get_settings))
+            .route("/security", put(admin_settings::update_security_settings))
+            .route(
+                "/security/blocked-ips",
+                get(admin_settings::get_blocked_ips),
+            )
+            .route("/security/block-ip", post(admin_settings::block_ip))
+            .route(
+                "/security/blocked-ips/:ip",
+                delete(admin_settings::unblock_ip),
+            )
+            .route("/security/stats", get(admin_settings::get_security_stats))
+            .route(
+                "/notifications/test",
+                post(admin_settings::test_notification_settings),
+            )
+            .route("/reset", post(admin_settings::reset_settings))
+            .route("/history/:key", get(admin_settings::get_setting_history))
+            .route(
+                "/history/:key/:history_id/rollback",
+                post(admin_settings::rollback_setting),
+            )
+            .route(
+                "/features/:feature/enabled",
+                get(admin_settings::is_feature_enabled),
+            )
+            .route(
+                "/maintenance-mode",
+                get(admin_settings::get_maintenance_mode).put(admin_settings::set_maintenance_mode),
+            )
+            .with_state(admin_settings_state.clone()),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
 
     // Public settings routes (no authentication required)
     let settings_public_routes = Router::new()
         .route("/public", get(admin_settings::get_public_settings))
-        .with_state(admin_settings_state);
+        .with_state(admin_settings_state.clone())
+        .route_layer(middleware::from_fn_with_state(
+            public_cache.clone(),
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
+        ));
 
     // User notification routes (protected)
     let user_notification_routes = Router::new()
@@ -410,6 +837,7 @@ fn create_app(
         )
         .route("/stats", get(user_notification::get_notification_stats))
         .route("/unread-count", get(user_notification::get_unread_count))
+        .route("/summary", get(user_notification::get_unread_summary))
         .route(
             "/preferences",
             get(user_notification::get_notification_preferences),
@@ -418,14 +846,55 @@ fn create_app(
             "/preferences",
             put(user_notification::update_notification_preference),
         )
+        .with_state(user_notification_state.clone())
+        .route_layer(middleware::from_fn_with_state(
+            auth_state.auth_service.clone(),
+            auth_middleware,
+        ));
+
+    // Long-lived routes, kept outside the request timeout
+    let user_notification_stream_routes = Router::new()
+        .route("/ws", get(user_notification::notifications_ws))
         .with_state(user_notification_state)
         .route_layer(middleware::from_fn_with_state(
             auth_state.auth_service.clone(),
             auth_middleware,
         ));
 
-    Router::new()
+    // Upload routes (protected, admins only); the body limit leaves headroom for multipart framing
+    let upload_routes = admin_only(
+        Router::new()
+            .route("/", post(upload::upload_file))
+            .route("/*key", delete(upload::delete_file))
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(
+                config.file_upload.max_size + UPLOAD_BODY_OVERHEAD,
+            ))
+            .with_state(upload_state),
+        auth_state.auth_service.clone(),
+        admin_settings_state.admin_settings_service.clone(),
+    );
+
+    // Sitemap route (public, served at the site root for crawlers)
+    let sitemap_routes = Router::new()
+        .route("/sitemap.xml", get(sitemap::get_sitemap))
+        .with_state(sitemap_state)
+        .route_layer(middleware::from_fn_with_state(
+            public_cache,
+            public_cache_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            maintenance_cache.clone(),
+            maintenance_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            api_rate_limit_middleware,
+        ));
+
+    let app = Router::new()
         .nest("/api/v1/auth", protected_routes)
+        .nest("/api/v1/auth/api-keys", api_key_routes)
         .nest("/api/v1/auth", public_routes)
         .nest("/api/v1/portfolio", portfolio_routes)
         .nest("/api/v1/portfolio/public", portfolio_public_routes)
@@ -433,35 +902,204 @@ fn create_app(
         .nest("/api/v1/services/public", service_public_routes)
         .nest("/api/v1/posts", post_protected_routes)
         .nest("/api/v1/posts", post_public_routes)
+        .nest("/api/v1/search", search_routes)
         .nest("/api/v1/comments", comment_protected_routes)
         .nest("/api/v1/comments", comment_public_routes)
         .nest("/api/v1/admin/audit-logs", audit_log_routes)
+        .nest("/api/v1/admin/analytics", analytics_routes)
+        .nest("/api/v1/admin/users", user_routes)
+        .nest("/api/v1/admin/posts", post_admin_routes)
         .nest("/api/v1/admin/settings", admin_settings_routes)
         .nest("/api/v1/settings", settings_public_routes)
         .nest("/api/v1/user/notifications", user_notification_routes)
-        .route("/api/v1/health", get(health_check))
-        .layer(
-            ServiceBuilder::new()
-                .layer(middleware::from_fn(security_headers_middleware))
-                .layer(middleware::from_fn(request_id_middleware))
-                .layer(middleware::from_fn(logging_middleware))
-                .layer(TraceLayer::new_for_http())
-                .layer(CompressionLayer::new())
-                .layer(cors)
-                .into_inner(),
+        .route(
+            "/api/v1/health",
+            get(health::health_check).with_state(health_state.clone()),
+        )
+        .route("/livez", get(health::liveness))
+        .route("/readyz", get(health::readiness).with_state(health_state))
+        .merge(sitemap_routes)
+        // JSON routes share the small server-wide body limit; uploads get their own below
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(config.server.max_body_size))
+        .nest("/api/v1/uploads", upload_routes)
+        .layer(TimeoutLayer::new(std::time::Duration::from_secs(
+            config.server.request_timeout_seconds,
+        )))
+        .layer(middleware::from_fn(request_timeout_middleware))
+        .nest(
+            "/api/v1/user/notifications",
+            user_notification_stream_routes,
         )
-        .with_state(auth_state)
+        .nest("/api/v1/portfolio", portfolio_transfer_routes)
+        .nest("/api/v1/posts", post_import_routes)
+        // Anything a public route didn't mark cacheable must not be stored
+        .layer(middleware::from_fn(no_store_middleware));
+
+    // Only the local disk backend needs the app to serve files itself
+    let app = match config.file_upload.backend {
+        StorageBackendKind::Local => app.nest_service(
+            &config.file_upload.public_url,
+            ServeDir::new(&config.file_upload.directory),
+        ),
+        StorageBackendKind::S3 => app,
+    };
+
+    app.layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn_with_state(
+                config.server.trust_proxy_headers,
+                proxy_headers_middleware,
+            ))
+            .layer(middleware::from_fn(security_headers_middleware))
+            .layer(middleware::from_fn(request_id_middleware))
+            .layer(middleware::from_fn(logging_middleware))
+            .layer(middleware::from_fn(payload_too_large_middleware))
+            .layer(TraceLayer::new_for_http())
+            .layer(CompressionLayer::new())
+            .layer(cors)
+            .into_inner(),
+    )
+    .with_state(auth_state)
+}
+
+fn spawn_scheduled_post_publisher(
+    background_tasks: &BackgroundTasks,
+    post_repository: Arc<PostRepository>,
+) {
+    background_tasks.spawn_periodic(
+        "scheduled post publisher",
+        std::time::Duration::from_secs(60),
+        move || {
+            let post_repository = post_repository.clone();
+            async move {
+                match post_repository.publish_due_posts().await {
+                    Ok(0) => {}
+                    Ok(count) => info!("Published {} scheduled post(s)", count),
+                    Err(e) => tracing::error!("Failed to publish scheduled posts: {}", e),
+                }
+            }
+        },
+    );
+}
+
+fn spawn_audit_log_retention(
+    background_tasks: &BackgroundTasks,
+    audit_log_service: Arc<dyn AuditLogServiceTrait>,
+    user_notification_service: Arc<dyn UserNotificationServiceTrait>,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+) {
+    background_tasks.spawn_periodic(
+        "audit log retention",
+        std::time::Duration::from_secs(24 * 60 * 60),
+        move || {
+            let audit_log_service = audit_log_service.clone();
+            let user_notification_service = user_notification_service.clone();
+            let admin_settings_service = admin_settings_service.clone();
+            async move {
+                let retention_days = match admin_settings_service.get_all_settings().await {
+                    Ok(settings) => settings.security.audit_log_retention_days,
+                    Err(e) => {
+                        tracing::error!("Failed to load audit log retention setting: {}", e);
+                        return;
+                    }
+                };
+
+                match audit_log_service.delete_old_logs(retention_days).await {
+                    Ok(count) => info!(
+                        "Deleted {} audit log(s) older than {} days",
+                        count, retention_days
+                    ),
+                    Err(e) => tracing::error!("Failed to delete old audit logs: {}", e),
+                }
+
+                match user_notification_service
+                    .cleanup_old_read_notifications()
+                    .await
+                {
+                    Ok(count) => info!("Deleted {} old notification read marker(s)", count),
+                    Err(e) => tracing::error!("Failed to clean up notification reads: {}", e),
+                }
+            }
+        },
+    );
 }
 
-async fn health_check() -> Result<axum::Json<serde_json::Value>, AppError> {
-    Ok(axum::Json(serde_json::json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "version": env!("CARGO_PKG_VERSION")
-    })))
+fn spawn_pending_comment_expiry(
+    background_tasks: &BackgroundTasks,
+    comment_service: Arc<dyn CommentServiceTrait>,
+) {
+    background_tasks.spawn_periodic(
+        "pending comment expiry",
+        std::time::Duration::from_secs(60 * 60),
+        move || {
+            let comment_service = comment_service.clone();
+            async move {
+                match comment_service.expire_pending_comments().await {
+                    Ok(0) => {}
+                    Ok(count) => info!("Expired {} stale pending comment(s)", count),
+                    Err(e) => tracing::error!("Failed to expire pending comments: {}", e),
+                }
+            }
+        },
+    );
+}
+
+fn spawn_daily_digest(
+    background_tasks: &BackgroundTasks,
+    email_notifier: Arc<EmailNotifier>,
+    send_time: chrono::NaiveTime,
+    dry_run: bool,
+) {
+    background_tasks.spawn("daily digest", move |token| async move {
+        loop {
+            let until_next = email_notifier::duration_until_next(send_time, chrono::Utc::now());
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = tokio::time::sleep(until_next) => {}
+            }
+
+            let sent = email_notifier.send_daily_digest(dry_run).await;
+            if sent > 0 {
+                info!("Sent {} daily digest email(s)", sent);
+            }
+        }
+    });
+}
+
+fn spawn_settings_cache_refresher(
+    background_tasks: &BackgroundTasks,
+    maintenance_cache: MaintenanceCache,
+    feature_flag_cache: FeatureFlagCache,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+) {
+    background_tasks.spawn_periodic(
+        "settings cache refresher",
+        std::time::Duration::from_secs(30),
+        move || {
+            let maintenance_cache = maintenance_cache.clone();
+            let feature_flag_cache = feature_flag_cache.clone();
+            let admin_settings_service = admin_settings_service.clone();
+            async move {
+                if let Err(e) = maintenance_cache
+                    .refresh(admin_settings_service.as_ref())
+                    .await
+                {
+                    tracing::error!("Failed to refresh maintenance mode: {}", e);
+                }
+
+                if let Err(e) = feature_flag_cache
+                    .refresh(admin_settings_service.as_ref())
+                    .await
+                {
+                    tracing::error!("Failed to refresh feature flags: {}", e);
+                }
+            }
+        },
+    );
 }
 
-async fn shutdown_signal() {
+async fn shutdown_signal(token: CancellationToken) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -485,4 +1123,5 @@ async fn shutdown_signal() {
     }
 
     info!("Received shutdown signal, starting graceful shutdown");
+    token.cancel();
 }
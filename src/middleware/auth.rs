@@ -1,10 +1,18 @@
 use axum::{
     extract::{Request, State},
-    http::header::{AUTHORIZATION, COOKIE},
-    middleware::Next,
+    http::{
+        header::{AUTHORIZATION, COOKIE},
+        Method,
+    },
+    middleware::{self, Next},
     response::Response,
+    Router,
 };
+use std::sync::Arc;
 
+use crate::middleware::ip_whitelist::ip_whitelist_middleware;
+use crate::models::user::ADMIN_ROLE;
+use crate::services::admin_settings_service::AdminSettingsServiceTrait;
 use crate::services::auth_service::{AuthService, Claims};
 use crate::utils::errors::AppError;
 
@@ -13,6 +21,24 @@ pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
+    // API keys are an alternative to the JWT for programmatic access
+    let api_key = request
+        .headers()
+        .get("x-api-key")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string());
+
+    if let Some(api_key) = api_key {
+        let read_only = matches!(*request.method(), Method::GET | Method::HEAD);
+        let claims = auth_service
+            .authenticate_api_key(&api_key, read_only)
+            .await?;
+
+        request.extensions_mut().insert(claims);
+
+        return Ok(next.run(request).await);
+    }
+
     // Try to get token from cookie first, then fallback to Authorization header
     let token = if let Some(cookie_header) = request.headers().get(COOKIE) {
         if let Ok(cookie_str) = cookie_header.to_str() {
@@ -41,12 +67,59 @@ pub async fn auth_middleware(
         .validate_token(&token)
         .map_err(|_| AppError::Unauthorized("Invalid or expired token".to_string()))?;
 
+    auth_service.ensure_token_not_revoked(&claims).await?;
+    auth_service.ensure_session_active(&claims).await?;
+
     // Add claims to request extensions so handlers can access them
     request.extensions_mut().insert(claims);
 
     Ok(next.run(request).await)
 }
 
+/// Rejects requests whose claims don't carry `role` with 403; tokens without a
+/// role claim count as `user`.
+/// Has to run after `auth_middleware`, so add it as an inner layer.
+pub async fn require_role(
+    State(role): State<&'static str>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let claims = request
+        .extensions()
+        .get::<Claims>()
+        .ok_or_else(|| AppError::Unauthorized("Missing authentication".to_string()))?;
+
+    if claims.role != role {
+        return Err(AppError::Forbidden(
+            "Insufficient privileges for this resource".to_string(),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Guards an admin route group: the IP whitelist first, then authentication, then
+/// the admin role. Apply after `with_state`, like any other route layer.
+pub fn admin_only<S>(
+    router: Router<S>,
+    auth_service: AuthService,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .route_layer(middleware::from_fn_with_state(ADMIN_ROLE, require_role))
+        .route_layer(middleware::from_fn_with_state(
+            auth_service,
+            auth_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            admin_settings_service,
+            ip_whitelist_middleware,
+        ))
+}
+
 // Helper function to extract admin_token from cookie string
 fn extract_admin_token_from_cookies(cookie_str: &str) -> Option<String> {
     for cookie in cookie_str.split(';') {
@@ -0,0 +1,272 @@
+use axum::{
+    extract::{Query, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use crate::models::admin_settings::FeatureSettings;
+use crate::services::admin_settings_service::AdminSettingsServiceTrait;
+use crate::utils::config::FeatureCheckFailOpen;
+use crate::utils::errors::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    Blog,
+    Portfolio,
+    Services,
+    Search,
+}
+
+impl Feature {
+    fn is_enabled(self, flags: &FeatureSettings) -> bool {
+        match self {
+            Feature::Blog => flags.blog_enabled,
+            Feature::Portfolio => flags.portfolio_enabled,
+            Feature::Services => flags.services_enabled,
+            Feature::Search => flags.search_enabled,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Feature::Blog => "Blog",
+            Feature::Portfolio => "Portfolio",
+            Feature::Services => "Services",
+            Feature::Search => "Search",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureAccess {
+    Read,
+    Write,
+}
+
+impl FeatureAccess {
+    fn of(method: &Method) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            FeatureAccess::Read
+        } else {
+            FeatureAccess::Write
+        }
+    }
+
+    fn fails_open(self, policy: FeatureCheckFailOpen) -> bool {
+        match self {
+            FeatureAccess::Read => policy.reads,
+            FeatureAccess::Write => policy.writes,
+        }
+    }
+}
+
+fn unavailable(feature: &str) -> AppError {
+    AppError::ServiceUnavailable(format!("{} is temporarily unavailable", feature))
+}
+
+/// Resolves a feature lookup, applying the fail-open policy when the lookup failed.
+/// Failing closed surfaces as 503 rather than "disabled" so clients can retry.
+pub fn resolve_feature_check<E: Display>(
+    feature: &str,
+    access: FeatureAccess,
+    policy: FeatureCheckFailOpen,
+    lookup: Result<bool, E>,
+) -> Result<bool, AppError> {
+    match lookup {
+        Ok(enabled) => Ok(enabled),
+        Err(e) => {
+            let fail_open = access.fails_open(policy);
+            tracing::error!(
+                "Failed to check whether {} is enabled, failing {} for {:?}: {}",
+                feature,
+                if fail_open { "open" } else { "closed" },
+                access,
+                e
+            );
+
+            if fail_open {
+                Ok(true)
+            } else {
+                Err(unavailable(feature))
+            }
+        }
+    }
+}
+
+// In-memory copy of the feature toggles so public requests don't hit the database.
+// Refreshed periodically from admin settings, like the maintenance flag.
+#[derive(Clone, Default)]
+pub struct FeatureFlagCache {
+    flags: Arc<RwLock<FeatureSettings>>,
+    // False until the first successful refresh and after a failed one
+    loaded: Arc<AtomicBool>,
+    fail_open: FeatureCheckFailOpen,
+}
+
+impl FeatureFlagCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What the gates do while the cached flags can't be trusted
+    pub fn with_fail_open(mut self, fail_open: FeatureCheckFailOpen) -> Self {
+        self.fail_open = fail_open;
+        self
+    }
+
+    pub fn get(&self) -> FeatureSettings {
+        self.flags
+            .read()
+            .map(|flags| flags.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, flags: FeatureSettings) {
+        if let Ok(mut current) = self.flags.write() {
+            *current = flags;
+            self.loaded.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Marks the cached flags as stale, e.g. after admin settings failed to load
+    pub fn invalidate(&self) {
+        self.loaded.store(false, Ordering::Relaxed);
+    }
+
+    pub async fn refresh(
+        &self,
+        admin_settings_service: &dyn AdminSettingsServiceTrait,
+    ) -> anyhow::Result<()> {
+        match admin_settings_service.get_all_settings().await {
+            Ok(settings) => {
+                self.set(settings.features);
+                Ok(())
+            }
+            Err(e) => {
+                self.invalidate();
+                Err(e)
+            }
+        }
+    }
+
+    /// Middleware state guarding one public route group behind `feature`
+    pub fn gate(&self, feature: Feature) -> FeatureGate {
+        FeatureGate {
+            cache: self.clone(),
+            feature,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FeatureGate {
+    cache: FeatureFlagCache,
+    feature: Feature,
+}
+
+// Apply to public route groups only; admin routes stay reachable so features can be re-enabled
+pub async fn feature_flag_middleware(
+    State(gate): State<FeatureGate>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    // Stale flags still gate the request when the policy fails open, and the
+    // last known state beats treating every feature as enabled
+    if !gate.cache.loaded.load(Ordering::Relaxed) {
+        let access = FeatureAccess::of(request.method());
+        if !access.fails_open(gate.cache.fail_open) {
+            return Err(unavailable(gate.feature.label()));
+        }
+    }
+
+    let flags = gate.cache.get();
+
+    if !gate.feature.is_enabled(&flags) {
+        return Err(AppError::NotFound(format!(
+            "{} is currently disabled",
+            gate.feature.label()
+        )));
+    }
+
+    // Search is a query parameter on the list endpoints rather than a route of its own
+    if !flags.search_enabled && has_search_query(&request) {
+        return Err(AppError::Forbidden(
+            "Search is currently disabled".to_string(),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+fn has_search_query(request: &Request) -> bool {
+    Query::<HashMap<String, String>>::try_from_uri(request.uri())
+        .is_ok_and(|Query(params)| params.get("search").is_some_and(|s| !s.trim().is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAIL_OPEN: FeatureCheckFailOpen = FeatureCheckFailOpen {
+        reads: true,
+        writes: true,
+    };
+    const FAIL_CLOSED: FeatureCheckFailOpen = FeatureCheckFailOpen {
+        reads: false,
+        writes: false,
+    };
+
+    fn lookup_failed() -> Result<bool, AppError> {
+        Err(AppError::Internal("settings unavailable".to_string()))
+    }
+
+    #[test]
+    fn test_successful_lookup_ignores_policy() {
+        let result = resolve_feature_check(
+            "Comments",
+            FeatureAccess::Write,
+            FAIL_CLOSED,
+            Ok::<_, AppError>(false),
+        );
+        assert!(matches!(result, Ok(false)));
+    }
+
+    #[test]
+    fn test_failed_lookup_fails_open() {
+        let result =
+            resolve_feature_check("Comments", FeatureAccess::Write, FAIL_OPEN, lookup_failed());
+        assert!(matches!(result, Ok(true)));
+    }
+
+    #[test]
+    fn test_failed_lookup_fails_closed() {
+        let result = resolve_feature_check(
+            "Comments",
+            FeatureAccess::Read,
+            FAIL_CLOSED,
+            lookup_failed(),
+        );
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(_))));
+    }
+
+    #[test]
+    fn test_default_policy_splits_reads_and_writes() {
+        let policy = FeatureCheckFailOpen::default();
+
+        let read = resolve_feature_check("Comments", FeatureAccess::Read, policy, lookup_failed());
+        assert!(matches!(read, Ok(true)));
+
+        let write =
+            resolve_feature_check("Comments", FeatureAccess::Write, policy, lookup_failed());
+        assert!(matches!(write, Err(AppError::ServiceUnavailable(_))));
+    }
+}
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use crate::services::admin_settings_service::AdminSettingsServiceTrait;
+use crate::utils::errors::AppError;
+use crate::utils::request::get_client_ip;
+
+// Restricts admin routes to the IPs/CIDR ranges in SecuritySettings.ip_whitelist.
+// An empty whitelist allows everyone.
+pub async fn ip_whitelist_middleware(
+    State(admin_settings_service): State<Arc<dyn AdminSettingsServiceTrait>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let settings = admin_settings_service
+        .get_all_settings()
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to load security settings: {}", e)))?;
+
+    let whitelist = &settings.security.ip_whitelist;
+    if whitelist.is_empty() {
+        return Ok(next.run(request).await);
+    }
+
+    let addr = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client_ip = get_client_ip(request.headers(), addr.as_ref());
+
+    let allowed = client_ip
+        .parse::<IpAddr>()
+        .map(|ip| is_ip_whitelisted(ip, whitelist))
+        .unwrap_or(false);
+
+    if !allowed {
+        tracing::warn!(
+            "Blocked admin request from non-whitelisted IP: {}",
+            client_ip
+        );
+        return Err(AppError::Forbidden(
+            "Your IP address is not allowed to access this resource".to_string(),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+pub fn is_ip_whitelisted(ip: IpAddr, whitelist: &[String]) -> bool {
+    if whitelist.is_empty() {
+        return true;
+    }
+
+    whitelist.iter().any(|entry| {
+        let entry = entry.trim();
+        if let Ok(network) = entry.parse::<IpNet>() {
+            network.contains(&ip)
+        } else if let Ok(allowed_ip) = entry.parse::<IpAddr>() {
+            allowed_ip == ip
+        } else {
+            tracing::warn!("Ignoring invalid IP whitelist entry: {}", entry);
+            false
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whitelist(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn test_empty_whitelist_allows_all() {
+        assert!(is_ip_whitelisted("203.0.113.7".parse().unwrap(), &[]));
+    }
+
+    #[test]
+    fn test_exact_ip_match() {
+        let list = whitelist(&["198.51.100.4", "2001:db8::1"]);
+        assert!(is_ip_whitelisted("198.51.100.4".parse().unwrap(), &list));
+        assert!(is_ip_whitelisted("2001:db8::1".parse().unwrap(), &list));
+    }
+
+    #[test]
+    fn test_cidr_match() {
+        let list = whitelist(&["10.0.0.0/8"]);
+        assert!(is_ip_whitelisted("10.20.30.40".parse().unwrap(), &list));
+    }
+
+    #[test]
+    fn test_rejects_ip_outside_whitelist() {
+        let list = whitelist(&["10.0.0.0/8", "198.51.100.4", "not-an-ip"]);
+        assert!(!is_ip_whitelisted("192.168.1.10".parse().unwrap(), &list));
+        assert!(!is_ip_whitelisted("198.51.100.5".parse().unwrap(), &list));
+    }
+}
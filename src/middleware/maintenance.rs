@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::models::admin_settings::localized_message;
+use crate::services::admin_settings_service::AdminSettingsServiceTrait;
+use crate::utils::errors::AppError;
+use crate::utils::request::get_accept_languages;
+
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub message: String,
+    pub messages: HashMap<String, String>,
+}
+
+// In-memory copy of the maintenance flag so public requests don't hit the database.
+// Refreshed periodically from admin settings.
+#[derive(Clone, Default)]
+pub struct MaintenanceCache {
+    status: Arc<RwLock<MaintenanceStatus>>,
+}
+
+impl MaintenanceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> MaintenanceStatus {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, status: MaintenanceStatus) {
+        if let Ok(mut current) = self.status.write() {
+            *current = status;
+        }
+    }
+
+    pub async fn refresh(
+        &self,
+        admin_settings_service: &dyn AdminSettingsServiceTrait,
+    ) -> anyhow::Result<()> {
+        let settings = admin_settings_service.get_all_settings().await?;
+
+        self.set(MaintenanceStatus {
+            enabled: settings.general.maintenance_mode,
+            message: settings.general.maintenance_message,
+            messages: settings.general.maintenance_messages,
+        });
+
+        Ok(())
+    }
+}
+
+// Apply to public route groups only; admin and auth routes stay reachable
+pub async fn maintenance_middleware(
+    State(cache): State<MaintenanceCache>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let status = cache.get();
+
+    if status.enabled {
+        let locales = get_accept_languages(request.headers());
+        let message = localized_message(&status.message, &status.messages, &locales);
+        let message = if message.trim().is_empty() {
+            "The site is currently under maintenance. Please check back later.".to_string()
+        } else {
+            message.to_string()
+        };
+
+        return Err(AppError::ServiceUnavailable(message));
+    }
+
+    Ok(next.run(request).await)
+}
@@ -1,3 +1,6 @@
 pub mod auth;
+pub mod feature_flags;
+pub mod ip_whitelist;
+pub mod maintenance;
 pub mod rate_limiter;
 pub mod security;
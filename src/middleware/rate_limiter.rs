@@ -1,8 +1,24 @@
 use anyhow::Result;
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
 use chrono::{DateTime, Utc};
-use redis::{aio::ConnectionManager, Client};
+use once_cell::sync::Lazy;
+use redis::{aio::ConnectionManager, Client, Script};
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::OnceCell;
+
+use crate::utils::errors::AppError;
+use crate::utils::request::get_client_ip;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthRateLimitInfo {
@@ -23,9 +39,52 @@ pub struct BlockedIpInfo {
     pub expires_at: Option<DateTime<Utc>>, // None = permanent
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiRateLimitInfo {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    pub retry_after_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiRateLimitRule {
+    pub limit: u32,
+    pub window_seconds: u64,
+}
+
+impl ApiRateLimitRule {
+    // Compares requests per second without floating point
+    fn is_tighter_than(&self, other: &Self) -> bool {
+        (self.limit as u64) * other.window_seconds < (other.limit as u64) * self.window_seconds
+    }
+}
+
+// Trims the window, counts it and records the request in one step, so concurrent
+// requests cannot all pass the count before any of them is added.
+// Returns {allowed, count before this request, oldest score or nil}.
+static API_RATE_LIMIT_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+        local now = tonumber(ARGV[1])
+        redis.call('ZREMRANGEBYSCORE', KEYS[1], 0, now - tonumber(ARGV[2]))
+        local count = redis.call('ZCARD', KEYS[1])
+        if count >= tonumber(ARGV[3]) then
+            local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+            return {0, count, tonumber(oldest[2]) or false}
+        end
+        redis.call('ZADD', KEYS[1], now, ARGV[4])
+        redis.call('EXPIRE', KEYS[1], ARGV[5])
+        return {1, count, false}
+        ",
+    )
+});
+
 #[derive(Clone)]
 pub struct RedisRateLimiter {
     client: Client,
+    // Opened on first use and shared by every caller; it reconnects by itself
+    connection: Arc<OnceCell<ConnectionManager>>,
 
     // Authentication rate limiting
     auth_ip_limit: u32,
@@ -38,10 +97,9 @@ pub struct RedisRateLimiter {
     ip_block_duration_hours: u64, // Block duration (0 = permanent)
 
     // General API rate limiting
-    #[allow(dead_code)]
     api_limit: u32,
-    #[allow(dead_code)]
     api_window_seconds: u64,
+    api_route_limits: HashMap<String, ApiRateLimitRule>,
 }
 
 impl RedisRateLimiter {
@@ -61,6 +119,7 @@ impl RedisRateLimiter {
 
         Ok(Self {
             client,
+            connection: Arc::new(OnceCell::new()),
             auth_ip_limit,
             auth_ip_window_seconds,
             auth_user_limit,
@@ -69,11 +128,129 @@ impl RedisRateLimiter {
             ip_block_duration_hours,
             api_limit,
             api_window_seconds,
+            api_route_limits: HashMap::new(),
+        })
+    }
+
+    /// Per-route overrides keyed by route template, optionally prefixed with a method
+    /// (`"POST /api/v1/comments"`)
+    pub fn with_route_limits(mut self, route_limits: HashMap<String, ApiRateLimitRule>) -> Self {
+        self.api_route_limits = route_limits;
+        self
+    }
+
+    /// Picks the rule for a request. Any matching route override takes precedence over the
+    /// global default; if both the method-specific and the plain route entry match, the
+    /// tightest of the two wins.
+    pub fn resolve_api_rule(&self, method: &str, route: &str) -> ApiRateLimitRule {
+        let method_key = format!("{} {}", method, route);
+
+        [
+            self.api_route_limits.get(&method_key),
+            self.api_route_limits.get(route),
+        ]
+        .into_iter()
+        .flatten()
+        .copied()
+        .reduce(|tightest, rule| {
+            if rule.is_tighter_than(&tightest) {
+                rule
+            } else {
+                tightest
+            }
+        })
+        .unwrap_or(ApiRateLimitRule {
+            limit: self.api_limit,
+            window_seconds: self.api_window_seconds,
         })
     }
 
     pub async fn get_connection(&self) -> Result<ConnectionManager> {
-        Ok(ConnectionManager::new(self.client.clone()).await?)
+        let connection = self
+            .connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await?;
+
+        Ok(connection.clone())
+    }
+
+    /// Checks that Redis is reachable
+    pub async fn ping(&self) -> Result<()> {
+        let mut conn = self.get_connection().await?;
+        redis::cmd("PING").query_async::<()>(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Record a view of a resource (e.g. "post", "project") for an IP, returning true only
+    /// for the first view within the window
+    pub async fn record_view(
+        &self,
+        resource: &str,
+        id: &str,
+        ip: &str,
+        window_seconds: u64,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection().await?;
+
+        let view_key = format!("{}_view:{}:{}", resource, id, ip);
+        let result: Option<String> = redis::cmd("SET")
+            .arg(&view_key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(window_seconds)
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(result.is_some())
+    }
+
+    /// Sliding-window limit on requests per IP, method and route; allowed requests are recorded
+    pub async fn check_api_rate_limit(
+        &self,
+        ip: &str,
+        method: &str,
+        route: &str,
+    ) -> Result<ApiRateLimitInfo> {
+        let rule = self.resolve_api_rule(method, route);
+        let mut conn = self.get_connection().await?;
+
+        // Millisecond scores so bursts within the same second are counted separately
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let window = rule.window_seconds * 1000;
+        let key = format!("api_rate_limit:{}:{} {}", ip, method, route);
+
+        let (allowed, count, oldest): (bool, u32, Option<u64>) = API_RATE_LIMIT_SCRIPT
+            .key(&key)
+            .arg(now)
+            .arg(window)
+            .arg(rule.limit)
+            .arg(format!("{}:{}", now, uuid::Uuid::new_v4()))
+            .arg(rule.window_seconds)
+            .invoke_async(&mut conn)
+            .await?;
+
+        if !allowed {
+            // The window frees up once the oldest request in it expires
+            let retry_after = oldest
+                .map(|score| (score + window).saturating_sub(now).div_ceil(1000))
+                .unwrap_or(rule.window_seconds)
+                .max(1);
+
+            return Ok(ApiRateLimitInfo {
+                allowed: false,
+                limit: rule.limit,
+                remaining: 0,
+                retry_after_seconds: Some(retry_after),
+            });
+        }
+
+        Ok(ApiRateLimitInfo {
+            allowed: true,
+            limit: rule.limit,
+            remaining: rule.limit - count - 1,
+            retry_after_seconds: None,
+        })
     }
 
     pub async fn check_auth_rate_limit(
@@ -273,13 +450,58 @@ impl RedisRateLimiter {
         Ok(())
     }
 
-    // Get all blocked IPs (simplified version)
+    // Get all blocked IPs by scanning the blocked_ip:* keyspace
     pub async fn get_blocked_ips(&self) -> Result<Vec<BlockedIpInfo>> {
-        let _conn = self.get_connection().await?;
+        let mut conn = self.get_connection().await?;
+
+        let mut blocked_ips = Vec::new();
+        // SCAN may return the same key more than once across iterations
+        let mut seen_keys = HashSet::new();
+        let mut cursor: u64 = 0;
+
+        loop {
+            // Use a COUNT hint so each SCAN round trip stays small on large keyspaces
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg("blocked_ip:*")
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut conn)
+                .await?;
+            let keys: Vec<String> = keys
+                .into_iter()
+                .filter(|key| seen_keys.insert(key.clone()))
+                .collect();
+
+            if !keys.is_empty() {
+                // Keys may expire between SCAN and MGET, so values are optional
+                let values: Vec<Option<String>> =
+                    redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+
+                for (key, value) in keys.iter().zip(values) {
+                    let Some(value) = value else {
+                        continue;
+                    };
+
+                    match serde_json::from_str::<BlockedIpInfo>(&value) {
+                        Ok(info) => blocked_ips.push(info),
+                        Err(e) => {
+                            tracing::warn!("Skipping malformed blocked IP entry {}: {}", key, e);
+                        }
+                    }
+                }
+            }
+
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
 
-        // This is a simplified version - in production you'd want to scan for blocked_ip:* keys
-        // For now, we'll return empty list and you can manually track blocked IPs
-        Ok(vec![])
+        blocked_ips.sort_by_key(|info| std::cmp::Reverse(info.blocked_at));
+
+        Ok(blocked_ips)
     }
 }
 
@@ -358,6 +580,118 @@ pub async fn clear_auth_rate_limit(
     Ok(())
 }
 
+// Per-account lockout, keyed by username rather than IP so failures spread across many
+// addresses still count against the same account
+fn account_failures_key(username: &str) -> String {
+    format!("account_failures:{}", username.trim().to_lowercase())
+}
+
+fn account_lock_key(username: &str) -> String {
+    format!("account_lock:{}", username.trim().to_lowercase())
+}
+
+// Seconds left on the account lock, or None if the account isn't locked
+pub async fn get_account_lock(limiter: &RedisRateLimiter, username: &str) -> Result<Option<u64>> {
+    let mut conn = limiter.get_connection().await?;
+
+    // TTL is -2 for a missing key; locks are always set with an expiry
+    let ttl: i64 = redis::cmd("TTL")
+        .arg(account_lock_key(username))
+        .query_async(&mut conn)
+        .await?;
+
+    Ok(u64::try_from(ttl).ok().filter(|seconds| *seconds > 0))
+}
+
+// Count a failed login for the account and lock it once max_attempts is reached.
+// Returns the lock duration when this failure locked the account.
+pub async fn record_account_failure(
+    limiter: &RedisRateLimiter,
+    username: &str,
+    max_attempts: u32,
+    lockout_seconds: u64,
+) -> Result<Option<u64>> {
+    let mut conn = limiter.get_connection().await?;
+    let failures_key = account_failures_key(username);
+
+    let failures: u32 = redis::cmd("INCR")
+        .arg(&failures_key)
+        .query_async(&mut conn)
+        .await?;
+
+    // Failures older than one lockout period are forgotten
+    if failures == 1 {
+        redis::cmd("EXPIRE")
+            .arg(&failures_key)
+            .arg(lockout_seconds)
+            .query_async::<()>(&mut conn)
+            .await?;
+    }
+
+    if failures < max_attempts {
+        return Ok(None);
+    }
+
+    redis::cmd("SET")
+        .arg(account_lock_key(username))
+        .arg(failures)
+        .arg("EX")
+        .arg(lockout_seconds)
+        .query_async::<()>(&mut conn)
+        .await?;
+
+    // Start counting afresh once the lock expires
+    redis::cmd("DEL")
+        .arg(&failures_key)
+        .query_async::<()>(&mut conn)
+        .await?;
+
+    Ok(Some(lockout_seconds))
+}
+
+// Clear the failure count and any lock after a successful login or password reset
+pub async fn clear_account_lock(limiter: &RedisRateLimiter, username: &str) -> Result<()> {
+    let mut conn = limiter.get_connection().await?;
+
+    redis::cmd("DEL")
+        .arg(account_failures_key(username))
+        .arg(account_lock_key(username))
+        .query_async::<()>(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+fn denied_token_key(jti: &str) -> String {
+    format!("denied_jti:{}", jti)
+}
+
+// Deny an access token by its jti until it would have expired anyway
+pub async fn deny_token(limiter: &RedisRateLimiter, jti: &str, ttl_seconds: u64) -> Result<()> {
+    let mut conn = limiter.get_connection().await?;
+
+    redis::cmd("SET")
+        .arg(denied_token_key(jti))
+        .arg(1)
+        .arg("EX")
+        .arg(ttl_seconds)
+        .query_async::<()>(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn is_token_denied(limiter: &RedisRateLimiter, jti: &str) -> Result<bool> {
+    let mut conn = limiter.get_connection().await?;
+
+    let exists: bool = redis::cmd("EXISTS")
+        .arg(denied_token_key(jti))
+        .query_async(&mut conn)
+        .await?;
+
+    Ok(exists)
+}
+
 // Simple function to check if IP should be auto-blocked
 pub async fn check_and_auto_block_ip(limiter: &RedisRateLimiter, ip: &str) -> Result<()> {
     let mut conn = limiter.get_connection().await?;
@@ -377,3 +711,127 @@ pub async fn check_and_auto_block_ip(limiter: &RedisRateLimiter, ip: &str) -> Re
 
     Ok(())
 }
+
+// Per-IP rate limit for public routes. Routers opt in through `route_layer`, so admin and
+// authenticated routes stay unlimited by leaving it off. Without Redis every request passes.
+pub async fn api_rate_limit_middleware(
+    State(rate_limiter): State<Option<Arc<RedisRateLimiter>>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(rate_limiter) = rate_limiter else {
+        return Ok(next.run(request).await);
+    };
+
+    let addr = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client_ip = get_client_ip(request.headers(), addr.as_ref());
+    let method = request.method().as_str().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let info = match rate_limiter
+        .check_api_rate_limit(&client_ip, &method, &route)
+        .await
+    {
+        Ok(info) => info,
+        Err(e) => {
+            // Fail open so a Redis outage doesn't take the public site down
+            tracing::warn!("API rate limit check failed: {}", e);
+            return Ok(next.run(request).await);
+        }
+    };
+
+    if !info.allowed {
+        return Err(AppError::TooManyRequests {
+            message: "Too many requests, please slow down".to_string(),
+            retry_after: info.retry_after_seconds,
+        });
+    }
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("x-ratelimit-limit", HeaderValue::from(info.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(info.remaining));
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> RedisRateLimiter {
+        // Client::open only parses the URL, no connection is made
+        RedisRateLimiter::new("redis://127.0.0.1/", 20, 300, 5, 900, 5, 24, 60, 60)
+            .unwrap()
+            .with_route_limits(HashMap::from([
+                (
+                    "POST /api/v1/comments".to_string(),
+                    ApiRateLimitRule {
+                        limit: 5,
+                        window_seconds: 60,
+                    },
+                ),
+                (
+                    "/api/v1/comments".to_string(),
+                    ApiRateLimitRule {
+                        limit: 30,
+                        window_seconds: 60,
+                    },
+                ),
+            ]))
+    }
+
+    #[test]
+    fn test_route_override_takes_precedence() {
+        let limiter = limiter();
+
+        // Both entries match a POST, the tighter method-specific one wins
+        assert_eq!(
+            limiter.resolve_api_rule("POST", "/api/v1/comments"),
+            ApiRateLimitRule {
+                limit: 5,
+                window_seconds: 60
+            }
+        );
+        assert_eq!(
+            limiter.resolve_api_rule("GET", "/api/v1/comments"),
+            ApiRateLimitRule {
+                limit: 30,
+                window_seconds: 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_unmatched_route_uses_default() {
+        assert_eq!(
+            limiter().resolve_api_rule("GET", "/api/v1/posts"),
+            ApiRateLimitRule {
+                limit: 60,
+                window_seconds: 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_tighter_rule_compares_rates() {
+        let per_minute = ApiRateLimitRule {
+            limit: 10,
+            window_seconds: 60,
+        };
+        let per_hour = ApiRateLimitRule {
+            limit: 100,
+            window_seconds: 3600,
+        };
+
+        assert!(per_hour.is_tighter_than(&per_minute));
+        assert!(!per_minute.is_tighter_than(&per_hour));
+    }
+}
@@ -1,13 +1,22 @@
 use axum::{
-    extract::Request,
-    http::{header, HeaderValue, Method},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use regex::Regex;
 use std::{sync::Arc, time::Duration};
-
-use super::rate_limiter::RedisRateLimiter;
-use crate::utils::{config::SecurityConfig, errors::AppError};
+use tracing::Instrument;
+
+use super::rate_limiter::{ApiRateLimitRule, RedisRateLimiter};
+use crate::utils::{
+    config::{CorsConfig, SecurityConfig},
+    errors::AppError,
+    request::{
+        request_id_from_headers, scope_request_id, FORWARDED_FOR_HEADER, REAL_IP_HEADER,
+        REQUEST_ID_HEADER,
+    },
+};
 
 // Create rate limiter with Redis backend
 pub async fn create_rate_limiter(
@@ -27,8 +36,24 @@ pub async fn create_rate_limiter(
         24, // ip_block_duration_hours: Block for 24 hours (0 = permanent)
         // General API rate limiting
         security_config.rate_limit.requests_per_minute as u32,
-        60, // api_window_seconds: 1 minute
-    )?;
+        security_config.rate_limit.window_seconds,
+    )?
+    .with_route_limits(
+        security_config
+            .rate_limit
+            .routes
+            .iter()
+            .map(|(route, rule)| {
+                (
+                    route.clone(),
+                    ApiRateLimitRule {
+                        limit: rule.requests,
+                        window_seconds: rule.window_seconds,
+                    },
+                )
+            })
+            .collect(),
+    );
 
     Ok(Arc::new(limiter))
 }
@@ -91,25 +116,125 @@ pub async fn security_headers_middleware(
 }
 
 pub async fn request_id_middleware(mut request: Request, next: Next) -> Result<Response, AppError> {
-    let request_id = uuid::Uuid::new_v4().to_string();
+    let request_id = request_id_from_headers(request.headers());
+    let header_value = HeaderValue::from_str(&request_id.0).unwrap();
 
-    // Add request ID to request headers for logging
-    request.headers_mut().insert(
-        header::HeaderName::from_static("x-request-id"),
-        HeaderValue::from_str(&request_id).unwrap(),
-    );
+    // Expose the ID to handlers (extension), logs (header) and error bodies (task scope)
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value.clone());
+    request.extensions_mut().insert(request_id.clone());
 
-    let mut response = next.run(request).await;
+    // Everything logged while handling the request carries its ID
+    let span = tracing::info_span!("request", request_id = %request_id.0);
+    let mut response = scope_request_id(request_id, next.run(request))
+        .instrument(span)
+        .await;
 
-    // Add request ID to response headers
-    response.headers_mut().insert(
-        header::HeaderName::from_static("x-request-id"),
-        HeaderValue::from_str(&request_id).unwrap(),
-    );
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
 
     Ok(response)
 }
 
+/// Drops client-supplied `X-Forwarded-For` and `X-Real-IP` unless the server is
+/// configured to sit behind a reverse proxy that sets them. `get_client_ip` prefers
+/// those headers, so without this anyone could choose the IP that is audited,
+/// rate limited and checked against the admin whitelist.
+pub async fn proxy_headers_middleware(
+    State(trust_proxy_headers): State<bool>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !trust_proxy_headers {
+        let headers = request.headers_mut();
+        headers.remove(FORWARDED_FOR_HEADER);
+        headers.remove(REAL_IP_HEADER);
+    }
+
+    next.run(request).await
+}
+
+/// Rewrites framework-generated 413 responses (body limit layers and extractors)
+/// into the standard error envelope.
+pub async fn payload_too_large_middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json {
+        return response;
+    }
+
+    AppError::PayloadTooLarge("Request body is too large".to_string()).into_response()
+}
+
+/// Turns the 408 produced by the request timeout layer into a 504 in the
+/// standard error envelope: the client sent its request in time, the server
+/// failed to answer it.
+pub async fn request_timeout_middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    if response.status() != StatusCode::REQUEST_TIMEOUT
+        || response.headers().contains_key(header::CONTENT_TYPE)
+    {
+        return response;
+    }
+
+    AppError::GatewayTimeout("The request took too long to process".to_string()).into_response()
+}
+
+/// `Cache-Control` value for public read routes
+pub fn public_cache_control(max_age_seconds: u64) -> HeaderValue {
+    HeaderValue::from_str(&format!("public, max-age={}", max_age_seconds))
+        .expect("Cache-Control value is ASCII")
+}
+
+/// Lets browsers and CDNs cache successful public reads. Errors and
+/// non-GET requests are left to `no_store_middleware`, and so are draft
+/// previews: a handler's own `Cache-Control` always wins, and requests
+/// carrying a `preview_token` are never marked public.
+pub async fn public_cache_middleware(
+    State(cache_control): State<HeaderValue>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_read = matches!(*request.method(), Method::GET | Method::HEAD);
+    let is_preview = request.uri().query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| pair.starts_with("preview_token="))
+    });
+    let mut response = next.run(request).await;
+
+    let status = response.status();
+    if is_read && !is_preview && (status.is_success() || status == StatusCode::NOT_MODIFIED) {
+        response
+            .headers_mut()
+            .entry(header::CACHE_CONTROL)
+            .or_insert(cache_control);
+    }
+
+    response
+}
+
+/// Marks every response that no route opted into caching (admin,
+/// authenticated, mutating and error responses) as `no-store`.
+pub async fn no_store_middleware(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+
+    response
+        .headers_mut()
+        .entry(header::CACHE_CONTROL)
+        .or_insert(HeaderValue::from_static("no-store"));
+
+    response
+}
+
 pub async fn logging_middleware(request: Request, next: Next) -> Response {
     let start = std::time::Instant::now();
     let method = request.method().clone();
@@ -149,24 +274,15 @@ pub async fn logging_middleware(request: Request, next: Next) -> Response {
 
 // Custom CORS middleware with configuration
 pub fn create_cors_layer(security_config: &SecurityConfig) -> tower_http::cors::CorsLayer {
-    use tower_http::cors::{Any, CorsLayer};
+    use tower_http::cors::{AllowOrigin, CorsLayer};
 
-    let mut cors = CorsLayer::new();
-    let allow_any_origin = security_config
-        .cors
-        .allowed_origins
-        .contains(&"*".to_string());
-
-    // Configure allowed origins
-    if allow_any_origin {
-        cors = cors.allow_origin(Any);
-    } else {
-        for origin in &security_config.cors.allowed_origins {
-            if let Ok(header_value) = origin.parse::<HeaderValue>() {
-                cors = cors.allow_origin(header_value);
-            }
-        }
-    }
+    // Origins are always echoed back individually: credentials can't be combined with "*"
+    let origins = OriginMatcher::new(&security_config.cors);
+    let mut cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            origins.matches(origin)
+        }))
+        .allow_credentials(true);
 
     // Configure allowed methods
     let methods: Vec<Method> = security_config
@@ -198,11 +314,70 @@ pub fn create_cors_layer(security_config: &SecurityConfig) -> tower_http::cors::
     // Configure max age
     cors = cors.max_age(Duration::from_secs(security_config.cors.max_age));
 
-    // Only allow credentials if not using wildcard origin
-    // CORS spec doesn't allow credentials with wildcard origin
-    if !allow_any_origin {
-        cors = cors.allow_credentials(true);
+    cors
+}
+
+// Matches request origins against the exact, suffix and regex lists from the CORS config
+struct OriginMatcher {
+    exact: Vec<String>,
+    suffixes: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl OriginMatcher {
+    fn new(cors: &CorsConfig) -> Self {
+        let patterns = cors
+            .allowed_origin_patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid CORS origin pattern {:?}: {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            exact: cors
+                .allowed_origins
+                .iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+            // "budhilaw.com" and ".budhilaw.com" both mean the domain and its subdomains
+            suffixes: cors
+                .allowed_origin_suffixes
+                .iter()
+                .map(|suffix| suffix.trim().trim_start_matches('.').to_ascii_lowercase())
+                .filter(|suffix| !suffix.is_empty())
+                .collect(),
+            patterns,
+        }
     }
 
-    cors
+    fn matches(&self, origin: &HeaderValue) -> bool {
+        let Ok(origin) = origin.to_str() else {
+            return false;
+        };
+
+        self.exact.iter().any(|allowed| allowed == origin)
+            || origin_host(origin).is_some_and(|host| {
+                self.suffixes.iter().any(|domain| {
+                    host == *domain
+                        || host
+                            .strip_suffix(domain.as_str())
+                            .is_some_and(|rest| rest.ends_with('.'))
+                })
+            })
+            || self.patterns.iter().any(|pattern| pattern.is_match(origin))
+    }
+}
+
+// Host part of "scheme://host[:port]", lowercased
+fn origin_host(origin: &str) -> Option<String> {
+    let (_, authority) = origin.split_once("://")?;
+    let host = authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host);
+    Some(host.to_ascii_lowercase())
 }
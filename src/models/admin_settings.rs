@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::utils::password::PasswordPolicy;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct AdminSettingsRecord {
     pub id: Uuid,
@@ -14,6 +19,75 @@ pub struct AdminSettingsRecord {
     pub created_at: DateTime<Utc>,
 }
 
+/// Returned in place of stored secrets. Sending it back on update keeps the stored value.
+pub const MASKED_SECRET: &str = "********";
+
+// JSON keys of the notification settings that hold credentials
+const NOTIFICATION_SECRET_KEYS: [&str; 3] = ["smtpPassword", "telegramBotToken", "webhookSecret"];
+
+impl AdminSettingsRecord {
+    pub fn masked(mut self) -> Self {
+        mask_setting_value(&self.setting_key, &mut self.setting_value);
+        self
+    }
+}
+
+/// A setting value as it was before an update replaced it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AdminSettingsHistory {
+    pub id: Uuid,
+    pub setting_key: String,
+    pub setting_value: serde_json::Value,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+    pub changed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AdminSettingsHistory {
+    pub fn masked(mut self) -> Self {
+        mask_setting_value(&self.setting_key, &mut self.setting_value);
+        self
+    }
+}
+
+/// Replaces credentials inside a raw setting value with `MASKED_SECRET`
+pub fn mask_setting_value(key: &str, value: &mut serde_json::Value) {
+    if key != "notifications" {
+        return;
+    }
+
+    for secret_key in NOTIFICATION_SECRET_KEYS {
+        if let Some(secret) = value.get_mut(secret_key) {
+            if secret.as_str().is_some_and(|s| !s.is_empty()) {
+                *secret = serde_json::Value::String(MASKED_SECRET.to_string());
+            }
+        }
+    }
+}
+
+/// Undoes `mask_setting_value` on an incoming value, taking the secrets from `stored`
+pub fn restore_masked_setting_value(
+    key: &str,
+    value: &mut serde_json::Value,
+    stored: &serde_json::Value,
+) {
+    if key != "notifications" {
+        return;
+    }
+
+    for secret_key in NOTIFICATION_SECRET_KEYS {
+        if let Some(secret) = value.get_mut(secret_key) {
+            if secret.as_str() == Some(MASKED_SECRET) {
+                *secret = stored
+                    .get(secret_key)
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminSettings {
     pub id: String,
@@ -31,10 +105,15 @@ pub struct GeneralSettings {
     pub site_name: String,
     #[serde(rename = "siteDescription")]
     pub site_description: String,
+    #[serde(rename = "siteUrl", default)]
+    pub site_url: Option<String>,
     #[serde(rename = "maintenanceMode")]
     pub maintenance_mode: bool,
     #[serde(rename = "maintenanceMessage")]
     pub maintenance_message: String,
+    // Translations of the maintenance message keyed by language code, e.g. "id" or "pt-BR"
+    #[serde(rename = "maintenanceMessages", default)]
+    pub maintenance_messages: HashMap<String, String>,
     pub photo_profile: Option<String>,
     pub social_media_links: SocialMediaLinks,
     pub files: FilesSettings,
@@ -54,6 +133,33 @@ pub struct FeatureSettings {
     pub contact_form_enabled: bool,
     #[serde(rename = "searchEnabled")]
     pub search_enabled: bool,
+    // Comment limits in characters, tunable without a redeploy
+    #[serde(rename = "commentMinLength", default = "default_comment_min_length")]
+    pub comment_min_length: u32,
+    #[serde(rename = "commentMaxLength", default = "default_comment_max_length")]
+    pub comment_max_length: u32,
+    #[serde(
+        rename = "authorNameMaxLength",
+        default = "default_author_name_max_length"
+    )]
+    pub author_name_max_length: u32,
+}
+
+// Ceilings for the comment limits above. They match the request validators
+// on comments and the author_name column, which reject anything longer anyway.
+pub const COMMENT_MAX_LENGTH_LIMIT: u32 = 20_000;
+pub const AUTHOR_NAME_MAX_LENGTH_LIMIT: u32 = 255;
+
+fn default_comment_min_length() -> u32 {
+    5
+}
+
+fn default_comment_max_length() -> u32 {
+    5000
+}
+
+fn default_author_name_max_length() -> u32 {
+    100
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +180,13 @@ pub struct NotificationSettings {
     pub telegram_bot_token: Option<String>,
     #[serde(rename = "telegramChatId")]
     pub telegram_chat_id: Option<String>,
+    #[serde(rename = "webhookUrl", default)]
+    pub webhook_url: Option<String>,
+    #[serde(rename = "webhookSecret", default)]
+    pub webhook_secret: Option<String>,
+    // Audit log actions to forward; empty forwards everything
+    #[serde(rename = "webhookEvents", default)]
+    pub webhook_events: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +205,41 @@ pub struct SecuritySettings {
     pub comment_rate_limit: CommentRateLimitSettings,
     #[serde(rename = "commentApprovalRequired", default)]
     pub comment_approval_required: bool,
+    #[serde(rename = "passwordPolicy", default)]
+    pub password_policy: PasswordPolicy,
+    #[serde(
+        rename = "auditLogRetentionDays",
+        default = "default_audit_log_retention_days"
+    )]
+    pub audit_log_retention_days: i32,
+    // How long an account stays locked once it reaches max_login_attempts failures
+    #[serde(
+        rename = "lockoutDurationMinutes",
+        default = "default_lockout_duration_minutes"
+    )]
+    pub lockout_duration_minutes: i32,
+    // Comments left pending for this many days are expired; 0 keeps them forever
+    #[serde(rename = "pendingCommentExpiryDays", default)]
+    pub pending_comment_expiry_days: i32,
+    #[serde(rename = "pendingCommentExpiryAction", default)]
+    pub pending_comment_expiry_action: PendingCommentExpiryAction,
+}
+
+fn default_audit_log_retention_days() -> i32 {
+    365
+}
+
+fn default_lockout_duration_minutes() -> i32 {
+    15
+}
+
+// What happens to a comment that has waited too long for moderation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PendingCommentExpiryAction {
+    #[default]
+    Reject,
+    Delete,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +254,63 @@ pub struct CommentRateLimitSettings {
     pub minute_window: i32, // in minutes
 }
 
+// Everything the unauthenticated /settings/public endpoint returns. Fields are copied
+// one by one so a new internal setting is never exposed by accident.
+#[derive(Debug, Serialize)]
+pub struct PublicSiteSettings {
+    pub site_name: String,
+    pub site_description: String,
+    pub maintenance_mode: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintenance_message: Option<String>,
+    pub photo_profile: Option<String>,
+    pub social_media_links: SocialMediaLinks,
+    pub files: FilesSettings,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicFeatureSettings {
+    pub portfolio_enabled: bool,
+    pub services_enabled: bool,
+    pub blog_enabled: bool,
+    pub contact_form_enabled: bool,
+    pub comments_enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicSettings {
+    pub site: PublicSiteSettings,
+    pub features: PublicFeatureSettings,
+}
+
+impl PublicSettings {
+    pub fn new(settings: AdminSettings, locales: &[String]) -> Self {
+        let general = settings.general;
+        let maintenance_message = general
+            .maintenance_mode
+            .then(|| general.maintenance_message_for(locales).to_string());
+
+        Self {
+            site: PublicSiteSettings {
+                site_name: general.site_name,
+                site_description: general.site_description,
+                maintenance_mode: general.maintenance_mode,
+                maintenance_message,
+                photo_profile: general.photo_profile,
+                social_media_links: general.social_media_links,
+                files: general.files,
+            },
+            features: PublicFeatureSettings {
+                portfolio_enabled: settings.features.portfolio_enabled,
+                services_enabled: settings.features.services_enabled,
+                blog_enabled: settings.features.blog_enabled,
+                contact_form_enabled: settings.features.contact_form_enabled,
+                comments_enabled: settings.features.comments_enabled,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateSettingsRequest {
     pub general: Option<GeneralSettings>,
@@ -141,9 +346,11 @@ impl Default for GeneralSettings {
             site_name: "Ericsson Budhilaw".to_string(),
             site_description: "Senior Software Engineer specializing in consulting and freelancing"
                 .to_string(),
+            site_url: None,
             maintenance_mode: false,
             maintenance_message:
                 "The site is currently under maintenance. Please check back later.".to_string(),
+            maintenance_messages: HashMap::new(),
             photo_profile: None,
             social_media_links: SocialMediaLinks::default(),
             files: FilesSettings::default(),
@@ -151,6 +358,65 @@ impl Default for GeneralSettings {
     }
 }
 
+impl GeneralSettings {
+    pub fn maintenance_message_for(&self, locales: &[String]) -> &str {
+        localized_message(
+            &self.maintenance_message,
+            &self.maintenance_messages,
+            locales,
+        )
+    }
+}
+
+static LANGUAGE_TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z]{2,3}(-[A-Za-z0-9]{2,8})*$").unwrap());
+
+/// Loose BCP 47 check: a 2-3 letter language followed by optional subtags
+pub fn is_language_tag(tag: &str) -> bool {
+    LANGUAGE_TAG_REGEX.is_match(tag)
+}
+
+/// Picks the translation for the most preferred locale that has one, trying the full
+/// tag ("pt-BR") before its primary language ("pt"), and falls back to `default`.
+pub fn localized_message<'a>(
+    default: &'a str,
+    messages: &'a HashMap<String, String>,
+    locales: &[String],
+) -> &'a str {
+    let find = |tag: &str| {
+        messages
+            .iter()
+            .find(|(key, message)| key.eq_ignore_ascii_case(tag) && !message.trim().is_empty())
+            .map(|(_, message)| message.as_str())
+    };
+
+    locales
+        .iter()
+        .find_map(|locale| {
+            find(locale).or_else(|| {
+                locale
+                    .split_once('-')
+                    .and_then(|(primary, _)| find(primary))
+            })
+        })
+        .unwrap_or(default)
+}
+
+impl FeatureSettings {
+    /// Looks up a toggle by its API name, e.g. "blog" or "contactForm"
+    pub fn is_enabled(&self, feature: &str) -> Option<bool> {
+        match feature {
+            "comments" => Some(self.comments_enabled),
+            "portfolio" => Some(self.portfolio_enabled),
+            "services" => Some(self.services_enabled),
+            "blog" => Some(self.blog_enabled),
+            "contactForm" => Some(self.contact_form_enabled),
+            "search" => Some(self.search_enabled),
+            _ => None,
+        }
+    }
+}
+
 impl Default for FeatureSettings {
     fn default() -> Self {
         Self {
@@ -160,10 +426,57 @@ impl Default for FeatureSettings {
             blog_enabled: true,
             contact_form_enabled: true,
             search_enabled: true,
+            comment_min_length: default_comment_min_length(),
+            comment_max_length: default_comment_max_length(),
+            author_name_max_length: default_author_name_max_length(),
         }
     }
 }
 
+impl NotificationSettings {
+    /// Whether enough SMTP configuration is present to attempt sending mail
+    pub fn is_smtp_configured(&self) -> bool {
+        let present =
+            |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        // Username doubles as the sender address; the password is optional for open relays
+        present(&self.smtp_host) && present(&self.smtp_username)
+    }
+
+    /// Copy safe to return to clients, with every stored credential masked
+    pub fn masked(mut self) -> Self {
+        for secret in [
+            &mut self.smtp_password,
+            &mut self.telegram_bot_token,
+            &mut self.webhook_secret,
+        ] {
+            if secret.as_deref().is_some_and(|s| !s.is_empty()) {
+                *secret = Some(MASKED_SECRET.to_string());
+            }
+        }
+        self
+    }
+
+    /// Puts the stored credentials back wherever a client echoed the masked placeholder
+    pub fn restore_secrets(&mut self, stored: &NotificationSettings) {
+        for (secret, stored) in [
+            (&mut self.smtp_password, &stored.smtp_password),
+            (&mut self.telegram_bot_token, &stored.telegram_bot_token),
+            (&mut self.webhook_secret, &stored.webhook_secret),
+        ] {
+            if secret.as_deref() == Some(MASKED_SECRET) {
+                *secret = stored.clone();
+            }
+        }
+    }
+
+    /// Whether a bot token and chat id are set for Telegram delivery
+    pub fn is_telegram_configured(&self) -> bool {
+        let present =
+            |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        present(&self.telegram_bot_token) && present(&self.telegram_chat_id)
+    }
+}
+
 impl Default for NotificationSettings {
     fn default() -> Self {
         Self {
@@ -175,6 +488,9 @@ impl Default for NotificationSettings {
             telegram_notifications: Some(false),
             telegram_bot_token: None,
             telegram_chat_id: None,
+            webhook_url: None,
+            webhook_secret: None,
+            webhook_events: vec![],
         }
     }
 }
@@ -189,6 +505,11 @@ impl Default for SecuritySettings {
             ip_whitelist: vec![],
             comment_rate_limit: CommentRateLimitSettings::default(),
             comment_approval_required: false,
+            password_policy: PasswordPolicy::default(),
+            audit_log_retention_days: default_audit_log_retention_days(),
+            lockout_duration_minutes: default_lockout_duration_minutes(),
+            pending_comment_expiry_days: 0,
+            pending_comment_expiry_action: PendingCommentExpiryAction::default(),
         }
     }
 }
@@ -225,6 +546,13 @@ impl Default for FilesSettings {
     }
 }
 
+impl AdminSettings {
+    pub fn masked(mut self) -> Self {
+        self.notifications = self.notifications.masked();
+        self
+    }
+}
+
 impl Default for AdminSettings {
     fn default() -> Self {
         Self {
@@ -238,3 +566,146 @@ impl Default for AdminSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> GeneralSettings {
+        GeneralSettings {
+            maintenance_message: "Back soon".to_string(),
+            maintenance_messages: HashMap::from([
+                ("id".to_string(), "Segera kembali".to_string()),
+                ("pt-BR".to_string(), "Voltamos logo".to_string()),
+            ]),
+            ..GeneralSettings::default()
+        }
+    }
+
+    fn locales(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_feature_settings_saved_before_comment_limits_use_defaults() {
+        let features: FeatureSettings = serde_json::from_value(serde_json::json!({
+            "commentsEnabled": true,
+            "portfolioEnabled": true,
+            "servicesEnabled": true,
+            "blogEnabled": true,
+            "contactFormEnabled": true,
+            "searchEnabled": true,
+        }))
+        .unwrap();
+
+        assert_eq!(features.comment_min_length, 5);
+        assert_eq!(features.comment_max_length, 5000);
+        assert_eq!(features.author_name_max_length, 100);
+    }
+
+    #[test]
+    fn test_maintenance_message_matches_locale() {
+        let settings = settings();
+
+        assert_eq!(
+            settings.maintenance_message_for(&locales(&["pt-br"])),
+            "Voltamos logo"
+        );
+        assert_eq!(
+            settings.maintenance_message_for(&locales(&["fr", "id-ID"])),
+            "Segera kembali"
+        );
+    }
+
+    #[test]
+    fn test_maintenance_message_falls_back_to_default() {
+        let settings = settings();
+
+        assert_eq!(
+            settings.maintenance_message_for(&locales(&["fr-FR", "de"])),
+            "Back soon"
+        );
+        assert_eq!(settings.maintenance_message_for(&[]), "Back soon");
+    }
+
+    #[test]
+    fn test_masked_notification_settings_round_trip() {
+        let stored = NotificationSettings {
+            smtp_password: Some("hunter2".to_string()),
+            telegram_bot_token: Some("123:secret-token".to_string()),
+            webhook_secret: Some(String::new()),
+            ..NotificationSettings::default()
+        };
+
+        let masked = stored.clone().masked();
+        assert_eq!(masked.smtp_password.as_deref(), Some(MASKED_SECRET));
+        assert_eq!(masked.telegram_bot_token.as_deref(), Some(MASKED_SECRET));
+        // Unset secrets stay unset so clients can tell them apart
+        assert_eq!(masked.webhook_secret.as_deref(), Some(""));
+
+        let mut update = NotificationSettings {
+            telegram_bot_token: Some("456:new-token".to_string()),
+            ..masked
+        };
+        update.restore_secrets(&stored);
+        assert_eq!(update.smtp_password.as_deref(), Some("hunter2"));
+        assert_eq!(update.telegram_bot_token.as_deref(), Some("456:new-token"));
+    }
+
+    #[test]
+    fn test_mask_setting_value() {
+        let mut value = serde_json::json!({
+            "smtpHost": "smtp.example.com",
+            "smtpPassword": "hunter2",
+            "telegramBotToken": null
+        });
+
+        mask_setting_value("notifications", &mut value);
+
+        assert_eq!(value["smtpHost"], "smtp.example.com");
+        assert_eq!(value["smtpPassword"], MASKED_SECRET);
+        assert!(value["telegramBotToken"].is_null());
+    }
+
+    #[test]
+    fn test_public_settings_exclude_sensitive_fields() {
+        let mut settings = AdminSettings::default();
+        settings.general.maintenance_mode = true;
+        settings.notifications.smtp_host = Some("smtp.example.com".to_string());
+        settings.notifications.smtp_password = Some("hunter2".to_string());
+        settings.notifications.telegram_bot_token = Some("123:secret-token".to_string());
+        settings.security.ip_whitelist = vec!["10.0.0.1".to_string()];
+
+        let value = serde_json::to_value(PublicSettings::new(settings, &[])).unwrap();
+        let json = value.to_string().to_lowercase();
+
+        assert_eq!(
+            value.as_object().unwrap().keys().collect::<Vec<_>>(),
+            ["features", "site"]
+        );
+        assert!(value["site"]["maintenance_message"].is_string());
+        for leaked in [
+            "security",
+            "notifications",
+            "smtp",
+            "telegram",
+            "webhook",
+            "whitelist",
+            "hunter2",
+            "secret-token",
+            "10.0.0.1",
+        ] {
+            assert!(!json.contains(leaked), "public settings contain {}", leaked);
+        }
+    }
+
+    #[test]
+    fn test_language_tags() {
+        for tag in ["en", "id", "pt-BR", "zh-Hant-TW", "es-419"] {
+            assert!(is_language_tag(tag), "{} should be accepted", tag);
+        }
+        for tag in ["", "e", "english", "en_US", "en-", "pt-BR!"] {
+            assert!(!is_language_tag(tag), "{} should be rejected", tag);
+        }
+    }
+}
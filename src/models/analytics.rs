@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PopularPost {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub category: String,
+    pub view_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CategoryViews {
+    pub category: String,
+    pub posts: i64,
+    pub views: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ViewTotals {
+    pub post_views: i64,
+    pub portfolio_views: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ContentCounts {
+    pub total_posts: i64,
+    pub published_posts: i64,
+    pub total_comments: i64,
+    pub pending_comments: i64,
+    pub total_projects: i64,
+    pub active_projects: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsSummary {
+    pub total_views: i64,
+    pub post_views: i64,
+    pub portfolio_views: i64,
+    pub most_viewed_posts: Vec<PopularPost>,
+    pub views_by_category: Vec<CategoryViews>,
+    pub counts: ContentCounts,
+    // When the summary was computed; responses may be served from cache
+    pub generated_at: DateTime<Utc>,
+}
@@ -51,6 +51,9 @@ pub struct AuditLogFilters {
     pub search: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    // Opaque `next_cursor` from a previous page; takes the place of `offset` and stays
+    // stable while new entries are being logged
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -60,6 +63,8 @@ pub struct AuditLogResponse {
     pub page: i64,
     pub per_page: i64,
     pub total_pages: i64,
+    // Present while more entries follow this page
+    pub next_cursor: Option<String>,
 }
 
 // Audit action types for type safety
@@ -76,6 +81,9 @@ pub enum AuditAction {
     PostDeleted,
     PostPublished,
     PostUnpublished,
+    PostFeatured,
+    PostUnfeatured,
+    PostsImported,
 
     // Portfolio
     PortfolioCreated,
@@ -83,6 +91,7 @@ pub enum AuditAction {
     PortfolioDeleted,
     PortfolioFeatured,
     PortfolioUnfeatured,
+    PortfolioImported,
 
     // Services
     ServiceCreated,
@@ -101,6 +110,11 @@ pub enum AuditAction {
 
     // Profile
     ProfileUpdated,
+
+    // Users
+    UserDeactivated,
+    UserReactivated,
+    UserRoleChanged,
 }
 
 impl std::fmt::Display for AuditAction {
@@ -114,11 +128,15 @@ impl std::fmt::Display for AuditAction {
             AuditAction::PostDeleted => "post_deleted",
             AuditAction::PostPublished => "post_published",
             AuditAction::PostUnpublished => "post_unpublished",
+            AuditAction::PostFeatured => "post_featured",
+            AuditAction::PostUnfeatured => "post_unfeatured",
+            AuditAction::PostsImported => "posts_imported",
             AuditAction::PortfolioCreated => "portfolio_created",
             AuditAction::PortfolioUpdated => "portfolio_updated",
             AuditAction::PortfolioDeleted => "portfolio_deleted",
             AuditAction::PortfolioFeatured => "portfolio_featured",
             AuditAction::PortfolioUnfeatured => "portfolio_unfeatured",
+            AuditAction::PortfolioImported => "portfolio_imported",
             AuditAction::ServiceCreated => "service_created",
             AuditAction::ServiceUpdated => "service_updated",
             AuditAction::ServiceDeleted => "service_deleted",
@@ -129,6 +147,9 @@ impl std::fmt::Display for AuditAction {
             AuditAction::CommentDeleted => "comment_deleted",
             AuditAction::SettingsUpdated => "settings_updated",
             AuditAction::ProfileUpdated => "profile_updated",
+            AuditAction::UserDeactivated => "user_deactivated",
+            AuditAction::UserReactivated => "user_reactivated",
+            AuditAction::UserRoleChanged => "user_role_changed",
         };
         write!(f, "{}", s)
     }
@@ -143,6 +164,43 @@ pub enum ResourceType {
     Comment,
     Settings,
     Profile,
+    ApiKey,
+    User,
+}
+
+impl ResourceType {
+    pub const ALL: [ResourceType; 9] = [
+        ResourceType::Authentication,
+        ResourceType::Post,
+        ResourceType::Portfolio,
+        ResourceType::Service,
+        ResourceType::Comment,
+        ResourceType::Settings,
+        ResourceType::Profile,
+        ResourceType::ApiKey,
+        ResourceType::User,
+    ];
+}
+
+impl std::str::FromStr for ResourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|resource_type| resource_type.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid resource type '{}'. Must be one of: {}",
+                    s,
+                    Self::ALL
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
 }
 
 impl std::fmt::Display for ResourceType {
@@ -155,6 +213,8 @@ impl std::fmt::Display for ResourceType {
             ResourceType::Comment => "comment",
             ResourceType::Settings => "settings",
             ResourceType::Profile => "profile",
+            ResourceType::ApiKey => "api_key",
+            ResourceType::User => "user",
         };
         write!(f, "{}", s)
     }
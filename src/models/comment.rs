@@ -1,9 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::utils::gravatar::gravatar_url;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Comment {
     pub id: Uuid,
@@ -25,6 +27,7 @@ pub struct CommentResponse {
     pub post_id: Uuid,
     pub author_name: String,
     pub author_email: String,
+    pub author_avatar: String,
     pub content: String,
     pub status: String,
     pub ip_address: Option<String>,
@@ -41,6 +44,7 @@ impl From<Comment> for CommentResponse {
             id: comment.id,
             post_id: comment.post_id,
             author_name: comment.author_name,
+            author_avatar: gravatar_url(&comment.author_email),
             author_email: comment.author_email,
             content: comment.content,
             status: comment.status,
@@ -54,26 +58,74 @@ impl From<Comment> for CommentResponse {
     }
 }
 
+// What anonymous readers may see of a comment: no email, IP or user agent
+#[derive(Debug, Serialize)]
+pub struct PublicComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub author_name: String,
+    pub author_avatar: String,
+    pub content: String,
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Comment> for PublicComment {
+    fn from(comment: Comment) -> Self {
+        Self {
+            id: comment.id,
+            post_id: comment.post_id,
+            author_name: comment.author_name,
+            author_avatar: gravatar_url(&comment.author_email),
+            content: comment.content,
+            parent_id: comment.parent_id,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: PublicComment,
+    pub replies: Vec<CommentThread>,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateCommentRequest {
     pub post_id: Uuid,
+    // Upper bounds are hard ceilings; the configured limits are checked by the service
     #[validate(length(
         min = 1,
-        max = 100,
-        message = "Author name is required and must be less than 100 characters"
+        max = 255,
+        message = "Author name is required and must be at most 255 characters"
     ))]
     pub author_name: String,
     #[validate(email(message = "Please provide a valid email address"))]
     pub author_email: String,
     #[validate(length(
-        min = 5,
-        max = 5000,
-        message = "Content must be between 5 and 5000 characters"
+        min = 1,
+        max = 20000,
+        message = "Content is required and must be at most 20000 characters"
     ))]
     pub content: String,
     pub parent_id: Option<Uuid>,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct EditCommentRequest {
+    #[validate(length(min = 1, message = "Edit token is required"))]
+    pub edit_token: String,
+    #[validate(length(
+        min = 1,
+        max = 20000,
+        message = "Content is required and must be at most 20000 characters"
+    ))]
+    pub content: String,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct UpdateCommentStatusRequest {
     #[validate(length(min = 1, message = "Status is required"))]
@@ -87,6 +139,7 @@ pub struct CommentQuery {
     pub post_id: Option<Uuid>,
     pub status: Option<String>,
     pub author_email: Option<String>,
+    pub search: Option<String>,
     pub include_replies: Option<bool>,
 }
 
@@ -108,6 +161,21 @@ pub struct CommentStats {
     pub comments_this_month: i64,
 }
 
+// Comments created on one day, broken down by their current status
+#[derive(Debug, Serialize, FromRow)]
+pub struct CommentDailyStats {
+    pub day: NaiveDate,
+    pub created: i64,
+    pub approved: i64,
+    pub rejected: i64,
+    pub spam: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentStatsQuery {
+    pub days: Option<i32>,
+}
+
 #[derive(Debug, Serialize, FromRow)]
 pub struct CommentModerationInfo {
     pub id: Uuid,
@@ -121,3 +189,25 @@ pub struct CommentModerationInfo {
     pub user_agent: Option<String>,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BannedCommenter {
+    pub id: Uuid,
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    pub reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    // None means the ban never expires
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateBanRequest {
+    #[validate(email(message = "Please provide a valid email address"))]
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    #[validate(length(max = 500, message = "Reason cannot exceed 500 characters"))]
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
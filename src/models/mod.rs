@@ -1,8 +1,11 @@
 pub mod admin_settings;
+pub mod analytics;
+pub mod api_key;
 pub mod audit_log;
 pub mod comment;
 pub mod portfolio;
 pub mod post;
+pub mod search;
 pub mod service;
 pub mod user;
 pub mod user_notification;
@@ -22,6 +22,9 @@ pub struct PortfolioProject {
     pub start_date: NaiveDate,
     pub end_date: Option<NaiveDate>,
     pub client: Option<String>,
+    pub sort_order: i32,
+    pub view_count: i32,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -44,6 +47,9 @@ pub struct PortfolioProjectResponse {
     pub start_date: NaiveDate,
     pub end_date: Option<NaiveDate>,
     pub client: Option<String>,
+    pub sort_order: i32,
+    pub view_count: i32,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -67,6 +73,9 @@ impl From<PortfolioProject> for PortfolioProjectResponse {
             start_date: project.start_date,
             end_date: project.end_date,
             client: project.client,
+            sort_order: project.sort_order,
+            view_count: project.view_count,
+            version: project.version,
             created_at: project.created_at,
             updated_at: project.updated_at,
         }
@@ -81,11 +90,9 @@ pub struct CreatePortfolioProjectRequest {
         message = "Title is required and must be less than 255 characters"
     ))]
     pub title: String,
-    #[validate(length(
-        min = 1,
-        max = 255,
-        message = "Slug is required and must be less than 255 characters"
-    ))]
+    // Generated from the title when left empty
+    #[serde(default)]
+    #[validate(length(max = 255, message = "Slug must be less than 255 characters"))]
     pub slug: String,
     #[validate(length(min = 1, message = "Description is required"))]
     pub description: String,
@@ -115,6 +122,7 @@ pub struct CreatePortfolioProjectRequest {
     pub end_date: Option<NaiveDate>,
     #[validate(length(max = 255, message = "Client name must be less than 255 characters"))]
     pub client: Option<String>,
+    pub sort_order: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -159,17 +167,46 @@ pub struct UpdatePortfolioProjectRequest {
     pub end_date: Option<NaiveDate>,
     #[validate(length(max = 255, message = "Client name must be less than 255 characters"))]
     pub client: Option<String>,
+    pub sort_order: Option<i32>,
+    /// Version the client read; the update is rejected if the project changed since
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderPortfolioProjectsRequest {
+    #[validate(length(min = 1, message = "At least one project is required"))]
+    pub projects: Vec<PortfolioProjectOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortfolioProjectOrder {
+    pub id: Uuid,
+    pub sort_order: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkFeatureProjectsRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Between 1 and 100 project IDs are required"
+    ))]
+    pub ids: Vec<Uuid>,
+    pub featured: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct PortfolioProjectQuery {
     pub page: Option<u32>,
     pub limit: Option<u32>,
+    pub search: Option<String>,
     pub category: Option<String>,
     pub status: Option<String>,
     pub featured: Option<bool>,
     pub active: Option<bool>,
     pub technologies: Option<Vec<String>>,
+    // Single technology the project must use, e.g. `?technology=Rust`
+    pub technology: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -189,3 +226,100 @@ pub struct PortfolioStats {
     pub featured_projects: i64,
     pub projects_this_year: i64,
 }
+
+/// Portable project data used by export and import. Ids, view counts and
+/// timestamps are left out so records can move between installations;
+/// the slug identifies a project on import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct PortfolioProjectRecord {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title is required and must be less than 255 characters"
+    ))]
+    pub title: String,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Slug is required and must be less than 255 characters"
+    ))]
+    pub slug: String,
+    #[validate(length(min = 1, message = "Description is required"))]
+    pub description: String,
+    pub long_description: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Category is required and must be less than 50 characters"
+    ))]
+    pub category: String,
+    pub technologies: Vec<String>,
+    #[validate(url(message = "Live URL must be a valid URL"))]
+    pub live_url: Option<String>,
+    #[validate(url(message = "GitHub URL must be a valid URL"))]
+    pub github_url: Option<String>,
+    #[validate(url(message = "Image URL must be a valid URL"))]
+    pub image_url: Option<String>,
+    pub featured: bool,
+    pub active: bool,
+    #[validate(length(
+        min = 1,
+        max = 20,
+        message = "Status is required and must be less than 20 characters"
+    ))]
+    pub status: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[validate(length(max = 255, message = "Client name must be less than 255 characters"))]
+    pub client: Option<String>,
+    pub sort_order: i32,
+}
+
+impl From<PortfolioProject> for PortfolioProjectRecord {
+    fn from(project: PortfolioProject) -> Self {
+        Self {
+            title: project.title,
+            slug: project.slug,
+            description: project.description,
+            long_description: project.long_description,
+            category: project.category,
+            technologies: project.technologies,
+            live_url: project.live_url,
+            github_url: project.github_url,
+            image_url: project.image_url,
+            featured: project.featured,
+            active: project.active,
+            status: project.status,
+            start_date: project.start_date,
+            end_date: project.end_date,
+            client: project.client,
+            sort_order: project.sort_order,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortfolioImportResult {
+    // Position of the record in the submitted array
+    pub index: usize,
+    pub slug: Option<String>,
+    pub outcome: ImportOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortfolioImportResponse {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub results: Vec<PortfolioImportResult>,
+}
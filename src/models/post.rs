@@ -1,9 +1,12 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::portfolio::ImportOutcome;
+use crate::utils::text::{count_words, reading_time_minutes};
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Post {
     pub id: Uuid,
@@ -21,6 +24,8 @@ pub struct Post {
     pub seo_keywords: Option<String>,
     pub view_count: i32,
     pub published_at: Option<DateTime<Utc>>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -41,13 +46,19 @@ pub struct PostResponse {
     pub seo_description: Option<String>,
     pub seo_keywords: Option<String>,
     pub view_count: i32,
+    pub word_count: usize,
+    pub reading_time_minutes: usize,
     pub published_at: Option<DateTime<Utc>>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl From<Post> for PostResponse {
     fn from(post: Post) -> Self {
+        let word_count = count_words(&post.content);
+
         Self {
             id: post.id,
             title: post.title,
@@ -63,7 +74,11 @@ impl From<Post> for PostResponse {
             seo_description: post.seo_description,
             seo_keywords: post.seo_keywords,
             view_count: post.view_count,
+            word_count,
+            reading_time_minutes: reading_time_minutes(word_count),
             published_at: post.published_at,
+            publish_at: post.publish_at,
+            version: post.version,
             created_at: post.created_at,
             updated_at: post.updated_at,
         }
@@ -103,6 +118,7 @@ pub struct CreatePostRequest {
     ))]
     pub seo_description: Option<String>,
     pub seo_keywords: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -138,6 +154,19 @@ pub struct UpdatePostRequest {
     ))]
     pub seo_description: Option<String>,
     pub seo_keywords: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Version the client read; the update is rejected if the post changed since
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkDeletePostsRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Between 1 and 100 post IDs are required"
+    ))]
+    pub ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -149,7 +178,43 @@ pub struct PostQuery {
     pub published: Option<bool>,
     pub featured: Option<bool>,
     pub author_id: Option<Uuid>,
+    #[serde(default, deserialize_with = "deserialize_comma_separated")]
     pub tags: Option<Vec<String>>,
+    // Opaque `next_cursor` from a previous page. Preferred over `page` for walking the
+    // list since rows inserted between fetches can't shift or duplicate results.
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkFeaturePostsRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Between 1 and 100 post IDs are required"
+    ))]
+    pub ids: Vec<Uuid>,
+    pub featured: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublishedPostsQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+// Query strings carry tags as a single comma-separated value, e.g. `?tags=rust,axum`
+fn deserialize_comma_separated<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value.map(|value| {
+        value
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }))
 }
 
 #[derive(Debug, Serialize)]
@@ -159,6 +224,64 @@ pub struct PostsResponse {
     pub page: u32,
     pub limit: u32,
     pub total_pages: u32,
+    // Present while more posts follow this page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct CategoryCount {
+    pub category: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagQuery {
+    pub limit: Option<u32>,
+}
+
+// Counted views of one post on one day
+#[derive(Debug, Serialize, FromRow)]
+pub struct PostDailyViews {
+    pub day: NaiveDate,
+    pub views: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostViewsQuery {
+    pub days: Option<i32>,
+}
+
+// Lets a shared preview link read an unpublished post
+#[derive(Debug, Deserialize)]
+pub struct PostPreviewTokenQuery {
+    pub preview_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostPreviewLinkQuery {
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostPreviewLink {
+    pub post_id: Uuid,
+    pub token: String,
+    // Public slug URL with the token attached, ready to share
+    pub path: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostSearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub limit: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -170,3 +293,85 @@ pub struct PostStats {
     pub posts_this_month: i64,
     pub total_views: i64,
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostImportMode {
+    // Leave posts whose slug already exists untouched
+    #[default]
+    Skip,
+    // Replace existing posts with the imported content
+    Overwrite,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostImportQuery {
+    #[serde(default)]
+    pub mode: PostImportMode,
+}
+
+/// One imported post, either a JSON array item or the YAML frontmatter of a
+/// markdown file (whose body becomes `content`). Field names follow common
+/// static site generators so exported frontmatter can be used as-is.
+#[derive(Debug, Deserialize)]
+pub struct PostImportItem {
+    pub title: String,
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub content: String,
+    pub excerpt: Option<String>,
+    pub category: Option<String>,
+    // Used when `category` is missing; only the first entry is kept
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Original publish date, RFC 3339 or YYYY-MM-DD
+    pub date: Option<String>,
+    pub published: Option<bool>,
+    pub draft: Option<bool>,
+    pub featured: Option<bool>,
+    pub featured_image: Option<String>,
+    pub seo_title: Option<String>,
+    pub seo_description: Option<String>,
+    pub seo_keywords: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum PostImportDocument {
+    Json(serde_json::Value),
+    // Files that couldn't be read from the archive carry the reason instead of contents
+    Markdown {
+        filename: String,
+        contents: Result<String, String>,
+    },
+}
+
+// A checked post ready to be written, keeping its original publish date
+#[derive(Debug)]
+pub struct PostImportRecord {
+    pub post: CreatePostRequest,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostImportResult {
+    // Position of the item in the array, or of the file in the archive
+    pub index: usize,
+    // File name for markdown imports
+    pub source: Option<String>,
+    pub slug: Option<String>,
+    pub outcome: ImportOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostImportResponse {
+    pub created: usize,
+    pub updated: usize,
+    // Existing slugs left alone in skip mode
+    pub skipped: usize,
+    // Documents that could not be parsed, validated or written; see each result's error
+    pub failed: usize,
+    pub results: Vec<PostImportResult>,
+}
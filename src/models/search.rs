@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DEFAULT_SEARCH_LIMIT_PER_KIND: u32 = 5;
+pub const MAX_SEARCH_LIMIT_PER_KIND: u32 = 10;
+pub const MAX_SEARCH_RESULTS: usize = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    // Results per kind, capped at MAX_SEARCH_LIMIT_PER_KIND
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Post,
+    Portfolio,
+    Service,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    pub title: String,
+    // Services have no public page of their own
+    pub slug: Option<String>,
+    pub summary: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+    pub total: usize,
+}
@@ -12,6 +12,11 @@ pub struct Service {
     pub features: Vec<String>,
     pub category: String,
     pub active: bool,
+    pub display_order: i32,
+    pub price_amount: Option<i64>,
+    pub price_currency: Option<String>,
+    pub price_type: Option<String>,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -24,6 +29,12 @@ pub struct ServiceResponse {
     pub features: Vec<String>,
     pub category: String,
     pub active: bool,
+    pub display_order: i32,
+    pub price_amount: Option<i64>,
+    pub price_currency: Option<String>,
+    pub price_type: Option<String>,
+    pub price_display: Option<String>,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -37,12 +48,76 @@ impl From<Service> for ServiceResponse {
             features: service.features,
             category: service.category,
             active: service.active,
+            display_order: service.display_order,
+            price_display: format_price(
+                service.price_amount,
+                service.price_currency.as_deref(),
+                service.price_type.as_deref(),
+            ),
+            price_amount: service.price_amount,
+            price_currency: service.price_currency,
+            price_type: service.price_type,
+            version: service.version,
             created_at: service.created_at,
             updated_at: service.updated_at,
         }
     }
 }
 
+// ISO 4217 codes we accept, with the number of minor-unit digits
+pub const SUPPORTED_CURRENCIES: &[(&str, u32)] = &[
+    ("USD", 2),
+    ("EUR", 2),
+    ("GBP", 2),
+    ("AUD", 2),
+    ("SGD", 2),
+    ("IDR", 2),
+    ("JPY", 0),
+];
+
+pub const PRICE_TYPES: &[&str] = &["fixed", "hourly", "starting_at"];
+
+/// Formats a price for display, e.g. "USD 1,500.00", "USD 75.00/hour" or "From IDR 500,000.00"
+pub fn format_price(
+    amount: Option<i64>,
+    currency: Option<&str>,
+    price_type: Option<&str>,
+) -> Option<String> {
+    let amount = amount?;
+    let currency = currency?;
+    let (_, exponent) = SUPPORTED_CURRENCIES
+        .iter()
+        .find(|(code, _)| *code == currency)?;
+
+    let divisor = 10_i64.pow(*exponent);
+    let whole = (amount / divisor).to_string();
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    let price = if *exponent > 0 {
+        format!(
+            "{} {}.{:0width$}",
+            currency,
+            grouped,
+            amount % divisor,
+            width = *exponent as usize
+        )
+    } else {
+        format!("{} {}", currency, grouped)
+    };
+
+    Some(match price_type.unwrap_or("fixed") {
+        "hourly" => format!("{}/hour", price),
+        "starting_at" => format!("From {}", price),
+        _ => price,
+    })
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateServiceRequest {
     #[validate(length(
@@ -61,6 +136,11 @@ pub struct CreateServiceRequest {
     ))]
     pub category: String,
     pub active: Option<bool>,
+    pub display_order: Option<i32>,
+    // Minor units, e.g. 150000 is USD 1,500.00
+    pub price_amount: Option<i64>,
+    pub price_currency: Option<String>,
+    pub price_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -80,7 +160,30 @@ pub struct UpdateServiceRequest {
         message = "Category is required and must be less than 100 characters"
     ))]
     pub category: String,
+    // Omitted fields below keep their stored values
     pub active: Option<bool>,
+    pub display_order: Option<i32>,
+    // Minor units, e.g. 150000 is USD 1,500.00
+    pub price_amount: Option<i64>,
+    pub price_currency: Option<String>,
+    pub price_type: Option<String>,
+    // Removes the stored price; the price fields above must then be omitted
+    #[serde(default)]
+    pub clear_price: bool,
+    /// Version the client read; the update is rejected if the service changed since
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderServicesRequest {
+    #[validate(length(min = 1, message = "At least one service is required"))]
+    pub services: Vec<ServiceOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceOrder {
+    pub id: Uuid,
+    pub display_order: i32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -89,6 +192,26 @@ pub struct ServiceQuery {
     pub limit: Option<u32>,
     pub category: Option<String>,
     pub active: Option<bool>,
+    pub sort: Option<ServiceSort>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceSort {
+    CreatedAt,
+    Title,
+    Category,
+}
+
+impl ServiceSort {
+    // Newest first by default; text columns sort alphabetically
+    pub fn order_by(self) -> &'static str {
+        match self {
+            ServiceSort::CreatedAt => "created_at DESC",
+            ServiceSort::Title => "title ASC, created_at DESC",
+            ServiceSort::Category => "category ASC, created_at DESC",
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -113,3 +236,77 @@ pub struct CategoryCount {
     pub category: String,
     pub count: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> Service {
+        Service {
+            id: Uuid::new_v4(),
+            title: "Web app".to_string(),
+            description: "Full-stack web application".to_string(),
+            features: vec!["API".to_string()],
+            category: "web development".to_string(),
+            active: true,
+            display_order: 1,
+            price_amount: Some(150_000),
+            price_currency: Some("USD".to_string()),
+            price_type: Some("starting_at".to_string()),
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(
+            format_price(Some(150_000), Some("USD"), Some("fixed")).as_deref(),
+            Some("USD 1,500.00")
+        );
+        assert_eq!(
+            format_price(Some(7_505), Some("EUR"), Some("hourly")).as_deref(),
+            Some("EUR 75.05/hour")
+        );
+        assert_eq!(
+            format_price(Some(50_000), Some("JPY"), Some("starting_at")).as_deref(),
+            Some("From JPY 50,000")
+        );
+        assert_eq!(format_price(None, Some("USD"), None), None);
+        assert_eq!(format_price(Some(100), Some("XXX"), None), None);
+    }
+
+    #[test]
+    fn test_service_pricing_round_trips_through_json() {
+        let service = service();
+
+        let json = serde_json::to_string(&service).unwrap();
+        let decoded: Service = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.price_amount, Some(150_000));
+        assert_eq!(decoded.price_currency.as_deref(), Some("USD"));
+        assert_eq!(decoded.price_type.as_deref(), Some("starting_at"));
+    }
+
+    #[test]
+    fn test_service_response_includes_price_display() {
+        let value = serde_json::to_value(ServiceResponse::from(service())).unwrap();
+
+        assert_eq!(value["price_amount"], 150_000);
+        assert_eq!(value["price_currency"], "USD");
+        assert_eq!(value["price_display"], "From USD 1,500.00");
+    }
+
+    #[test]
+    fn test_create_request_pricing_is_optional() {
+        let request: CreateServiceRequest = serde_json::from_str(
+            r#"{"title": "Web app", "description": "Full-stack web application", "features": [], "category": "Web"}"#,
+        )
+        .unwrap();
+
+        assert!(request.price_amount.is_none());
+        assert!(request.price_currency.is_none());
+        assert!(request.price_type.is_none());
+    }
+}
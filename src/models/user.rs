@@ -61,6 +61,8 @@ pub struct LoginRequest {
     pub username: String,
     #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
     pub password: String,
+    // Issue a long-lived refresh token instead of the standard one
+    pub remember_me: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -68,6 +70,32 @@ pub struct LoginResponse {
     pub token: String,
     pub user: UserResponse,
     pub expires_at: DateTime<Utc>,
+    // Opaque refresh token, only ever sent to clients as an httpOnly cookie
+    #[serde(skip_serializing)]
+    pub refresh_token: String,
+    #[serde(skip_serializing)]
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+// Client details recorded against a refresh token when it is issued or rotated
+#[derive(Debug, Clone, Default)]
+pub struct SessionClient {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+// An active refresh token, listed so users can see and revoke their logins
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub remember_me: bool,
+    // The session making the request
+    pub current: bool,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -98,6 +126,26 @@ pub struct ChangePasswordRequest {
     pub new_password: String,
 }
 
+// Body of POST /auth/refresh for clients that don't keep the refresh cookie
+#[derive(Debug, Default, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(message = "Please provide a valid email address"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, message = "Reset token is required"))]
+    pub token: String,
+    #[validate(length(min = 8, message = "New password must be at least 8 characters"))]
+    pub new_password: String,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateUserRequest {
     #[validate(length(
@@ -119,3 +167,36 @@ pub struct CreateUserRequest {
     #[validate(length(max = 20, message = "Phone number must be less than 20 characters"))]
     pub phone: Option<String>,
 }
+
+// Roles an admin can assign; only `admin` accounts can manage other users
+pub const ADMIN_ROLE: &str = "admin";
+pub const USER_ROLE: &str = "user";
+pub const USER_ROLES: [&str; 2] = [ADMIN_ROLE, USER_ROLE];
+
+#[derive(Debug, Deserialize)]
+pub struct UserQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub role: Option<String>,
+    pub is_active: Option<bool>,
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsersResponse {
+    pub users: Vec<UserResponse>,
+    pub total: i64,
+    pub page: u32,
+    pub limit: u32,
+    pub total_pages: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserStatusRequest {
+    pub is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserRoleRequest {
+    pub role: String,
+}
@@ -4,6 +4,30 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::audit_log::AuditLog;
+
+// Notification types users can set preferences for, and filter notifications by
+pub const NOTIFICATION_TYPES: [&str; 18] = [
+    "login",
+    "logout",
+    "post_created",
+    "post_updated",
+    "post_published",
+    "portfolio_created",
+    "portfolio_updated",
+    "service_created",
+    "service_updated",
+    "comment_created",
+    "comment_approved",
+    "comment_rejected",
+    "settings_updated",
+    "profile_updated",
+    "error",
+    "warning",
+    "system_alert",
+    "digest",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct UserNotificationRead {
     pub id: Uuid,
@@ -24,6 +48,22 @@ pub struct UserNotificationPreference {
     pub updated_at: DateTime<Utc>,
 }
 
+// A user who asked to receive a notification type by email
+#[derive(Debug, Clone, FromRow)]
+pub struct NotificationEmailRecipient {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+}
+
+// Narrows a notification listing; `action` is the notification type
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    pub unread_only: bool,
+    pub action: Option<String>,
+    pub resource_type: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct MarkNotificationReadRequest {
     pub audit_log_id: Uuid,
@@ -41,7 +81,7 @@ pub struct UpdateNotificationPreferenceRequest {
     pub delivery_method: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, FromRow)]
 pub struct NotificationWithReadStatus {
     pub id: Uuid,
     pub user_id: Option<Uuid>,
@@ -58,6 +98,27 @@ pub struct NotificationWithReadStatus {
     pub read_at: Option<DateTime<Utc>>,
 }
 
+// Freshly created audit logs are unread for everyone
+impl From<AuditLog> for NotificationWithReadStatus {
+    fn from(log: AuditLog) -> Self {
+        Self {
+            id: log.id,
+            user_id: log.user_id,
+            user_name: log.user_name,
+            action: log.action,
+            resource_type: log.resource_type,
+            resource_id: log.resource_id,
+            resource_title: log.resource_title,
+            details: log.details,
+            success: log.success,
+            error_message: log.error_message,
+            created_at: log.created_at,
+            read: false,
+            read_at: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct NotificationStats {
     pub total_notifications: i64,
@@ -70,6 +131,26 @@ pub struct NotificationStats {
 #[derive(Debug, Serialize)]
 pub struct UserNotificationsResponse {
     pub notifications: Vec<NotificationWithReadStatus>,
+    // Counts only the notifications matching the filter
+    pub total: i64,
+    // Derived from the requested offset as `offset / limit + 1`, rounding down, so an
+    // offset that isn't a multiple of `limit` reports the page it starts inside
+    pub page: i64,
+    pub limit: i64,
+    pub total_pages: i64,
     pub stats: NotificationStats,
     pub preferences: Vec<UserNotificationPreference>,
 }
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct NotificationTypeCount {
+    pub notification_type: String,
+    pub unread: i64,
+}
+
+// Unread counts per notification type, for badges like "3 new comments"
+#[derive(Debug, Serialize)]
+pub struct UnreadNotificationSummary {
+    pub total_unread: i64,
+    pub by_type: Vec<NotificationTypeCount>,
+}
@@ -1,12 +1,16 @@
 use crate::models::admin_settings::{
-    AdminSettings, AdminSettingsRecord, FeatureSettings, GeneralSettings, NotificationSettings,
-    SecuritySettings,
+    AdminSettings, AdminSettingsHistory, AdminSettingsRecord, FeatureSettings, GeneralSettings,
+    NotificationSettings, SecuritySettings,
 };
+use crate::utils::errors::AppError;
 use anyhow::{anyhow, Result};
 use chrono::Utc;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+// Older history entries beyond this many per setting key are pruned on update
+const MAX_HISTORY_PER_KEY: i64 = 50;
+
 pub struct AdminSettingsRepository {
     pool: PgPool,
 }
@@ -122,21 +126,114 @@ impl AdminSettingsRepository {
         value: serde_json::Value,
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettingsRecord> {
-        let record = sqlx::query_as!(
-            AdminSettingsRecord,
+        let mut tx = self.pool.begin().await?;
+        let record = Self::update_setting_in_tx(&mut tx, key, value, updated_by).await?;
+        tx.commit().await?;
+
+        Ok(record)
+    }
+
+    // Archives the current value into admin_settings_history before overwriting it
+    async fn update_setting_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        key: &str,
+        value: serde_json::Value,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO admin_settings_history
+                (setting_key, setting_value, updated_by, updated_at, changed_by)
+            SELECT setting_key, setting_value, updated_by, updated_at, $2
+            FROM admin_settings
+            WHERE setting_key = $1
+            "#,
+        )
+        .bind(key)
+        .bind(updated_by)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Setting '{}' not found", key)).into());
+        }
+
+        let record = sqlx::query_as::<_, AdminSettingsRecord>(
             r#"
-            UPDATE admin_settings 
+            UPDATE admin_settings
             SET setting_value = $1, updated_by = $2, updated_at = NOW()
             WHERE setting_key = $3
             RETURNING id, setting_key, setting_value, description, updated_by, updated_at, created_at
             "#,
-            value,
-            updated_by,
-            key
         )
-        .fetch_one(&self.pool)
+        .bind(value)
+        .bind(updated_by)
+        .bind(key)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM admin_settings_history
+            WHERE setting_key = $1
+              AND id NOT IN (
+                  SELECT id FROM admin_settings_history
+                  WHERE setting_key = $1
+                  ORDER BY created_at DESC
+                  LIMIT $2
+              )
+            "#,
+        )
+        .bind(key)
+        .bind(MAX_HISTORY_PER_KEY)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Previous values of a setting, newest first
+    pub async fn get_history(&self, key: &str) -> Result<Vec<AdminSettingsHistory>> {
+        let history = sqlx::query_as::<_, AdminSettingsHistory>(
+            r#"
+            SELECT id, setting_key, setting_value, updated_by, updated_at, changed_by, created_at
+            FROM admin_settings_history
+            WHERE setting_key = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(key)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Restores a setting to a value from its history. The value being replaced is
+    /// archived like any other update, so a rollback can itself be rolled back.
+    pub async fn rollback(
+        &self,
+        key: &str,
+        history_id: Uuid,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord> {
+        let mut tx = self.pool.begin().await?;
+
+        let value: Option<serde_json::Value> = sqlx::query_scalar(
+            "SELECT setting_value FROM admin_settings_history WHERE id = $1 AND setting_key = $2",
+        )
+        .bind(history_id)
+        .bind(key)
+        .fetch_optional(&mut *tx)
         .await?;
 
+        let value = value.ok_or_else(|| {
+            AppError::NotFound(format!("History entry not found for setting '{}'", key))
+        })?;
+
+        let record = Self::update_setting_in_tx(&mut tx, key, value, updated_by).await?;
+        tx.commit().await?;
+
         Ok(record)
     }
 
@@ -193,37 +290,14 @@ impl AdminSettingsRepository {
         // Execute all updates in a transaction
         let mut tx = self.pool.begin().await?;
 
-        sqlx::query!(
-            "UPDATE admin_settings SET setting_value = $1, updated_by = $2, updated_at = NOW() WHERE setting_key = 'general'",
-            general_value,
-            updated_by
-        )
-        .execute(&mut *tx)
-        .await?;
-
-        sqlx::query!(
-            "UPDATE admin_settings SET setting_value = $1, updated_by = $2, updated_at = NOW() WHERE setting_key = 'features'",
-            features_value,
-            updated_by
-        )
-        .execute(&mut *tx)
-        .await?;
-
-        sqlx::query!(
-            "UPDATE admin_settings SET setting_value = $1, updated_by = $2, updated_at = NOW() WHERE setting_key = 'notifications'",
-            notifications_value,
-            updated_by
-        )
-        .execute(&mut *tx)
-        .await?;
-
-        sqlx::query!(
-            "UPDATE admin_settings SET setting_value = $1, updated_by = $2, updated_at = NOW() WHERE setting_key = 'security'",
-            security_value,
-            updated_by
-        )
-        .execute(&mut *tx)
-        .await?;
+        for (key, value) in [
+            ("general", general_value),
+            ("features", features_value),
+            ("notifications", notifications_value),
+            ("security", security_value),
+        ] {
+            Self::update_setting_in_tx(&mut tx, key, value, updated_by).await?;
+        }
 
         tx.commit().await?;
 
@@ -235,15 +309,9 @@ impl AdminSettingsRepository {
 
         if let Some(record) = record {
             let features: FeatureSettings = serde_json::from_value(record.setting_value)?;
-            match feature {
-                "comments" => Ok(features.comments_enabled),
-                "portfolio" => Ok(features.portfolio_enabled),
-                "services" => Ok(features.services_enabled),
-                "blog" => Ok(features.blog_enabled),
-                "contactForm" => Ok(features.contact_form_enabled),
-                "search" => Ok(features.search_enabled),
-                _ => Err(anyhow!("Unknown feature: {}", feature)),
-            }
+            features
+                .is_enabled(feature)
+                .ok_or_else(|| anyhow!("Unknown feature: {}", feature))
         } else {
             // Default to true if setting doesn't exist
             Ok(true)
@@ -261,12 +329,12 @@ impl AdminSettingsRepository {
         }
     }
 
-    pub async fn get_maintenance_message(&self) -> Result<String> {
+    pub async fn get_maintenance_message(&self, locales: &[String]) -> Result<String> {
         let record = self.get_setting("general").await?;
 
         if let Some(record) = record {
             let general: GeneralSettings = serde_json::from_value(record.setting_value)?;
-            Ok(general.maintenance_message)
+            Ok(general.maintenance_message_for(locales).to_string())
         } else {
             Ok("The site is currently under maintenance. Please check back later.".to_string())
         }
@@ -328,3 +396,69 @@ impl AdminSettingsRepository {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_rollback_restores_previous_value(pool: PgPool) {
+        let repository = AdminSettingsRepository::new(pool);
+        let original = repository.get_setting("features").await.unwrap().unwrap();
+
+        let mut changed = original.setting_value.clone();
+        changed["commentsEnabled"] = serde_json::json!(false);
+        changed["searchEnabled"] = serde_json::json!(false);
+        repository
+            .update_setting("features", changed.clone(), None)
+            .await
+            .unwrap();
+
+        let history = repository.get_history("features").await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].setting_value, original.setting_value);
+
+        let restored = repository
+            .rollback("features", history[0].id, None)
+            .await
+            .unwrap();
+        assert_eq!(restored.setting_value, original.setting_value);
+
+        // The rollback is recorded too, so the changed value can be restored again
+        let history = repository.get_history("features").await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].setting_value, changed);
+
+        let err = repository
+            .rollback("security", history[0].id, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AppError>(),
+            Some(AppError::NotFound(_))
+        ));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_history_is_limited_per_key(pool: PgPool) {
+        let repository = AdminSettingsRepository::new(pool);
+        let value = repository
+            .get_setting("notifications")
+            .await
+            .unwrap()
+            .unwrap()
+            .setting_value;
+
+        for _ in 0..MAX_HISTORY_PER_KEY + 5 {
+            repository
+                .update_setting("notifications", value.clone(), None)
+                .await
+                .unwrap();
+        }
+
+        let history = repository.get_history("notifications").await.unwrap();
+        assert_eq!(history.len(), MAX_HISTORY_PER_KEY as usize);
+    }
+}
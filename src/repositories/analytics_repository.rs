@@ -0,0 +1,176 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::database::query_timing::timed;
+use crate::models::analytics::{CategoryViews, ContentCounts, PopularPost, ViewTotals};
+use crate::utils::errors::AppError;
+
+#[async_trait]
+pub trait AnalyticsRepositoryTrait: Send + Sync {
+    async fn get_view_totals(&self) -> Result<ViewTotals, AppError>;
+    async fn get_most_viewed_posts(&self, limit: u32) -> Result<Vec<PopularPost>, AppError>;
+    async fn get_views_by_category(&self) -> Result<Vec<CategoryViews>, AppError>;
+    async fn get_content_counts(&self) -> Result<ContentCounts, AppError>;
+}
+
+pub struct AnalyticsRepository {
+    pool: PgPool,
+}
+
+impl AnalyticsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Soft-deleted posts are left out of every aggregate
+#[async_trait]
+impl AnalyticsRepositoryTrait for AnalyticsRepository {
+    async fn get_view_totals(&self) -> Result<ViewTotals, AppError> {
+        let totals = timed(
+            "analytics.view_totals",
+            sqlx::query_as::<_, ViewTotals>(
+                r#"
+                SELECT
+                    (SELECT COALESCE(SUM(view_count), 0)::BIGINT
+                     FROM posts WHERE deleted_at IS NULL) AS post_views,
+                    (SELECT COALESCE(SUM(view_count), 0)::BIGINT
+                     FROM portfolio_projects) AS portfolio_views
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to sum view counts")?;
+
+        Ok(totals)
+    }
+
+    async fn get_most_viewed_posts(&self, limit: u32) -> Result<Vec<PopularPost>, AppError> {
+        let posts = timed(
+            "analytics.most_viewed_posts",
+            sqlx::query_as::<_, PopularPost>(
+                r#"
+                SELECT id, title, slug, category, view_count::BIGINT AS view_count
+                FROM posts
+                WHERE deleted_at IS NULL
+                ORDER BY view_count DESC, published_at DESC NULLS LAST
+                LIMIT $1
+                "#,
+            )
+            .bind(limit as i64)
+            .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch most viewed posts")?;
+
+        Ok(posts)
+    }
+
+    async fn get_views_by_category(&self) -> Result<Vec<CategoryViews>, AppError> {
+        let categories = timed(
+            "analytics.views_by_category",
+            sqlx::query_as::<_, CategoryViews>(
+                r#"
+                SELECT category, COUNT(*) AS posts, COALESCE(SUM(view_count), 0)::BIGINT AS views
+                FROM posts
+                WHERE deleted_at IS NULL
+                GROUP BY category
+                ORDER BY views DESC, category ASC
+                "#,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to sum views by category")?;
+
+        Ok(categories)
+    }
+
+    async fn get_content_counts(&self) -> Result<ContentCounts, AppError> {
+        let counts = timed(
+            "analytics.content_counts",
+            sqlx::query_as::<_, ContentCounts>(
+                r#"
+                SELECT
+                    (SELECT COUNT(*) FROM posts WHERE deleted_at IS NULL) AS total_posts,
+                    (SELECT COUNT(*) FROM posts
+                     WHERE deleted_at IS NULL AND published = true) AS published_posts,
+                    (SELECT COUNT(*) FROM comments) AS total_comments,
+                    (SELECT COUNT(*) FROM comments WHERE status = 'pending') AS pending_comments,
+                    (SELECT COUNT(*) FROM portfolio_projects) AS total_projects,
+                    (SELECT COUNT(*) FROM portfolio_projects WHERE active = true) AS active_projects
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to count content")?;
+
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::post::CreatePostRequest;
+    use crate::repositories::{PostRepository, PostRepositoryTrait};
+
+    fn post_request(slug: &str, category: &str) -> CreatePostRequest {
+        CreatePostRequest {
+            title: format!("Post {}", slug),
+            slug: slug.to_string(),
+            content: "Test content".to_string(),
+            excerpt: None,
+            category: category.to_string(),
+            tags: vec![],
+            featured_image: None,
+            featured: None,
+            published: Some(true),
+            seo_title: None,
+            seo_description: None,
+            seo_keywords: None,
+            publish_at: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_most_viewed_posts_are_ordered_by_view_count(pool: PgPool) {
+        let posts = PostRepository::new(pool.clone());
+        let repository = AnalyticsRepository::new(pool.clone());
+
+        for (slug, category, views) in [
+            ("quiet", "Rust", 3),
+            ("popular", "Go", 120),
+            ("steady", "Rust", 40),
+        ] {
+            let post = posts.create(post_request(slug, category)).await.unwrap();
+            sqlx::query("UPDATE posts SET view_count = $1 WHERE id = $2")
+                .bind(views)
+                .bind(post.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let most_viewed = repository.get_most_viewed_posts(10).await.unwrap();
+        let slugs: Vec<_> = most_viewed.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, ["popular", "steady", "quiet"]);
+
+        let top = repository.get_most_viewed_posts(2).await.unwrap();
+        assert_eq!(top.len(), 2);
+
+        let categories = repository.get_views_by_category().await.unwrap();
+        assert_eq!(categories[0].category, "Go");
+        assert_eq!(categories[0].views, 120);
+        assert_eq!(categories[1].category, "Rust");
+        assert_eq!(categories[1].views, 43);
+        assert_eq!(categories[1].posts, 2);
+
+        let totals = repository.get_view_totals().await.unwrap();
+        assert_eq!(totals.post_views, 163);
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::api_key::ApiKey;
+use crate::utils::errors::AppError;
+
+#[async_trait]
+pub trait ApiKeyRepositoryTrait: Send + Sync {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        key_prefix: &str,
+        key_hash: &str,
+        scopes: &[String],
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, AppError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, AppError>;
+    async fn find_active_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError>;
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<bool, AppError>;
+    async fn touch_last_used(&self, id: Uuid) -> Result<(), AppError>;
+}
+
+pub struct ApiKeyRepository {
+    pool: PgPool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepositoryTrait for ApiKeyRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        key_prefix: &str,
+        key_hash: &str,
+        scopes: &[String],
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, AppError> {
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            INSERT INTO api_keys (user_id, name, key_prefix, key_hash, scopes, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, user_id, name, key_prefix, key_hash, scopes, last_used_at, expires_at, revoked_at, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(key_prefix)
+        .bind(key_hash)
+        .bind(scopes)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to create API key")?;
+
+        Ok(api_key)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, AppError> {
+        let api_keys = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, user_id, name, key_prefix, key_hash, scopes, last_used_at, expires_at, revoked_at, created_at
+            FROM api_keys
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch API keys")?;
+
+        Ok(api_keys)
+    }
+
+    // Only keys that are neither revoked nor expired can authenticate
+    async fn find_active_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, user_id, name, key_prefix, key_hash, scopes, last_used_at, expires_at, revoked_at, created_at
+            FROM api_keys
+            WHERE key_hash = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch API key")?;
+
+        Ok(api_key)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to revoke API key")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch_last_used(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update API key last used time")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::test_support::insert_user;
+    use chrono::Duration;
+
+    fn read_scope() -> Vec<String> {
+        vec!["read".to_string()]
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_expired_key_is_not_active(pool: PgPool) {
+        let user_id = insert_user(&pool, "api_user").await;
+        let repository = ApiKeyRepository::new(pool);
+
+        repository
+            .create(
+                user_id,
+                "expired",
+                "pk_expired",
+                "expired_hash",
+                &read_scope(),
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+        repository
+            .create(
+                user_id,
+                "current",
+                "pk_current",
+                "current_hash",
+                &read_scope(),
+                Some(Utc::now() + Duration::days(1)),
+            )
+            .await
+            .unwrap();
+
+        assert!(repository
+            .find_active_by_hash("expired_hash")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repository
+            .find_active_by_hash("current_hash")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_revoked_key_is_not_active(pool: PgPool) {
+        let user_id = insert_user(&pool, "api_user").await;
+        let repository = ApiKeyRepository::new(pool);
+
+        let api_key = repository
+            .create(
+                user_id,
+                "script",
+                "pk_script",
+                "script_hash",
+                &read_scope(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(repository
+            .find_active_by_hash("script_hash")
+            .await
+            .unwrap()
+            .is_some());
+
+        assert!(repository.revoke(api_key.id, user_id).await.unwrap());
+        assert!(repository
+            .find_active_by_hash("script_hash")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Revoking twice reports nothing changed
+        assert!(!repository.revoke(api_key.id, user_id).await.unwrap());
+    }
+}
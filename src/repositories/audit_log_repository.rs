@@ -1,10 +1,29 @@
+use crate::database::query_timing::timed;
 use crate::models::audit_log::{
     AuditLog, AuditLogFilters, AuditLogResponse, CreateAuditLogRequest,
 };
+use crate::utils::{cursor::Cursor, text::contains_pattern};
 use anyhow::Result;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+// Clamp user-supplied paging so the page math can't divide by zero or go negative
+fn normalize_pagination(limit: Option<i64>, offset: Option<i64>) -> (i64, i64) {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = offset.unwrap_or(0).max(0);
+    (limit, offset)
+}
+
+// Returns (page, total_pages) for an already-normalized limit and offset
+fn page_numbers(total_count: i64, limit: i64, offset: i64) -> (i64, i64) {
+    let page = (offset / limit) + 1;
+    let total_pages = (total_count + limit - 1) / limit;
+    (page, total_pages)
+}
+
 pub struct AuditLogRepository {
     pool: PgPool,
 }
@@ -14,6 +33,54 @@ impl AuditLogRepository {
         Self { pool }
     }
 
+    // Append the WHERE clause shared by the list and count queries
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filters: &AuditLogFilters) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(start_date) = filters.start_date {
+            builder.push(" AND created_at >= ").push_bind(start_date);
+        }
+
+        if let Some(end_date) = filters.end_date {
+            builder.push(" AND created_at <= ").push_bind(end_date);
+        }
+
+        if let Some(action) = &filters.action {
+            builder.push(" AND action = ").push_bind(action.clone());
+        }
+
+        if let Some(resource_type) = &filters.resource_type {
+            builder
+                .push(" AND resource_type = ")
+                .push_bind(resource_type.clone());
+        }
+
+        if let Some(user_id) = filters.user_id {
+            builder.push(" AND user_id = ").push_bind(user_id);
+        }
+
+        if let Some(success) = filters.success {
+            builder.push(" AND success = ").push_bind(success);
+        }
+
+        if let Some(search) = filters
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let pattern = contains_pattern(search);
+            builder
+                .push(" AND (user_name ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR details ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR resource_title ILIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+    }
+
     pub async fn create(&self, request: CreateAuditLogRequest) -> Result<AuditLog> {
         let audit_log = sqlx::query_as!(
             AuditLog,
@@ -65,100 +132,62 @@ impl AuditLogRepository {
     }
 
     pub async fn get_all_with_filters(&self, filters: AuditLogFilters) -> Result<AuditLogResponse> {
-        let limit = filters.limit.unwrap_or(20);
-        let offset = filters.offset.unwrap_or(0);
-        let page = (offset / limit) + 1;
-
-        // Build the WHERE clause dynamically
-        let mut where_conditions = Vec::new();
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(start_date) = filters.start_date {
-            param_count += 1;
-            where_conditions.push(format!("created_at >= ${}", param_count));
-            params.push(Box::new(start_date));
-        }
-
-        if let Some(end_date) = filters.end_date {
-            param_count += 1;
-            where_conditions.push(format!("created_at <= ${}", param_count));
-            params.push(Box::new(end_date));
-        }
-
-        if let Some(action) = filters.action {
-            param_count += 1;
-            where_conditions.push(format!("action = ${}", param_count));
-            params.push(Box::new(action));
-        }
-
-        if let Some(resource_type) = filters.resource_type {
-            param_count += 1;
-            where_conditions.push(format!("resource_type = ${}", param_count));
-            params.push(Box::new(resource_type));
-        }
-
-        if let Some(user_id) = filters.user_id {
-            param_count += 1;
-            where_conditions.push(format!("user_id = ${}", param_count));
-            params.push(Box::new(user_id));
-        }
-
-        if let Some(success) = filters.success {
-            param_count += 1;
-            where_conditions.push(format!("success = ${}", param_count));
-            params.push(Box::new(success));
-        }
-
-        if let Some(search) = filters.search {
-            param_count += 1;
-            where_conditions.push(format!(
-                "(user_name ILIKE ${} OR details ILIKE ${} OR resource_title ILIKE ${})",
-                param_count, param_count, param_count
-            ));
-            params.push(Box::new(format!("%{}%", search)));
-        }
-
-        let where_clause = if where_conditions.is_empty() {
-            String::new()
-        } else {
-            format!("WHERE {}", where_conditions.join(" AND "))
-        };
+        let (limit, offset) = normalize_pagination(filters.limit, filters.offset);
+        let cursor = filters.cursor.as_deref().map(Cursor::decode).transpose()?;
 
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) as count FROM audit_logs {}", where_clause);
+        // Get total count using the same filters as the data query
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM audit_logs");
+        Self::push_filters(&mut count_builder, &filters);
 
-        let total_count: i64 = sqlx::query(&count_query)
-            .fetch_one(&self.pool)
-            .await?
-            .get("count");
+        let total_count: i64 = timed(
+            "audit_logs.get_all_with_filters.count",
+            count_builder.build_query_scalar().fetch_one(&self.pool),
+        )
+        .await?;
 
         // Get paginated results
-        param_count += 1;
-        let limit_param = param_count;
-        param_count += 1;
-        let offset_param = param_count;
-
-        let data_query = format!(
+        let mut logs_builder = QueryBuilder::<Postgres>::new(
             r#"
             SELECT id, user_id, user_name, action, resource_type, resource_id, 
                    resource_title, details, old_values, new_values, 
                    ip_address, user_agent, success, error_message, created_at
-            FROM audit_logs 
-            {} 
-            ORDER BY created_at DESC 
-            LIMIT ${} OFFSET ${}
+            FROM audit_logs
             "#,
-            where_clause, limit_param, offset_param
         );
+        Self::push_filters(&mut logs_builder, &filters);
+        if let Some(cursor) = &cursor {
+            logs_builder
+                .push(" AND (created_at, id) < (")
+                .push_bind(cursor.created_at)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        // One extra row tells us whether another page follows
+        logs_builder
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit + 1);
+        if cursor.is_none() {
+            logs_builder.push(" OFFSET ").push_bind(offset);
+        }
 
-        let logs = sqlx::query_as::<_, AuditLog>(&data_query)
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&self.pool)
-            .await?;
+        let mut logs = timed(
+            "audit_logs.get_all_with_filters",
+            logs_builder
+                .build_query_as::<AuditLog>()
+                .fetch_all(&self.pool),
+        )
+        .await?;
 
-        let total_pages = (total_count + limit - 1) / limit;
+        let next_cursor = if logs.len() > limit as usize {
+            logs.truncate(limit as usize);
+            logs.last()
+                .map(|log| Cursor::new(log.created_at, log.id).encode())
+        } else {
+            None
+        };
+
+        let (page, total_pages) = page_numbers(total_count, limit, offset);
 
         Ok(AuditLogResponse {
             logs,
@@ -166,6 +195,7 @@ impl AuditLogRepository {
             page,
             per_page: limit,
             total_pages,
+            next_cursor,
         })
     }
 
@@ -197,19 +227,19 @@ impl AuditLogRepository {
         resource_type: String,
         resource_id: Uuid,
     ) -> Result<Vec<AuditLog>> {
-        let logs = sqlx::query_as!(
-            AuditLog,
+        // Oldest first so the entries read as the resource's change history
+        let logs = sqlx::query_as::<_, AuditLog>(
             r#"
             SELECT id, user_id, user_name, action, resource_type, resource_id, 
                    resource_title, details, old_values, new_values, 
                    ip_address, user_agent, success, error_message, created_at
             FROM audit_logs 
             WHERE resource_type = $1 AND resource_id = $2 
-            ORDER BY created_at DESC
+            ORDER BY created_at ASC, id ASC
             "#,
-            resource_type,
-            resource_id
         )
+        .bind(resource_type)
+        .bind(resource_id)
         .fetch_all(&self.pool)
         .await?;
 
@@ -278,3 +308,145 @@ impl AuditLogRepository {
         Ok(result.rows_affected())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_limit_is_clamped_to_one() {
+        let (limit, offset) = normalize_pagination(Some(0), Some(5));
+        assert_eq!((limit, offset), (1, 5));
+        assert_eq!(page_numbers(3, limit, offset), (6, 3));
+    }
+
+    #[test]
+    fn test_huge_limit_is_clamped_to_max() {
+        let (limit, offset) = normalize_pagination(Some(i64::MAX), None);
+        assert_eq!((limit, offset), (MAX_PAGE_SIZE, 0));
+        assert_eq!(page_numbers(250, limit, offset), (1, 3));
+    }
+
+    #[test]
+    fn test_negative_offset_is_clamped_to_zero() {
+        let (limit, offset) = normalize_pagination(Some(10), Some(-30));
+        assert_eq!((limit, offset), (10, 0));
+        assert_eq!(page_numbers(25, limit, offset), (1, 3));
+    }
+
+    #[test]
+    fn test_defaults_and_empty_result() {
+        let (limit, offset) = normalize_pagination(None, None);
+        assert_eq!((limit, offset), (DEFAULT_PAGE_SIZE, 0));
+        assert_eq!(page_numbers(0, limit, offset), (1, 0));
+    }
+
+    async fn log_action(repository: &AuditLogRepository, action: &str) {
+        repository
+            .create(CreateAuditLogRequest {
+                user_id: None,
+                user_name: Some("admin".to_string()),
+                action: action.to_string(),
+                resource_type: "post".to_string(),
+                resource_id: None,
+                resource_title: None,
+                details: None,
+                old_values: None,
+                new_values: None,
+                ip_address: None,
+                user_agent: None,
+                success: true,
+                error_message: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    fn filters(action: Option<&str>, cursor: Option<String>) -> AuditLogFilters {
+        AuditLogFilters {
+            start_date: None,
+            end_date: None,
+            action: action.map(str::to_string),
+            resource_type: None,
+            user_id: None,
+            success: None,
+            search: None,
+            limit: Some(2),
+            offset: None,
+            cursor,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_cursor_pages_are_stable_across_inserts(pool: PgPool) {
+        let repository = AuditLogRepository::new(pool);
+
+        for action in ["first", "second", "third", "fourth"] {
+            log_action(&repository, action).await;
+        }
+
+        let page = repository
+            .get_all_with_filters(filters(None, None))
+            .await
+            .unwrap();
+        let actions: Vec<_> = page.logs.iter().map(|log| log.action.as_str()).collect();
+        assert_eq!(actions, ["fourth", "third"]);
+
+        // New activity lands at the top without pushing older rows onto the next page
+        log_action(&repository, "fifth").await;
+
+        let next = repository
+            .get_all_with_filters(filters(None, page.next_cursor))
+            .await
+            .unwrap();
+        let actions: Vec<_> = next.logs.iter().map(|log| log.action.as_str()).collect();
+        assert_eq!(actions, ["second", "first"]);
+        assert!(next.next_cursor.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_resource_history_is_oldest_first(pool: PgPool) {
+        let repository = AuditLogRepository::new(pool);
+        let post_id = Uuid::new_v4();
+
+        for action in ["post_created", "post_updated", "post_published"] {
+            sqlx::query(
+                "INSERT INTO audit_logs (action, resource_type, resource_id, success) VALUES ($1, 'post', $2, true)",
+            )
+            .bind(action)
+            .bind(post_id)
+            .execute(&repository.pool)
+            .await
+            .unwrap();
+        }
+        log_action(&repository, "unrelated").await;
+
+        let history = repository
+            .get_by_resource("post".to_string(), post_id)
+            .await
+            .unwrap();
+        let actions: Vec<_> = history.iter().map(|log| log.action.as_str()).collect();
+
+        assert_eq!(actions, ["post_created", "post_updated", "post_published"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_filters_apply_to_logs_and_total(pool: PgPool) {
+        let repository = AuditLogRepository::new(pool);
+
+        for action in ["login", "post_created", "login"] {
+            log_action(&repository, action).await;
+        }
+
+        let page = repository
+            .get_all_with_filters(filters(Some("login"), None))
+            .await
+            .unwrap();
+
+        assert_eq!(page.total_count, 2);
+        assert!(page.logs.iter().all(|log| log.action == "login"));
+    }
+}
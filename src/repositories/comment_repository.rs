@@ -1,14 +1,18 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 use anyhow::{Context, Result};
 
+use crate::database::query_timing::timed;
+use crate::models::admin_settings::PendingCommentExpiryAction;
 use crate::models::comment::{
-    Comment, CommentModerationInfo, CommentQuery, CommentResponse, CommentStats, CommentsResponse,
-    CreateCommentRequest, UpdateCommentStatusRequest,
+    BannedCommenter, Comment, CommentDailyStats, CommentModerationInfo, CommentQuery,
+    CommentResponse, CommentStats, CommentsResponse, CreateBanRequest, CreateCommentRequest,
+    UpdateCommentStatusRequest,
 };
 use crate::utils::errors::AppError;
+use crate::utils::text::contains_pattern;
 
 #[async_trait]
 pub trait CommentRepositoryTrait: Send + Sync {
@@ -32,6 +36,8 @@ pub trait CommentRepositoryTrait: Send + Sync {
         id: Uuid,
         status: UpdateCommentStatusRequest,
     ) -> Result<Comment, AppError>;
+    // Spends the comment's edit token; None when it was already used
+    async fn update_content(&self, id: Uuid, content: &str) -> Result<Option<Comment>, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
     async fn get_by_post(
         &self,
@@ -40,8 +46,29 @@ pub trait CommentRepositoryTrait: Send + Sync {
     ) -> Result<Vec<Comment>, AppError>;
     async fn get_pending_moderation(&self) -> Result<Vec<CommentModerationInfo>, AppError>;
     async fn get_stats(&self) -> Result<CommentStats, AppError>;
+    async fn get_stats_timeseries(&self, days: i32) -> Result<Vec<CommentDailyStats>, AppError>;
     async fn get_replies(&self, parent_id: Uuid) -> Result<Vec<Comment>, AppError>;
+    async fn get_post_title(&self, post_id: Uuid) -> Result<Option<String>, AppError>;
     async fn bulk_update_status(&self, ids: Vec<Uuid>, status: String) -> Result<i64, AppError>;
+    async fn expire_pending(
+        &self,
+        days: i32,
+        action: PendingCommentExpiryAction,
+    ) -> Result<u64, AppError>;
+    async fn count_spam_by_ip(&self, ip_address: &str) -> Result<i64, AppError>;
+    async fn has_clean_history(&self, ip_address: &str) -> Result<bool, AppError>;
+    async fn ban_commenter(
+        &self,
+        request: CreateBanRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<BannedCommenter, AppError>;
+    async fn unban_commenter(&self, id: Uuid) -> Result<(), AppError>;
+    async fn list_bans(&self) -> Result<Vec<BannedCommenter>, AppError>;
+    async fn find_active_ban(
+        &self,
+        email: &str,
+        ip_address: Option<&str>,
+    ) -> Result<Option<BannedCommenter>, AppError>;
     async fn count_recent_comments_by_ip(
         &self,
         ip_address: &str,
@@ -57,6 +84,47 @@ impl CommentRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Append the WHERE clause shared by the list and count queries
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &CommentQuery) {
+        builder.push(" WHERE TRUE");
+
+        if let Some(status) = &query.status {
+            builder.push(" AND status = ").push_bind(status.clone());
+        }
+
+        if let Some(post_id) = query.post_id {
+            builder.push(" AND post_id = ").push_bind(post_id);
+        }
+
+        if let Some(author_email) = &query.author_email {
+            builder
+                .push(" AND LOWER(author_email) = LOWER(")
+                .push_bind(author_email.clone())
+                .push(")");
+        }
+
+        if let Some(search) = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let pattern = contains_pattern(search);
+            builder
+                .push(" AND (author_name ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR author_email ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR content ILIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+
+        if query.include_replies == Some(false) {
+            builder.push(" AND parent_id IS NULL");
+        }
+    }
 }
 
 #[async_trait]
@@ -82,25 +150,38 @@ impl CommentRepositoryTrait for CommentRepository {
         let limit = query.limit.unwrap_or(20).min(100);
         let offset = (query.page.unwrap_or(1) - 1) * limit;
 
-        // Get total count
-        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments")
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count comments")?;
+        // Get total count using the same filters as the data query
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM comments");
+        Self::push_filters(&mut count_builder, &query);
 
-        // Get comments with simplified query
-        let comments = sqlx::query_as::<_, Comment>(
+        let total: i64 = timed(
+            "comments.find_all.count",
+            count_builder.build_query_scalar().fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to count comments")?;
+
+        // Get comments
+        let mut comments_builder = QueryBuilder::<Postgres>::new(
             r#"
             SELECT id, post_id, author_name, author_email, content, status, 
                    ip_address::text as ip_address, user_agent, parent_id, created_at, updated_at
-            FROM comments 
-            ORDER BY created_at DESC 
-            LIMIT $1 OFFSET $2
+            FROM comments
             "#,
+        );
+        Self::push_filters(&mut comments_builder, &query);
+        comments_builder
+            .push(" ORDER BY created_at DESC LIMIT ")
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let comments = timed(
+            "comments.find_all",
+            comments_builder
+                .build_query_as::<Comment>()
+                .fetch_all(&self.pool),
         )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&self.pool)
         .await
         .context("Failed to fetch comments")?;
 
@@ -191,7 +272,7 @@ impl CommentRepositoryTrait for CommentRepository {
         let updated_comment = sqlx::query_as::<_, Comment>(
             r#"
             UPDATE comments 
-            SET status = $1, updated_at = NOW()
+            SET status = $1, updated_at = NOW(), moderated_at = NOW()
             WHERE id = $2
             RETURNING id, post_id, author_name, author_email, content, status, 
                       ip_address::text as ip_address, user_agent, parent_id, created_at, updated_at
@@ -207,6 +288,25 @@ impl CommentRepositoryTrait for CommentRepository {
         Ok(updated_comment)
     }
 
+    async fn update_content(&self, id: Uuid, content: &str) -> Result<Option<Comment>, AppError> {
+        let updated_comment = sqlx::query_as::<_, Comment>(
+            r#"
+            UPDATE comments 
+            SET content = $1, edit_token_used_at = NOW(), updated_at = NOW()
+            WHERE id = $2 AND edit_token_used_at IS NULL
+            RETURNING id, post_id, author_name, author_email, content, status, 
+                      ip_address::text as ip_address, user_agent, parent_id, created_at, updated_at
+            "#,
+        )
+        .bind(content)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to update comment content")?;
+
+        Ok(updated_comment)
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), AppError> {
         let result = sqlx::query("DELETE FROM comments WHERE id = $1")
             .bind(id)
@@ -315,6 +415,37 @@ impl CommentRepositoryTrait for CommentRepository {
         })
     }
 
+    // One row per day for the last `days` days, today included. Days come from
+    // generate_series so days without any comments still show up with zeros.
+    async fn get_stats_timeseries(&self, days: i32) -> Result<Vec<CommentDailyStats>, AppError> {
+        let stats = timed(
+            "comments.stats_timeseries",
+            sqlx::query_as::<_, CommentDailyStats>(
+                r#"
+                SELECT d.day::date AS day,
+                       COUNT(c.id) AS created,
+                       COUNT(c.id) FILTER (WHERE c.status = 'approved') AS approved,
+                       COUNT(c.id) FILTER (WHERE c.status = 'rejected') AS rejected,
+                       COUNT(c.id) FILTER (WHERE c.status = 'spam') AS spam
+                FROM generate_series(
+                    date_trunc('day', NOW()) - make_interval(days => $1 - 1),
+                    date_trunc('day', NOW()),
+                    INTERVAL '1 day'
+                ) AS d(day)
+                LEFT JOIN comments c ON date_trunc('day', c.created_at) = d.day
+                GROUP BY d.day
+                ORDER BY d.day
+                "#,
+            )
+            .bind(days)
+            .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch comment stats timeseries")?;
+
+        Ok(stats)
+    }
+
     async fn get_replies(&self, parent_id: Uuid) -> Result<Vec<Comment>, AppError> {
         let replies = sqlx::query_as::<_, Comment>(
             r#"
@@ -333,9 +464,19 @@ impl CommentRepositoryTrait for CommentRepository {
         Ok(replies)
     }
 
+    async fn get_post_title(&self, post_id: Uuid) -> Result<Option<String>, AppError> {
+        let title = sqlx::query_scalar::<_, String>("SELECT title FROM posts WHERE id = $1")
+            .bind(post_id)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to fetch post title")?;
+
+        Ok(title)
+    }
+
     async fn bulk_update_status(&self, ids: Vec<Uuid>, status: String) -> Result<i64, AppError> {
         let result =
-            sqlx::query("UPDATE comments SET status = $1, updated_at = NOW() WHERE id = ANY($2)")
+            sqlx::query("UPDATE comments SET status = $1, updated_at = NOW(), moderated_at = NOW() WHERE id = ANY($2)")
                 .bind(&status)
                 .bind(&ids)
                 .execute(&self.pool)
@@ -345,6 +486,53 @@ impl CommentRepositoryTrait for CommentRepository {
         Ok(result.rows_affected() as i64)
     }
 
+    // Rejects or deletes comments that have been pending for more than `days` days.
+    // Comments a moderator has touched are skipped, and deleting never removes a comment
+    // whose replies (at any depth) include one that is no longer pending.
+    async fn expire_pending(
+        &self,
+        days: i32,
+        action: PendingCommentExpiryAction,
+    ) -> Result<u64, AppError> {
+        let result = match action {
+            PendingCommentExpiryAction::Reject => sqlx::query(
+                r#"
+                UPDATE comments
+                SET status = 'rejected', updated_at = NOW()
+                WHERE status = 'pending'
+                  AND moderated_at IS NULL
+                  AND created_at < NOW() - make_interval(days => $1)
+                "#,
+            ),
+            PendingCommentExpiryAction::Delete => sqlx::query(
+                r#"
+                WITH RECURSIVE protected AS (
+                    SELECT parent_id AS id
+                    FROM comments
+                    WHERE parent_id IS NOT NULL
+                      AND (status <> 'pending' OR moderated_at IS NOT NULL)
+                    UNION
+                    SELECT c.parent_id
+                    FROM comments c
+                    JOIN protected p ON c.id = p.id
+                    WHERE c.parent_id IS NOT NULL
+                )
+                DELETE FROM comments
+                WHERE status = 'pending'
+                  AND moderated_at IS NULL
+                  AND created_at < NOW() - make_interval(days => $1)
+                  AND id NOT IN (SELECT id FROM protected)
+                "#,
+            ),
+        }
+        .bind(days)
+        .execute(&self.pool)
+        .await
+        .context("Failed to expire pending comments")?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn count_recent_comments_by_ip(
         &self,
         ip_address: &str,
@@ -361,4 +549,393 @@ impl CommentRepositoryTrait for CommentRepository {
 
         Ok(result)
     }
+
+    async fn count_spam_by_ip(&self, ip_address: &str) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM comments WHERE ip_address = $1::inet AND status = 'spam'",
+        )
+        .bind(ip_address)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count spam comments by IP")?;
+
+        Ok(count)
+    }
+
+    // True when the IP has had a comment approved and never one rejected or marked spam
+    async fn has_clean_history(&self, ip_address: &str) -> Result<bool, AppError> {
+        let clean = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE status = 'approved') > 0
+               AND COUNT(*) FILTER (WHERE status IN ('rejected', 'spam')) = 0
+            FROM comments
+            WHERE ip_address = $1::inet
+            "#,
+        )
+        .bind(ip_address)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check comment history by IP")?;
+
+        Ok(clean)
+    }
+
+    async fn ban_commenter(
+        &self,
+        request: CreateBanRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<BannedCommenter, AppError> {
+        let ban = sqlx::query_as::<_, BannedCommenter>(
+            r#"
+            INSERT INTO banned_commenters (email, ip_address, reason, created_by, expires_at)
+            VALUES (LOWER($1), $2::inet, $3, $4, $5)
+            RETURNING id, email, host(ip_address) AS ip_address, reason, created_by,
+                      expires_at, created_at
+            "#,
+        )
+        .bind(request.email)
+        .bind(request.ip_address)
+        .bind(request.reason)
+        .bind(created_by)
+        .bind(request.expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to ban commenter")?;
+
+        Ok(ban)
+    }
+
+    async fn unban_commenter(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM banned_commenters WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to unban commenter")?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Ban not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_bans(&self) -> Result<Vec<BannedCommenter>, AppError> {
+        let bans = sqlx::query_as::<_, BannedCommenter>(
+            r#"
+            SELECT id, email, host(ip_address) AS ip_address, reason, created_by,
+                   expires_at, created_at
+            FROM banned_commenters
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list banned commenters")?;
+
+        Ok(bans)
+    }
+
+    // Expired bans stay listed for reference but no longer match
+    async fn find_active_ban(
+        &self,
+        email: &str,
+        ip_address: Option<&str>,
+    ) -> Result<Option<BannedCommenter>, AppError> {
+        let ban = sqlx::query_as::<_, BannedCommenter>(
+            r#"
+            SELECT id, email, host(ip_address) AS ip_address, reason, created_by,
+                   expires_at, created_at
+            FROM banned_commenters
+            WHERE (LOWER(email) = LOWER($1) OR ip_address = $2::inet)
+              AND (expires_at IS NULL OR expires_at > NOW())
+            LIMIT 1
+            "#,
+        )
+        .bind(email)
+        .bind(ip_address)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to check banned commenters")?;
+
+        Ok(ban)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_post(pool: &PgPool) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO posts (title, slug, content, category) VALUES ('Post', 'post', 'Content', 'General') RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    fn comment_request(post_id: Uuid, author_name: &str, content: &str) -> CreateCommentRequest {
+        CreateCommentRequest {
+            post_id,
+            author_name: author_name.to_string(),
+            author_email: format!("{}@example.com", author_name.to_lowercase()),
+            content: content.to_string(),
+            parent_id: None,
+        }
+    }
+
+    fn query() -> CommentQuery {
+        CommentQuery {
+            page: None,
+            limit: None,
+            post_id: None,
+            status: None,
+            author_email: None,
+            search: None,
+            include_replies: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_find_all_total_reflects_filters(pool: PgPool) {
+        let post_id = insert_post(&pool).await;
+        let repository = CommentRepository::new(pool);
+
+        for (name, content, status) in [
+            ("Alice", "Great article, thanks", "approved"),
+            ("Bob", "Buy cheap spam here", "pending"),
+            ("Carol", "Another spam attempt", "pending"),
+            ("Dave", "Waiting for review", "pending"),
+        ] {
+            repository
+                .create_with_status(
+                    comment_request(post_id, name, content),
+                    None,
+                    None,
+                    status.to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let all = repository.find_all(query()).await.unwrap();
+        assert_eq!(all.total, 4);
+
+        let pending = repository
+            .find_all(CommentQuery {
+                status: Some("pending".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(pending.total, 3);
+
+        let pending_spam = repository
+            .find_all(CommentQuery {
+                status: Some("pending".to_string()),
+                search: Some("spam".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(pending_spam.total, 2);
+        assert_eq!(pending_spam.comments.len(), 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_stats_timeseries_fills_empty_days(pool: PgPool) {
+        let post_id = insert_post(&pool).await;
+        let repository = CommentRepository::new(pool.clone());
+
+        for (name, status) in [
+            ("Alice", "approved"),
+            ("Bob", "spam"),
+            ("Carol", "rejected"),
+        ] {
+            repository
+                .create_with_status(
+                    comment_request(post_id, name, "A comment"),
+                    None,
+                    None,
+                    status.to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        // Move Carol's comment two days back, leaving yesterday empty
+        sqlx::query(
+            "UPDATE comments SET created_at = NOW() - INTERVAL '2 days' WHERE author_name = 'Carol'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let series = repository.get_stats_timeseries(3).await.unwrap();
+
+        assert_eq!(series.len(), 3);
+        assert!(series.windows(2).all(|pair| pair[0].day < pair[1].day));
+
+        assert_eq!(series[0].created, 1);
+        assert_eq!(series[0].rejected, 1);
+
+        let yesterday = &series[1];
+        assert_eq!(
+            (
+                yesterday.created,
+                yesterday.approved,
+                yesterday.rejected,
+                yesterday.spam
+            ),
+            (0, 0, 0, 0)
+        );
+
+        assert_eq!(series[2].created, 2);
+        assert_eq!(series[2].approved, 1);
+        assert_eq!(series[2].spam, 1);
+    }
+
+    async fn status_of(pool: &PgPool, author_name: &str) -> Option<String> {
+        sqlx::query_scalar("SELECT status FROM comments WHERE author_name = $1")
+            .bind(author_name)
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_expire_pending_skips_moderated_comments(pool: PgPool) {
+        let post_id = insert_post(&pool).await;
+        let repository = CommentRepository::new(pool.clone());
+
+        let mut ids = Vec::new();
+        for name in ["Old", "Reset", "Fresh"] {
+            let comment = repository
+                .create_with_status(
+                    comment_request(post_id, name, "Awaiting review"),
+                    None,
+                    None,
+                    "pending".to_string(),
+                )
+                .await
+                .unwrap();
+            ids.push(comment.id);
+        }
+
+        // A moderator put "Reset" back into the queue
+        repository
+            .update_status(
+                ids[1],
+                UpdateCommentStatusRequest {
+                    status: "pending".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "UPDATE comments SET created_at = NOW() - INTERVAL '40 days' WHERE author_name IN ('Old', 'Reset')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let expired = repository
+            .expire_pending(30, PendingCommentExpiryAction::Reject)
+            .await
+            .unwrap();
+
+        assert_eq!(expired, 1);
+        assert_eq!(status_of(&pool, "Old").await.as_deref(), Some("rejected"));
+        assert_eq!(status_of(&pool, "Reset").await.as_deref(), Some("pending"));
+        assert_eq!(status_of(&pool, "Fresh").await.as_deref(), Some("pending"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_expire_pending_delete_keeps_moderated_replies(pool: PgPool) {
+        let post_id = insert_post(&pool).await;
+        let repository = CommentRepository::new(pool.clone());
+
+        let parent = repository
+            .create_with_status(
+                comment_request(post_id, "Parent", "Old question"),
+                None,
+                None,
+                "pending".to_string(),
+            )
+            .await
+            .unwrap();
+        repository
+            .create_with_status(
+                comment_request(post_id, "Lonely", "Nobody replied"),
+                None,
+                None,
+                "pending".to_string(),
+            )
+            .await
+            .unwrap();
+        repository
+            .create_with_status(
+                CreateCommentRequest {
+                    parent_id: Some(parent.id),
+                    ..comment_request(post_id, "Reply", "Approved answer")
+                },
+                None,
+                None,
+                "approved".to_string(),
+            )
+            .await
+            .unwrap();
+
+        sqlx::query("UPDATE comments SET created_at = NOW() - INTERVAL '40 days'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let expired = repository
+            .expire_pending(30, PendingCommentExpiryAction::Delete)
+            .await
+            .unwrap();
+
+        // Deleting the parent would cascade to its approved reply
+        assert_eq!(expired, 1);
+        assert_eq!(status_of(&pool, "Lonely").await, None);
+        assert_eq!(status_of(&pool, "Parent").await.as_deref(), Some("pending"));
+        assert_eq!(status_of(&pool, "Reply").await.as_deref(), Some("approved"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_ip_history(pool: PgPool) {
+        let post_id = insert_post(&pool).await;
+        let repository = CommentRepository::new(pool);
+
+        for (name, ip, status) in [
+            ("Good", "203.0.113.1", "approved"),
+            ("Mixed", "203.0.113.2", "approved"),
+            ("Mixed", "203.0.113.2", "rejected"),
+            ("Spammer", "203.0.113.3", "spam"),
+            ("Spammer", "203.0.113.3", "spam"),
+        ] {
+            repository
+                .create_with_status(
+                    comment_request(post_id, name, "A comment"),
+                    Some(ip.to_string()),
+                    None,
+                    status.to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        assert!(repository.has_clean_history("203.0.113.1").await.unwrap());
+        assert!(!repository.has_clean_history("203.0.113.2").await.unwrap());
+        assert!(!repository.has_clean_history("198.51.100.9").await.unwrap());
+        assert_eq!(repository.count_spam_by_ip("203.0.113.3").await.unwrap(), 2);
+        assert_eq!(repository.count_spam_by_ip("203.0.113.1").await.unwrap(), 0);
+    }
 }
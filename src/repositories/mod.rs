@@ -1,17 +1,29 @@
 pub mod admin_settings_repository;
+pub mod analytics_repository;
+pub mod api_key_repository;
 pub mod audit_log_repository;
 pub mod comment_repository;
+pub mod password_reset_repository;
 pub mod portfolio_repository;
 pub mod post_repository;
+pub mod refresh_token_repository;
+pub mod search_repository;
 pub mod service_repository;
+#[cfg(test)]
+mod test_support;
 pub mod user_notification_repository;
 pub mod user_repository;
 
 pub use admin_settings_repository::AdminSettingsRepository;
+pub use analytics_repository::{AnalyticsRepository, AnalyticsRepositoryTrait};
+pub use api_key_repository::{ApiKeyRepository, ApiKeyRepositoryTrait};
 pub use audit_log_repository::AuditLogRepository;
 pub use comment_repository::{CommentRepository, CommentRepositoryTrait};
+pub use password_reset_repository::{PasswordResetRepository, PasswordResetRepositoryTrait};
 pub use portfolio_repository::{PortfolioRepository, PortfolioRepositoryTrait};
 pub use post_repository::{PostRepository, PostRepositoryTrait};
+pub use refresh_token_repository::{RefreshTokenRepository, RefreshTokenRepositoryTrait};
+pub use search_repository::{SearchRepository, SearchRepositoryTrait};
 pub use service_repository::{ServiceRepository, ServiceRepositoryTrait};
 pub use user_notification_repository::UserNotificationRepository;
 pub use user_repository::{UserRepository, UserRepositoryTrait};
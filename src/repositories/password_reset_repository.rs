@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::errors::AppError;
+
+#[async_trait]
+pub trait PasswordResetRepositoryTrait: Send + Sync {
+    async fn create_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+    async fn consume_token(&self, token_hash: &str) -> Result<Option<Uuid>, AppError>;
+    async fn invalidate_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+}
+
+pub struct PasswordResetRepository {
+    pool: PgPool,
+}
+
+impl PasswordResetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepositoryTrait for PasswordResetRepository {
+    async fn create_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to create password reset token")?;
+
+        Ok(())
+    }
+
+    // Marks an unused, unexpired token as used and returns its user
+    async fn consume_token(&self, token_hash: &str) -> Result<Option<Uuid>, AppError> {
+        let user_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE password_reset_tokens 
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to consume password reset token")?;
+
+        Ok(user_id)
+    }
+
+    async fn invalidate_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE password_reset_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to invalidate password reset tokens")?;
+
+        Ok(())
+    }
+}
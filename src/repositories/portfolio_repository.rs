@@ -1,13 +1,17 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{Acquire, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use crate::database::query_timing::timed;
 use crate::models::portfolio::{
-    CreatePortfolioProjectRequest, PortfolioProject, PortfolioProjectQuery,
-    PortfolioProjectsResponse, PortfolioStats, UpdatePortfolioProjectRequest,
+    CreatePortfolioProjectRequest, ImportOutcome, PortfolioProject, PortfolioProjectQuery,
+    PortfolioProjectRecord, PortfolioProjectsResponse, PortfolioStats,
+    UpdatePortfolioProjectRequest,
 };
 use crate::utils::errors::AppError;
+use crate::utils::text::contains_pattern;
 
 #[async_trait]
 pub trait PortfolioRepositoryTrait: Send + Sync {
@@ -28,8 +32,27 @@ pub trait PortfolioRepositoryTrait: Send + Sync {
     ) -> Result<PortfolioProject, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
     async fn get_featured(&self, limit: Option<u32>) -> Result<Vec<PortfolioProject>, AppError>;
+    async fn get_active_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError>;
     async fn get_stats(&self) -> Result<PortfolioStats, AppError>;
     async fn update_featured_status(&self, id: Uuid, featured: bool) -> Result<(), AppError>;
+    async fn increment_view_count(&self, id: Uuid) -> Result<(), AppError>;
+    async fn reorder(&self, orders: Vec<(Uuid, i32)>) -> Result<(), AppError>;
+    async fn bulk_update_featured(
+        &self,
+        ids: &[Uuid],
+        featured: bool,
+        max_featured: i64,
+    ) -> Result<u64, AppError>;
+    async fn check_slug_exists(
+        &self,
+        slug: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, AppError>;
+    async fn find_all_for_export(&self) -> Result<Vec<PortfolioProject>, AppError>;
+    async fn import(
+        &self,
+        records: &[PortfolioProjectRecord],
+    ) -> Result<Vec<Result<ImportOutcome, AppError>>, AppError>;
 }
 
 pub struct PortfolioRepository {
@@ -40,6 +63,57 @@ impl PortfolioRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Append the WHERE clause shared by the list and count queries.
+    // Without an `active` filter all projects are returned (admin view).
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &PortfolioProjectQuery) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(search) = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let pattern = contains_pattern(search);
+            builder
+                .push(" AND (title ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR description ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR long_description ILIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+
+        if let Some(category) = &query.category {
+            builder.push(" AND category = ").push_bind(category.clone());
+        }
+
+        if let Some(status) = &query.status {
+            builder.push(" AND status = ").push_bind(status.clone());
+        }
+
+        if let Some(featured) = query.featured {
+            builder.push(" AND featured = ").push_bind(featured);
+        }
+
+        if let Some(active) = query.active {
+            builder.push(" AND active = ").push_bind(active);
+        }
+
+        if let Some(technology) = query
+            .technology
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            builder
+                .push(" AND ")
+                .push_bind(technology.to_string())
+                .push(" = ANY(technologies)");
+        }
+    }
 }
 
 #[async_trait]
@@ -49,7 +123,7 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
             r#"
             SELECT id, title, slug, description, long_description, category, technologies, 
                    live_url, github_url, image_url, featured, active, status, start_date, 
-                   end_date, client, created_at, updated_at
+                   end_date, client, sort_order, view_count, version, created_at, updated_at
             FROM portfolio_projects 
             WHERE id = $1
             "#,
@@ -67,7 +141,7 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
             r#"
             SELECT id, title, slug, description, long_description, category, technologies, 
                    live_url, github_url, image_url, featured, active, status, start_date, 
-                   end_date, client, created_at, updated_at
+                   end_date, client, sort_order, view_count, version, created_at, updated_at
             FROM portfolio_projects 
             WHERE slug = $1
             "#,
@@ -87,70 +161,42 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
         let limit = query.limit.unwrap_or(10).min(100);
         let offset = (query.page.unwrap_or(1) - 1) * limit;
 
-        // Build WHERE clause - if active is not specified, return ALL projects (for admin)
-        let (where_clause, count_query, projects_query) = if let Some(active) = query.active {
-            // Filter by active status
-            let where_clause = "WHERE active = $1";
-            let count_query = format!("SELECT COUNT(*) FROM portfolio_projects {}", where_clause);
-            let projects_query = format!(
-                r#"
-                SELECT id, title, slug, description, long_description, category, technologies, 
-                       live_url, github_url, image_url, featured, active, status, start_date, 
-                       end_date, client, created_at, updated_at
-                FROM portfolio_projects 
-                {}
-                ORDER BY featured DESC, created_at DESC 
-                LIMIT $2 OFFSET $3
-                "#,
-                where_clause
-            );
-            (Some(active), count_query, projects_query)
-        } else {
-            // Return ALL projects (admin view)
-            let count_query = "SELECT COUNT(*) FROM portfolio_projects".to_string();
-            let projects_query = r#"
-                SELECT id, title, slug, description, long_description, category, technologies, 
-                       live_url, github_url, image_url, featured, active, status, start_date, 
-                       end_date, client, created_at, updated_at
-                FROM portfolio_projects 
-                ORDER BY featured DESC, created_at DESC 
-                LIMIT $1 OFFSET $2
-            "#
-            .to_string();
-            (None, count_query, projects_query)
-        };
+        // Get total count using the same filters as the data query
+        let mut count_builder =
+            QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM portfolio_projects");
+        Self::push_filters(&mut count_builder, &query);
 
-        // Get total count
-        let total: i64 = if let Some(active) = where_clause {
-            sqlx::query_scalar(&count_query)
-                .bind(active)
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to count portfolio projects")?
-        } else {
-            sqlx::query_scalar(&count_query)
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to count portfolio projects")?
-        };
+        let total: i64 = timed(
+            "portfolio.find_all.count",
+            count_builder.build_query_scalar().fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to count portfolio projects")?;
 
         // Get projects
-        let projects = if let Some(active) = where_clause {
-            sqlx::query_as::<_, PortfolioProject>(&projects_query)
-                .bind(active)
-                .bind(limit as i64)
-                .bind(offset as i64)
-                .fetch_all(&self.pool)
-                .await
-                .context("Failed to fetch portfolio projects")?
-        } else {
-            sqlx::query_as::<_, PortfolioProject>(&projects_query)
-                .bind(limit as i64)
-                .bind(offset as i64)
-                .fetch_all(&self.pool)
-                .await
-                .context("Failed to fetch portfolio projects")?
-        };
+        let mut projects_builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, title, slug, description, long_description, category, technologies, 
+                   live_url, github_url, image_url, featured, active, status, start_date, 
+                   end_date, client, sort_order, view_count, version, created_at, updated_at
+            FROM portfolio_projects
+            "#,
+        );
+        Self::push_filters(&mut projects_builder, &query);
+        projects_builder
+            .push(" ORDER BY sort_order ASC, created_at DESC LIMIT ")
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let projects = timed(
+            "portfolio.find_all",
+            projects_builder
+                .build_query_as::<PortfolioProject>()
+                .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch portfolio projects")?;
 
         let total_pages = (total as f64 / limit as f64).ceil() as u32;
 
@@ -172,12 +218,15 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
             INSERT INTO portfolio_projects (
                 title, slug, description, long_description, category, technologies, 
                 live_url, github_url, image_url, featured, active, status, start_date, 
-                end_date, client
+                end_date, client, sort_order
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                COALESCE($16, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM portfolio_projects))
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id, title, slug, description, long_description, category, technologies, 
                       live_url, github_url, image_url, featured, active, status, start_date, 
-                      end_date, client, created_at, updated_at
+                      end_date, client, sort_order, view_count, version, created_at, updated_at
             "#,
         )
         .bind(&project.title)
@@ -195,9 +244,16 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
         .bind(project.start_date)
         .bind(project.end_date)
         .bind(&project.client)
+        .bind(project.sort_order)
         .fetch_one(&self.pool)
         .await
-        .context("Failed to create portfolio project")?;
+        .map_err(|e| {
+            AppError::from_unique_violation(
+                e,
+                "Portfolio project",
+                "Failed to create portfolio project",
+            )
+        })?;
 
         Ok(created_project)
     }
@@ -213,11 +269,12 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
             SET title = $1, slug = $2, description = $3, long_description = $4, category = $5, 
                 technologies = $6, live_url = $7, github_url = $8, image_url = $9, 
                 featured = $10, active = $11, status = $12, start_date = $13, end_date = $14, 
-                client = $15, updated_at = NOW()
-            WHERE id = $16
+                client = $15, sort_order = COALESCE($16, sort_order), version = version + 1,
+                updated_at = NOW()
+            WHERE id = $17 AND version = $18
             RETURNING id, title, slug, description, long_description, category, technologies, 
                       live_url, github_url, image_url, featured, active, status, start_date, 
-                      end_date, client, created_at, updated_at
+                      end_date, client, sort_order, view_count, version, created_at, updated_at
             "#,
         )
         .bind(&project.title)
@@ -235,13 +292,31 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
         .bind(project.start_date)
         .bind(project.end_date)
         .bind(&project.client)
+        .bind(project.sort_order)
         .bind(id)
+        .bind(project.version)
         .fetch_optional(&self.pool)
         .await
-        .context("Failed to update portfolio project")?
-        .ok_or(AppError::NotFound(
-            "Portfolio project not found".to_string(),
-        ))?;
+        .map_err(|e| {
+            AppError::from_unique_violation(
+                e,
+                "Portfolio project",
+                "Failed to update portfolio project",
+            )
+        })?;
+
+        // No row means the project is gone or someone else saved a newer version first
+        let Some(updated_project) = updated_project else {
+            return match self.find_by_id(id).await? {
+                Some(_) => Err(AppError::Conflict(
+                    "Portfolio project was modified by someone else; reload it and try again"
+                        .to_string(),
+                )),
+                None => Err(AppError::NotFound(
+                    "Portfolio project not found".to_string(),
+                )),
+            };
+        };
 
         Ok(updated_project)
     }
@@ -269,10 +344,10 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
             r#"
             SELECT id, title, slug, description, long_description, category, technologies, 
                    live_url, github_url, image_url, featured, active, status, start_date, 
-                   end_date, client, created_at, updated_at
+                   end_date, client, sort_order, view_count, version, created_at, updated_at
             FROM portfolio_projects 
             WHERE featured = true AND active = true
-            ORDER BY created_at DESC 
+            ORDER BY sort_order ASC, created_at DESC 
             LIMIT $1
             "#,
         )
@@ -284,6 +359,22 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
         Ok(projects)
     }
 
+    async fn get_active_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError> {
+        let slugs = sqlx::query_as::<_, (String, DateTime<Utc>)>(
+            r#"
+            SELECT slug, updated_at
+            FROM portfolio_projects 
+            WHERE active = true
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch active portfolio project slugs")?;
+
+        Ok(slugs)
+    }
+
     async fn get_stats(&self) -> Result<PortfolioStats, AppError> {
         let total_projects: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM portfolio_projects")
             .fetch_one(&self.pool)
@@ -328,7 +419,7 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
 
     async fn update_featured_status(&self, id: Uuid, featured: bool) -> Result<(), AppError> {
         let result = sqlx::query(
-            "UPDATE portfolio_projects SET featured = $1, updated_at = NOW() WHERE id = $2",
+            "UPDATE portfolio_projects SET featured = $1, version = version + 1, updated_at = NOW() WHERE id = $2",
         )
         .bind(featured)
         .bind(id)
@@ -344,4 +435,478 @@ impl PortfolioRepositoryTrait for PortfolioRepository {
 
         Ok(())
     }
+
+    async fn increment_view_count(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE portfolio_projects SET view_count = view_count + 1 WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to increment view count")?;
+
+        Ok(())
+    }
+
+    async fn reorder(&self, orders: Vec<(Uuid, i32)>) -> Result<(), AppError> {
+        let (ids, sort_orders): (Vec<Uuid>, Vec<i32>) = orders.into_iter().unzip();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE portfolio_projects p
+            SET sort_order = o.sort_order, version = p.version + 1, updated_at = NOW()
+            FROM UNNEST($1::uuid[], $2::int[]) AS o(id, sort_order)
+            WHERE p.id = o.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&sort_orders)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to reorder portfolio projects")?;
+
+        // Dropping the transaction rolls back if any ID didn't match a project
+        if result.rows_affected() != ids.len() as u64 {
+            return Err(AppError::NotFound(
+                "One or more portfolio projects not found".to_string(),
+            ));
+        }
+
+        tx.commit().await.context("Failed to commit reorder")?;
+
+        Ok(())
+    }
+
+    async fn bulk_update_featured(
+        &self,
+        ids: &[Uuid],
+        featured: bool,
+        max_featured: i64,
+    ) -> Result<u64, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query(
+            "UPDATE portfolio_projects SET featured = $1, version = version + 1, updated_at = NOW() WHERE id = ANY($2)",
+        )
+        .bind(featured)
+        .bind(ids)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to bulk update featured projects")?;
+
+        // Dropping the transaction rolls back if the batch pushed past the featured limit
+        let featured_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM portfolio_projects WHERE featured = true")
+                .fetch_one(&mut *tx)
+                .await
+                .context("Failed to count featured projects")?;
+        if featured_count > max_featured {
+            return Err(AppError::Validation(format!(
+                "Cannot have more than {} featured projects",
+                max_featured
+            )));
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit bulk featured update")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn check_slug_exists(
+        &self,
+        slug: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, AppError> {
+        let query = match exclude_id {
+            Some(id) => sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM portfolio_projects WHERE slug = $1 AND id != $2",
+            )
+            .bind(slug)
+            .bind(id),
+            None => sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM portfolio_projects WHERE slug = $1",
+            )
+            .bind(slug),
+        };
+
+        let count = query
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to check slug existence")?;
+
+        Ok(count > 0)
+    }
+
+    async fn find_all_for_export(&self) -> Result<Vec<PortfolioProject>, AppError> {
+        let projects = sqlx::query_as::<_, PortfolioProject>(
+            r#"
+            SELECT id, title, slug, description, long_description, category, technologies,
+                   live_url, github_url, image_url, featured, active, status, start_date,
+                   end_date, client, sort_order, view_count, version, created_at, updated_at
+            FROM portfolio_projects
+            ORDER BY sort_order ASC, slug ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch portfolio projects for export")?;
+
+        Ok(projects)
+    }
+
+    async fn import(
+        &self,
+        records: &[PortfolioProjectRecord],
+    ) -> Result<Vec<Result<ImportOutcome, AppError>>, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // Each record runs in its own savepoint so one bad row doesn't abort the batch
+        let mut outcomes = Vec::with_capacity(records.len());
+        for record in records {
+            let mut savepoint = tx.begin().await.context("Failed to create savepoint")?;
+
+            let created = sqlx::query_scalar::<_, bool>(
+                r#"
+                INSERT INTO portfolio_projects (
+                    title, slug, description, long_description, category, technologies,
+                    live_url, github_url, image_url, featured, active, status, start_date,
+                    end_date, client, sort_order
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                ON CONFLICT (slug) DO UPDATE SET
+                    title = EXCLUDED.title,
+                    description = EXCLUDED.description,
+                    long_description = EXCLUDED.long_description,
+                    category = EXCLUDED.category,
+                    technologies = EXCLUDED.technologies,
+                    live_url = EXCLUDED.live_url,
+                    github_url = EXCLUDED.github_url,
+                    image_url = EXCLUDED.image_url,
+                    featured = EXCLUDED.featured,
+                    active = EXCLUDED.active,
+                    status = EXCLUDED.status,
+                    start_date = EXCLUDED.start_date,
+                    end_date = EXCLUDED.end_date,
+                    client = EXCLUDED.client,
+                    sort_order = EXCLUDED.sort_order,
+                    version = portfolio_projects.version + 1,
+                    updated_at = NOW()
+                RETURNING (xmax = 0)
+                "#,
+            )
+            .bind(&record.title)
+            .bind(&record.slug)
+            .bind(&record.description)
+            .bind(&record.long_description)
+            .bind(&record.category)
+            .bind(&record.technologies)
+            .bind(&record.live_url)
+            .bind(&record.github_url)
+            .bind(&record.image_url)
+            .bind(record.featured)
+            .bind(record.active)
+            .bind(&record.status)
+            .bind(record.start_date)
+            .bind(record.end_date)
+            .bind(&record.client)
+            .bind(record.sort_order)
+            .fetch_one(&mut *savepoint)
+            .await;
+
+            match created {
+                Ok(created) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .context("Failed to release savepoint")?;
+                    outcomes.push(Ok(if created {
+                        ImportOutcome::Created
+                    } else {
+                        ImportOutcome::Updated
+                    }));
+                }
+                Err(e) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .context("Failed to roll back savepoint")?;
+                    outcomes.push(Err(AppError::from_unique_violation(
+                        e,
+                        "Portfolio project",
+                        "Failed to import portfolio project",
+                    )));
+                }
+            }
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit portfolio import")?;
+
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn project_request(
+        slug: &str,
+        technologies: &[&str],
+        status: &str,
+    ) -> CreatePortfolioProjectRequest {
+        CreatePortfolioProjectRequest {
+            title: format!("Project {}", slug),
+            slug: slug.to_string(),
+            description: "A portfolio project".to_string(),
+            long_description: None,
+            category: "Web".to_string(),
+            technologies: technologies.iter().map(|t| t.to_string()).collect(),
+            live_url: None,
+            github_url: None,
+            image_url: None,
+            featured: None,
+            active: Some(true),
+            status: status.to_string(),
+            start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            end_date: None,
+            client: None,
+            sort_order: None,
+        }
+    }
+
+    fn query() -> PortfolioProjectQuery {
+        PortfolioProjectQuery {
+            page: Some(1),
+            limit: Some(10),
+            search: None,
+            category: None,
+            status: None,
+            featured: None,
+            active: Some(true),
+            technologies: None,
+            technology: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_find_all_filters_by_technology(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        for request in [
+            project_request("rust-api", &["Rust", "PostgreSQL"], "completed"),
+            project_request("rust-cli", &["Rust"], "in_progress"),
+            project_request("react-app", &["React", "TypeScript"], "completed"),
+        ] {
+            repository.create(request).await.unwrap();
+        }
+
+        let response = repository
+            .find_all(PortfolioProjectQuery {
+                technology: Some("Rust".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.total, 2);
+        assert_eq!(response.projects.len(), 2);
+
+        let response = repository
+            .find_all(PortfolioProjectQuery {
+                technology: Some("Rust".to_string()),
+                status: Some("completed".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.projects[0].slug, "rust-api");
+
+        // Array membership is exact, not a substring match
+        let response = repository
+            .find_all(PortfolioProjectQuery {
+                technology: Some("Rus".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.total, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_find_all_searches_text_fields(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        repository
+            .create(CreatePortfolioProjectRequest {
+                long_description: Some("Built with a custom job scheduler".to_string()),
+                ..project_request("scheduler", &["Go"], "completed")
+            })
+            .await
+            .unwrap();
+        repository
+            .create(project_request("other", &["Go"], "completed"))
+            .await
+            .unwrap();
+
+        let response = repository
+            .find_all(PortfolioProjectQuery {
+                search: Some("SCHEDULER".to_string()),
+                ..query()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.total, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_reorder_persists_featured_order(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        let mut ids = Vec::new();
+        for slug in ["first", "second", "third"] {
+            let project = repository
+                .create(CreatePortfolioProjectRequest {
+                    featured: Some(true),
+                    ..project_request(slug, &["Rust"], "completed")
+                })
+                .await
+                .unwrap();
+            ids.push(project.id);
+        }
+
+        repository
+            .reorder(vec![(ids[0], 3), (ids[1], 1), (ids[2], 2)])
+            .await
+            .unwrap();
+
+        let slugs: Vec<_> = repository
+            .get_featured(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.slug)
+            .collect();
+        assert_eq!(slugs, ["second", "third", "first"]);
+
+        let project = repository.find_by_id(ids[0]).await.unwrap().unwrap();
+        assert_eq!(project.sort_order, 3);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_reorder_with_unknown_id_changes_nothing(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        let project = repository
+            .create(project_request("only", &["Rust"], "completed"))
+            .await
+            .unwrap();
+
+        let result = repository
+            .reorder(vec![(project.id, 10), (Uuid::new_v4(), 11)])
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let project = repository.find_by_id(project.id).await.unwrap().unwrap();
+        assert_eq!(project.sort_order, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_bulk_featured_over_limit_changes_nothing(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        let mut ids = Vec::new();
+        for slug in ["one", "two", "three"] {
+            let project = repository
+                .create(project_request(slug, &["Rust"], "completed"))
+                .await
+                .unwrap();
+            ids.push(project.id);
+        }
+
+        let updated = repository
+            .bulk_update_featured(&ids[..2], true, 2)
+            .await
+            .unwrap();
+        assert_eq!(updated, 2);
+
+        let result = repository.bulk_update_featured(&ids[2..], true, 2).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let third = repository.find_by_id(ids[2]).await.unwrap().unwrap();
+        assert!(!third.featured);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_export_import_round_trip(pool: PgPool) {
+        let repository = PortfolioRepository::new(pool);
+
+        let mut ids = Vec::new();
+        for request in [
+            CreatePortfolioProjectRequest {
+                featured: Some(true),
+                live_url: Some("https://example.com".to_string()),
+                end_date: NaiveDate::from_ymd_opt(2025, 6, 30),
+                client: Some("Acme".to_string()),
+                ..project_request("rust-api", &["Rust", "PostgreSQL"], "completed")
+            },
+            project_request("react-app", &["React"], "in_progress"),
+        ] {
+            ids.push(repository.create(request).await.unwrap().id);
+        }
+
+        let export = |projects: Vec<PortfolioProject>| {
+            projects
+                .into_iter()
+                .map(PortfolioProjectRecord::from)
+                .collect::<Vec<_>>()
+        };
+        let exported = export(repository.find_all_for_export().await.unwrap());
+        assert_eq!(exported.len(), 2);
+
+        // Importing into an empty table recreates every project
+        for id in ids {
+            repository.delete(id).await.unwrap();
+        }
+        let outcomes = repository.import(&exported).await.unwrap();
+        assert!(outcomes
+            .iter()
+            .all(|o| matches!(o, Ok(ImportOutcome::Created))));
+        assert_eq!(
+            export(repository.find_all_for_export().await.unwrap()),
+            exported
+        );
+
+        // Importing the same data again updates in place
+        let outcomes = repository.import(&exported).await.unwrap();
+        assert!(outcomes
+            .iter()
+            .all(|o| matches!(o, Ok(ImportOutcome::Updated))));
+        assert_eq!(
+            export(repository.find_all_for_export().await.unwrap()),
+            exported
+        );
+    }
 }
@@ -1,12 +1,16 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{Acquire, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use crate::database::query_timing::timed;
+use crate::models::portfolio::ImportOutcome;
 use crate::models::post::{
-    CreatePostRequest, Post, PostQuery, PostStats, PostsResponse, UpdatePostRequest,
+    CategoryCount, CreatePostRequest, Post, PostDailyViews, PostImportMode, PostImportRecord,
+    PostQuery, PostStats, PostsResponse, TagCount, UpdatePostRequest,
 };
-use crate::utils::errors::AppError;
+use crate::utils::{cursor::Cursor, errors::AppError, text::contains_pattern};
 
 #[async_trait]
 pub trait PostRepositoryTrait: Send + Sync {
@@ -16,7 +20,16 @@ pub trait PostRepositoryTrait: Send + Sync {
     async fn create(&self, post: CreatePostRequest) -> Result<Post, AppError>;
     async fn update(&self, id: Uuid, post: UpdatePostRequest) -> Result<Post, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    async fn bulk_delete(&self, ids: &[Uuid]) -> Result<Vec<(Uuid, String)>, AppError>;
+    async fn bulk_update_featured(&self, ids: &[Uuid], featured: bool) -> Result<u64, AppError>;
+    async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+    async fn purge(&self, id: Uuid) -> Result<(), AppError>;
     async fn get_published(&self, limit: Option<u32>) -> Result<Vec<Post>, AppError>;
+    async fn get_published_paginated(
+        &self,
+        page: u32,
+        limit: u32,
+    ) -> Result<PostsResponse, AppError>;
     async fn get_featured(&self, limit: Option<u32>) -> Result<Vec<Post>, AppError>;
     async fn get_by_category(
         &self,
@@ -28,14 +41,31 @@ pub trait PostRepositoryTrait: Send + Sync {
         tags: Vec<String>,
         limit: Option<u32>,
     ) -> Result<Vec<Post>, AppError>;
+    async fn get_related(&self, id: Uuid, limit: Option<u32>) -> Result<Vec<Post>, AppError>;
+    async fn search_ranked(&self, query: &str, limit: u32) -> Result<Vec<Post>, AppError>;
+    async fn get_published_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError>;
+    async fn get_tag_counts(&self, limit: Option<u32>) -> Result<Vec<TagCount>, AppError>;
+    async fn get_category_counts(&self) -> Result<Vec<CategoryCount>, AppError>;
     async fn get_stats(&self) -> Result<PostStats, AppError>;
     async fn update_published_status(&self, id: Uuid, published: bool) -> Result<(), AppError>;
     async fn increment_view_count(&self, id: Uuid) -> Result<(), AppError>;
+    async fn record_view(&self, id: Uuid, ip_hash: Option<&str>) -> Result<(), AppError>;
+    async fn view_timeseries(
+        &self,
+        post_id: Uuid,
+        days: i32,
+    ) -> Result<Vec<PostDailyViews>, AppError>;
+    async fn publish_due_posts(&self) -> Result<u64, AppError>;
     async fn check_slug_exists(
         &self,
         slug: &str,
         exclude_id: Option<Uuid>,
     ) -> Result<bool, AppError>;
+    async fn import(
+        &self,
+        records: &[PostImportRecord],
+        mode: PostImportMode,
+    ) -> Result<Vec<Result<ImportOutcome, AppError>>, AppError>;
 }
 
 pub struct PostRepository {
@@ -46,6 +76,44 @@ impl PostRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Append the WHERE clause shared by the list and count queries
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &PostQuery) {
+        builder.push(" WHERE deleted_at IS NULL");
+
+        if let Some(search) = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let pattern = contains_pattern(search);
+            builder
+                .push(" AND (title ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR excerpt ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR content ILIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+
+        if let Some(category) = &query.category {
+            builder.push(" AND category = ").push_bind(category.clone());
+        }
+
+        if let Some(published) = query.published {
+            builder.push(" AND published = ").push_bind(published);
+        }
+
+        if let Some(featured) = query.featured {
+            builder.push(" AND featured = ").push_bind(featured);
+        }
+
+        if let Some(tags) = query.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            builder.push(" AND tags && ").push_bind(tags.clone());
+        }
+    }
 }
 
 #[async_trait]
@@ -55,9 +123,9 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
@@ -73,9 +141,9 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE slug = $1
+            WHERE slug = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(slug)
@@ -89,31 +157,60 @@ impl PostRepositoryTrait for PostRepository {
     async fn find_all(&self, query: PostQuery) -> Result<PostsResponse, AppError> {
         let limit = query.limit.unwrap_or(10).min(100);
         let offset = (query.page.unwrap_or(1) - 1) * limit;
+        let cursor = query.cursor.as_deref().map(Cursor::decode).transpose()?;
+
+        // Get total count using the same filters as the data query
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM posts");
+        Self::push_filters(&mut count_builder, &query);
 
-        // For simplicity, using basic query without complex dynamic binding
-        let base_count_query = "SELECT COUNT(*) FROM posts";
-        let base_posts_query = r#"
+        let total: i64 = timed(
+            "posts.find_all.count",
+            count_builder.build_query_scalar().fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to count posts")?;
+
+        // Get posts
+        let mut posts_builder = QueryBuilder::<Postgres>::new(
+            r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
-            FROM posts 
-            ORDER BY created_at DESC 
-            LIMIT $1 OFFSET $2
-        "#;
+                   published_at, publish_at, version, created_at, updated_at
+            FROM posts
+            "#,
+        );
+        Self::push_filters(&mut posts_builder, &query);
+        if let Some(cursor) = &cursor {
+            posts_builder
+                .push(" AND (created_at, id) < (")
+                .push_bind(cursor.created_at)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        // One extra row tells us whether another page follows
+        posts_builder
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit as i64 + 1);
+        if cursor.is_none() {
+            posts_builder.push(" OFFSET ").push_bind(offset as i64);
+        }
 
-        // Get total count
-        let total: i64 = sqlx::query_scalar(base_count_query)
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count posts")?;
+        let mut posts = timed(
+            "posts.find_all",
+            posts_builder.build_query_as::<Post>().fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch posts")?;
 
-        // Get posts
-        let posts = sqlx::query_as::<_, Post>(base_posts_query)
-            .bind(limit as i64)
-            .bind(offset as i64)
-            .fetch_all(&self.pool)
-            .await
-            .context("Failed to fetch posts")?;
+        let next_cursor = if posts.len() > limit as usize {
+            posts.truncate(limit as usize);
+            posts
+                .last()
+                .map(|post| Cursor::new(post.created_at, post.id).encode())
+        } else {
+            None
+        };
 
         let total_pages = (total as f64 / limit as f64).ceil() as u32;
 
@@ -123,6 +220,7 @@ impl PostRepositoryTrait for PostRepository {
             page: query.page.unwrap_or(1),
             limit,
             total_pages,
+            next_cursor,
         })
     }
 
@@ -131,12 +229,12 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             INSERT INTO posts (
                 title, slug, content, excerpt, category, tags, featured_image, featured, 
-                published, seo_title, seo_description, seo_keywords, published_at
+                published, seo_title, seo_description, seo_keywords, published_at, publish_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                       published, seo_title, seo_description, seo_keywords, view_count, 
-                      published_at, created_at, updated_at
+                      published_at, publish_at, version, created_at, updated_at
             "#,
         )
         .bind(&post.title)
@@ -156,22 +254,24 @@ impl PostRepositoryTrait for PostRepository {
         } else {
             None
         })
+        .bind(post.publish_at)
         .fetch_one(&self.pool)
         .await
-        .context("Failed to create post")?;
+        .map_err(|e| AppError::from_unique_violation(e, "Post", "Failed to create post"))?;
 
         Ok(created_post)
     }
 
     async fn update(&self, id: Uuid, post: UpdatePostRequest) -> Result<Post, AppError> {
         // Check if we're changing published status
-        let current_published =
-            sqlx::query_scalar::<_, bool>("SELECT published FROM posts WHERE id = $1")
-                .bind(id)
-                .fetch_optional(&self.pool)
-                .await
-                .context("Failed to check current published status")?
-                .unwrap_or(false);
+        let current_published = sqlx::query_scalar::<_, bool>(
+            "SELECT published FROM posts WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to check current published status")?
+        .unwrap_or(false);
 
         let new_published = post.published.unwrap_or(false);
         let _published_at = if !current_published && new_published {
@@ -188,17 +288,18 @@ impl PostRepositoryTrait for PostRepository {
             UPDATE posts 
             SET title = $1, slug = $2, content = $3, excerpt = $4, category = $5, 
                 tags = $6, featured_image = $7, featured = $8, published = $9, seo_title = $10, 
-                seo_description = $11, seo_keywords = $12, 
+                seo_description = $11, seo_keywords = $12, publish_at = $13,
                 published_at = CASE 
                     WHEN $9 = true AND published = false THEN NOW()
                     WHEN $9 = false THEN NULL
                     ELSE published_at
                 END,
+                version = version + 1,
                 updated_at = NOW()
-            WHERE id = $13
+            WHERE id = $14 AND version = $15 AND deleted_at IS NULL
             RETURNING id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                       published, seo_title, seo_description, seo_keywords, view_count, 
-                      published_at, created_at, updated_at
+                      published_at, publish_at, version, created_at, updated_at
             "#,
         )
         .bind(&post.title)
@@ -213,21 +314,106 @@ impl PostRepositoryTrait for PostRepository {
         .bind(&post.seo_title)
         .bind(&post.seo_description)
         .bind(&post.seo_keywords)
+        .bind(post.publish_at)
         .bind(id)
+        .bind(post.version)
         .fetch_optional(&self.pool)
         .await
-        .context("Failed to update post")?
-        .ok_or(AppError::NotFound("Post not found".to_string()))?;
+        .map_err(|e| AppError::from_unique_violation(e, "Post", "Failed to update post"))?;
+
+        // No row means the post is gone or someone else saved a newer version first
+        let Some(updated_post) = updated_post else {
+            return match self.find_by_id(id).await? {
+                Some(_) => Err(AppError::Conflict(
+                    "Post was modified by someone else; reload it and try again".to_string(),
+                )),
+                None => Err(AppError::NotFound("Post not found".to_string())),
+            };
+        };
 
         Ok(updated_post)
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "UPDATE posts SET deleted_at = NOW(), version = version + 1, updated_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete post")?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Soft-deletes in a single statement so either every listed post goes or none do.
+    // Returns the id and title of each post actually deleted.
+    async fn bulk_delete(&self, ids: &[Uuid]) -> Result<Vec<(Uuid, String)>, AppError> {
+        let deleted = sqlx::query_as::<_, (Uuid, String)>(
+            r#"
+            UPDATE posts
+            SET deleted_at = NOW(), version = version + 1, updated_at = NOW()
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            RETURNING id, title
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to bulk delete posts")?;
+
+        Ok(deleted)
+    }
+
+    async fn bulk_update_featured(&self, ids: &[Uuid], featured: bool) -> Result<u64, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query(
+            "UPDATE posts SET featured = $1, version = version + 1, updated_at = NOW() WHERE id = ANY($2) AND deleted_at IS NULL",
+        )
+        .bind(featured)
+        .bind(ids)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to bulk update featured posts")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit bulk featured update")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "UPDATE posts SET deleted_at = NULL, version = version + 1, updated_at = NOW() WHERE id = $1 AND deleted_at IS NOT NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to restore post")?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Deleted post not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn purge(&self, id: Uuid) -> Result<(), AppError> {
         let result = sqlx::query("DELETE FROM posts WHERE id = $1")
             .bind(id)
             .execute(&self.pool)
             .await
-            .context("Failed to delete post")?;
+            .context("Failed to purge post")?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound("Post not found".to_string()));
@@ -247,9 +433,10 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE published = true 
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
             ORDER BY published_at DESC 
             LIMIT $1
             "#,
@@ -266,6 +453,56 @@ impl PostRepositoryTrait for PostRepository {
         Ok(posts)
     }
 
+    async fn get_published_paginated(
+        &self,
+        page: u32,
+        limit: u32,
+    ) -> Result<PostsResponse, AppError> {
+        let page = page.max(1);
+        let limit = limit.clamp(1, 100);
+        let offset = (page - 1)
+            .checked_mul(limit)
+            .ok_or_else(|| AppError::BadRequest("Page is out of range".to_string()))?;
+
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM posts
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count published posts")?;
+
+        let posts = sqlx::query_as::<_, Post>(
+            r#"
+            SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
+                   published, seo_title, seo_description, seo_keywords, view_count, 
+                   published_at, publish_at, version, created_at, updated_at
+            FROM posts 
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+            ORDER BY published_at DESC, id DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch published posts")?;
+
+        Ok(PostsResponse {
+            posts: posts.into_iter().map(|p| p.into()).collect(),
+            total,
+            page,
+            limit,
+            total_pages: (total as f64 / limit as f64).ceil() as u32,
+            next_cursor: None,
+        })
+    }
+
     async fn get_featured(&self, limit: Option<u32>) -> Result<Vec<Post>, AppError> {
         let limit = limit.unwrap_or(5).min(20);
 
@@ -273,9 +510,10 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE featured = true AND published = true
+            WHERE featured = true AND published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
             ORDER BY published_at DESC 
             LIMIT $1
             "#,
@@ -299,9 +537,10 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE category = $1 AND published = true
+            WHERE category = $1 AND published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
             ORDER BY published_at DESC 
             LIMIT $2
             "#,
@@ -326,9 +565,10 @@ impl PostRepositoryTrait for PostRepository {
             r#"
             SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured, 
                    published, seo_title, seo_description, seo_keywords, view_count, 
-                   published_at, created_at, updated_at
+                   published_at, publish_at, version, created_at, updated_at
             FROM posts 
-            WHERE tags && $1 AND published = true
+            WHERE tags && $1 AND published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
             ORDER BY published_at DESC 
             LIMIT $2
             "#,
@@ -342,42 +582,187 @@ impl PostRepositoryTrait for PostRepository {
         Ok(posts)
     }
 
-    async fn get_stats(&self) -> Result<PostStats, AppError> {
-        let total_posts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts")
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count total posts")?;
+    async fn get_related(&self, id: Uuid, limit: Option<u32>) -> Result<Vec<Post>, AppError> {
+        let limit = limit.unwrap_or(5).min(10);
 
-        let published_posts: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE published = true")
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to count published posts")?;
+        // Rank by the number of tags shared with the source post
+        let posts = sqlx::query_as::<_, Post>(
+            r#"
+            SELECT p.id, p.title, p.slug, p.content, p.excerpt, p.category, p.tags, p.featured_image, 
+                   p.featured, p.published, p.seo_title, p.seo_description, p.seo_keywords, 
+                   p.view_count, p.published_at, p.publish_at, p.version, p.created_at, p.updated_at
+            FROM posts p, posts source
+            WHERE source.id = $1 AND p.id != source.id 
+              AND p.published = true AND p.deleted_at IS NULL
+              AND (p.publish_at IS NULL OR p.publish_at <= NOW())
+              AND p.tags && source.tags
+            ORDER BY cardinality(ARRAY(
+                         SELECT unnest(p.tags) INTERSECT SELECT unnest(source.tags)
+                     )) DESC,
+                     p.published_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch related posts by tags")?;
+
+        if !posts.is_empty() {
+            return Ok(posts);
+        }
+
+        // Fall back to posts from the same category
+        let posts = sqlx::query_as::<_, Post>(
+            r#"
+            SELECT p.id, p.title, p.slug, p.content, p.excerpt, p.category, p.tags, p.featured_image, 
+                   p.featured, p.published, p.seo_title, p.seo_description, p.seo_keywords, 
+                   p.view_count, p.published_at, p.publish_at, p.version, p.created_at, p.updated_at
+            FROM posts p, posts source
+            WHERE source.id = $1 AND p.id != source.id 
+              AND p.published = true AND p.deleted_at IS NULL
+              AND (p.publish_at IS NULL OR p.publish_at <= NOW())
+              AND p.category = source.category
+            ORDER BY p.published_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch related posts by category")?;
+
+        Ok(posts)
+    }
+
+    async fn search_ranked(&self, query: &str, limit: u32) -> Result<Vec<Post>, AppError> {
+        // websearch_to_tsquery accepts free-form input (quoted phrases, -exclusions, "or")
+        // without raising syntax errors; ts_rank_cd honours the title > excerpt > content weights
+        let posts = timed(
+            "posts.search_ranked",
+            sqlx::query_as::<_, Post>(
+                r#"
+                SELECT id, title, slug, content, excerpt, category, tags, featured_image, featured,
+                       published, seo_title, seo_description, seo_keywords, view_count,
+                       published_at, publish_at, version, created_at, updated_at
+                FROM posts, websearch_to_tsquery('english', $1) AS query
+                WHERE search_vector @@ query
+                  AND published = true AND deleted_at IS NULL
+                  AND (publish_at IS NULL OR publish_at <= NOW())
+                ORDER BY ts_rank_cd(search_vector, query) DESC, published_at DESC
+                LIMIT $2
+                "#,
+            )
+            .bind(query)
+            .bind(limit as i64)
+            .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to search posts")?;
+
+        Ok(posts)
+    }
+
+    async fn get_published_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError> {
+        let slugs = sqlx::query_as::<_, (String, DateTime<Utc>)>(
+            r#"
+            SELECT slug, updated_at
+            FROM posts 
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+            ORDER BY published_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch published post slugs")?;
+
+        Ok(slugs)
+    }
+
+    async fn get_tag_counts(&self, limit: Option<u32>) -> Result<Vec<TagCount>, AppError> {
+        // A NULL limit returns every tag
+        let tags = sqlx::query_as::<_, TagCount>(
+            r#"
+            SELECT tag, COUNT(*) AS count
+            FROM posts, unnest(tags) AS tag
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+            GROUP BY tag
+            ORDER BY count DESC, tag ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit.map(|limit| limit.min(100) as i64))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch tag counts")?;
 
-        let draft_posts: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE published = false")
+        Ok(tags)
+    }
+
+    async fn get_category_counts(&self) -> Result<Vec<CategoryCount>, AppError> {
+        let categories = sqlx::query_as::<_, CategoryCount>(
+            r#"
+            SELECT category, COUNT(*) AS count
+            FROM posts
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+              AND category IS NOT NULL AND btrim(category) <> ''
+            GROUP BY category
+            ORDER BY count DESC, category ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch category counts")?;
+
+        Ok(categories)
+    }
+
+    async fn get_stats(&self) -> Result<PostStats, AppError> {
+        let total_posts: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE deleted_at IS NULL")
                 .fetch_one(&self.pool)
                 .await
-                .context("Failed to count draft posts")?;
+                .context("Failed to count total posts")?;
+
+        let published_posts: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM posts WHERE published = true AND deleted_at IS NULL",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count published posts")?;
+
+        let draft_posts: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM posts WHERE published = false AND deleted_at IS NULL",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count draft posts")?;
 
         let featured_posts: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM posts WHERE featured = true AND published = true",
+            "SELECT COUNT(*) FROM posts WHERE featured = true AND published = true AND deleted_at IS NULL",
         )
         .fetch_one(&self.pool)
         .await
         .context("Failed to count featured posts")?;
 
         let posts_this_month: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM posts WHERE EXTRACT(MONTH FROM created_at) = EXTRACT(MONTH FROM CURRENT_DATE) AND EXTRACT(YEAR FROM created_at) = EXTRACT(YEAR FROM CURRENT_DATE)"
+            "SELECT COUNT(*) FROM posts WHERE deleted_at IS NULL AND EXTRACT(MONTH FROM created_at) = EXTRACT(MONTH FROM CURRENT_DATE) AND EXTRACT(YEAR FROM created_at) = EXTRACT(YEAR FROM CURRENT_DATE)"
         )
         .fetch_one(&self.pool)
         .await
         .context("Failed to count posts this month")?;
 
-        let total_views: i64 = sqlx::query_scalar("SELECT COALESCE(SUM(view_count), 0) FROM posts")
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to sum total views")?;
+        let total_views: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(view_count), 0) FROM posts WHERE deleted_at IS NULL",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to sum total views")?;
 
         Ok(PostStats {
             total_posts,
@@ -399,8 +784,10 @@ impl PostRepositoryTrait for PostRepository {
                     WHEN $1 = false THEN NULL
                     ELSE published_at
                 END,
+                publish_at = NULL,
+                version = version + 1,
                 updated_at = NOW() 
-            WHERE id = $2
+            WHERE id = $2 AND deleted_at IS NULL
             "#,
         )
         .bind(published)
@@ -426,6 +813,78 @@ impl PostRepositoryTrait for PostRepository {
         Ok(())
     }
 
+    async fn record_view(&self, id: Uuid, ip_hash: Option<&str>) -> Result<(), AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // The cumulative counter stays for existing clients; events feed the trend charts
+        sqlx::query("UPDATE posts SET view_count = view_count + 1 WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to increment view count")?;
+
+        sqlx::query("INSERT INTO post_view_events (post_id, ip_hash) VALUES ($1, $2)")
+            .bind(id)
+            .bind(ip_hash)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record post view event")?;
+
+        tx.commit().await.context("Failed to commit post view")?;
+
+        Ok(())
+    }
+
+    async fn view_timeseries(
+        &self,
+        post_id: Uuid,
+        days: i32,
+    ) -> Result<Vec<PostDailyViews>, AppError> {
+        let views = timed(
+            "posts.view_timeseries",
+            sqlx::query_as::<_, PostDailyViews>(
+                r#"
+                SELECT d.day::date AS day, COUNT(e.id) AS views
+                FROM generate_series(
+                    date_trunc('day', NOW()) - make_interval(days => $2 - 1),
+                    date_trunc('day', NOW()),
+                    INTERVAL '1 day'
+                ) AS d(day)
+                LEFT JOIN post_view_events e
+                    ON e.post_id = $1 AND date_trunc('day', e.viewed_at) = d.day
+                GROUP BY d.day
+                ORDER BY d.day
+                "#,
+            )
+            .bind(post_id)
+            .bind(days)
+            .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch post view timeseries")?;
+
+        Ok(views)
+    }
+
+    async fn publish_due_posts(&self) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE posts 
+            SET published = true, published_at = NOW(), publish_at = NULL, version = version + 1, updated_at = NOW() 
+            WHERE publish_at <= NOW() AND published = false AND deleted_at IS NULL
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to publish scheduled posts")?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn check_slug_exists(
         &self,
         slug: &str,
@@ -448,4 +907,482 @@ impl PostRepositoryTrait for PostRepository {
 
         Ok(count > 0)
     }
+
+    async fn import(
+        &self,
+        records: &[PostImportRecord],
+        mode: PostImportMode,
+    ) -> Result<Vec<Result<ImportOutcome, AppError>>, AppError> {
+        // Overwriting also brings back a trashed post with the same slug
+        let on_conflict = match mode {
+            PostImportMode::Skip => "DO NOTHING",
+            PostImportMode::Overwrite => {
+                r#"DO UPDATE SET
+                    title = EXCLUDED.title,
+                    content = EXCLUDED.content,
+                    excerpt = EXCLUDED.excerpt,
+                    category = EXCLUDED.category,
+                    tags = EXCLUDED.tags,
+                    featured_image = EXCLUDED.featured_image,
+                    featured = EXCLUDED.featured,
+                    published = EXCLUDED.published,
+                    seo_title = EXCLUDED.seo_title,
+                    seo_description = EXCLUDED.seo_description,
+                    seo_keywords = EXCLUDED.seo_keywords,
+                    published_at = EXCLUDED.published_at,
+                    publish_at = NULL,
+                    deleted_at = NULL,
+                    version = posts.version + 1,
+                    updated_at = NOW()"#
+            }
+        };
+        let sql = format!(
+            r#"
+            INSERT INTO posts (
+                title, slug, content, excerpt, category, tags, featured_image, featured,
+                published, seo_title, seo_description, seo_keywords, published_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ON CONFLICT (slug) {}
+            RETURNING (xmax = 0)
+            "#,
+            on_conflict
+        );
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // Each post runs in its own savepoint so one bad row doesn't abort the batch
+        let mut outcomes = Vec::with_capacity(records.len());
+        for record in records {
+            let post = &record.post;
+            let mut savepoint = tx.begin().await.context("Failed to create savepoint")?;
+
+            let created = sqlx::query_scalar::<_, bool>(&sql)
+                .bind(&post.title)
+                .bind(&post.slug)
+                .bind(&post.content)
+                .bind(&post.excerpt)
+                .bind(&post.category)
+                .bind(&post.tags)
+                .bind(&post.featured_image)
+                .bind(post.featured.unwrap_or(false))
+                .bind(post.published.unwrap_or(false))
+                .bind(&post.seo_title)
+                .bind(&post.seo_description)
+                .bind(&post.seo_keywords)
+                .bind(record.published_at)
+                .fetch_optional(&mut *savepoint)
+                .await;
+
+            match created {
+                Ok(created) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .context("Failed to release savepoint")?;
+                    outcomes.push(Ok(match created {
+                        Some(true) => ImportOutcome::Created,
+                        Some(false) => ImportOutcome::Updated,
+                        // DO NOTHING returns no row for an existing slug
+                        None => ImportOutcome::Skipped,
+                    }));
+                }
+                Err(e) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .context("Failed to roll back savepoint")?;
+                    outcomes.push(Err(AppError::from_unique_violation(
+                        e,
+                        "Post",
+                        "Failed to import post",
+                    )));
+                }
+            }
+        }
+
+        tx.commit().await.context("Failed to commit post import")?;
+
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn post_request(slug: &str) -> CreatePostRequest {
+        CreatePostRequest {
+            title: "Test post".to_string(),
+            slug: slug.to_string(),
+            content: "Test content".to_string(),
+            excerpt: None,
+            category: "Programming".to_string(),
+            tags: vec![],
+            featured_image: None,
+            featured: None,
+            published: Some(true),
+            seo_title: None,
+            seo_description: None,
+            seo_keywords: None,
+            publish_at: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_publish_due_posts_publishes_past_schedule(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let post = repository
+            .create(CreatePostRequest {
+                published: Some(false),
+                publish_at: Some(Utc::now() - Duration::minutes(5)),
+                ..post_request("scheduled-post")
+            })
+            .await
+            .unwrap();
+        assert!(!post.published);
+
+        let published = repository.publish_due_posts().await.unwrap();
+        assert_eq!(published, 1);
+
+        let post = repository.find_by_id(post.id).await.unwrap().unwrap();
+        assert!(post.published);
+        assert!(post.published_at.is_some());
+        assert!(post.publish_at.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_unpublished_post_is_not_republished_by_the_sweep(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let post = repository
+            .create(CreatePostRequest {
+                published: Some(false),
+                publish_at: Some(Utc::now() - Duration::minutes(5)),
+                ..post_request("scheduled-then-pulled")
+            })
+            .await
+            .unwrap();
+        repository.publish_due_posts().await.unwrap();
+
+        // Taking the post down clears the schedule, so the next sweep leaves it alone
+        repository
+            .update_published_status(post.id, false)
+            .await
+            .unwrap();
+        assert_eq!(repository.publish_due_posts().await.unwrap(), 0);
+
+        let post = repository.find_by_id(post.id).await.unwrap().unwrap();
+        assert!(!post.published);
+        assert!(post.publish_at.is_none());
+    }
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_deleted_post_is_hidden_until_restored(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let post = repository
+            .create(post_request("soft-deleted-post"))
+            .await
+            .unwrap();
+
+        repository.delete(post.id).await.unwrap();
+        assert!(repository.find_by_id(post.id).await.unwrap().is_none());
+        assert!(repository.get_published(None).await.unwrap().is_empty());
+
+        repository.restore(post.id).await.unwrap();
+        assert!(repository.find_by_id(post.id).await.unwrap().is_some());
+        assert_eq!(repository.get_published(None).await.unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_published_pagination_counts_all_published_posts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        for i in 0..3 {
+            repository
+                .create(post_request(&format!("published-{}", i)))
+                .await
+                .unwrap();
+        }
+        repository
+            .create(CreatePostRequest {
+                published: Some(false),
+                ..post_request("draft")
+            })
+            .await
+            .unwrap();
+
+        let first = repository.get_published_paginated(1, 2).await.unwrap();
+        assert_eq!(first.posts.len(), 2);
+        assert_eq!(first.total, 3);
+        assert_eq!(first.total_pages, 2);
+
+        let second = repository.get_published_paginated(2, 2).await.unwrap();
+        assert_eq!(second.posts.len(), 1);
+        assert!(first.posts.iter().all(|post| post.id != second.posts[0].id));
+
+        let result = repository.get_published_paginated(u32::MAX, 100).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_tag_counts_aggregate_published_posts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+        let tagged = |slug: &str, tags: &[&str]| CreatePostRequest {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..post_request(slug)
+        };
+
+        repository
+            .create(tagged("rust-web", &["rust", "web"]))
+            .await
+            .unwrap();
+        repository
+            .create(tagged("rust-cli", &["rust", "cli"]))
+            .await
+            .unwrap();
+        repository
+            .create(tagged("web-design", &["web", "rust"]))
+            .await
+            .unwrap();
+        repository
+            .create(CreatePostRequest {
+                published: Some(false),
+                ..tagged("draft", &["rust", "draft"])
+            })
+            .await
+            .unwrap();
+        let deleted = repository
+            .create(tagged("deleted", &["cli", "deleted"]))
+            .await
+            .unwrap();
+        repository.delete(deleted.id).await.unwrap();
+
+        let counts: Vec<(String, i64)> = repository
+            .get_tag_counts(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| (tag.tag, tag.count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("rust".to_string(), 3),
+                ("web".to_string(), 2),
+                ("cli".to_string(), 1),
+            ]
+        );
+
+        assert_eq!(repository.get_tag_counts(Some(1)).await.unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_category_counts_cover_published_posts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+        let categorized = |slug: &str, category: &str| CreatePostRequest {
+            category: category.to_string(),
+            ..post_request(slug)
+        };
+
+        for (slug, category) in [
+            ("rust-1", "Programming"),
+            ("rust-2", "Programming"),
+            ("travel-1", "Travel"),
+            ("untitled", " "),
+        ] {
+            repository
+                .create(categorized(slug, category))
+                .await
+                .unwrap();
+        }
+        repository
+            .create(CreatePostRequest {
+                published: Some(false),
+                ..categorized("draft", "Travel")
+            })
+            .await
+            .unwrap();
+
+        let counts: Vec<(String, i64)> = repository
+            .get_category_counts()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|category| (category.category, category.count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("Programming".to_string(), 2), ("Travel".to_string(), 1)]
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_bulk_featured_skips_deleted_posts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let kept = repository.create(post_request("kept")).await.unwrap();
+        let deleted = repository.create(post_request("gone")).await.unwrap();
+        repository.delete(deleted.id).await.unwrap();
+
+        let updated = repository
+            .bulk_update_featured(&[kept.id, deleted.id, Uuid::new_v4()], true)
+            .await
+            .unwrap();
+        assert_eq!(updated, 1);
+
+        let kept = repository.find_by_id(kept.id).await.unwrap().unwrap();
+        assert!(kept.featured);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_bulk_delete_reports_only_affected_posts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let first = repository.create(post_request("bulk-1")).await.unwrap();
+        let second = repository.create(post_request("bulk-2")).await.unwrap();
+        let kept = repository.create(post_request("bulk-3")).await.unwrap();
+        repository.delete(second.id).await.unwrap();
+
+        // Already deleted and unknown ids are skipped rather than failing the batch
+        let deleted = repository
+            .bulk_delete(&[first.id, second.id, Uuid::new_v4()])
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, vec![(first.id, first.title)]);
+        assert!(repository.find_by_id(first.id).await.unwrap().is_none());
+        assert!(repository.find_by_id(kept.id).await.unwrap().is_some());
+    }
+
+    fn cursor_query(cursor: Option<String>) -> PostQuery {
+        PostQuery {
+            page: None,
+            limit: Some(2),
+            category: None,
+            search: None,
+            published: None,
+            featured: None,
+            author_id: None,
+            tags: None,
+            cursor,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_cursor_pages_are_stable_across_inserts(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        for slug in ["post-1", "post-2", "post-3", "post-4"] {
+            repository.create(post_request(slug)).await.unwrap();
+        }
+
+        let first = repository.find_all(cursor_query(None)).await.unwrap();
+        let first_slugs: Vec<_> = first.posts.iter().map(|p| p.slug.clone()).collect();
+        assert_eq!(first_slugs, ["post-4", "post-3"]);
+
+        // A new post would shift an offset-based second page back onto "post-3"
+        repository.create(post_request("post-5")).await.unwrap();
+
+        let second = repository
+            .find_all(cursor_query(first.next_cursor))
+            .await
+            .unwrap();
+        let second_slugs: Vec<_> = second.posts.iter().map(|p| p.slug.clone()).collect();
+        assert_eq!(second_slugs, ["post-2", "post-1"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_invalid_cursor_is_a_bad_request(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        let result = repository
+            .find_all(cursor_query(Some("garbage".to_string())))
+            .await;
+
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_search_ranked_puts_title_matches_first(pool: PgPool) {
+        let repository = PostRepository::new(pool);
+
+        repository
+            .create(CreatePostRequest {
+                title: "Notes from the week".to_string(),
+                content: "A long tangent about borrow checker errors".to_string(),
+                ..post_request("body-match")
+            })
+            .await
+            .unwrap();
+        repository
+            .create(CreatePostRequest {
+                title: "Taming the borrow checker".to_string(),
+                ..post_request("title-match")
+            })
+            .await
+            .unwrap();
+        repository.create(post_request("no-match")).await.unwrap();
+
+        let posts = repository
+            .search_ranked("borrow checker", 10)
+            .await
+            .unwrap();
+        let slugs: Vec<_> = posts.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, ["title-match", "body-match"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_view_timeseries_buckets_events_by_day(pool: PgPool) {
+        let repository = PostRepository::new(pool.clone());
+        let post = repository.create(post_request("viewed")).await.unwrap();
+        let other = repository.create(post_request("other")).await.unwrap();
+
+        for (post_id, days_ago) in [
+            (post.id, 0),
+            (post.id, 2),
+            (post.id, 2),
+            (post.id, 10),
+            (other.id, 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO post_view_events (post_id, viewed_at) VALUES ($1, NOW() - make_interval(days => $2))",
+            )
+            .bind(post_id)
+            .bind(days_ago)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let series = repository.view_timeseries(post.id, 7).await.unwrap();
+        let views: Vec<i64> = series.iter().map(|day| day.views).collect();
+        assert_eq!(views, [0, 0, 0, 0, 2, 0, 1]);
+
+        // Recording a view bumps the cumulative counter and adds an event
+        repository.record_view(post.id, Some("hash")).await.unwrap();
+        let post = repository.find_by_id(post.id).await.unwrap().unwrap();
+        assert_eq!(post.view_count, 1);
+        let series = repository.view_timeseries(post.id, 1).await.unwrap();
+        assert_eq!(series[0].views, 2);
+    }
 }
@@ -0,0 +1,398 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::models::user::{SessionClient, UserSession};
+use crate::utils::errors::AppError;
+
+#[async_trait]
+pub trait RefreshTokenRepositoryTrait: Send + Sync {
+    async fn create_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        remember_me: bool,
+        client: &SessionClient,
+    ) -> Result<(), AppError>;
+    async fn rotate_token(
+        &self,
+        token_hash: &str,
+        new_token_hash: &str,
+        lifetimes: RefreshTokenLifetimes,
+        client: &SessionClient,
+    ) -> Result<Option<RotatedRefreshToken>, AppError>;
+    async fn revoke_token(&self, token_hash: &str) -> Result<Option<Uuid>, AppError>;
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<u64, AppError>;
+    async fn list_active_for_user(
+        &self,
+        user_id: Uuid,
+        current_token_hash: Option<&str>,
+    ) -> Result<Vec<UserSession>, AppError>;
+    async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        current_token_hash: Option<&str>,
+    ) -> Result<Option<bool>, AppError>;
+    async fn revoke_others_for_user(
+        &self,
+        user_id: Uuid,
+        current_token_hash: &str,
+    ) -> Result<u64, AppError>;
+}
+
+// Lifetimes in seconds; which one applies depends on whether the session was remembered
+#[derive(Debug, Clone, Copy)]
+pub struct RefreshTokenLifetimes {
+    pub standard: i64,
+    pub remembered: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RotatedRefreshToken {
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct RefreshTokenRepository {
+    pool: PgPool,
+}
+
+impl RefreshTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepositoryTrait for RefreshTokenRepository {
+    async fn create_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        remember_me: bool,
+        client: &SessionClient,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens
+                (user_id, token_hash, expires_at, remember_me, ip_address, user_agent)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .bind(remember_me)
+        .bind(&client.ip_address)
+        .bind(&client.user_agent)
+        .execute(&self.pool)
+        .await
+        .context("Failed to create refresh token")?;
+
+        Ok(())
+    }
+
+    // Swaps an active token for a new one in place, so the session keeps its id and start time
+    // while the presented token stops working. The expiry is extended by the session's lifetime.
+    async fn rotate_token(
+        &self,
+        token_hash: &str,
+        new_token_hash: &str,
+        lifetimes: RefreshTokenLifetimes,
+        client: &SessionClient,
+    ) -> Result<Option<RotatedRefreshToken>, AppError> {
+        let rotated = sqlx::query_as::<_, RotatedRefreshToken>(
+            r#"
+            UPDATE refresh_tokens
+            SET token_hash = $2,
+                expires_at = NOW() + make_interval(
+                    secs => CASE WHEN remember_me THEN $4 ELSE $3 END
+                ),
+                ip_address = COALESCE($5, ip_address),
+                user_agent = COALESCE($6, user_agent),
+                last_used_at = NOW()
+            WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            RETURNING user_id, expires_at
+            "#,
+        )
+        .bind(token_hash)
+        .bind(new_token_hash)
+        .bind(lifetimes.standard as f64)
+        .bind(lifetimes.remembered as f64)
+        .bind(&client.ip_address)
+        .bind(&client.user_agent)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to rotate refresh token")?;
+
+        Ok(rotated)
+    }
+
+    // Revokes an active, unexpired token and returns its user, so a token can only be used once
+    async fn revoke_token(&self, token_hash: &str) -> Result<Option<Uuid>, AppError> {
+        let user_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to revoke refresh token")?;
+
+        Ok(user_id)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to revoke refresh tokens")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_active_for_user(
+        &self,
+        user_id: Uuid,
+        current_token_hash: Option<&str>,
+    ) -> Result<Vec<UserSession>, AppError> {
+        let sessions = sqlx::query_as::<_, UserSession>(
+            r#"
+            SELECT id, ip_address, user_agent, created_at, last_used_at, expires_at, remember_me,
+                   (token_hash = $2) IS TRUE AS current
+            FROM refresh_tokens
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            ORDER BY last_used_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(current_token_hash)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list sessions")?;
+
+        Ok(sessions)
+    }
+
+    // Returns whether the revoked session was the current one, or None if no such session
+    async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        current_token_hash: Option<&str>,
+    ) -> Result<Option<bool>, AppError> {
+        let current = sqlx::query_scalar::<_, bool>(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > NOW()
+            RETURNING (token_hash = $3) IS TRUE
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .bind(current_token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to revoke session")?;
+
+        Ok(current)
+    }
+
+    async fn revoke_others_for_user(
+        &self,
+        user_id: Uuid,
+        current_token_hash: &str,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND token_hash <> $2 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(current_token_hash)
+        .execute(&self.pool)
+        .await
+        .context("Failed to revoke other sessions")?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::test_support::insert_user;
+    use chrono::Duration;
+
+    const LIFETIMES: RefreshTokenLifetimes = RefreshTokenLifetimes {
+        standard: 7 * 24 * 60 * 60,
+        remembered: 30 * 24 * 60 * 60,
+    };
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_rotated_token_cannot_be_reused(pool: PgPool) {
+        let user_id = insert_user(&pool, "refresh_user").await;
+        let repository = RefreshTokenRepository::new(pool);
+        let expires_at = Utc::now() + Duration::days(7);
+
+        repository
+            .create_token(
+                user_id,
+                "first",
+                expires_at,
+                false,
+                &SessionClient::default(),
+            )
+            .await
+            .unwrap();
+
+        // First use rotates the token
+        assert_eq!(
+            repository.revoke_token("first").await.unwrap(),
+            Some(user_id)
+        );
+        repository
+            .create_token(
+                user_id,
+                "second",
+                expires_at,
+                false,
+                &SessionClient::default(),
+            )
+            .await
+            .unwrap();
+
+        // Replaying the rotated token fails, the new one still works
+        assert_eq!(repository.revoke_token("first").await.unwrap(), None);
+        assert_eq!(
+            repository.revoke_token("second").await.unwrap(),
+            Some(user_id)
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_revoke_all_for_user(pool: PgPool) {
+        let user_id = insert_user(&pool, "refresh_user").await;
+        let repository = RefreshTokenRepository::new(pool);
+        let expires_at = Utc::now() + Duration::days(7);
+
+        for hash in ["a", "b"] {
+            repository
+                .create_token(user_id, hash, expires_at, false, &SessionClient::default())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(repository.revoke_all_for_user(user_id).await.unwrap(), 2);
+        assert_eq!(repository.revoke_token("a").await.unwrap(), None);
+        assert_eq!(repository.revoke_token("b").await.unwrap(), None);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_rotation_keeps_session(pool: PgPool) {
+        let user_id = insert_user(&pool, "refresh_user").await;
+        let repository = RefreshTokenRepository::new(pool);
+        let expires_at = Utc::now() + Duration::days(7);
+        let client = SessionClient {
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some("Firefox".to_string()),
+        };
+
+        repository
+            .create_token(user_id, "first", expires_at, true, &client)
+            .await
+            .unwrap();
+        let before = repository
+            .list_active_for_user(user_id, None)
+            .await
+            .unwrap();
+
+        // A remembered session is extended by the longer lifetime
+        let rotated = repository
+            .rotate_token("first", "second", LIFETIMES, &SessionClient::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rotated.user_id, user_id);
+        assert!(rotated.expires_at > Utc::now() + Duration::days(29));
+        assert!(repository
+            .rotate_token("first", "third", LIFETIMES, &SessionClient::default())
+            .await
+            .unwrap()
+            .is_none());
+
+        let after = repository
+            .list_active_for_user(user_id, Some("second"))
+            .await
+            .unwrap();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].id, before[0].id);
+        assert_eq!(after[0].user_agent.as_deref(), Some("Firefox"));
+        assert!(after[0].remember_me);
+        assert!(after[0].current);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_revoke_sessions(pool: PgPool) {
+        let user_id = insert_user(&pool, "refresh_user").await;
+        let repository = RefreshTokenRepository::new(pool);
+        let expires_at = Utc::now() + Duration::days(7);
+
+        for hash in ["current", "laptop", "phone"] {
+            repository
+                .create_token(user_id, hash, expires_at, false, &SessionClient::default())
+                .await
+                .unwrap();
+        }
+
+        let sessions = repository
+            .list_active_for_user(user_id, Some("current"))
+            .await
+            .unwrap();
+        let other = sessions.iter().find(|session| !session.current).unwrap();
+
+        assert_eq!(
+            repository
+                .revoke_session(user_id, other.id, Some("current"))
+                .await
+                .unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            repository
+                .revoke_session(user_id, other.id, Some("current"))
+                .await
+                .unwrap(),
+            None
+        );
+
+        assert_eq!(
+            repository
+                .revoke_others_for_user(user_id, "current")
+                .await
+                .unwrap(),
+            1
+        );
+        let remaining = repository
+            .list_active_for_user(user_id, Some("current"))
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].current);
+    }
+}
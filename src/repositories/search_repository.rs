@@ -0,0 +1,198 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::database::query_timing::timed;
+use crate::models::search::{SearchResult, SearchResultKind};
+use crate::utils::errors::AppError;
+use crate::utils::text::contains_pattern;
+
+type SearchRow = (Uuid, String, Option<String>, Option<String>, DateTime<Utc>);
+
+#[async_trait]
+pub trait SearchRepositoryTrait: Send + Sync {
+    async fn search_posts(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError>;
+    async fn search_projects(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError>;
+    async fn search_services(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError>;
+}
+
+pub struct SearchRepository {
+    pool: PgPool,
+}
+
+impl SearchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn search(
+        &self,
+        name: &str,
+        sql: &str,
+        kind: SearchResultKind,
+        term: &str,
+        limit: u32,
+    ) -> Result<Vec<SearchResult>, AppError> {
+        let rows = timed(
+            name,
+            sqlx::query_as::<_, SearchRow>(sql)
+                .bind(contains_pattern(term))
+                .bind(limit as i64)
+                .fetch_all(&self.pool),
+        )
+        .await
+        .with_context(|| format!("Failed to run {}", name))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, title, slug, summary, updated_at)| SearchResult {
+                kind,
+                id,
+                title,
+                slug,
+                summary,
+                updated_at,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for SearchRepository {
+    async fn search_posts(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError> {
+        // Title matches rank ahead of body-only matches
+        self.search(
+            "search.posts",
+            r#"
+            SELECT id, title, slug, excerpt, updated_at
+            FROM posts
+            WHERE published = true AND deleted_at IS NULL
+              AND (publish_at IS NULL OR publish_at <= NOW())
+              AND (title ILIKE $1 ESCAPE '\' OR excerpt ILIKE $1 ESCAPE '\' OR content ILIKE $1 ESCAPE '\')
+            ORDER BY (title ILIKE $1 ESCAPE '\') DESC, published_at DESC
+            LIMIT $2
+            "#,
+            SearchResultKind::Post,
+            term,
+            limit,
+        )
+        .await
+    }
+
+    async fn search_projects(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError> {
+        self.search(
+            "search.projects",
+            r#"
+            SELECT id, title, slug, description, updated_at
+            FROM portfolio_projects
+            WHERE active = true
+              AND (title ILIKE $1 ESCAPE '\' OR description ILIKE $1 ESCAPE '\' OR long_description ILIKE $1 ESCAPE '\')
+            ORDER BY (title ILIKE $1 ESCAPE '\') DESC, sort_order ASC, created_at DESC
+            LIMIT $2
+            "#,
+            SearchResultKind::Portfolio,
+            term,
+            limit,
+        )
+        .await
+    }
+
+    async fn search_services(&self, term: &str, limit: u32) -> Result<Vec<SearchResult>, AppError> {
+        self.search(
+            "search.services",
+            r#"
+            SELECT id, title, NULL::TEXT, description, updated_at
+            FROM services
+            WHERE active = true
+              AND (title ILIKE $1 ESCAPE '\' OR description ILIKE $1 ESCAPE '\')
+            ORDER BY (title ILIKE $1 ESCAPE '\') DESC, display_order ASC
+            LIMIT $2
+            "#,
+            SearchResultKind::Service,
+            term,
+            limit,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{post::CreatePostRequest, service::CreateServiceRequest};
+    use crate::repositories::{
+        PostRepository, PostRepositoryTrait, ServiceRepository, ServiceRepositoryTrait,
+    };
+
+    fn post_request(title: &str, published: bool) -> CreatePostRequest {
+        CreatePostRequest {
+            title: title.to_string(),
+            slug: title.to_lowercase().replace(' ', "-"),
+            content: "Test content".to_string(),
+            excerpt: None,
+            category: "Programming".to_string(),
+            tags: vec![],
+            featured_image: None,
+            featured: None,
+            published: Some(published),
+            seo_title: None,
+            seo_description: None,
+            seo_keywords: None,
+            publish_at: None,
+        }
+    }
+
+    fn service_request(title: &str, description: &str) -> CreateServiceRequest {
+        CreateServiceRequest {
+            title: title.to_string(),
+            description: description.to_string(),
+            features: vec![],
+            category: "Consulting".to_string(),
+            active: Some(true),
+            display_order: None,
+            price_amount: None,
+            price_currency: None,
+            price_type: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_term_matches_posts_and_services(pool: PgPool) {
+        let posts = PostRepository::new(pool.clone());
+        let services = ServiceRepository::new(pool.clone());
+        let repository = SearchRepository::new(pool);
+
+        posts
+            .create(post_request("Profiling Rust services", true))
+            .await
+            .unwrap();
+        posts
+            .create(post_request("Draft about rust", false))
+            .await
+            .unwrap();
+        services
+            .create(service_request(
+                "Performance audit",
+                "Profiling and tuning of RUST backends",
+            ))
+            .await
+            .unwrap();
+        services
+            .create(service_request("Design", "Landing pages"))
+            .await
+            .unwrap();
+
+        let posts = repository.search_posts("rust", 5).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].kind, SearchResultKind::Post);
+        assert_eq!(posts[0].slug.as_deref(), Some("profiling-rust-services"));
+
+        let services = repository.search_services("rust", 5).await.unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].kind, SearchResultKind::Service);
+        assert_eq!(services[0].title, "Performance audit");
+    }
+}
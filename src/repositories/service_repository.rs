@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use crate::database::query_timing::timed;
 use crate::models::service::{
-    CategoryCount, CreateServiceRequest, Service, ServiceQuery, ServiceStats, ServicesResponse,
-    UpdateServiceRequest,
+    CategoryCount, CreateServiceRequest, Service, ServiceQuery, ServiceSort, ServiceStats,
+    ServicesResponse, UpdateServiceRequest,
 };
 use crate::utils::errors::AppError;
 
@@ -20,6 +21,7 @@ pub trait ServiceRepositoryTrait: Send + Sync {
     async fn get_stats(&self) -> Result<ServiceStats, AppError>;
     async fn update_active_status(&self, id: Uuid, active: bool) -> Result<(), AppError>;
     async fn get_by_category(&self, category: &str) -> Result<Vec<Service>, AppError>;
+    async fn reorder(&self, orders: Vec<(Uuid, i32)>) -> Result<(), AppError>;
 }
 
 pub struct ServiceRepository {
@@ -30,6 +32,19 @@ impl ServiceRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Append the WHERE clause shared by the list and count queries
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ServiceQuery) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(category) = &query.category {
+            builder.push(" AND category = ").push_bind(category.clone());
+        }
+
+        if let Some(active) = query.active {
+            builder.push(" AND active = ").push_bind(active);
+        }
+    }
 }
 
 #[async_trait]
@@ -37,7 +52,8 @@ impl ServiceRepositoryTrait for ServiceRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Service>, AppError> {
         let service = sqlx::query_as::<_, Service>(
             r#"
-            SELECT id, title, description, features, category, active, created_at, updated_at
+            SELECT id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
             FROM services 
             WHERE id = $1
             "#,
@@ -54,49 +70,42 @@ impl ServiceRepositoryTrait for ServiceRepository {
         let limit = query.limit.unwrap_or(10).min(100);
         let offset = (query.page.unwrap_or(1) - 1) * limit;
 
-        let mut where_conditions = Vec::new();
-        let mut bind_count = 0;
-
-        // Build dynamic WHERE clause
-        if query.category.is_some() {
-            bind_count += 1;
-            where_conditions.push(format!("category = ${}", bind_count));
-        }
-
-        if query.active.is_some() {
-            bind_count += 1;
-            where_conditions.push(format!("active = ${}", bind_count));
-        }
-
-        let _where_clause = if where_conditions.is_empty() {
-            String::new()
-        } else {
-            format!("WHERE {}", where_conditions.join(" AND "))
-        };
-
-        // For simplicity, let's create a basic query without dynamic binding
-        let base_query = "SELECT COUNT(*) FROM services".to_string();
-        let services_query = r#"
-            SELECT id, title, description, features, category, active, created_at, updated_at
-            FROM services 
-            ORDER BY created_at DESC 
-            LIMIT $1 OFFSET $2
-        "#
-        .to_string();
+        // Get total count using the same filters as the data query
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM services");
+        Self::push_filters(&mut count_builder, &query);
 
-        // Get total count
-        let total: i64 = sqlx::query_scalar(&base_query)
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count services")?;
+        let total: i64 = timed(
+            "services.find_all.count",
+            count_builder.build_query_scalar().fetch_one(&self.pool),
+        )
+        .await
+        .context("Failed to count services")?;
 
         // Get services
-        let services = sqlx::query_as::<_, Service>(&services_query)
-            .bind(limit as i64)
-            .bind(offset as i64)
-            .fetch_all(&self.pool)
-            .await
-            .context("Failed to fetch services")?;
+        let mut services_builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
+            FROM services
+            "#,
+        );
+        Self::push_filters(&mut services_builder, &query);
+        services_builder
+            .push(" ORDER BY ")
+            .push(query.sort.unwrap_or(ServiceSort::CreatedAt).order_by())
+            .push(" LIMIT ")
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let services = timed(
+            "services.find_all",
+            services_builder
+                .build_query_as::<Service>()
+                .fetch_all(&self.pool),
+        )
+        .await
+        .context("Failed to fetch services")?;
 
         let total_pages = (total as f64 / limit as f64).ceil() as u32;
 
@@ -113,10 +122,16 @@ impl ServiceRepositoryTrait for ServiceRepository {
         let created_service = sqlx::query_as::<_, Service>(
             r#"
             INSERT INTO services (
-                title, description, features, category, active
+                title, description, features, category, active, display_order,
+                price_amount, price_currency, price_type
             )
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, title, description, features, category, active, created_at, updated_at
+            VALUES (
+                $1, $2, $3, $4, $5,
+                COALESCE($6, (SELECT COALESCE(MAX(display_order), 0) + 1 FROM services)),
+                $7, $8, $9
+            )
+            RETURNING id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
             "#,
         )
         .bind(&service.title)
@@ -124,6 +139,10 @@ impl ServiceRepositoryTrait for ServiceRepository {
         .bind(&service.features)
         .bind(&service.category)
         .bind(service.active.unwrap_or(true))
+        .bind(service.display_order)
+        .bind(service.price_amount)
+        .bind(&service.price_currency)
+        .bind(&service.price_type)
         .fetch_one(&self.pool)
         .await
         .context("Failed to create service")?;
@@ -135,21 +154,43 @@ impl ServiceRepositoryTrait for ServiceRepository {
         let updated_service = sqlx::query_as::<_, Service>(
             r#"
             UPDATE services 
-            SET title = $1, description = $2, features = $3, category = $4, active = $5, updated_at = NOW()
-            WHERE id = $6
-            RETURNING id, title, description, features, category, active, created_at, updated_at
+            SET title = $1, description = $2, features = $3, category = $4,
+                active = COALESCE($5, active), display_order = COALESCE($6, display_order),
+                price_amount = CASE WHEN $12 THEN NULL ELSE COALESCE($7, price_amount) END,
+                price_currency = CASE WHEN $12 THEN NULL ELSE COALESCE($8, price_currency) END,
+                price_type = CASE WHEN $12 THEN NULL ELSE COALESCE($9, price_type) END,
+                version = version + 1,
+                updated_at = NOW()
+            WHERE id = $10 AND version = $11
+            RETURNING id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
             "#,
         )
         .bind(&service.title)
         .bind(&service.description)
         .bind(&service.features)
         .bind(&service.category)
-        .bind(service.active.unwrap_or(true))
+        .bind(service.active)
+        .bind(service.display_order)
+        .bind(service.price_amount)
+        .bind(&service.price_currency)
+        .bind(&service.price_type)
         .bind(id)
+        .bind(service.version)
+        .bind(service.clear_price)
         .fetch_optional(&self.pool)
         .await
-        .context("Failed to update service")?
-        .ok_or(AppError::NotFound("Service not found".to_string()))?;
+        .context("Failed to update service")?;
+
+        // No row means the service is gone or someone else saved a newer version first
+        let Some(updated_service) = updated_service else {
+            return match self.find_by_id(id).await? {
+                Some(_) => Err(AppError::Conflict(
+                    "Service was modified by someone else; reload it and try again".to_string(),
+                )),
+                None => Err(AppError::NotFound("Service not found".to_string())),
+            };
+        };
 
         Ok(updated_service)
     }
@@ -171,10 +212,11 @@ impl ServiceRepositoryTrait for ServiceRepository {
     async fn get_active(&self) -> Result<Vec<Service>, AppError> {
         let services = sqlx::query_as::<_, Service>(
             r#"
-            SELECT id, title, description, features, category, active, created_at, updated_at
+            SELECT id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
             FROM services 
             WHERE active = true 
-            ORDER BY created_at DESC
+            ORDER BY display_order ASC, created_at DESC
             "#,
         )
         .fetch_all(&self.pool)
@@ -219,7 +261,7 @@ impl ServiceRepositoryTrait for ServiceRepository {
 
     async fn update_active_status(&self, id: Uuid, active: bool) -> Result<(), AppError> {
         let result =
-            sqlx::query("UPDATE services SET active = $1, updated_at = NOW() WHERE id = $2")
+            sqlx::query("UPDATE services SET active = $1, version = version + 1, updated_at = NOW() WHERE id = $2")
                 .bind(active)
                 .bind(id)
                 .execute(&self.pool)
@@ -236,7 +278,8 @@ impl ServiceRepositoryTrait for ServiceRepository {
     async fn get_by_category(&self, category: &str) -> Result<Vec<Service>, AppError> {
         let services = sqlx::query_as::<_, Service>(
             r#"
-            SELECT id, title, description, features, category, active, created_at, updated_at
+            SELECT id, title, description, features, category, active, display_order,
+                   price_amount, price_currency, price_type, version, created_at, updated_at
             FROM services 
             WHERE category = $1 AND active = true
             ORDER BY created_at DESC
@@ -249,4 +292,308 @@ impl ServiceRepositoryTrait for ServiceRepository {
 
         Ok(services)
     }
+
+    async fn reorder(&self, orders: Vec<(Uuid, i32)>) -> Result<(), AppError> {
+        let ids: Vec<Uuid> = orders.iter().map(|(id, _)| *id).collect();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // Every ID must exist, otherwise nothing is reordered
+        let existing: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM services WHERE id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to check services")?;
+
+        if let Some(missing) = ids.iter().find(|id| !existing.contains(id)) {
+            return Err(AppError::NotFound(format!("Service {} not found", missing)));
+        }
+
+        for (id, display_order) in orders {
+            sqlx::query("UPDATE services SET display_order = $1, version = version + 1, updated_at = NOW() WHERE id = $2")
+                .bind(display_order)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to update service display order")?;
+        }
+
+        tx.commit().await.context("Failed to commit reorder")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service_request(title: &str, category: &str, active: bool) -> CreateServiceRequest {
+        CreateServiceRequest {
+            title: title.to_string(),
+            description: "Test description".to_string(),
+            features: vec![],
+            category: category.to_string(),
+            active: Some(active),
+            display_order: None,
+            price_amount: None,
+            price_currency: None,
+            price_type: None,
+        }
+    }
+
+    fn query(
+        category: Option<&str>,
+        active: Option<bool>,
+        sort: Option<ServiceSort>,
+    ) -> ServiceQuery {
+        ServiceQuery {
+            page: Some(1),
+            limit: Some(10),
+            category: category.map(str::to_string),
+            active,
+            sort,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_find_all_applies_filters_to_total(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+
+        for request in [
+            service_request("Web app", "Web Development", true),
+            service_request("Landing page", "Web Development", true),
+            service_request("Legacy site", "Web Development", false),
+            service_request("Mobile app", "Mobile Development", true),
+        ] {
+            repository.create(request).await.unwrap();
+        }
+
+        let response = repository
+            .find_all(query(Some("Web Development"), Some(true), None))
+            .await
+            .unwrap();
+        assert_eq!(response.total, 2);
+        assert_eq!(response.services.len(), 2);
+        assert_eq!(response.total_pages, 1);
+
+        let response = repository
+            .find_all(query(None, Some(false), None))
+            .await
+            .unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.services[0].title, "Legacy site");
+
+        let response = repository.find_all(query(None, None, None)).await.unwrap();
+        assert_eq!(response.total, 4);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_find_all_sorts_by_title(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+
+        for title in ["Consulting", "API design", "Web app"] {
+            repository
+                .create(service_request(title, "Web Development", true))
+                .await
+                .unwrap();
+        }
+
+        let response = repository
+            .find_all(query(
+                Some("Web Development"),
+                Some(true),
+                Some(ServiceSort::Title),
+            ))
+            .await
+            .unwrap();
+        let titles: Vec<_> = response.services.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["API design", "Consulting", "Web app"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_reorder_changes_active_order(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+
+        let first = repository
+            .create(service_request("First", "Web Development", true))
+            .await
+            .unwrap();
+        let second = repository
+            .create(service_request("Second", "Web Development", true))
+            .await
+            .unwrap();
+        assert_eq!(second.display_order, first.display_order + 1);
+
+        repository
+            .reorder(vec![(first.id, 2), (second.id, 1)])
+            .await
+            .unwrap();
+
+        let titles: Vec<_> = repository
+            .get_active()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| s.title)
+            .collect();
+        assert_eq!(titles, ["Second", "First"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_reorder_with_unknown_id_changes_nothing(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+
+        let service = repository
+            .create(service_request("Only", "Web Development", true))
+            .await
+            .unwrap();
+
+        let result = repository
+            .reorder(vec![(service.id, 10), (Uuid::new_v4(), 11)])
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let service = repository.find_by_id(service.id).await.unwrap().unwrap();
+        assert_eq!(service.display_order, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_create_defaults_to_active(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+        let service = repository
+            .create(CreateServiceRequest {
+                active: None,
+                ..service_request("Unspecified", "Web Development", false)
+            })
+            .await
+            .unwrap();
+
+        assert!(service.active);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_update_keeps_omitted_price_and_status(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+        let service = repository
+            .create(CreateServiceRequest {
+                price_amount: Some(150000),
+                price_currency: Some("USD".to_string()),
+                price_type: Some("fixed".to_string()),
+                ..service_request("Priced", "Web Development", false)
+            })
+            .await
+            .unwrap();
+
+        let updated = repository
+            .update(
+                service.id,
+                UpdateServiceRequest {
+                    title: "Renamed".to_string(),
+                    description: service.description.clone(),
+                    features: vec![],
+                    category: service.category.clone(),
+                    active: None,
+                    display_order: None,
+                    price_amount: None,
+                    price_currency: None,
+                    price_type: None,
+                    clear_price: false,
+                    version: service.version,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.title, "Renamed");
+        assert!(!updated.active);
+        assert_eq!(updated.price_amount, Some(150000));
+        assert_eq!(updated.price_currency.as_deref(), Some("USD"));
+        assert_eq!(updated.price_type.as_deref(), Some("fixed"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_stale_update_is_rejected(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+        let service = repository
+            .create(service_request("Versioned", "Web Development", true))
+            .await
+            .unwrap();
+
+        let rename = |title: &str| UpdateServiceRequest {
+            title: title.to_string(),
+            description: service.description.clone(),
+            features: vec![],
+            category: service.category.clone(),
+            active: None,
+            display_order: None,
+            price_amount: None,
+            price_currency: None,
+            price_type: None,
+            clear_price: false,
+            version: service.version,
+        };
+
+        repository
+            .update(service.id, rename("First edit"))
+            .await
+            .unwrap();
+        let result = repository.update(service.id, rename("Second edit")).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        let service = repository.find_by_id(service.id).await.unwrap().unwrap();
+        assert_eq!(service.title, "First edit");
+        assert_eq!(service.version, 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_update_can_clear_price(pool: PgPool) {
+        let repository = ServiceRepository::new(pool);
+        let service = repository
+            .create(CreateServiceRequest {
+                price_amount: Some(150000),
+                price_currency: Some("USD".to_string()),
+                price_type: Some("fixed".to_string()),
+                ..service_request("Priced", "Web Development", true)
+            })
+            .await
+            .unwrap();
+        assert_eq!(service.price_amount, Some(150000));
+
+        let updated = repository
+            .update(
+                service.id,
+                UpdateServiceRequest {
+                    title: service.title.clone(),
+                    description: service.description.clone(),
+                    features: vec![],
+                    category: service.category.clone(),
+                    active: None,
+                    display_order: None,
+                    price_amount: None,
+                    price_currency: None,
+                    price_type: None,
+                    clear_price: true,
+                    version: service.version,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.price_amount, None);
+        assert_eq!(updated.price_currency, None);
+        assert_eq!(updated.price_type, None);
+    }
 }
@@ -0,0 +1,14 @@
+// Fixtures shared by the repository tests
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub(crate) async fn insert_user(pool: &PgPool, username: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO users (username, email, password_hash) VALUES ($1, $2, 'hash') RETURNING id",
+    )
+    .bind(username)
+    .bind(format!("{}@example.com", username))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
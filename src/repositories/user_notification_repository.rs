@@ -1,10 +1,12 @@
+use crate::database::query_timing::timed;
 use crate::models::user_notification::{
-    NotificationStats, NotificationWithReadStatus, UpdateNotificationPreferenceRequest,
-    UserNotificationPreference, UserNotificationRead,
+    NotificationEmailRecipient, NotificationFilter, NotificationStats, NotificationTypeCount,
+    NotificationWithReadStatus, UpdateNotificationPreferenceRequest, UserNotificationPreference,
+    UserNotificationRead, NOTIFICATION_TYPES,
 };
 use anyhow::Result;
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub struct UserNotificationRepository {
@@ -16,6 +18,35 @@ impl UserNotificationRepository {
         Self { pool }
     }
 
+    // Append the join and WHERE clause shared by the list and count queries
+    fn push_filters(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        user_id: Uuid,
+        filter: &NotificationFilter,
+    ) {
+        builder
+            .push(
+                " FROM audit_logs al LEFT JOIN user_notification_reads unr \
+                 ON al.id = unr.audit_log_id AND unr.user_id = ",
+            )
+            .push_bind(user_id)
+            .push(" WHERE 1 = 1");
+
+        if filter.unread_only {
+            builder.push(" AND unr.id IS NULL");
+        }
+
+        if let Some(action) = &filter.action {
+            builder.push(" AND al.action = ").push_bind(action.clone());
+        }
+
+        if let Some(resource_type) = &filter.resource_type {
+            builder
+                .push(" AND al.resource_type = ")
+                .push_bind(resource_type.clone());
+        }
+    }
+
     // Mark a single notification as read
     pub async fn mark_notification_read(
         &self,
@@ -100,13 +131,14 @@ impl UserNotificationRepository {
         user_id: Uuid,
         limit: Option<i64>,
         offset: Option<i64>,
+        filter: &NotificationFilter,
     ) -> Result<Vec<NotificationWithReadStatus>> {
         let limit = limit.unwrap_or(50);
         let offset = offset.unwrap_or(0);
 
-        let records = sqlx::query!(
+        let mut builder = QueryBuilder::new(
             r#"
-            SELECT 
+            SELECT
                 al.id,
                 al.user_id,
                 al.user_name,
@@ -118,46 +150,68 @@ impl UserNotificationRepository {
                 al.success,
                 al.error_message,
                 al.created_at,
-                CASE WHEN unr.id IS NOT NULL THEN true ELSE false END as read,
-                unr.read_at as "read_at?"
+                unr.id IS NOT NULL AS read,
+                unr.read_at
+            "#,
+        );
+        Self::push_filters(&mut builder, user_id, filter);
+        builder
+            .push(" ORDER BY al.created_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let notifications = builder
+            .build_query_as::<NotificationWithReadStatus>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(notifications)
+    }
+
+    // Count the notifications a filtered listing pages through
+    pub async fn count_notifications(
+        &self,
+        user_id: Uuid,
+        filter: &NotificationFilter,
+    ) -> Result<i64> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*)");
+        Self::push_filters(&mut builder, user_id, filter);
+
+        let count: i64 = builder.build_query_scalar().fetch_one(&self.pool).await?;
+
+        Ok(count)
+    }
+
+    // Unread notifications per type (audit log action), most frequent first
+    pub async fn get_unread_counts_by_type(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<NotificationTypeCount>> {
+        let counts = sqlx::query_as::<_, NotificationTypeCount>(
+            r#"
+            SELECT al.action AS notification_type, COUNT(*) AS unread
             FROM audit_logs al
             LEFT JOIN user_notification_reads unr ON al.id = unr.audit_log_id AND unr.user_id = $1
-            ORDER BY al.created_at DESC
-            LIMIT $2 OFFSET $3
+            WHERE unr.id IS NULL AND al.action = ANY($2)
+            GROUP BY al.action
+            ORDER BY unread DESC, al.action
             "#,
-            user_id,
-            limit,
-            offset
         )
+        .bind(user_id)
+        .bind(&NOTIFICATION_TYPES[..])
         .fetch_all(&self.pool)
         .await?;
 
-        let notifications = records
-            .into_iter()
-            .map(|record| NotificationWithReadStatus {
-                id: record.id,
-                user_id: record.user_id,
-                user_name: record.user_name,
-                action: record.action,
-                resource_type: record.resource_type,
-                resource_id: record.resource_id,
-                resource_title: record.resource_title,
-                details: record.details,
-                success: record.success,
-                error_message: record.error_message,
-                created_at: record.created_at,
-                read: record.read.unwrap_or(false),
-                read_at: record.read_at,
-            })
-            .collect();
-
-        Ok(notifications)
+        Ok(counts)
     }
 
     // Get notification statistics for a user
     pub async fn get_notification_stats(&self, user_id: Uuid) -> Result<NotificationStats> {
-        let stats = sqlx::query!(
-            r#"
+        let stats = timed(
+            "user_notifications.get_notification_stats",
+            sqlx::query!(
+                r#"
             SELECT 
                 COUNT(al.id) as total_notifications,
                 COUNT(al.id) - COUNT(unr.id) as unread_notifications,
@@ -167,9 +221,10 @@ impl UserNotificationRepository {
             FROM audit_logs al
             LEFT JOIN user_notification_reads unr ON al.id = unr.audit_log_id AND unr.user_id = $1
             "#,
-            user_id
+                user_id
+            )
+            .fetch_one(&self.pool),
         )
-        .fetch_one(&self.pool)
         .await?;
 
         Ok(NotificationStats {
@@ -309,6 +364,47 @@ impl UserNotificationRepository {
         Ok(count.unwrap_or(0))
     }
 
+    // Active users with an enabled email (or both) preference for this notification type
+    pub async fn get_email_recipients(
+        &self,
+        notification_type: &str,
+    ) -> Result<Vec<NotificationEmailRecipient>> {
+        let recipients = sqlx::query_as::<_, NotificationEmailRecipient>(
+            r#"
+            SELECT u.id AS user_id, u.username, u.email
+            FROM user_notification_preferences unp
+            JOIN users u ON u.id = unp.user_id
+            WHERE unp.notification_type = $1
+              AND unp.enabled = true
+              AND unp.delivery_method IN ('email', 'both')
+              AND u.is_active = true
+            "#,
+        )
+        .bind(notification_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    // Active users who opted into the daily digest
+    pub async fn get_digest_recipients(&self) -> Result<Vec<NotificationEmailRecipient>> {
+        let recipients = sqlx::query_as::<_, NotificationEmailRecipient>(
+            r#"
+            SELECT u.id AS user_id, u.username, u.email
+            FROM user_notification_preferences unp
+            JOIN users u ON u.id = unp.user_id
+            WHERE unp.notification_type = 'digest'
+              AND unp.enabled = true
+              AND u.is_active = true
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recipients)
+    }
+
     // Clean up old read notifications (older than 30 days)
     pub async fn cleanup_old_read_notifications(&self) -> Result<i64> {
         let result = sqlx::query!(
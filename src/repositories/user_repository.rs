@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
-use crate::models::user::{CreateUserRequest, UpdateProfileRequest, User};
+use crate::models::user::{
+    CreateUserRequest, UpdateProfileRequest, User, UserQuery, UserResponse, UsersResponse,
+    ADMIN_ROLE,
+};
 use crate::utils::errors::AppError;
+use crate::utils::text::contains_pattern;
 
 #[async_trait]
 pub trait UserRepositoryTrait: Send + Sync {
@@ -23,6 +27,7 @@ pub trait UserRepositoryTrait: Send + Sync {
     ) -> Result<User, AppError>;
     async fn update_password(&self, id: Uuid, password_hash: String) -> Result<(), AppError>;
     async fn update_last_login(&self, id: Uuid) -> Result<(), AppError>;
+    async fn touch_activity(&self, id: Uuid, timeout_minutes: i32) -> Result<bool, AppError>;
     async fn check_username_exists(
         &self,
         username: &str,
@@ -33,6 +38,12 @@ pub trait UserRepositoryTrait: Send + Sync {
         email: &str,
         exclude_id: Option<Uuid>,
     ) -> Result<bool, AppError>;
+    // Admin user management sees deactivated accounts as well
+    async fn find_all(&self, query: UserQuery) -> Result<UsersResponse, AppError>;
+    async fn find_by_id_including_inactive(&self, id: Uuid) -> Result<Option<User>, AppError>;
+    // Both refuse to deactivate or demote the last active admin
+    async fn set_active(&self, id: Uuid, is_active: bool) -> Result<User, AppError>;
+    async fn update_role(&self, id: Uuid, role: &str) -> Result<User, AppError>;
 }
 
 pub struct UserRepository {
@@ -43,6 +54,59 @@ impl UserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Locks every active admin row, so concurrent deactivations and demotions queue up
+    // behind this check instead of each seeing the other admin still in place
+    async fn ensure_not_last_admin(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+    ) -> Result<(), AppError> {
+        let admins: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM users WHERE role = $1 AND is_active = true FOR UPDATE",
+        )
+        .bind(ADMIN_ROLE)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to lock active admins")?;
+
+        if admins.contains(&id) && admins.len() <= 1 {
+            return Err(AppError::Conflict(
+                "Cannot deactivate or demote the last active admin".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Append the WHERE clause shared by the list and count queries
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &UserQuery) {
+        builder.push(" WHERE TRUE");
+
+        if let Some(role) = &query.role {
+            builder.push(" AND role = ").push_bind(role.clone());
+        }
+
+        if let Some(is_active) = query.is_active {
+            builder.push(" AND is_active = ").push_bind(is_active);
+        }
+
+        if let Some(search) = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let pattern = contains_pattern(search);
+            builder
+                .push(" AND (username ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR email ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR full_name ILIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+    }
 }
 
 #[async_trait]
@@ -118,7 +182,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(&user.phone)
         .fetch_one(&self.pool)
         .await
-        .context("Failed to create user")?;
+        .map_err(|e| AppError::from_unique_violation(e, "User", "Failed to create user"))?;
 
         Ok(created_user)
     }
@@ -144,7 +208,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .context("Failed to update user profile")?
+        .map_err(|e| AppError::from_unique_violation(e, "User", "Failed to update user profile"))?
         .ok_or(AppError::NotFound("User not found".to_string()))?;
 
         Ok(updated_user)
@@ -177,6 +241,26 @@ impl UserRepositoryTrait for UserRepository {
         Ok(())
     }
 
+    // Records activity only while the session is still live; returns false once the
+    // user has been idle (no login or request) for longer than the timeout, or deactivated
+    async fn touch_activity(&self, id: Uuid, timeout_minutes: i32) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET last_activity_at = NOW()
+            WHERE id = $1 AND is_active = true
+              AND GREATEST(last_activity_at, last_login) > NOW() - make_interval(mins => $2)
+            "#,
+        )
+        .bind(id)
+        .bind(timeout_minutes)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update user activity")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn check_username_exists(
         &self,
         username: &str,
@@ -226,4 +310,188 @@ impl UserRepositoryTrait for UserRepository {
 
         Ok(count > 0)
     }
+
+    async fn find_all(&self, query: UserQuery) -> Result<UsersResponse, AppError> {
+        let limit = query.limit.unwrap_or(20).clamp(1, 100);
+        let page = query.page.unwrap_or(1).max(1);
+        let offset = (page - 1) * limit;
+
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM users");
+        Self::push_filters(&mut count_builder, &query);
+        let total: i64 = count_builder
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count users")?;
+
+        let mut users_builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, username, email, password_hash, full_name, phone, role,
+                   is_active, last_login, created_at, updated_at
+            FROM users
+            "#,
+        );
+        Self::push_filters(&mut users_builder, &query);
+        users_builder
+            .push(" ORDER BY created_at ASC LIMIT ")
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let users = users_builder
+            .build_query_as::<User>()
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch users")?;
+
+        Ok(UsersResponse {
+            users: users.into_iter().map(UserResponse::from).collect(),
+            total,
+            page,
+            limit,
+            total_pages: (total as f64 / limit as f64).ceil() as u32,
+        })
+    }
+
+    async fn find_by_id_including_inactive(&self, id: Uuid) -> Result<Option<User>, AppError> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, username, email, password_hash, full_name, phone, role,
+                   is_active, last_login, created_at, updated_at
+            FROM users
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch user by id")?;
+
+        Ok(user)
+    }
+
+    async fn set_active(&self, id: Uuid, is_active: bool) -> Result<User, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+        if !is_active {
+            Self::ensure_not_last_admin(&mut tx, id).await?;
+        }
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users SET is_active = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, username, email, password_hash, full_name, phone, role,
+                      is_active, last_login, created_at, updated_at
+            "#,
+        )
+        .bind(is_active)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to update user status")?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        tx.commit().await.context("Failed to update user status")?;
+
+        Ok(user)
+    }
+
+    async fn update_role(&self, id: Uuid, role: &str) -> Result<User, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+        if role != ADMIN_ROLE {
+            Self::ensure_not_last_admin(&mut tx, id).await?;
+        }
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users SET role = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, username, email, password_hash, full_name, phone, role,
+                      is_active, last_login, created_at, updated_at
+            "#,
+        )
+        .bind(role)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to update user role")?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        tx.commit().await.context("Failed to update user role")?;
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::test_support;
+
+    async fn insert_user_last_seen(pool: &PgPool, last_login_minutes_ago: i32) -> Uuid {
+        let id = test_support::insert_user(pool, "activity_user").await;
+        sqlx::query(
+            "UPDATE users SET last_login = NOW() - make_interval(mins => $1) WHERE id = $2",
+        )
+        .bind(last_login_minutes_ago)
+        .bind(id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        id
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_touch_activity_keeps_recent_session_alive(pool: PgPool) {
+        let user_id = insert_user_last_seen(&pool, 5).await;
+        let repository = UserRepository::new(pool);
+
+        assert!(repository.touch_activity(user_id, 30).await.unwrap());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_touch_activity_rejects_idle_session(pool: PgPool) {
+        let user_id = insert_user_last_seen(&pool, 90).await;
+        let repository = UserRepository::new(pool);
+
+        assert!(!repository.touch_activity(user_id, 30).await.unwrap());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_duplicate_username_is_a_conflict(pool: PgPool) {
+        use axum::{http::StatusCode, response::IntoResponse};
+
+        let repository = UserRepository::new(pool);
+        let request = |email: &str| CreateUserRequest {
+            username: "duplicate".to_string(),
+            email: email.to_string(),
+            password: "Password123!".to_string(),
+            full_name: "Duplicate User".to_string(),
+            phone: None,
+        };
+
+        repository
+            .create(request("first@example.com"), "hash".to_string())
+            .await
+            .unwrap();
+
+        let err = repository
+            .create(request("second@example.com"), "hash".to_string())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, AppError::Conflict(msg) if msg == "User with this username already exists")
+        );
+        assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+    }
 }
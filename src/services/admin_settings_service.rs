@@ -1,12 +1,22 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use std::sync::Arc;
+use ipnet::IpNet;
+use std::{
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
+};
 use uuid::Uuid;
 
 use crate::{
     models::admin_settings::{
-        AdminSettings, AdminSettingsRecord, FeatureSettings, GeneralSettings, NotificationSettings,
-        SecuritySettings, UpdateSettingsRequest,
+        is_language_tag, restore_masked_setting_value, AdminSettings, AdminSettingsHistory,
+        AdminSettingsRecord, FeatureSettings, GeneralSettings, NotificationSettings,
+        SecuritySettings, UpdateSettingsRequest, AUTHOR_NAME_MAX_LENGTH_LIMIT,
+        COMMENT_MAX_LENGTH_LIMIT,
     },
     repositories::AdminSettingsRepository,
 };
@@ -47,9 +57,16 @@ pub trait AdminSettingsServiceTrait: Send + Sync {
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettings>;
     async fn reset_to_defaults(&self, updated_by: Option<Uuid>) -> Result<AdminSettings>;
+    async fn get_setting_history(&self, key: &str) -> Result<Vec<AdminSettingsHistory>>;
+    async fn rollback_setting(
+        &self,
+        key: &str,
+        history_id: Uuid,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord>;
     async fn is_feature_enabled(&self, feature: &str) -> Result<bool>;
     async fn is_maintenance_mode(&self) -> Result<bool>;
-    async fn get_maintenance_message(&self) -> Result<String>;
+    async fn get_maintenance_message(&self, locales: &[String]) -> Result<String>;
 }
 
 pub struct AdminSettingsService {
@@ -61,6 +78,60 @@ impl AdminSettingsService {
         Self { repository }
     }
 
+    // Clients get secrets masked, so a masked value coming back means "leave unchanged"
+    async fn with_stored_secrets(
+        &self,
+        mut settings: NotificationSettings,
+    ) -> Result<NotificationSettings> {
+        let stored = self.repository.get_all_settings().await?.notifications;
+        settings.restore_secrets(&stored);
+        Ok(settings)
+    }
+
+    fn validate_setting_key(key: &str) -> Result<()> {
+        match key {
+            "general" | "features" | "notifications" | "security" => Ok(()),
+            _ => Err(anyhow::anyhow!("Unknown setting key: {}", key)),
+        }
+    }
+
+    // Helper method to validate general settings
+    fn validate_general_settings(&self, settings: &GeneralSettings) -> Result<()> {
+        if settings.site_name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Site name cannot be empty"));
+        }
+
+        if settings.site_description.len() > 500 {
+            return Err(anyhow::anyhow!(
+                "Site description cannot exceed 500 characters"
+            ));
+        }
+
+        if settings.maintenance_message.len() > 1000 {
+            return Err(anyhow::anyhow!(
+                "Maintenance message cannot exceed 1000 characters"
+            ));
+        }
+
+        for (language, message) in &settings.maintenance_messages {
+            if !is_language_tag(language) {
+                return Err(anyhow::anyhow!(
+                    "Invalid maintenance message language code: {}",
+                    language
+                ));
+            }
+
+            if message.len() > 1000 {
+                return Err(anyhow::anyhow!(
+                    "Maintenance message for {} cannot exceed 1000 characters",
+                    language
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     // Helper method to validate feature settings
     fn validate_feature_settings(&self, settings: &FeatureSettings) -> Result<()> {
         // Add any business logic validation here
@@ -72,6 +143,26 @@ impl AdminSettingsService {
             ));
         }
 
+        if settings.comment_min_length == 0 {
+            return Err(anyhow::anyhow!("Comment minimum length must be at least 1"));
+        }
+
+        if settings.comment_max_length < settings.comment_min_length
+            || settings.comment_max_length > COMMENT_MAX_LENGTH_LIMIT
+        {
+            return Err(anyhow::anyhow!(
+                "Comment maximum length must be between the minimum length and {}",
+                COMMENT_MAX_LENGTH_LIMIT
+            ));
+        }
+
+        if !(1..=AUTHOR_NAME_MAX_LENGTH_LIMIT).contains(&settings.author_name_max_length) {
+            return Err(anyhow::anyhow!(
+                "Author name maximum length must be between 1 and {}",
+                AUTHOR_NAME_MAX_LENGTH_LIMIT
+            ));
+        }
+
         Ok(())
     }
 
@@ -89,9 +180,34 @@ impl AdminSettingsService {
             ));
         }
 
+        if settings.lockout_duration_minutes < 1 || settings.lockout_duration_minutes > 1440 {
+            return Err(anyhow::anyhow!(
+                "Lockout duration must be between 1 and 1440 minutes"
+            ));
+        }
+
+        if settings.pending_comment_expiry_days < 0 || settings.pending_comment_expiry_days > 365 {
+            return Err(anyhow::anyhow!(
+                "Pending comment expiry must be between 0 (disabled) and 365 days"
+            ));
+        }
+
+        if settings.password_policy.min_length < 8 || settings.password_policy.min_length > 128 {
+            return Err(anyhow::anyhow!(
+                "Password minimum length must be between 8 and 128 characters"
+            ));
+        }
+
+        // delete_old_logs refuses to remove anything newer than 30 days
+        if settings.audit_log_retention_days < 30 || settings.audit_log_retention_days > 3650 {
+            return Err(anyhow::anyhow!(
+                "Audit log retention must be between 30 and 3650 days"
+            ));
+        }
+
         // Validate IP whitelist format if provided
         for ip in &settings.ip_whitelist {
-            if !self.is_valid_ip_or_cidr(ip) {
+            if !Self::is_valid_ip_or_cidr(ip) {
                 return Err(anyhow::anyhow!(
                     "Invalid IP address or CIDR notation: {}",
                     ip
@@ -102,35 +218,10 @@ impl AdminSettingsService {
         Ok(())
     }
 
-    // Helper method to validate IP address or CIDR notation
-    fn is_valid_ip_or_cidr(&self, ip_str: &str) -> bool {
-        // Basic validation - in a real implementation, use a proper IP validation library
-        if ip_str.contains('/') {
-            // CIDR notation
-            let parts: Vec<&str> = ip_str.split('/').collect();
-            if parts.len() != 2 {
-                return false;
-            }
-
-            // Validate IP part
-            if !self.is_valid_ip(parts[0]) {
-                return false;
-            }
-
-            // Validate prefix length
-            if let Ok(prefix) = parts[1].parse::<u8>() {
-                prefix <= 32 // For IPv4
-            } else {
-                false
-            }
-        } else {
-            self.is_valid_ip(ip_str)
-        }
-    }
-
-    // Helper method to validate IP address
-    fn is_valid_ip(&self, ip_str: &str) -> bool {
-        ip_str.parse::<std::net::IpAddr>().is_ok()
+    // Accepts a single IPv4/IPv6 address or a network such as 10.0.0.0/8 or 2001:db8::/48,
+    // matching what the IP whitelist middleware can enforce
+    fn is_valid_ip_or_cidr(ip_str: &str) -> bool {
+        ip_str.parse::<IpNet>().is_ok() || ip_str.parse::<IpAddr>().is_ok()
     }
 
     // Helper method to get feature-specific settings
@@ -141,7 +232,9 @@ impl AdminSettingsService {
             "comments" => Ok(serde_json::json!({
                 "enabled": settings.features.comments_enabled,
                 "moderation_required": true, // Could be configurable
-                "max_length": 1000 // Could be configurable
+                "min_length": settings.features.comment_min_length,
+                "max_length": settings.features.comment_max_length,
+                "author_name_max_length": settings.features.author_name_max_length
             })),
             "portfolio" => Ok(serde_json::json!({
                 "enabled": settings.features.portfolio_enabled,
@@ -184,6 +277,10 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettings> {
         // Validate each section if provided
+        if let Some(ref general) = request.general {
+            self.validate_general_settings(general)?;
+        }
+
         if let Some(ref features) = request.features {
             self.validate_feature_settings(features)?;
         }
@@ -206,6 +303,7 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         }
 
         if let Some(notifications) = request.notifications {
+            let notifications = self.with_stored_secrets(notifications).await?;
             self.repository
                 .update_notification_settings(notifications, updated_by)
                 .await?;
@@ -224,11 +322,15 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
     async fn update_setting(
         &self,
         key: &str,
-        value: serde_json::Value,
+        mut value: serde_json::Value,
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettingsRecord> {
         // Validate the setting based on its key
         match key {
+            "general" => {
+                let general: GeneralSettings = serde_json::from_value(value.clone())?;
+                self.validate_general_settings(&general)?;
+            }
             "features" => {
                 let features: FeatureSettings = serde_json::from_value(value.clone())?;
                 self.validate_feature_settings(&features)?;
@@ -237,11 +339,15 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
                 let security: SecuritySettings = serde_json::from_value(value.clone())?;
                 self.validate_security_settings(&security)?;
             }
-            "general" | "notifications" => {
+            "notifications" => {
                 // Basic validation for these settings
                 if !value.is_object() {
                     return Err(anyhow::anyhow!("Setting value must be a JSON object"));
                 }
+
+                if let Some(stored) = self.repository.get_setting(key).await? {
+                    restore_masked_setting_value(key, &mut value, &stored.setting_value);
+                }
             }
             _ => {
                 return Err(anyhow::anyhow!("Unknown setting key: {}", key));
@@ -256,23 +362,7 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         settings: GeneralSettings,
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettings> {
-        // Validate general settings
-        if settings.site_name.trim().is_empty() {
-            return Err(anyhow::anyhow!("Site name cannot be empty"));
-        }
-
-        if settings.site_description.len() > 500 {
-            return Err(anyhow::anyhow!(
-                "Site description cannot exceed 500 characters"
-            ));
-        }
-
-        if settings.maintenance_message.len() > 1000 {
-            return Err(anyhow::anyhow!(
-                "Maintenance message cannot exceed 1000 characters"
-            ));
-        }
-
+        self.validate_general_settings(&settings)?;
         self.repository
             .update_general_settings(settings, updated_by)
             .await
@@ -295,6 +385,7 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         updated_by: Option<Uuid>,
     ) -> Result<AdminSettings> {
         // No specific validation needed for notification settings currently
+        let settings = self.with_stored_secrets(settings).await?;
         self.repository
             .update_notification_settings(settings, updated_by)
             .await
@@ -315,6 +406,21 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         self.repository.reset_to_defaults(updated_by).await
     }
 
+    async fn get_setting_history(&self, key: &str) -> Result<Vec<AdminSettingsHistory>> {
+        Self::validate_setting_key(key)?;
+        self.repository.get_history(key).await
+    }
+
+    async fn rollback_setting(
+        &self,
+        key: &str,
+        history_id: Uuid,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord> {
+        Self::validate_setting_key(key)?;
+        self.repository.rollback(key, history_id, updated_by).await
+    }
+
     async fn is_feature_enabled(&self, feature: &str) -> Result<bool> {
         self.repository.is_feature_enabled(feature).await
     }
@@ -323,7 +429,285 @@ impl AdminSettingsServiceTrait for AdminSettingsService {
         self.repository.is_maintenance_mode().await
     }
 
-    async fn get_maintenance_message(&self) -> Result<String> {
-        self.repository.get_maintenance_message().await
+    async fn get_maintenance_message(&self, locales: &[String]) -> Result<String> {
+        self.repository.get_maintenance_message(locales).await
+    }
+}
+
+// Another instance's writes become visible here after at most this long
+pub const SETTINGS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+struct CachedSettings {
+    settings: AdminSettings,
+    loaded_at: Instant,
+}
+
+#[derive(Default)]
+struct SettingsCacheState {
+    entry: Option<CachedSettings>,
+    // Bumped on every invalidation so a load that raced with an update is not stored
+    generation: u64,
+}
+
+/// Snapshot of how often reads were served from memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Keeps the full `AdminSettings` in memory so feature, maintenance and comment checks
+/// don't query the database on every request. Entries expire after `ttl`, and every
+/// write through this service invalidates the cache immediately.
+pub struct CachedAdminSettingsService {
+    inner: Arc<dyn AdminSettingsServiceTrait>,
+    ttl: Duration,
+    state: RwLock<SettingsCacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachedAdminSettingsService {
+    pub fn new(inner: Arc<dyn AdminSettingsServiceTrait>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            state: RwLock::new(SettingsCacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stats(&self) -> SettingsCacheStats {
+        SettingsCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn invalidate(&self) {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.entry = None;
+        state.generation += 1;
+    }
+
+    // The fresh cached settings, or the generation a reload should be stored under
+    fn lookup(&self) -> std::result::Result<AdminSettings, u64> {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+
+        match state
+            .entry
+            .as_ref()
+            .filter(|entry| entry.loaded_at.elapsed() < self.ttl)
+        {
+            Some(entry) => Ok(entry.settings.clone()),
+            None => Err(state.generation),
+        }
+    }
+
+    async fn cached_settings(&self) -> Result<AdminSettings> {
+        let generation = match self.lookup() {
+            Ok(settings) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(settings);
+            }
+            Err(generation) => generation,
+        };
+
+        let misses = self.misses.fetch_add(1, Ordering::Relaxed) + 1;
+        tracing::debug!(
+            "Admin settings cache miss (hits: {}, misses: {})",
+            self.hits.load(Ordering::Relaxed),
+            misses
+        );
+
+        let settings = self.inner.get_all_settings().await?;
+
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        if state.generation == generation {
+            state.entry = Some(CachedSettings {
+                settings: settings.clone(),
+                loaded_at: Instant::now(),
+            });
+        }
+
+        Ok(settings)
+    }
+
+    fn invalidated<T>(&self, result: Result<T>) -> Result<T> {
+        self.invalidate();
+        result
+    }
+}
+
+#[async_trait]
+impl AdminSettingsServiceTrait for CachedAdminSettingsService {
+    async fn get_all_settings(&self) -> Result<AdminSettings> {
+        self.cached_settings().await
+    }
+
+    async fn get_setting(&self, key: &str) -> Result<Option<AdminSettingsRecord>> {
+        self.inner.get_setting(key).await
+    }
+
+    async fn update_settings(
+        &self,
+        request: UpdateSettingsRequest,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettings> {
+        self.invalidated(self.inner.update_settings(request, updated_by).await)
+    }
+
+    async fn update_setting(
+        &self,
+        key: &str,
+        value: serde_json::Value,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord> {
+        self.invalidated(self.inner.update_setting(key, value, updated_by).await)
+    }
+
+    async fn update_general_settings(
+        &self,
+        settings: GeneralSettings,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettings> {
+        self.invalidated(
+            self.inner
+                .update_general_settings(settings, updated_by)
+                .await,
+        )
+    }
+
+    async fn update_feature_settings(
+        &self,
+        settings: FeatureSettings,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettings> {
+        self.invalidated(
+            self.inner
+                .update_feature_settings(settings, updated_by)
+                .await,
+        )
+    }
+
+    async fn update_notification_settings(
+        &self,
+        settings: NotificationSettings,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettings> {
+        self.invalidated(
+            self.inner
+                .update_notification_settings(settings, updated_by)
+                .await,
+        )
+    }
+
+    async fn update_security_settings(
+        &self,
+        settings: SecuritySettings,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettings> {
+        self.invalidated(
+            self.inner
+                .update_security_settings(settings, updated_by)
+                .await,
+        )
+    }
+
+    async fn reset_to_defaults(&self, updated_by: Option<Uuid>) -> Result<AdminSettings> {
+        self.invalidated(self.inner.reset_to_defaults(updated_by).await)
+    }
+
+    async fn get_setting_history(&self, key: &str) -> Result<Vec<AdminSettingsHistory>> {
+        self.inner.get_setting_history(key).await
+    }
+
+    async fn rollback_setting(
+        &self,
+        key: &str,
+        history_id: Uuid,
+        updated_by: Option<Uuid>,
+    ) -> Result<AdminSettingsRecord> {
+        self.invalidated(
+            self.inner
+                .rollback_setting(key, history_id, updated_by)
+                .await,
+        )
+    }
+
+    async fn is_feature_enabled(&self, feature: &str) -> Result<bool> {
+        self.cached_settings()
+            .await?
+            .features
+            .is_enabled(feature)
+            .ok_or_else(|| anyhow::anyhow!("Unknown feature: {}", feature))
+    }
+
+    async fn is_maintenance_mode(&self) -> Result<bool> {
+        Ok(self.cached_settings().await?.general.maintenance_mode)
+    }
+
+    async fn get_maintenance_message(&self, locales: &[String]) -> Result<String> {
+        let settings = self.cached_settings().await?;
+        Ok(settings
+            .general
+            .maintenance_message_for(locales)
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    fn cached_service(pool: PgPool) -> CachedAdminSettingsService {
+        CachedAdminSettingsService::new(
+            Arc::new(AdminSettingsService::new(Arc::new(
+                AdminSettingsRepository::new(pool),
+            ))),
+            Duration::from_secs(300),
+        )
+    }
+
+    #[test]
+    fn test_ip_whitelist_entries_accept_ipv4_and_ipv6_networks() {
+        for valid in ["203.0.113.7", "10.0.0.0/8", "2001:db8::1", "2001:db8::/48"] {
+            assert!(
+                AdminSettingsService::is_valid_ip_or_cidr(valid),
+                "{}",
+                valid
+            );
+        }
+        for invalid in ["10.0.0.0/33", "2001:db8::/129", "not-an-ip", "10.0.0.1/"] {
+            assert!(
+                !AdminSettingsService::is_valid_ip_or_cidr(invalid),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "requires a Postgres database via DATABASE_URL"]
+    async fn test_update_is_visible_to_next_cached_read(pool: PgPool) {
+        let service = cached_service(pool);
+
+        assert!(service.is_feature_enabled("blog").await.unwrap());
+        assert!(service.is_feature_enabled("search").await.unwrap());
+        assert_eq!(service.stats(), SettingsCacheStats { hits: 1, misses: 1 });
+
+        let features = FeatureSettings {
+            blog_enabled: false,
+            ..service.get_all_settings().await.unwrap().features
+        };
+        service
+            .update_feature_settings(features, None)
+            .await
+            .unwrap();
+
+        assert!(!service.is_feature_enabled("blog").await.unwrap());
+        assert_eq!(service.stats(), SettingsCacheStats { hits: 2, misses: 2 });
     }
 }
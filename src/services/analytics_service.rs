@@ -0,0 +1,136 @@
+use crate::utils::errors::AppError;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+type Result<T> = std::result::Result<T, AppError>;
+
+use crate::{
+    models::analytics::AnalyticsSummary,
+    repositories::analytics_repository::AnalyticsRepositoryTrait,
+};
+
+/// How long a computed summary is reused; the aggregates scan whole tables
+pub const ANALYTICS_CACHE_TTL: Duration = Duration::from_secs(60);
+const MOST_VIEWED_POSTS_LIMIT: u32 = 10;
+
+#[async_trait::async_trait]
+pub trait AnalyticsServiceTrait: Send + Sync {
+    async fn get_summary(&self) -> Result<AnalyticsSummary>;
+}
+
+pub struct AnalyticsService {
+    repository: Arc<dyn AnalyticsRepositoryTrait>,
+    ttl: Duration,
+    cached: Mutex<Option<(AnalyticsSummary, Instant)>>,
+}
+
+impl AnalyticsService {
+    pub fn new(repository: Arc<dyn AnalyticsRepositoryTrait>, ttl: Duration) -> Self {
+        Self {
+            repository,
+            ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    fn cached_summary(&self) -> Option<AnalyticsSummary> {
+        let cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+
+        cached
+            .as_ref()
+            .filter(|(_, computed_at)| computed_at.elapsed() < self.ttl)
+            .map(|(summary, _)| summary.clone())
+    }
+
+    async fn compute_summary(&self) -> Result<AnalyticsSummary> {
+        let (totals, most_viewed_posts, views_by_category, counts) = tokio::try_join!(
+            self.repository.get_view_totals(),
+            self.repository
+                .get_most_viewed_posts(MOST_VIEWED_POSTS_LIMIT),
+            self.repository.get_views_by_category(),
+            self.repository.get_content_counts(),
+        )?;
+
+        Ok(AnalyticsSummary {
+            total_views: totals.post_views + totals.portfolio_views,
+            post_views: totals.post_views,
+            portfolio_views: totals.portfolio_views,
+            most_viewed_posts,
+            views_by_category,
+            counts,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsServiceTrait for AnalyticsService {
+    async fn get_summary(&self) -> Result<AnalyticsSummary> {
+        if let Some(summary) = self.cached_summary() {
+            return Ok(summary);
+        }
+
+        let summary = self.compute_summary().await?;
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((summary.clone(), Instant::now()));
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::analytics::{CategoryViews, ContentCounts, PopularPost, ViewTotals};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct CountingRepository {
+        loads: AtomicU32,
+    }
+
+    #[async_trait::async_trait]
+    impl AnalyticsRepositoryTrait for CountingRepository {
+        async fn get_view_totals(&self) -> Result<ViewTotals> {
+            self.loads.fetch_add(1, Ordering::SeqCst);
+            Ok(ViewTotals {
+                post_views: 30,
+                portfolio_views: 12,
+            })
+        }
+
+        async fn get_most_viewed_posts(&self, _limit: u32) -> Result<Vec<PopularPost>> {
+            Ok(vec![])
+        }
+
+        async fn get_views_by_category(&self) -> Result<Vec<CategoryViews>> {
+            Ok(vec![])
+        }
+
+        async fn get_content_counts(&self) -> Result<ContentCounts> {
+            Ok(ContentCounts {
+                total_posts: 0,
+                published_posts: 0,
+                total_comments: 0,
+                pending_comments: 0,
+                total_projects: 0,
+                active_projects: 0,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_cached_until_ttl_expires() {
+        let repository = Arc::new(CountingRepository::default());
+        let service = AnalyticsService::new(repository.clone(), Duration::from_millis(50));
+
+        let summary = service.get_summary().await.unwrap();
+        assert_eq!(summary.total_views, 42);
+        service.get_summary().await.unwrap();
+        assert_eq!(repository.loads.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        service.get_summary().await.unwrap();
+        assert_eq!(repository.loads.load(Ordering::SeqCst), 2);
+    }
+}
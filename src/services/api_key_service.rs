@@ -0,0 +1,120 @@
+use crate::utils::errors::AppError;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+type Result<T> = std::result::Result<T, AppError>;
+
+use crate::{
+    models::api_key::{ApiKey, CreateApiKeyRequest, CreatedApiKeyResponse, API_KEY_SCOPES},
+    repositories::api_key_repository::ApiKeyRepositoryTrait,
+};
+
+// Prefix makes keys recognisable in configs and secret scanners
+const API_KEY_PREFIX: &str = "pk_";
+
+#[async_trait::async_trait]
+pub trait ApiKeyServiceTrait: Send + Sync {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        request: CreateApiKeyRequest,
+    ) -> Result<CreatedApiKeyResponse>;
+    async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKey>>;
+    async fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<()>;
+    async fn authenticate(&self, key: &str) -> Result<ApiKey>;
+}
+
+#[derive(Clone)]
+pub struct ApiKeyService {
+    repository: Arc<dyn ApiKeyRepositoryTrait>,
+}
+
+impl ApiKeyService {
+    pub fn new(repository: Arc<dyn ApiKeyRepositoryTrait>) -> Self {
+        Self { repository }
+    }
+
+    fn hash_key(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyServiceTrait for ApiKeyService {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        request: CreateApiKeyRequest,
+    ) -> Result<CreatedApiKeyResponse> {
+        request.validate()?;
+
+        let scopes = request.scopes.unwrap_or_else(|| vec!["read".to_string()]);
+        if scopes.is_empty() {
+            return Err(AppError::Validation(
+                "At least one scope is required".to_string(),
+            ));
+        }
+        if let Some(scope) = scopes
+            .iter()
+            .find(|scope| !API_KEY_SCOPES.contains(&scope.as_str()))
+        {
+            return Err(AppError::Validation(format!(
+                "Invalid scope '{}'. Must be one of: {}",
+                scope,
+                API_KEY_SCOPES.join(", ")
+            )));
+        }
+
+        let mut key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key_bytes);
+        let key = format!("{}{}", API_KEY_PREFIX, hex::encode(key_bytes));
+        let key_prefix = &key[..API_KEY_PREFIX.len() + 8];
+
+        let expires_at = request
+            .expires_in_days
+            .map(|days| Utc::now() + Duration::days(days));
+
+        let api_key = self
+            .repository
+            .create(
+                user_id,
+                request.name.trim(),
+                key_prefix,
+                &Self::hash_key(&key),
+                &scopes,
+                expires_at,
+            )
+            .await?;
+
+        Ok(CreatedApiKeyResponse { key, api_key })
+    }
+
+    async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
+        self.repository.find_by_user(user_id).await
+    }
+
+    async fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        if !self.repository.revoke(id, user_id).await? {
+            return Err(AppError::NotFound("API key not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn authenticate(&self, key: &str) -> Result<ApiKey> {
+        let api_key = self
+            .repository
+            .find_active_by_hash(&Self::hash_key(key))
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired API key".to_string()))?;
+
+        if let Err(e) = self.repository.touch_last_used(api_key.id).await {
+            tracing::warn!("Failed to update API key last used time: {}", e);
+        }
+
+        Ok(api_key)
+    }
+}
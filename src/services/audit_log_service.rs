@@ -1,12 +1,17 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use serde_json::json;
+use serde::Serialize;
+use serde_json::{json, Value};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
     models::audit_log::{AuditLog, AuditLogFilters, AuditLogResponse, CreateAuditLogRequest},
     repositories::AuditLogRepository,
+    services::{
+        email_notifier::EmailNotifier, notification_hub::NotificationHub,
+        webhook_dispatcher::WebhookDispatcher,
+    },
 };
 
 #[async_trait]
@@ -41,6 +46,8 @@ pub trait AuditLogServiceTrait: Send + Sync {
         new_values: Option<serde_json::Value>,
         success: bool,
         error_message: Option<String>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
     ) -> Result<AuditLog>;
 
     #[allow(clippy::too_many_arguments)]
@@ -55,15 +62,73 @@ pub trait AuditLogServiceTrait: Send + Sync {
         ip_address: Option<String>,
         user_agent: Option<String>,
     ) -> Result<AuditLog>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn log_crud_operation(
+        &self,
+        user_id: Option<Uuid>,
+        user_name: Option<String>,
+        action: &str,
+        resource_type: &str,
+        resource_id: Uuid,
+        resource_title: Option<String>,
+        old_values: Option<serde_json::Value>,
+        new_values: Option<serde_json::Value>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<AuditLog>;
+}
+
+// Never copied into audit logs, matched against field names
+const REDACTED_FIELDS: &[&str] = &["password", "secret", "token", "hash"];
+
+/// Full before and after snapshots of an entity, as `(old_values, new_values)`, so a
+/// log entry shows the whole record either side of the change. Sensitive fields are
+/// redacted in both.
+pub fn audit_snapshots<T: Serialize>(
+    before: &T,
+    after: &T,
+) -> (Option<serde_json::Value>, Option<serde_json::Value>) {
+    (redacted_snapshot(before), redacted_snapshot(after))
+}
+
+fn redacted_snapshot<T: Serialize>(entity: &T) -> Option<serde_json::Value> {
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(entity) else {
+        return None;
+    };
+
+    for (field, value) in fields.iter_mut() {
+        if REDACTED_FIELDS
+            .iter()
+            .any(|redacted| field.to_lowercase().contains(redacted))
+        {
+            *value = json!("[REDACTED]");
+        }
+    }
+
+    Some(Value::Object(fields))
 }
 
 pub struct AuditLogService {
     repository: Arc<AuditLogRepository>,
+    webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
+    email_notifier: Option<Arc<EmailNotifier>>,
+    notification_hub: Option<NotificationHub>,
 }
 
 impl AuditLogService {
-    pub fn new(repository: Arc<AuditLogRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<AuditLogRepository>,
+        webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
+        email_notifier: Option<Arc<EmailNotifier>>,
+        notification_hub: Option<NotificationHub>,
+    ) -> Self {
+        Self {
+            repository,
+            webhook_dispatcher,
+            email_notifier,
+            notification_hub,
+        }
     }
 
     // Helper method to create audit log for admin actions
@@ -81,6 +146,8 @@ impl AuditLogService {
         new_values: Option<serde_json::Value>,
         success: bool,
         error_message: Option<String>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
     ) -> Result<AuditLog> {
         let request = CreateAuditLogRequest {
             user_id,
@@ -92,8 +159,8 @@ impl AuditLogService {
             details,
             old_values,
             new_values,
-            ip_address: None, // This should be extracted from request context
-            user_agent: None, // This should be extracted from request context
+            ip_address: ip_address.and_then(|ip| ip.parse().ok()),
+            user_agent,
             success,
             error_message,
         };
@@ -132,41 +199,24 @@ impl AuditLogService {
 
         self.create(request).await
     }
-
-    // Helper method to log CRUD operations
-    #[allow(clippy::too_many_arguments)]
-    pub async fn log_crud_operation(
-        &self,
-        user_id: Option<Uuid>,
-        user_name: Option<String>,
-        action: &str,
-        resource_type: &str,
-        resource_id: Uuid,
-        resource_title: Option<String>,
-        old_values: Option<serde_json::Value>,
-        new_values: Option<serde_json::Value>,
-    ) -> Result<AuditLog> {
-        self.log_admin_action(
-            user_id,
-            user_name,
-            action,
-            resource_type,
-            Some(resource_id),
-            resource_title,
-            Some(format!("{} operation on {}", action, resource_type)),
-            old_values,
-            new_values,
-            true,
-            None,
-        )
-        .await
-    }
 }
 
 #[async_trait]
 impl AuditLogServiceTrait for AuditLogService {
     async fn create(&self, request: CreateAuditLogRequest) -> Result<AuditLog> {
-        self.repository.create(request).await
+        let audit_log = self.repository.create(request).await?;
+
+        if let Some(ref dispatcher) = self.webhook_dispatcher {
+            dispatcher.dispatch(audit_log.clone());
+        }
+        if let Some(ref notifier) = self.email_notifier {
+            notifier.notify(audit_log.clone());
+        }
+        if let Some(ref hub) = self.notification_hub {
+            hub.publish_to_all(audit_log.clone().into());
+        }
+
+        Ok(audit_log)
     }
 
     async fn get_by_id(&self, id: Uuid) -> Result<Option<AuditLog>> {
@@ -241,15 +291,15 @@ impl AuditLogServiceTrait for AuditLogService {
 
         // Get top actions, resources, and users
         let mut top_actions: Vec<_> = action_counts.into_iter().collect();
-        top_actions.sort_by(|a, b| b.1.cmp(&a.1));
+        top_actions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
         top_actions.truncate(5);
 
         let mut top_resources: Vec<_> = resource_counts.into_iter().collect();
-        top_resources.sort_by(|a, b| b.1.cmp(&a.1));
+        top_resources.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
         top_resources.truncate(5);
 
         let mut top_users: Vec<_> = user_counts.into_iter().collect();
-        top_users.sort_by(|a, b| b.1.cmp(&a.1));
+        top_users.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
         top_users.truncate(5);
 
         Ok(json!({
@@ -295,6 +345,8 @@ impl AuditLogServiceTrait for AuditLogService {
         new_values: Option<serde_json::Value>,
         success: bool,
         error_message: Option<String>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
     ) -> Result<AuditLog> {
         let request = CreateAuditLogRequest {
             user_id,
@@ -306,8 +358,8 @@ impl AuditLogServiceTrait for AuditLogService {
             details,
             old_values,
             new_values,
-            ip_address: None,
-            user_agent: None,
+            ip_address: ip_address.and_then(|ip| ip.parse().ok()),
+            user_agent,
             success,
             error_message,
         };
@@ -345,4 +397,96 @@ impl AuditLogServiceTrait for AuditLogService {
 
         self.create(request).await
     }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn log_crud_operation(
+        &self,
+        user_id: Option<Uuid>,
+        user_name: Option<String>,
+        action: &str,
+        resource_type: &str,
+        resource_id: Uuid,
+        resource_title: Option<String>,
+        old_values: Option<serde_json::Value>,
+        new_values: Option<serde_json::Value>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<AuditLog> {
+        self.log_admin_action(
+            user_id,
+            user_name,
+            action,
+            resource_type,
+            Some(resource_id),
+            resource_title,
+            Some(format!("{} operation on {}", action, resource_type)),
+            old_values,
+            new_values,
+            true,
+            None,
+            ip_address,
+            user_agent,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Entity {
+        title: String,
+        published: bool,
+        password_hash: String,
+        updated_at: i64,
+    }
+
+    fn entity(title: &str, published: bool, password_hash: &str, updated_at: i64) -> Entity {
+        Entity {
+            title: title.to_string(),
+            published,
+            password_hash: password_hash.to_string(),
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_audit_snapshots_keep_every_field() {
+        let (old_values, new_values) = audit_snapshots(
+            &entity("Draft", false, "a", 1),
+            &entity("Final", false, "a", 2),
+        );
+
+        assert_eq!(
+            old_values,
+            Some(json!({
+                "title": "Draft",
+                "published": false,
+                "password_hash": "[REDACTED]",
+                "updated_at": 1
+            }))
+        );
+        assert_eq!(
+            new_values,
+            Some(json!({
+                "title": "Final",
+                "published": false,
+                "password_hash": "[REDACTED]",
+                "updated_at": 2
+            }))
+        );
+    }
+
+    #[test]
+    fn test_audit_snapshots_redact_sensitive_fields() {
+        let (old_values, new_values) = audit_snapshots(
+            &entity("Post", false, "old-hash", 1),
+            &entity("Post", true, "new-hash", 1),
+        );
+
+        assert_eq!(old_values.unwrap()["password_hash"], "[REDACTED]");
+        assert_eq!(new_values.unwrap()["password_hash"], "[REDACTED]");
+    }
 }
@@ -1,49 +1,111 @@
 use anyhow::Result;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::models::user::{LoginRequest, LoginResponse, User, UserResponse};
-use crate::repositories::user_repository::UserRepositoryTrait;
-use crate::utils::{errors::AppError, password::PasswordService};
+use crate::middleware::rate_limiter::{deny_token, is_token_denied, RedisRateLimiter};
+use crate::models::admin_settings::SecuritySettings;
+use crate::models::user::{
+    LoginRequest, LoginResponse, SessionClient, User, UserResponse, UserSession, USER_ROLE,
+};
+use crate::repositories::{
+    password_reset_repository::PasswordResetRepositoryTrait,
+    refresh_token_repository::{RefreshTokenLifetimes, RefreshTokenRepositoryTrait},
+    user_repository::UserRepositoryTrait,
+};
+use crate::services::{
+    admin_settings_service::AdminSettingsServiceTrait, api_key_service::ApiKeyServiceTrait,
+    email_service::EmailServiceTrait,
+};
+use crate::utils::{
+    errors::AppError,
+    password::{validate_strength, PasswordService},
+};
+
+// Password reset tokens are valid for one hour
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 60;
+// Refresh token lifetime for logins with "remember me" checked
+const REMEMBER_ME_EXPIRY_DAYS: i64 = 30;
+// Every authenticated request checks the denylist, so a slow Redis must not stall it
+const TOKEN_DENYLIST_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String, // user_id
     pub username: String,
+    // Tokens issued before the role claim existed are treated as plain users
+    #[serde(default = "default_role")]
     pub role: String,
+    // Unique per token; empty on tokens issued before it was added
+    #[serde(default)]
+    pub jti: String,
     pub exp: i64,
     pub iat: i64,
 }
 
+fn default_role() -> String {
+    USER_ROLE.to_string()
+}
+
 #[derive(Clone)]
 pub struct AuthService {
     user_repository: Arc<dyn UserRepositoryTrait>,
+    password_reset_repository: Arc<dyn PasswordResetRepositoryTrait>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+    api_key_service: Arc<dyn ApiKeyServiceTrait>,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+    email_service: Arc<dyn EmailServiceTrait>,
     jwt_secret: String,
     token_expiry: i64,
+    refresh_token_expiry: i64,
     password_service: PasswordService,
+    rate_limiter: Option<Arc<RedisRateLimiter>>,
 }
 
 impl AuthService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: Arc<dyn UserRepositoryTrait>,
+        password_reset_repository: Arc<dyn PasswordResetRepositoryTrait>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+        api_key_service: Arc<dyn ApiKeyServiceTrait>,
+        admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+        email_service: Arc<dyn EmailServiceTrait>,
         jwt_secret: String,
         token_expiry: i64,
+        refresh_token_expiry: i64,
     ) -> Self {
         Self {
             user_repository,
+            password_reset_repository,
+            refresh_token_repository,
+            api_key_service,
+            admin_settings_service,
+            email_service,
             jwt_secret,
             token_expiry,
+            refresh_token_expiry,
             password_service: PasswordService::new(),
+            rate_limiter: None,
         }
     }
 
+    /// Redis backs the access token denylist; without it, logged-out tokens stay
+    /// valid until they expire
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<Arc<RedisRateLimiter>>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
     pub async fn authenticate_user(
         &self,
         request: LoginRequest,
+        client: &SessionClient,
     ) -> Result<LoginResponse, AppError> {
         // Validate request
         request.validate()?;
@@ -67,24 +129,44 @@ impl AuthService {
         // Update last login
         self.user_repository.update_last_login(user.id).await?;
 
+        let remember_me = request.remember_me.unwrap_or(false);
+        let (refresh_token, refresh_expires_at) = self
+            .issue_refresh_token(user.id, remember_me, client)
+            .await?;
+
         // Generate JWT token
-        let (token, expires_at) = self.generate_token(&user)?;
+        let (token, expires_at) = self.generate_token(&user, refresh_expires_at).await?;
 
         Ok(LoginResponse {
             token,
             user: user.into(),
             expires_at,
+            refresh_token,
+            refresh_expires_at,
         })
     }
 
-    pub fn generate_token(&self, user: &User) -> Result<(String, chrono::DateTime<Utc>), AppError> {
+    /// Issue an access JWT. Its lifetime follows the admin-configured
+    /// `session_timeout`, falling back to the config `token_expiry`, and never
+    /// runs past `session_expires_at`, the end of the refresh token it belongs to.
+    pub async fn generate_token(
+        &self,
+        user: &User,
+        session_expires_at: chrono::DateTime<Utc>,
+    ) -> Result<(String, chrono::DateTime<Utc>), AppError> {
+        let token_expiry = match self.session_timeout_minutes().await {
+            Some(minutes) => i64::from(minutes) * 60,
+            None => self.token_expiry,
+        };
+
         let now = Utc::now();
-        let expiration = now + Duration::seconds(self.token_expiry);
+        let expiration = (now + Duration::seconds(token_expiry)).min(session_expires_at);
 
         let claims = Claims {
             sub: user.id.to_string(),
             username: user.username.clone(),
             role: user.role.clone(),
+            jti: Uuid::new_v4().to_string(),
             exp: expiration.timestamp(),
             iat: now.timestamp(),
         };
@@ -110,6 +192,105 @@ impl AuthService {
         Ok(token_data.claims)
     }
 
+    /// Resolve an `X-API-Key` header to the same claims a JWT would carry.
+    /// Keys without the "write" scope may only make read-only requests.
+    pub async fn authenticate_api_key(
+        &self,
+        key: &str,
+        read_only: bool,
+    ) -> Result<Claims, AppError> {
+        let api_key = self.api_key_service.authenticate(key).await?;
+
+        if !read_only && !api_key.has_scope("write") {
+            return Err(AppError::Forbidden(
+                "API key does not have the write scope".to_string(),
+            ));
+        }
+
+        let user = self.get_user_by_id(api_key.user_id).await?;
+        if !user.is_active {
+            return Err(AppError::Unauthorized("Account is disabled".to_string()));
+        }
+
+        let now = Utc::now();
+        let expires_at = api_key
+            .expires_at
+            .unwrap_or_else(|| now + Duration::seconds(self.token_expiry));
+
+        Ok(Claims {
+            sub: user.id.to_string(),
+            username: user.username,
+            role: user.role,
+            // API keys aren't JWTs and are revoked through their own endpoint, so there
+            // is nothing for logout to put on the token denylist
+            jti: String::new(),
+            exp: expires_at.timestamp(),
+            iat: now.timestamp(),
+        })
+    }
+
+    /// Reject sessions that have been idle for longer than `session_timeout`.
+    ///
+    /// The JWT `exp` caps how long a single token lives, fixed when it's issued.
+    /// This check is separate: every authenticated request counts as activity,
+    /// and once a user has made no request (or login) for `session_timeout`
+    /// minutes their tokens stop working even if `exp` hasn't passed, e.g. after
+    /// an admin shortens the timeout or when refresh tokens keep minting new JWTs.
+    pub async fn ensure_session_active(&self, claims: &Claims) -> Result<(), AppError> {
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid or expired token".to_string()))?;
+
+        self.record_activity(user_id).await
+    }
+
+    async fn record_activity(&self, user_id: Uuid) -> Result<(), AppError> {
+        let Some(timeout_minutes) = self.session_timeout_minutes().await else {
+            return Ok(());
+        };
+
+        if !self
+            .user_repository
+            .touch_activity(user_id, timeout_minutes)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Session expired due to inactivity".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn session_timeout_minutes(&self) -> Option<i32> {
+        match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) if settings.security.session_timeout > 0 => {
+                Some(settings.security.session_timeout)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load session timeout, using config expiry: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Failed logins allowed per account and how long it then stays locked, in seconds.
+    /// Falls back to the default security settings if they can't be loaded.
+    pub async fn account_lockout_policy(&self) -> (u32, u64) {
+        let security = match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) => settings.security,
+            Err(e) => {
+                tracing::warn!("Failed to load lockout settings, using defaults: {}", e);
+                SecuritySettings::default()
+            }
+        };
+
+        (
+            security.max_login_attempts.max(1) as u32,
+            security.lockout_duration_minutes.max(1) as u64 * 60,
+        )
+    }
+
     pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<User, AppError> {
         let user = self
             .user_repository
@@ -120,21 +301,187 @@ impl AuthService {
         Ok(user)
     }
 
-    pub async fn refresh_token(&self, old_token: &str) -> Result<LoginResponse, AppError> {
-        let claims = self.validate_token(old_token)?;
-        let user_id = Uuid::parse_str(&claims.sub)
-            .map_err(|_| AppError::Internal("Invalid user ID in token".to_string()))?;
+    /// Exchange a refresh token for a new access token, rotating the refresh token.
+    /// The old token stops working, so replaying it fails. The session itself carries on.
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: &SessionClient,
+    ) -> Result<LoginResponse, AppError> {
+        let new_refresh_token = Self::generate_opaque_token();
+
+        let rotated = self
+            .refresh_token_repository
+            .rotate_token(
+                &Self::hash_token(refresh_token),
+                &Self::hash_token(&new_refresh_token),
+                self.refresh_token_lifetimes(),
+                client,
+            )
+            .await?
+            .ok_or_else(|| {
+                AppError::Unauthorized("Invalid or expired refresh token".to_string())
+            })?;
 
-        let user = self.get_user_by_id(user_id).await?;
-        let (token, expires_at) = self.generate_token(&user)?;
+        let user = self.get_user_by_id(rotated.user_id).await?;
+        if !user.is_active {
+            return Err(AppError::Unauthorized("Account is disabled".to_string()));
+        }
+
+        // Refreshing doesn't revive a session that has already gone idle
+        self.record_activity(user.id).await?;
+
+        let (token, expires_at) = self.generate_token(&user, rotated.expires_at).await?;
 
         Ok(LoginResponse {
             token,
             user: user.into(),
             expires_at,
+            refresh_token: new_refresh_token,
+            refresh_expires_at: rotated.expires_at,
         })
     }
 
+    pub async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<(), AppError> {
+        self.refresh_token_repository
+            .revoke_token(&Self::hash_token(refresh_token))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        self.refresh_token_repository
+            .revoke_all_for_user(user_id)
+            .await
+    }
+
+    /// Deny the access token behind `claims` for the rest of its lifetime.
+    /// API keys and tokens issued before the `jti` claim existed carry no `jti`
+    /// and are left alone.
+    pub async fn revoke_access_token(&self, claims: &Claims) {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return;
+        };
+        if claims.jti.is_empty() {
+            return;
+        }
+
+        let Ok(ttl_seconds) = u64::try_from(claims.exp - Utc::now().timestamp()) else {
+            return;
+        };
+        if ttl_seconds == 0 {
+            return;
+        }
+
+        if let Err(e) = deny_token(rate_limiter, &claims.jti, ttl_seconds).await {
+            tracing::warn!("Failed to deny access token {}: {}", claims.jti, e);
+        }
+    }
+
+    /// Reject access tokens on the denylist. Fails open when Redis can't be reached
+    /// or doesn't answer within `TOKEN_DENYLIST_TIMEOUT`.
+    pub async fn ensure_token_not_revoked(&self, claims: &Claims) -> Result<(), AppError> {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return Ok(());
+        };
+        if claims.jti.is_empty() {
+            return Ok(());
+        }
+
+        match tokio::time::timeout(
+            TOKEN_DENYLIST_TIMEOUT,
+            is_token_denied(rate_limiter, &claims.jti),
+        )
+        .await
+        {
+            Ok(Ok(true)) => Err(AppError::Unauthorized("Token has been revoked".to_string())),
+            Ok(Ok(false)) => Ok(()),
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to check token denylist, allowing request: {}", e);
+                Ok(())
+            }
+            Err(_) => {
+                tracing::warn!("Token denylist check timed out, allowing request");
+                Ok(())
+            }
+        }
+    }
+
+    fn refresh_token_lifetimes(&self) -> RefreshTokenLifetimes {
+        RefreshTokenLifetimes {
+            standard: self.refresh_token_expiry,
+            remembered: Duration::days(REMEMBER_ME_EXPIRY_DAYS).num_seconds(),
+        }
+    }
+
+    async fn issue_refresh_token(
+        &self,
+        user_id: Uuid,
+        remember_me: bool,
+        client: &SessionClient,
+    ) -> Result<(String, chrono::DateTime<Utc>), AppError> {
+        let token = Self::generate_opaque_token();
+        let lifetimes = self.refresh_token_lifetimes();
+        let lifetime = if remember_me {
+            lifetimes.remembered
+        } else {
+            lifetimes.standard
+        };
+        let expires_at = Utc::now() + Duration::seconds(lifetime);
+
+        self.refresh_token_repository
+            .create_token(
+                user_id,
+                &Self::hash_token(&token),
+                expires_at,
+                remember_me,
+                client,
+            )
+            .await?;
+
+        Ok((token, expires_at))
+    }
+
+    /// Active sessions for the user. The one holding `current_refresh_token` is flagged.
+    pub async fn list_sessions(
+        &self,
+        user_id: Uuid,
+        current_refresh_token: Option<&str>,
+    ) -> Result<Vec<UserSession>, AppError> {
+        let current_hash = current_refresh_token.map(Self::hash_token);
+
+        self.refresh_token_repository
+            .list_active_for_user(user_id, current_hash.as_deref())
+            .await
+    }
+
+    /// Revoke one of the user's sessions. Returns whether it was the current session.
+    pub async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        current_refresh_token: Option<&str>,
+    ) -> Result<bool, AppError> {
+        let current_hash = current_refresh_token.map(Self::hash_token);
+
+        self.refresh_token_repository
+            .revoke_session(user_id, session_id, current_hash.as_deref())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Session not found".to_string()))
+    }
+
+    /// Revoke every session of the user except the current one
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: Uuid,
+        current_refresh_token: &str,
+    ) -> Result<u64, AppError> {
+        self.refresh_token_repository
+            .revoke_others_for_user(user_id, &Self::hash_token(current_refresh_token))
+            .await
+    }
+
     pub async fn update_profile(
         &self,
         user_id: Uuid,
@@ -180,11 +527,7 @@ impl AuthService {
         request.validate()?;
 
         // Verify password strength
-        if !PasswordService::is_strong_password(&request.new_password) {
-            return Err(AppError::Validation(
-                "Password must be at least 8 characters and contain a mix of uppercase, lowercase, numbers, and special characters".to_string()
-            ));
-        }
+        self.enforce_password_policy(&request.new_password).await?;
 
         // Get current user
         let user = self.get_user_by_id(user_id).await?;
@@ -208,6 +551,121 @@ impl AuthService {
             .update_password(user_id, new_hash)
             .await?;
 
+        // Sign out other sessions that may have been using the old password
+        self.revoke_all_for_user(user_id).await?;
+
+        Ok(())
+    }
+
+    /// Issue a reset token for the account with this email, if any.
+    /// Returns the matching user so callers can audit, but never reveals it to clients.
+    pub async fn request_password_reset(&self, email: &str) -> Result<Option<User>, AppError> {
+        let Some(user) = self.user_repository.find_by_email(email.trim()).await? else {
+            return Ok(None);
+        };
+
+        // Issue and deliver in the background: waiting on SMTP here would make known emails
+        // measurably slower to answer than unknown ones
+        let service = self.clone();
+        let recipient = user.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.send_password_reset(&recipient).await {
+                tracing::warn!("Failed to send password reset email: {}", e);
+            }
+        });
+
+        Ok(Some(user))
+    }
+
+    async fn send_password_reset(&self, user: &User) -> Result<(), AppError> {
+        let token = Self::generate_opaque_token();
+        let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES);
+
+        self.password_reset_repository
+            .create_token(user.id, &Self::hash_token(&token), expires_at)
+            .await?;
+
+        let site_url = self
+            .admin_settings_service
+            .get_all_settings()
+            .await
+            .ok()
+            .and_then(|settings| settings.general.site_url)
+            .filter(|url| !url.trim().is_empty());
+
+        let reset_instructions = match site_url {
+            Some(url) => format!(
+                "Reset your password here: {}/reset-password?token={}",
+                url.trim_end_matches('/'),
+                token
+            ),
+            None => format!("Your password reset token is: {}", token),
+        };
+
+        let body = format!(
+            "Hi {},\n\nWe received a request to reset your password.\n\n{}\n\nThis link expires in {} minutes. If you didn't request this, you can ignore this email.",
+            user.full_name.as_deref().unwrap_or(&user.username),
+            reset_instructions,
+            PASSWORD_RESET_TOKEN_MINUTES
+        );
+
+        self.email_service
+            .send_email(&user.email, "Password reset request", &body)
+            .await
+    }
+
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<User, AppError> {
+        // Check strength before consuming the token so a weak password doesn't burn it
+        self.enforce_password_policy(new_password).await?;
+
+        let user_id = self
+            .password_reset_repository
+            .consume_token(&Self::hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Invalid or expired reset token".to_string()))?;
+
+        let user = self.get_user_by_id(user_id).await?;
+        let new_hash = self.password_service.hash_password(new_password)?;
+
+        self.user_repository
+            .update_password(user.id, new_hash)
+            .await?;
+
+        // Any other outstanding reset links and sessions for this account are now stale
+        self.password_reset_repository
+            .invalidate_for_user(user.id)
+            .await?;
+        self.revoke_all_for_user(user.id).await?;
+
+        Ok(user)
+    }
+
+    /// Apply the admin-configured password policy when strong passwords are required.
+    /// Falls back to the default (strict) policy if settings can't be loaded.
+    async fn enforce_password_policy(&self, password: &str) -> Result<(), AppError> {
+        let security = match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) => settings.security,
+            Err(e) => {
+                tracing::warn!("Failed to load security settings, using defaults: {}", e);
+                SecuritySettings::default()
+            }
+        };
+
+        if security.require_strong_passwords {
+            validate_strength(password, &security.password_policy)?;
+        }
+
         Ok(())
     }
+
+    fn generate_opaque_token() -> String {
+        let mut token_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut token_bytes);
+        hex::encode(token_bytes)
+    }
+
+    // Only hashes of reset and refresh tokens are persisted
+    fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
 }
@@ -1,12 +1,23 @@
-use crate::utils::errors::AppError;
-use chrono::Utc;
+use crate::utils::errors::{validation_error_summary, AppError};
+use crate::utils::frontmatter::split_frontmatter;
+use crate::utils::preview_token::{sign_preview_token, verify_preview_token};
+use crate::utils::request::hash_client_ip;
+use crate::utils::text::{summarize, truncate_at_word};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 type Result<T> = std::result::Result<T, AppError>;
 
 use crate::{
+    middleware::rate_limiter::RedisRateLimiter,
+    models::portfolio::ImportOutcome,
     models::post::{
-        CreatePostRequest, Post, PostQuery, PostStats, PostsResponse, UpdatePostRequest,
+        BulkDeletePostsRequest, BulkFeaturePostsRequest, CategoryCount, CreatePostRequest, Post,
+        PostDailyViews, PostImportDocument, PostImportItem, PostImportMode, PostImportRecord,
+        PostImportResponse, PostImportResult, PostPreviewLink, PostQuery, PostStats, PostsResponse,
+        PublishedPostsQuery, TagCount, UpdatePostRequest,
     },
     repositories::post_repository::PostRepositoryTrait,
 };
@@ -19,24 +30,78 @@ pub trait BlogServiceTrait: Send + Sync {
     async fn create_post(&self, request: CreatePostRequest) -> Result<Post>;
     async fn update_post(&self, id: Uuid, request: UpdatePostRequest) -> Result<Post>;
     async fn delete_post(&self, id: Uuid) -> Result<()>;
-    async fn get_published_posts(&self, limit: Option<u32>) -> Result<Vec<Post>>;
+    async fn bulk_delete_posts(
+        &self,
+        request: BulkDeletePostsRequest,
+    ) -> Result<Vec<(Uuid, String)>>;
+    async fn bulk_update_featured(&self, request: BulkFeaturePostsRequest) -> Result<u64>;
+    async fn restore_post(&self, id: Uuid) -> Result<()>;
+    async fn purge_post(&self, id: Uuid) -> Result<()>;
+    async fn get_published_posts(&self, query: PublishedPostsQuery) -> Result<PostsResponse>;
     async fn get_featured_posts(&self, limit: Option<u32>) -> Result<Vec<Post>>;
     async fn get_posts_by_category(&self, category: &str, limit: Option<u32>) -> Result<Vec<Post>>;
     async fn get_posts_by_tags(&self, tags: Vec<String>, limit: Option<u32>) -> Result<Vec<Post>>;
+    async fn get_related_posts(&self, id: Uuid, limit: Option<u32>) -> Result<Vec<Post>>;
+    async fn search_posts(&self, query: &str, limit: Option<u32>) -> Result<Vec<Post>>;
+    async fn get_published_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>>;
+    async fn get_tag_counts(&self, limit: Option<u32>) -> Result<Vec<TagCount>>;
+    async fn get_category_counts(&self) -> Result<Vec<CategoryCount>>;
     async fn get_blog_statistics(&self) -> Result<PostStats>;
     async fn publish_post(&self, id: Uuid) -> Result<()>;
     async fn unpublish_post(&self, id: Uuid) -> Result<()>;
     async fn increment_view_count(&self, id: Uuid) -> Result<()>;
+    async fn record_view(&self, id: Uuid, ip: &str) -> Result<()>;
+    async fn get_view_timeseries(&self, id: Uuid, days: i32) -> Result<Vec<PostDailyViews>>;
+    /// Imports posts by slug; invalid documents are reported as failed, not fatal
+    async fn import_posts(
+        &self,
+        documents: Vec<PostImportDocument>,
+        mode: PostImportMode,
+    ) -> Result<PostImportResponse>;
+    async fn create_preview_link(
+        &self,
+        id: Uuid,
+        expires_in_hours: Option<i64>,
+    ) -> Result<PostPreviewLink>;
+    /// Whether `token` grants read access to the unpublished post `id`
+    fn verify_preview_token(&self, id: Uuid, token: &str) -> bool;
 }
 
+// Repeat views from the same IP within this window are not counted
+const VIEW_DEDUP_WINDOW_SECONDS: u64 = 6 * 60 * 60;
+
+// The view timeseries covers at most a year of daily buckets
+const MAX_VIEW_STATS_DAYS: i32 = 365;
+
+// Keeps a single import transaction reasonably short
+const MAX_IMPORT_POSTS: usize = 200;
+
+// Category for imported posts that don't name one
+const DEFAULT_IMPORT_CATEGORY: &str = "Uncategorized";
+
+// Preview links last a day unless asked otherwise, and never more than a week
+const DEFAULT_PREVIEW_LINK_HOURS: i64 = 24;
+const MAX_PREVIEW_LINK_HOURS: i64 = 7 * 24;
+
 #[derive(Clone)]
 pub struct BlogService {
     repository: Arc<dyn PostRepositoryTrait>,
+    rate_limiter: Option<Arc<RedisRateLimiter>>,
+    // Signs preview tokens and keys the stored IP hashes
+    secret: String,
 }
 
 impl BlogService {
-    pub fn new(repository: Arc<dyn PostRepositoryTrait>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn PostRepositoryTrait>,
+        rate_limiter: Option<Arc<RedisRateLimiter>>,
+        secret: String,
+    ) -> Self {
+        Self {
+            repository,
+            rate_limiter,
+            secret,
+        }
     }
 }
 
@@ -81,37 +146,7 @@ impl BlogServiceTrait for BlogService {
             request.slug = format!("{}-{}", request.slug, Utc::now().timestamp());
         }
 
-        // Business logic: Auto-generate SEO fields if empty
-        if request.seo_title.is_none()
-            || request
-                .seo_title
-                .as_ref()
-                .unwrap_or(&String::new())
-                .is_empty()
-        {
-            request.seo_title = Some(self.generate_seo_title(&request.title));
-        }
-
-        if request.seo_description.is_none()
-            || request
-                .seo_description
-                .as_ref()
-                .unwrap_or(&String::new())
-                .is_empty()
-        {
-            request.seo_description = Some(self.generate_seo_description(&request.content));
-        }
-
-        // Business logic: Extract and set keywords if not provided
-        if request.seo_keywords.is_none()
-            || request
-                .seo_keywords
-                .as_ref()
-                .unwrap_or(&String::new())
-                .is_empty()
-        {
-            request.seo_keywords = Some(self.extract_keywords(&request.content, &request.tags));
-        }
+        self.fill_generated_fields(&mut request);
 
         self.repository.create(request).await
     }
@@ -134,8 +169,16 @@ impl BlogServiceTrait for BlogService {
             return Err(AppError::Validation("Slug already exists".to_string()));
         }
 
-        // Business logic: Update SEO fields if they're empty
+        // Business logic: Update the excerpt and SEO fields if they're empty
         let mut request = request;
+        if request
+            .excerpt
+            .as_deref()
+            .is_none_or(|excerpt| excerpt.trim().is_empty())
+        {
+            request.excerpt = Some(self.generate_excerpt(&request.content));
+        }
+
         if request
             .seo_title
             .as_ref()
@@ -178,16 +221,51 @@ impl BlogServiceTrait for BlogService {
         self.repository.delete(id).await
     }
 
-    async fn get_published_posts(&self, limit: Option<u32>) -> Result<Vec<Post>> {
+    async fn bulk_delete_posts(
+        &self,
+        request: BulkDeletePostsRequest,
+    ) -> Result<Vec<(Uuid, String)>> {
+        request.validate()?;
+
+        let mut ids = request.ids;
+        ids.sort_unstable();
+        ids.dedup();
+
+        self.repository.bulk_delete(&ids).await
+    }
+
+    async fn bulk_update_featured(&self, request: BulkFeaturePostsRequest) -> Result<u64> {
+        request.validate()?;
+
+        let mut ids = request.ids;
+        ids.sort_unstable();
+        ids.dedup();
+
+        self.repository
+            .bulk_update_featured(&ids, request.featured)
+            .await
+    }
+
+    async fn restore_post(&self, id: Uuid) -> Result<()> {
+        self.repository.restore(id).await
+    }
+
+    async fn purge_post(&self, id: Uuid) -> Result<()> {
+        self.repository.purge(id).await
+    }
+
+    async fn get_published_posts(&self, query: PublishedPostsQuery) -> Result<PostsResponse> {
         // Business logic: Apply reasonable limit
-        let limit = limit.unwrap_or(10);
+        let limit = query.limit.unwrap_or(10);
         if limit > 100 {
             return Err(AppError::Validation(
                 "Limit cannot exceed 100 posts".to_string(),
             ));
         }
 
-        self.repository.get_published(Some(limit)).await
+        self.repository
+            .get_published_paginated(query.page.unwrap_or(1), limit)
+            .await
     }
 
     async fn get_featured_posts(&self, limit: Option<u32>) -> Result<Vec<Post>> {
@@ -242,6 +320,54 @@ impl BlogServiceTrait for BlogService {
         self.repository.get_by_tags(tags, Some(limit)).await
     }
 
+    async fn get_related_posts(&self, id: Uuid, limit: Option<u32>) -> Result<Vec<Post>> {
+        // Business logic: Ensure post exists
+        if self.repository.find_by_id(id).await?.is_none() {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        // Business logic: Keep suggestions short
+        let limit = limit.unwrap_or(5).min(10);
+
+        self.repository.get_related(id, Some(limit)).await
+    }
+
+    async fn search_posts(&self, query: &str, limit: Option<u32>) -> Result<Vec<Post>> {
+        // Business logic: Require a meaningful query
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(AppError::Validation(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+        if query.chars().count() > 200 {
+            return Err(AppError::Validation(
+                "Search query cannot exceed 200 characters".to_string(),
+            ));
+        }
+
+        let limit = limit.unwrap_or(10);
+        if limit > 50 {
+            return Err(AppError::Validation(
+                "Limit cannot exceed 50 posts".to_string(),
+            ));
+        }
+
+        self.repository.search_ranked(query, limit).await
+    }
+
+    async fn get_published_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>> {
+        self.repository.get_published_slugs().await
+    }
+
+    async fn get_tag_counts(&self, limit: Option<u32>) -> Result<Vec<TagCount>> {
+        self.repository.get_tag_counts(limit).await
+    }
+
+    async fn get_category_counts(&self) -> Result<Vec<CategoryCount>> {
+        self.repository.get_category_counts().await
+    }
+
     async fn get_blog_statistics(&self) -> Result<PostStats> {
         self.repository.get_stats().await
     }
@@ -295,6 +421,148 @@ impl BlogServiceTrait for BlogService {
 
         Ok(())
     }
+
+    async fn record_view(&self, id: Uuid, ip: &str) -> Result<()> {
+        // Business logic: Count each IP once per window, but never stop counting when Redis is down
+        if let Some(rate_limiter) = &self.rate_limiter {
+            match rate_limiter
+                .record_view("post", &id.to_string(), ip, VIEW_DEDUP_WINDOW_SECONDS)
+                .await
+            {
+                Ok(false) => return Ok(()),
+                Ok(true) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to deduplicate post view, counting anyway: {}", e);
+                }
+            }
+        }
+
+        // Business logic: Only count views of published posts; store a hash, never the raw IP
+        if let Some(post) = self.repository.find_by_id(id).await? {
+            if post.published {
+                let ip_hash = hash_client_ip(&self.secret, ip);
+                self.repository.record_view(id, Some(&ip_hash)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_view_timeseries(&self, id: Uuid, days: i32) -> Result<Vec<PostDailyViews>> {
+        // Business logic: Ensure post exists
+        if self.repository.find_by_id(id).await?.is_none() {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        self.repository
+            .view_timeseries(id, days.clamp(1, MAX_VIEW_STATS_DAYS))
+            .await
+    }
+
+    async fn import_posts(
+        &self,
+        documents: Vec<PostImportDocument>,
+        mode: PostImportMode,
+    ) -> Result<PostImportResponse> {
+        if documents.len() > MAX_IMPORT_POSTS {
+            return Err(AppError::Validation(format!(
+                "Cannot import more than {} posts at once",
+                MAX_IMPORT_POSTS
+            )));
+        }
+
+        // Business logic: Check every document up front, only valid ones reach the database
+        let mut results = Vec::with_capacity(documents.len());
+        let mut valid = Vec::new();
+        let mut seen_slugs = HashSet::new();
+        for (index, document) in documents.into_iter().enumerate() {
+            let source = match &document {
+                PostImportDocument::Json(_) => None,
+                PostImportDocument::Markdown { filename, .. } => Some(filename.clone()),
+            };
+
+            let checked = Self::parse_import_document(document)
+                .and_then(|item| self.build_import_record(item))
+                .and_then(|record| {
+                    if seen_slugs.insert(record.post.slug.clone()) {
+                        Ok(record)
+                    } else {
+                        Err("Duplicate slug in import".to_string())
+                    }
+                });
+
+            // Valid documents get their real outcome once the database has run them
+            let (slug, outcome, error) = match checked {
+                Ok(record) => {
+                    let slug = record.post.slug.clone();
+                    valid.push((results.len(), record));
+                    (Some(slug), ImportOutcome::Skipped, None)
+                }
+                Err(error) => (None, ImportOutcome::Failed, Some(error)),
+            };
+            results.push(PostImportResult {
+                index,
+                source,
+                slug,
+                outcome,
+                error,
+            });
+        }
+
+        let (positions, records): (Vec<usize>, Vec<PostImportRecord>) = valid.into_iter().unzip();
+        let outcomes = self.repository.import(&records, mode).await?;
+        for (position, outcome) in positions.into_iter().zip(outcomes) {
+            match outcome {
+                Ok(ImportOutcome::Skipped) => {
+                    results[position].error =
+                        Some("A post with this slug already exists".to_string())
+                }
+                Ok(outcome) => results[position].outcome = outcome,
+                Err(e) => {
+                    results[position].outcome = ImportOutcome::Failed;
+                    results[position].error = Some(e.to_string());
+                }
+            }
+        }
+
+        let count = |outcome| results.iter().filter(|r| r.outcome == outcome).count();
+        Ok(PostImportResponse {
+            created: count(ImportOutcome::Created),
+            updated: count(ImportOutcome::Updated),
+            skipped: count(ImportOutcome::Skipped),
+            failed: count(ImportOutcome::Failed),
+            results,
+        })
+    }
+
+    async fn create_preview_link(
+        &self,
+        id: Uuid,
+        expires_in_hours: Option<i64>,
+    ) -> Result<PostPreviewLink> {
+        let post = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        let hours = expires_in_hours
+            .unwrap_or(DEFAULT_PREVIEW_LINK_HOURS)
+            .clamp(1, MAX_PREVIEW_LINK_HOURS);
+        let expires_at = Utc::now() + Duration::hours(hours);
+        let token = sign_preview_token(&self.secret, post.id, expires_at);
+
+        Ok(PostPreviewLink {
+            post_id: post.id,
+            path: format!("/api/v1/posts/slug/{}?preview_token={}", post.slug, token),
+            token,
+            expires_at,
+        })
+    }
+
+    fn verify_preview_token(&self, id: Uuid, token: &str) -> bool {
+        verify_preview_token(&self.secret, id, token, Utc::now())
+    }
 }
 
 impl BlogService {
@@ -332,6 +600,126 @@ impl BlogService {
         Ok(())
     }
 
+    fn parse_import_document(
+        document: PostImportDocument,
+    ) -> std::result::Result<PostImportItem, String> {
+        match document {
+            PostImportDocument::Json(value) => {
+                serde_json::from_value(value).map_err(|e| e.to_string())
+            }
+            PostImportDocument::Markdown { contents, .. } => {
+                let contents = contents?;
+                let (yaml, body) = split_frontmatter(&contents)
+                    .ok_or_else(|| "Missing YAML frontmatter".to_string())?;
+                let mut item: PostImportItem = serde_yaml::from_str(yaml)
+                    .map_err(|e| format!("Invalid frontmatter: {}", e))?;
+                item.content = body.to_string();
+                Ok(item)
+            }
+        }
+    }
+
+    fn build_import_record(
+        &self,
+        item: PostImportItem,
+    ) -> std::result::Result<PostImportRecord, String> {
+        let slug = match item.slug.as_deref().map(str::trim) {
+            Some(slug) if !slug.is_empty() => {
+                // Existing URLs are kept, so the slug must already be in canonical form
+                if self.generate_slug(slug) != slug {
+                    return Err(
+                        "Slug must contain only lowercase letters, numbers and hyphens".to_string(),
+                    );
+                }
+                slug.to_string()
+            }
+            _ => self.generate_slug(&item.title),
+        };
+        if slug.is_empty() {
+            return Err("Could not generate a slug from the title".to_string());
+        }
+
+        let published_date = item.date.as_deref().map(parse_import_date).transpose()?;
+        let published = item
+            .published
+            .unwrap_or_else(|| !item.draft.unwrap_or(false));
+        let category = item
+            .category
+            .or_else(|| item.categories.into_iter().next())
+            .unwrap_or_else(|| DEFAULT_IMPORT_CATEGORY.to_string());
+
+        let mut post = CreatePostRequest {
+            title: item.title.trim().to_string(),
+            slug,
+            content: item.content,
+            excerpt: item.excerpt,
+            category,
+            tags: item.tags,
+            featured_image: item.featured_image,
+            featured: item.featured,
+            published: Some(published),
+            seo_title: item.seo_title,
+            seo_description: item.seo_description,
+            seo_keywords: item.seo_keywords,
+            publish_at: None,
+        };
+        post.validate()
+            .map_err(|errors| validation_error_summary(&errors))?;
+        self.validate_post_content(&post.title, &post.content)
+            .map_err(|e| e.to_string())?;
+        self.fill_generated_fields(&mut post);
+
+        Ok(PostImportRecord {
+            post,
+            // Keep the original date so imported posts sort as they did before
+            published_at: published.then(|| published_date.unwrap_or_else(Utc::now)),
+        })
+    }
+
+    // Fill in the excerpt and SEO fields left empty by the author
+    fn fill_generated_fields(&self, request: &mut CreatePostRequest) {
+        // Business logic: Auto-generate the excerpt if empty
+        if request
+            .excerpt
+            .as_deref()
+            .is_none_or(|excerpt| excerpt.trim().is_empty())
+        {
+            request.excerpt = Some(self.generate_excerpt(&request.content));
+        }
+
+        // Business logic: Auto-generate SEO fields if empty
+        if request.seo_title.is_none()
+            || request
+                .seo_title
+                .as_ref()
+                .unwrap_or(&String::new())
+                .is_empty()
+        {
+            request.seo_title = Some(self.generate_seo_title(&request.title));
+        }
+
+        if request.seo_description.is_none()
+            || request
+                .seo_description
+                .as_ref()
+                .unwrap_or(&String::new())
+                .is_empty()
+        {
+            request.seo_description = Some(self.generate_seo_description(&request.content));
+        }
+
+        // Business logic: Extract and set keywords if not provided
+        if request.seo_keywords.is_none()
+            || request
+                .seo_keywords
+                .as_ref()
+                .unwrap_or(&String::new())
+                .is_empty()
+        {
+            request.seo_keywords = Some(self.extract_keywords(&request.content, &request.tags));
+        }
+    }
+
     fn generate_slug(&self, title: &str) -> String {
         title
             .trim()
@@ -346,24 +734,15 @@ impl BlogService {
     }
 
     fn generate_seo_title(&self, title: &str) -> String {
-        if title.len() <= 60 {
-            title.to_string()
-        } else {
-            format!("{}...", &title[..57])
-        }
+        truncate_at_word(title, 60)
     }
 
     fn generate_seo_description(&self, content: &str) -> String {
-        let clean_content = content
-            .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace() || ".,!?".contains(*c))
-            .collect::<String>();
+        summarize(content, 160)
+    }
 
-        if clean_content.len() <= 160 {
-            clean_content
-        } else {
-            format!("{}...", &clean_content[..157])
-        }
+    fn generate_excerpt(&self, content: &str) -> String {
+        summarize(content, 160)
     }
 
     fn extract_keywords(&self, content: &str, tags: &[String]) -> String {
@@ -383,3 +762,59 @@ impl BlogService {
         keywords.join(", ")
     }
 }
+
+fn parse_import_date(date: &str) -> std::result::Result<DateTime<Utc>, String> {
+    let date = date.trim();
+    // RFC 3339 (Hugo) or "2024-01-15 10:00:00 +0700" (Jekyll)
+    if let Ok(date) = DateTime::parse_from_rfc3339(date)
+        .or_else(|_| DateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S %z"))
+    {
+        return Ok(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|day| day.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| format!("Invalid date '{}', expected RFC 3339 or YYYY-MM-DD", date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_import_document_reads_frontmatter() {
+        let item = BlogService::parse_import_document(PostImportDocument::Markdown {
+            filename: "hello.md".to_string(),
+            contents: Ok("---\ntitle: Hello from Jekyll\nslug: hello-jekyll\ntags: [rust, migration]\ndate: 2023-05-01\nlayout: post\n---\n\nBody text\n".to_string()),
+        })
+        .unwrap();
+
+        assert_eq!(item.title, "Hello from Jekyll");
+        assert_eq!(item.slug.as_deref(), Some("hello-jekyll"));
+        assert_eq!(item.tags, ["rust", "migration"]);
+        assert_eq!(item.date.as_deref(), Some("2023-05-01"));
+        assert_eq!(item.content, "Body text\n");
+    }
+
+    #[test]
+    fn test_parse_import_document_requires_frontmatter() {
+        let result = BlogService::parse_import_document(PostImportDocument::Markdown {
+            filename: "plain.md".to_string(),
+            contents: Ok("# No frontmatter".to_string()),
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_import_date_formats() {
+        let midnight = parse_import_date("2023-05-01").unwrap();
+        assert_eq!(midnight.to_rfc3339(), "2023-05-01T00:00:00+00:00");
+
+        let hugo = parse_import_date("2023-05-01T10:00:00+07:00").unwrap();
+        assert_eq!(hugo.to_rfc3339(), "2023-05-01T03:00:00+00:00");
+
+        let jekyll = parse_import_date("2023-05-01 10:00:00 +0700").unwrap();
+        assert_eq!(jekyll, hugo);
+
+        assert!(parse_import_date("May 1st").is_err());
+    }
+}
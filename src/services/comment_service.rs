@@ -1,15 +1,25 @@
-use crate::utils::errors::AppError;
-use std::sync::Arc;
+use crate::middleware::feature_flags::{resolve_feature_check, FeatureAccess};
+use crate::utils::{
+    config::FeatureCheckFailOpen,
+    edit_token::{sign_edit_token, verify_edit_token},
+    errors::AppError,
+};
+use chrono::{Duration, Utc};
+use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 type Result<T> = std::result::Result<T, AppError>;
 
 use crate::{
+    models::admin_settings::FeatureSettings,
     models::comment::{
-        Comment, CommentModerationInfo, CommentQuery, CommentStats, CommentsResponse,
-        CreateCommentRequest, UpdateCommentStatusRequest,
+        BannedCommenter, Comment, CommentDailyStats, CommentModerationInfo, CommentQuery,
+        CommentStats, CommentThread, CommentsResponse, CreateBanRequest, CreateCommentRequest,
+        EditCommentRequest, UpdateCommentStatusRequest,
     },
     repositories::comment_repository::CommentRepositoryTrait,
-    services::admin_settings_service::AdminSettingsServiceTrait,
+    services::{
+        admin_settings_service::AdminSettingsServiceTrait, audit_log_service::AuditLogServiceTrait,
+    },
 };
 
 #[async_trait::async_trait]
@@ -22,6 +32,8 @@ pub trait CommentServiceTrait: Send + Sync {
         ip_address: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Comment>;
+    fn create_edit_token(&self, comment: &Comment) -> String;
+    async fn edit_comment(&self, id: Uuid, request: EditCommentRequest) -> Result<Comment>;
     async fn update_comment_status(
         &self,
         id: Uuid,
@@ -33,38 +45,112 @@ pub trait CommentServiceTrait: Send + Sync {
         post_id: Uuid,
         include_replies: bool,
     ) -> Result<Vec<Comment>>;
+    async fn get_threaded_comments(&self, post_id: Uuid) -> Result<Vec<CommentThread>>;
     async fn get_comment_replies(&self, parent_id: Uuid) -> Result<Vec<Comment>>;
     async fn get_pending_comments(&self) -> Result<Vec<CommentModerationInfo>>;
     async fn get_comment_statistics(&self) -> Result<CommentStats>;
+    async fn get_comment_stats_timeseries(&self, days: i32) -> Result<Vec<CommentDailyStats>>;
     async fn bulk_moderate_comments(&self, ids: Vec<Uuid>, status: String) -> Result<i64>;
     async fn approve_comment(&self, id: Uuid) -> Result<()>;
     async fn reject_comment(&self, id: Uuid) -> Result<()>;
+    async fn expire_pending_comments(&self) -> Result<u64>;
+    async fn ban_commenter(
+        &self,
+        request: CreateBanRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<BannedCommenter>;
+    async fn unban_commenter(&self, id: Uuid) -> Result<()>;
+    async fn list_bans(&self) -> Result<Vec<BannedCommenter>>;
 }
 
+// Top-level comments are depth 0; replies deeper than this are rejected
+const MAX_REPLY_DEPTH: usize = 3;
+
+// Commenters may fix their comment for this long after posting
+const EDIT_WINDOW_MINUTES: i64 = 15;
+
+// New comments from an IP with this many earlier spam comments go straight to spam
+const SPAM_HISTORY_THRESHOLD: i64 = 2;
+
+// The stats timeseries covers at most a year of daily buckets
+const MAX_STATS_DAYS: i32 = 365;
+
 #[derive(Clone)]
 pub struct CommentService {
     repository: Arc<dyn CommentRepositoryTrait>,
     admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+    audit_log_service: Arc<dyn AuditLogServiceTrait>,
+    edit_token_secret: String,
+    feature_check_fail_open: FeatureCheckFailOpen,
 }
 
 impl CommentService {
     pub fn new(
         repository: Arc<dyn CommentRepositoryTrait>,
         admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+        audit_log_service: Arc<dyn AuditLogServiceTrait>,
+        edit_token_secret: String,
+        feature_check_fail_open: FeatureCheckFailOpen,
     ) -> Self {
         Self {
             repository,
             admin_settings_service,
+            audit_log_service,
+            edit_token_secret,
+            feature_check_fail_open,
+        }
+    }
+
+    // Record a comment_created event so admins get notified; never fails comment creation
+    async fn notify_comment_created(&self, comment: &Comment) {
+        let post_title = match self.repository.get_post_title(comment.post_id).await {
+            Ok(title) => title,
+            Err(e) => {
+                tracing::warn!("Failed to fetch post title for comment notification: {}", e);
+                None
+            }
+        };
+
+        let details = format!(
+            "{} commented on \"{}\" (status: {})",
+            comment.author_name,
+            post_title.as_deref().unwrap_or("Unknown post"),
+            comment.status
+        );
+
+        if let Err(e) = self
+            .audit_log_service
+            .log_admin_action(
+                None,
+                Some(comment.author_name.clone()),
+                "comment_created",
+                "comment",
+                Some(comment.id),
+                post_title,
+                Some(details),
+                None,
+                None,
+                true,
+                None,
+                None,
+                None,
+            )
+            .await
+        {
+            tracing::warn!("Failed to record comment_created audit log: {}", e);
         }
     }
 
     // Check if comments are enabled in admin settings
     async fn check_comments_enabled(&self) -> Result<()> {
-        let comments_enabled = self
-            .admin_settings_service
-            .is_feature_enabled("comments")
-            .await
-            .unwrap_or(true); // Default to enabled if check fails
+        let comments_enabled = resolve_feature_check(
+            "Comments",
+            FeatureAccess::Write,
+            self.feature_check_fail_open,
+            self.admin_settings_service
+                .is_feature_enabled("comments")
+                .await,
+        )?;
 
         if !comments_enabled {
             return Err(AppError::Validation(
@@ -102,11 +188,25 @@ impl CommentServiceTrait for CommentService {
         // Check if comments are enabled
         self.check_comments_enabled().await?;
 
+        // Banned commenters are turned away before any other check
+        if self
+            .repository
+            .find_active_ban(&request.author_email, ip_address.as_deref())
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Forbidden(
+                "You are not allowed to post comments".to_string(),
+            ));
+        }
+
         // Business logic: Validate comment content
+        let limits = self.comment_limits().await;
         self.validate_comment_content(
             &request.content,
             &request.author_name,
             &request.author_email,
+            &limits,
         )?;
 
         // Business logic: Check for spam
@@ -116,6 +216,12 @@ impl CommentServiceTrait for CommentService {
             ));
         }
 
+        // Business logic: Replies must target a comment on the same post and respect max depth
+        if let Some(parent_id) = request.parent_id {
+            self.validate_reply_depth(request.post_id, parent_id)
+                .await?;
+        }
+
         // Business logic: Rate limiting check by IP
         if let Some(ref ip) = ip_address {
             if self.check_rate_limit(ip).await? {
@@ -126,21 +232,87 @@ impl CommentServiceTrait for CommentService {
             }
         }
 
-        // Business logic: Auto-moderate based on content and email
+        // Business logic: Auto-moderate based on content, email and the IP's history
         let requires_moderation = self
-            .requires_moderation(&request.content, &request.author_email)
+            .requires_moderation(
+                &request.content,
+                &request.author_email,
+                ip_address.as_deref(),
+            )
             .await?;
 
         // Determine initial status based on admin settings and content analysis
-        let initial_status = if requires_moderation {
+        let initial_status = if self.is_known_spam_ip(ip_address.as_deref()).await? {
+            "spam"
+        } else if requires_moderation {
             "pending"
         } else {
             "approved"
         };
 
-        self.repository
+        let comment = self
+            .repository
             .create_with_status(request, ip_address, user_agent, initial_status.to_string())
-            .await
+            .await?;
+
+        self.notify_comment_created(&comment).await;
+
+        Ok(comment)
+    }
+
+    fn create_edit_token(&self, comment: &Comment) -> String {
+        sign_edit_token(&self.edit_token_secret, comment.id, comment.created_at)
+    }
+
+    async fn edit_comment(&self, id: Uuid, request: EditCommentRequest) -> Result<Comment> {
+        let comment = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+
+        // Business logic: Only the holder of a valid token may edit, and only briefly
+        if !verify_edit_token(
+            &self.edit_token_secret,
+            comment.id,
+            comment.created_at,
+            &request.edit_token,
+        ) {
+            return Err(AppError::Forbidden("Invalid edit token".to_string()));
+        }
+
+        if Utc::now() > comment.created_at + Duration::minutes(EDIT_WINDOW_MINUTES) {
+            return Err(AppError::Forbidden(
+                "The edit window for this comment has expired".to_string(),
+            ));
+        }
+
+        if comment.status != "pending" && comment.status != "approved" {
+            return Err(AppError::Forbidden(
+                "This comment can no longer be edited".to_string(),
+            ));
+        }
+
+        // Business logic: Edited content goes through the same checks as new comments
+        let limits = self.comment_limits().await;
+        self.validate_comment_content(
+            &request.content,
+            &comment.author_name,
+            &comment.author_email,
+            &limits,
+        )?;
+
+        if self.is_spam_content(&request.content) {
+            return Err(AppError::Validation(
+                "Comment appears to be spam and has been rejected".to_string(),
+            ));
+        }
+
+        // Business logic: The token is one-time, so a replayed token can't edit again
+        self.repository
+            .update_content(id, &request.content)
+            .await?
+            .ok_or_else(|| AppError::Forbidden("This edit token has already been used".to_string()))
     }
 
     async fn update_comment_status(
@@ -198,6 +370,12 @@ impl CommentServiceTrait for CommentService {
         Ok(comments)
     }
 
+    async fn get_threaded_comments(&self, post_id: Uuid) -> Result<Vec<CommentThread>> {
+        let comments = self.get_comments_by_post(post_id, true).await?;
+
+        Ok(Self::build_threads(comments))
+    }
+
     async fn get_comment_replies(&self, parent_id: Uuid) -> Result<Vec<Comment>> {
         // Check if comments are enabled
         self.check_comments_enabled().await?;
@@ -223,6 +401,12 @@ impl CommentServiceTrait for CommentService {
         self.repository.get_stats().await
     }
 
+    async fn get_comment_stats_timeseries(&self, days: i32) -> Result<Vec<CommentDailyStats>> {
+        self.repository
+            .get_stats_timeseries(days.clamp(1, MAX_STATS_DAYS))
+            .await
+    }
+
     async fn bulk_moderate_comments(&self, ids: Vec<Uuid>, status: String) -> Result<i64> {
         // Business logic: Validate bulk operation
         if ids.is_empty() {
@@ -258,14 +442,148 @@ impl CommentServiceTrait for CommentService {
         self.update_comment_status(id, request).await?;
         Ok(())
     }
+
+    // Applies the pending_comment_expiry_* security settings; a no-op while disabled
+    async fn expire_pending_comments(&self) -> Result<u64> {
+        let security = self
+            .admin_settings_service
+            .get_all_settings()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load security settings: {}", e)))?
+            .security;
+
+        if security.pending_comment_expiry_days <= 0 {
+            return Ok(0);
+        }
+
+        self.repository
+            .expire_pending(
+                security.pending_comment_expiry_days,
+                security.pending_comment_expiry_action,
+            )
+            .await
+    }
+
+    async fn ban_commenter(
+        &self,
+        request: CreateBanRequest,
+        created_by: Option<Uuid>,
+    ) -> Result<BannedCommenter> {
+        if request.email.is_none() && request.ip_address.is_none() {
+            return Err(AppError::Validation(
+                "An email or IP address to ban is required".to_string(),
+            ));
+        }
+
+        if let Some(ref ip) = request.ip_address {
+            if ip.parse::<std::net::IpAddr>().is_err() {
+                return Err(AppError::Validation("Invalid IP address".to_string()));
+            }
+        }
+
+        if request
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            return Err(AppError::Validation(
+                "Ban expiry must be in the future".to_string(),
+            ));
+        }
+
+        self.repository.ban_commenter(request, created_by).await
+    }
+
+    async fn unban_commenter(&self, id: Uuid) -> Result<()> {
+        self.repository.unban_commenter(id).await
+    }
+
+    async fn list_bans(&self) -> Result<Vec<BannedCommenter>> {
+        self.repository.list_bans().await
+    }
 }
 
 impl CommentService {
+    async fn validate_reply_depth(&self, post_id: Uuid, parent_id: Uuid) -> Result<()> {
+        let parent = self
+            .repository
+            .find_by_id(parent_id)
+            .await?
+            .ok_or_else(|| AppError::Validation("Parent comment not found".to_string()))?;
+
+        if parent.post_id != post_id {
+            return Err(AppError::Validation(
+                "Parent comment belongs to a different post".to_string(),
+            ));
+        }
+
+        // Walk up the parent chain; the new reply sits one level below its parent
+        let mut depth = 1;
+        let mut current = parent.parent_id;
+        while let Some(ancestor_id) = current {
+            depth += 1;
+            if depth > MAX_REPLY_DEPTH {
+                return Err(AppError::Validation(format!(
+                    "Replies cannot be nested more than {} levels deep",
+                    MAX_REPLY_DEPTH
+                )));
+            }
+
+            current = self
+                .repository
+                .find_by_id(ancestor_id)
+                .await?
+                .and_then(|ancestor| ancestor.parent_id);
+        }
+
+        Ok(())
+    }
+
+    // Assemble a flat, chronologically ordered list into nested threads.
+    // Replies whose parent isn't in the list (e.g. not approved) are dropped with it.
+    fn build_threads(comments: Vec<Comment>) -> Vec<CommentThread> {
+        let mut children: HashMap<Option<Uuid>, Vec<Comment>> = HashMap::new();
+        for comment in comments {
+            children.entry(comment.parent_id).or_default().push(comment);
+        }
+
+        fn attach(
+            parent_id: Option<Uuid>,
+            children: &mut HashMap<Option<Uuid>, Vec<Comment>>,
+        ) -> Vec<CommentThread> {
+            children
+                .remove(&parent_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|comment| {
+                    let replies = attach(Some(comment.id), children);
+                    CommentThread {
+                        comment: comment.into(),
+                        replies,
+                    }
+                })
+                .collect()
+        }
+
+        attach(None, &mut children)
+    }
+
+    // Length limits from the feature settings; the defaults apply if they can't be read
+    async fn comment_limits(&self) -> FeatureSettings {
+        match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) => settings.features,
+            Err(e) => {
+                tracing::warn!("Failed to load comment limits, using defaults: {}", e);
+                FeatureSettings::default()
+            }
+        }
+    }
+
     fn validate_comment_content(
         &self,
         content: &str,
         author_name: &str,
         author_email: &str,
+        limits: &FeatureSettings,
     ) -> Result<()> {
         if content.trim().is_empty() {
             return Err(AppError::Validation(
@@ -273,26 +591,29 @@ impl CommentService {
             ));
         }
 
-        if content.trim().len() < 5 {
-            return Err(AppError::Validation(
-                "Comment must be at least 5 characters long".to_string(),
-            ));
+        if content.trim().chars().count() < limits.comment_min_length as usize {
+            return Err(AppError::Validation(format!(
+                "Comment must be at least {} characters long",
+                limits.comment_min_length
+            )));
         }
 
-        if content.len() > 5000 {
-            return Err(AppError::Validation(
-                "Comment cannot exceed 5000 characters".to_string(),
-            ));
+        if content.chars().count() > limits.comment_max_length as usize {
+            return Err(AppError::Validation(format!(
+                "Comment cannot exceed {} characters",
+                limits.comment_max_length
+            )));
         }
 
         if author_name.trim().is_empty() {
             return Err(AppError::Validation("Author name is required".to_string()));
         }
 
-        if author_name.len() > 100 {
-            return Err(AppError::Validation(
-                "Author name cannot exceed 100 characters".to_string(),
-            ));
+        if author_name.chars().count() > limits.author_name_max_length as usize {
+            return Err(AppError::Validation(format!(
+                "Author name cannot exceed {} characters",
+                limits.author_name_max_length
+            )));
         }
 
         if author_email.trim().is_empty() {
@@ -359,7 +680,20 @@ impl CommentService {
         false
     }
 
-    async fn requires_moderation(&self, content: &str, email: &str) -> Result<bool> {
+    async fn is_known_spam_ip(&self, ip_address: Option<&str>) -> Result<bool> {
+        let Some(ip) = ip_address else {
+            return Ok(false);
+        };
+
+        Ok(self.repository.count_spam_by_ip(ip).await? >= SPAM_HISTORY_THRESHOLD)
+    }
+
+    async fn requires_moderation(
+        &self,
+        content: &str,
+        email: &str,
+        ip_address: Option<&str>,
+    ) -> Result<bool> {
         // Check admin setting first - if comment approval is required, all comments need moderation
         let settings = self
             .admin_settings_service
@@ -407,6 +741,13 @@ impl CommentService {
             return Ok(false);
         }
 
+        // Returning commenters whose earlier comments were all approved skip the queue
+        if let Some(ip) = ip_address {
+            if self.repository.has_clean_history(ip).await? {
+                return Ok(false);
+            }
+        }
+
         // First-time commenters from non-trusted domains require moderation
         Ok(true)
     }
@@ -0,0 +1,359 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::models::{
+    audit_log::AuditLog,
+    user_notification::{NotificationFilter, NotificationWithReadStatus},
+};
+use crate::repositories::UserNotificationRepository;
+use crate::services::{
+    admin_settings_service::AdminSettingsServiceTrait, email_service::EmailServiceTrait,
+};
+
+// Only the most recent unread items are listed; the total is still reported
+const DIGEST_MAX_ITEMS: i64 = 100;
+
+/// Emails audit log events to users whose notification preferences ask for email delivery.
+#[derive(Clone)]
+pub struct EmailNotifier {
+    user_notification_repository: Arc<UserNotificationRepository>,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+    email_service: Arc<dyn EmailServiceTrait>,
+}
+
+impl EmailNotifier {
+    pub fn new(
+        user_notification_repository: Arc<UserNotificationRepository>,
+        admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+        email_service: Arc<dyn EmailServiceTrait>,
+    ) -> Self {
+        Self {
+            user_notification_repository,
+            admin_settings_service,
+            email_service,
+        }
+    }
+
+    /// Sends happen on a spawned task so SMTP latency never blocks the request
+    pub fn notify(&self, audit_log: AuditLog) {
+        let notifier = self.clone();
+
+        tokio::spawn(async move {
+            notifier.send_notifications(audit_log).await;
+        });
+    }
+
+    /// Emails each digest subscriber a summary of their unread notifications.
+    /// In dry-run mode the emails are logged instead of sent. Returns how many digests went out.
+    pub async fn send_daily_digest(&self, dry_run: bool) -> usize {
+        let recipients = match self
+            .user_notification_repository
+            .get_digest_recipients()
+            .await
+        {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                tracing::warn!("Failed to load digest recipients: {}", e);
+                return 0;
+            }
+        };
+
+        if recipients.is_empty() {
+            return 0;
+        }
+
+        if !dry_run {
+            match self.admin_settings_service.get_all_settings().await {
+                Ok(settings) if settings.notifications.is_smtp_configured() => {}
+                Ok(_) => {
+                    tracing::warn!("Skipping daily digest: SMTP settings are incomplete");
+                    return 0;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load notification settings: {}", e);
+                    return 0;
+                }
+            }
+        }
+
+        let mut sent = 0;
+
+        for recipient in recipients {
+            let unread_count = match self
+                .user_notification_repository
+                .get_unread_count(recipient.user_id)
+                .await
+            {
+                Ok(0) => continue,
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to count unread notifications for user {}: {}",
+                        recipient.user_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let unread: Vec<NotificationWithReadStatus> = match self
+                .user_notification_repository
+                .get_notifications_with_read_status(
+                    recipient.user_id,
+                    Some(DIGEST_MAX_ITEMS),
+                    Some(0),
+                    &NotificationFilter {
+                        unread_only: true,
+                        ..Default::default()
+                    },
+                )
+                .await
+            {
+                Ok(notifications) => notifications,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to load notifications for user {}: {}",
+                        recipient.user_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if unread.is_empty() {
+                continue;
+            }
+
+            let subject = format!("[Digest] {} unread notification(s)", unread_count);
+            let body = compose_digest(&recipient.username, unread_count, &unread);
+
+            if dry_run {
+                tracing::info!(
+                    "[dry run] Daily digest for {} <{}>\nSubject: {}\n{}",
+                    recipient.username,
+                    recipient.email,
+                    subject,
+                    body
+                );
+                sent += 1;
+                continue;
+            }
+
+            match self
+                .email_service
+                .send_email(&recipient.email, &subject, &body)
+                .await
+            {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(
+                    "Failed to send daily digest to user {}: {}",
+                    recipient.user_id,
+                    e
+                ),
+            }
+        }
+
+        sent
+    }
+
+    async fn send_notifications(&self, audit_log: AuditLog) {
+        let settings = match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) => settings.notifications,
+            Err(e) => {
+                tracing::warn!("Failed to load notification settings: {}", e);
+                return;
+            }
+        };
+
+        if !settings.email_notifications {
+            return;
+        }
+
+        let recipients = match self
+            .user_notification_repository
+            .get_email_recipients(&audit_log.action)
+            .await
+        {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                tracing::warn!("Failed to load email notification recipients: {}", e);
+                return;
+            }
+        };
+
+        if recipients.is_empty() {
+            return;
+        }
+
+        if !settings.is_smtp_configured() {
+            tracing::warn!(
+                "Skipping email notification for '{}': SMTP settings are incomplete",
+                audit_log.action
+            );
+            return;
+        }
+
+        let subject = format!("[Notification] {}", audit_log.action.replace('_', " "));
+
+        for recipient in recipients {
+            let body = format!(
+                "Hi {},\n\n{}\n\nResource: {}{}\nStatus: {}\nTime: {}",
+                recipient.username,
+                audit_log
+                    .details
+                    .as_deref()
+                    .unwrap_or("A new event was recorded."),
+                audit_log.resource_type,
+                audit_log
+                    .resource_title
+                    .as_deref()
+                    .map(|title| format!(" ({})", title))
+                    .unwrap_or_default(),
+                if audit_log.success {
+                    "success"
+                } else {
+                    "failed"
+                },
+                audit_log.created_at.to_rfc3339()
+            );
+
+            if let Err(e) = self
+                .email_service
+                .send_email(&recipient.email, &subject, &body)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to send email notification to user {}: {}",
+                    recipient.user_id,
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Builds the digest body with unread items grouped by resource type
+pub fn compose_digest(
+    username: &str,
+    unread_count: i64,
+    notifications: &[NotificationWithReadStatus],
+) -> String {
+    let mut groups: BTreeMap<&str, Vec<&NotificationWithReadStatus>> = BTreeMap::new();
+    for notification in notifications {
+        groups
+            .entry(notification.resource_type.as_str())
+            .or_default()
+            .push(notification);
+    }
+
+    let mut body = format!(
+        "Hi {},\n\nYou have {} unread notification(s).\n",
+        username, unread_count
+    );
+
+    for (resource_type, items) in groups {
+        body.push_str(&format!("\n{} ({})\n", resource_type, items.len()));
+        for item in items {
+            body.push_str(&format!(
+                "- {}{} at {}\n",
+                item.action.replace('_', " "),
+                item.resource_title
+                    .as_deref()
+                    .map(|title| format!(": {}", title))
+                    .unwrap_or_default(),
+                item.created_at.format("%Y-%m-%d %H:%M UTC")
+            ));
+        }
+    }
+
+    if unread_count > notifications.len() as i64 {
+        body.push_str(&format!(
+            "\n...and {} more.\n",
+            unread_count - notifications.len() as i64
+        ));
+    }
+
+    body
+}
+
+/// Time left until the next occurrence of `send_time` (UTC)
+pub fn duration_until_next(send_time: NaiveTime, now: DateTime<Utc>) -> std::time::Duration {
+    let today = now.date_naive().and_time(send_time).and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+
+    (next - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn notification(resource_type: &str, action: &str, title: &str) -> NotificationWithReadStatus {
+        NotificationWithReadStatus {
+            id: Uuid::new_v4(),
+            user_id: None,
+            user_name: None,
+            action: action.to_string(),
+            resource_type: resource_type.to_string(),
+            resource_id: None,
+            resource_title: Some(title.to_string()),
+            details: None,
+            success: true,
+            error_message: None,
+            created_at: Utc.with_ymd_and_hms(2025, 6, 20, 9, 30, 0).unwrap(),
+            read: false,
+            read_at: None,
+        }
+    }
+
+    #[test]
+    fn test_compose_digest_groups_by_resource_type() {
+        let notifications = vec![
+            notification("post", "post_created", "Hello"),
+            notification("comment", "comment_created", "Nice post"),
+            notification("post", "post_published", "Hello"),
+        ];
+
+        let body = compose_digest("admin", 3, &notifications);
+
+        assert!(body.contains("You have 3 unread notification(s)."));
+        assert!(body.contains("comment (1)\n- comment created: Nice post"));
+        assert!(body
+            .contains("post (2)\n- post created: Hello at 2025-06-20 09:30 UTC\n- post published"));
+        assert!(!body.contains("more."));
+    }
+
+    #[test]
+    fn test_compose_digest_mentions_items_beyond_the_list() {
+        let notifications = vec![notification("post", "post_created", "Hello")];
+
+        let body = compose_digest("admin", 5, &notifications);
+
+        assert!(body.contains("...and 4 more."));
+    }
+
+    #[test]
+    fn test_duration_until_next_send_time() {
+        let send_time = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+
+        let before = Utc.with_ymd_and_hms(2025, 6, 20, 7, 30, 0).unwrap();
+        assert_eq!(
+            duration_until_next(send_time, before),
+            std::time::Duration::from_secs(30 * 60)
+        );
+
+        // Already past today's send time, so wait for tomorrow
+        let after = Utc.with_ymd_and_hms(2025, 6, 20, 8, 0, 0).unwrap();
+        assert_eq!(
+            duration_until_next(send_time, after),
+            std::time::Duration::from_secs(24 * 60 * 60)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use lettre::{
+    message::header::ContentType, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use std::sync::Arc;
+
+use crate::{
+    models::admin_settings::NotificationSettings,
+    services::admin_settings_service::AdminSettingsServiceTrait, utils::errors::AppError,
+};
+
+type Result<T> = std::result::Result<T, AppError>;
+
+#[async_trait]
+pub trait EmailServiceTrait: Send + Sync {
+    async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct EmailService {
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+}
+
+impl EmailService {
+    pub fn new(admin_settings_service: Arc<dyn AdminSettingsServiceTrait>) -> Self {
+        Self {
+            admin_settings_service,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailServiceTrait for EmailService {
+    async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<()> {
+        // SMTP configuration lives in admin settings so it can change without a redeploy
+        let settings = self
+            .admin_settings_service
+            .get_all_settings()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get settings: {}", e)))?
+            .notifications;
+
+        if !settings.email_notifications {
+            return Err(AppError::ExternalService(
+                "Email notifications are disabled".to_string(),
+            ));
+        }
+
+        send_with_settings(&settings, to, subject, body).await
+    }
+}
+
+/// Sends one email with the given SMTP settings, whether or not email notifications are enabled
+pub async fn send_with_settings(
+    settings: &NotificationSettings,
+    to: &str,
+    subject: &str,
+    body: &str,
+) -> Result<()> {
+    let host = settings
+        .smtp_host
+        .clone()
+        .filter(|host| !host.trim().is_empty())
+        .ok_or_else(|| AppError::ExternalService("SMTP host is not configured".to_string()))?;
+    let from = settings
+        .smtp_username
+        .clone()
+        .filter(|username| !username.trim().is_empty())
+        .ok_or_else(|| AppError::ExternalService("SMTP username is not configured".to_string()))?;
+
+    let message = Message::builder()
+        .from(
+            from.parse()
+                .map_err(|_| AppError::ExternalService("Invalid sender address".to_string()))?,
+        )
+        .to(to
+            .parse()
+            .map_err(|_| AppError::Validation("Invalid recipient address".to_string()))?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+
+    // Port 465 uses implicit TLS, everything else upgrades with STARTTLS
+    let port = settings.smtp_port.unwrap_or(587) as u16;
+    let builder = if port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+    }
+    .map_err(|e| AppError::ExternalService(format!("Invalid SMTP configuration: {}", e)))?
+    .port(port);
+
+    let builder = match (
+        settings.smtp_username.clone(),
+        settings.smtp_password.clone(),
+    ) {
+        (Some(username), Some(password)) => {
+            builder.credentials(Credentials::new(username, password))
+        }
+        _ => builder,
+    };
+
+    builder
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| AppError::ExternalService(format!("Failed to send email: {}", e)))?;
+
+    Ok(())
+}
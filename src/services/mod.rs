@@ -1,8 +1,18 @@
 pub mod admin_settings_service;
+pub mod analytics_service;
+pub mod api_key_service;
 pub mod audit_log_service;
 pub mod auth_service;
 pub mod blog_service;
 pub mod comment_service;
+pub mod email_notifier;
+pub mod email_service;
+pub mod notification_hub;
+pub mod notification_tester;
 pub mod portfolio_service;
+pub mod search_service;
 pub mod service_service;
+pub mod storage;
 pub mod user_notification_service;
+pub mod user_service;
+pub mod webhook_dispatcher;
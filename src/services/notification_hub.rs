@@ -0,0 +1,46 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::models::user_notification::NotificationWithReadStatus;
+
+// Slow clients that fall further behind than this lose the oldest notifications
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Registry of per-user broadcast channels for pushing notifications to connected clients.
+#[derive(Clone, Default)]
+pub struct NotificationHub {
+    channels: Arc<DashMap<Uuid, broadcast::Sender<NotificationWithReadStatus>>>,
+}
+
+impl NotificationHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self, user_id: Uuid) -> broadcast::Receiver<NotificationWithReadStatus> {
+        self.channels
+            .entry(user_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    pub fn publish(&self, user_id: Uuid, notification: NotificationWithReadStatus) {
+        if let Some(sender) = self.channels.get(&user_id) {
+            // Only fails when nobody is listening, which the cleanup below handles
+            let _ = sender.send(notification);
+        }
+        self.channels
+            .remove_if(&user_id, |_, sender| sender.receiver_count() == 0);
+    }
+
+    /// Every audit log is a notification for every user, so fan out to all connected users
+    pub fn publish_to_all(&self, notification: NotificationWithReadStatus) {
+        let user_ids: Vec<Uuid> = self.channels.iter().map(|entry| *entry.key()).collect();
+
+        for user_id in user_ids {
+            self.publish(user_id, notification.clone());
+        }
+    }
+}
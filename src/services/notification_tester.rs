@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+
+use crate::models::admin_settings::NotificationSettings;
+use crate::services::email_service::send_with_settings;
+
+// Each channel gets this long to connect and send before it is reported as failed
+const CHANNEL_TIMEOUT: Duration = Duration::from_secs(10);
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+const TEST_MESSAGE: &str = "This is a test notification from your site's admin settings.";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelTestResult {
+    pub channel: String,
+    pub configured: bool,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationTestReport {
+    // True only when at least one channel was tried and none of them failed
+    pub success: bool,
+    pub channels: Vec<ChannelTestResult>,
+}
+
+/// A notification channel that can send a one-off test message
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn is_configured(&self, settings: &NotificationSettings) -> bool;
+    async fn send_test(&self, settings: &NotificationSettings) -> Result<(), String>;
+}
+
+/// Sends a test email from the SMTP username to itself
+pub struct SmtpChannel;
+
+#[async_trait]
+impl NotificationChannel for SmtpChannel {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    fn is_configured(&self, settings: &NotificationSettings) -> bool {
+        settings.is_smtp_configured()
+    }
+
+    async fn send_test(&self, settings: &NotificationSettings) -> Result<(), String> {
+        let to = settings.smtp_username.as_deref().unwrap_or_default();
+
+        send_with_settings(settings, to, "Test notification", TEST_MESSAGE)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Checks the bot token with `getMe`, then posts a test message to the configured chat
+pub struct TelegramChannel {
+    client: reqwest::Client,
+}
+
+impl TelegramChannel {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn call(&self, token: &str, method: &str, body: serde_json::Value) -> Result<(), String> {
+        let response = self
+            .client
+            .post(format!("{}/bot{}/{}", TELEGRAM_API_URL, token, method))
+            .json(&body)
+            .send()
+            .await
+            // The request URL contains the bot token
+            .map_err(|e| format!("Telegram {} failed: {}", method, e.without_url()))?;
+
+        if response.status().is_success() {
+            return Ok(());
+        }
+
+        let status = response.status();
+        let description = response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body["description"].as_str().map(str::to_string))
+            .unwrap_or_else(|| status.to_string());
+
+        Err(format!("Telegram {} failed: {}", method, description))
+    }
+}
+
+impl Default for TelegramChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for TelegramChannel {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn is_configured(&self, settings: &NotificationSettings) -> bool {
+        settings.is_telegram_configured()
+    }
+
+    async fn send_test(&self, settings: &NotificationSettings) -> Result<(), String> {
+        let token = settings.telegram_bot_token.as_deref().unwrap_or_default();
+        let chat_id = settings.telegram_chat_id.as_deref().unwrap_or_default();
+
+        self.call(token, "getMe", json!({})).await?;
+        self.call(
+            token,
+            "sendMessage",
+            json!({ "chat_id": chat_id, "text": TEST_MESSAGE }),
+        )
+        .await
+    }
+}
+
+/// Tries every configured channel with the given settings. Nothing is persisted.
+pub struct NotificationTester {
+    channels: Vec<Arc<dyn NotificationChannel>>,
+    timeout: Duration,
+}
+
+impl NotificationTester {
+    pub fn new(channels: Vec<Arc<dyn NotificationChannel>>, timeout: Duration) -> Self {
+        Self { channels, timeout }
+    }
+
+    pub async fn test_channels(&self, settings: &NotificationSettings) -> NotificationTestReport {
+        let mut channels = Vec::with_capacity(self.channels.len());
+
+        for channel in &self.channels {
+            if !channel.is_configured(settings) {
+                channels.push(ChannelTestResult {
+                    channel: channel.name().to_string(),
+                    configured: false,
+                    success: false,
+                    message: "Not configured".to_string(),
+                });
+                continue;
+            }
+
+            let outcome =
+                match tokio::time::timeout(self.timeout, channel.send_test(settings)).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => Err(format!(
+                        "Timed out after {} seconds",
+                        self.timeout.as_secs()
+                    )),
+                };
+
+            channels.push(ChannelTestResult {
+                channel: channel.name().to_string(),
+                configured: true,
+                success: outcome.is_ok(),
+                message: match outcome {
+                    Ok(()) => "Test message sent".to_string(),
+                    Err(error) => redact_secrets(&error, settings),
+                },
+            });
+        }
+
+        let tested: Vec<_> = channels.iter().filter(|result| result.configured).collect();
+        let success = !tested.is_empty() && tested.iter().all(|result| result.success);
+
+        NotificationTestReport { success, channels }
+    }
+}
+
+impl Default for NotificationTester {
+    fn default() -> Self {
+        Self::new(
+            vec![Arc::new(SmtpChannel), Arc::new(TelegramChannel::new())],
+            CHANNEL_TIMEOUT,
+        )
+    }
+}
+
+// Transport errors can echo credentials back, e.g. in a failed request URL
+fn redact_secrets(message: &str, settings: &NotificationSettings) -> String {
+    [&settings.smtp_password, &settings.telegram_bot_token]
+        .into_iter()
+        .flatten()
+        .filter(|secret| !secret.is_empty())
+        .fold(message.to_string(), |message, secret| {
+            message.replace(secret.as_str(), "[REDACTED]")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockChannel {
+        name: &'static str,
+        configured: bool,
+        outcome: Result<(), String>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl NotificationChannel for MockChannel {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn is_configured(&self, _settings: &NotificationSettings) -> bool {
+            self.configured
+        }
+
+        async fn send_test(&self, _settings: &NotificationSettings) -> Result<(), String> {
+            tokio::time::sleep(self.delay).await;
+            self.outcome.clone()
+        }
+    }
+
+    fn mock(name: &'static str, configured: bool, outcome: Result<(), String>) -> MockChannel {
+        MockChannel {
+            name,
+            configured,
+            outcome,
+            delay: Duration::ZERO,
+        }
+    }
+
+    fn settings() -> NotificationSettings {
+        NotificationSettings {
+            smtp_password: Some("hunter2".to_string()),
+            telegram_bot_token: Some("123:secret-token".to_string()),
+            ..NotificationSettings::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_lists_each_channel() {
+        let tester = NotificationTester::new(
+            vec![
+                Arc::new(mock("smtp", true, Ok(()))),
+                Arc::new(mock("telegram", false, Ok(()))),
+            ],
+            Duration::from_secs(1),
+        );
+
+        let report = tester.test_channels(&settings()).await;
+
+        assert!(report.success);
+        assert_eq!(report.channels.len(), 2);
+        assert!(report.channels[0].configured && report.channels[0].success);
+        assert_eq!(report.channels[1].channel, "telegram");
+        assert!(!report.channels[1].configured && !report.channels[1].success);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_redacted() {
+        let tester = NotificationTester::new(
+            vec![
+                Arc::new(mock("smtp", true, Ok(()))),
+                Arc::new(mock(
+                    "telegram",
+                    true,
+                    Err("POST /bot123:secret-token/getMe failed; smtp said hunter2".to_string()),
+                )),
+            ],
+            Duration::from_secs(1),
+        );
+
+        let report = tester.test_channels(&settings()).await;
+
+        assert!(!report.success);
+        let telegram = &report.channels[1];
+        assert!(!telegram.success);
+        assert!(!telegram.message.contains("secret-token"));
+        assert!(!telegram.message.contains("hunter2"));
+        assert!(telegram.message.contains("[REDACTED]"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_channels_time_out() {
+        let tester = NotificationTester::new(
+            vec![Arc::new(MockChannel {
+                delay: Duration::from_secs(5),
+                ..mock("smtp", true, Ok(()))
+            })],
+            Duration::from_millis(10),
+        );
+
+        let report = tester.test_channels(&settings()).await;
+
+        assert!(!report.success);
+        assert!(report.channels[0].message.starts_with("Timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_nothing_configured_is_not_success() {
+        let tester = NotificationTester::new(
+            vec![Arc::new(mock("smtp", false, Ok(())))],
+            Duration::from_secs(1),
+        );
+
+        let report = tester.test_channels(&settings()).await;
+
+        assert!(!report.success);
+    }
+}
@@ -1,12 +1,18 @@
-use crate::utils::errors::AppError;
+use crate::utils::errors::{validation_error_summary, AppError};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 type Result<T> = std::result::Result<T, AppError>;
 
 use crate::{
+    middleware::rate_limiter::RedisRateLimiter,
     models::portfolio::{
-        CreatePortfolioProjectRequest, PortfolioProject, PortfolioProjectQuery,
-        PortfolioProjectsResponse, PortfolioStats, UpdatePortfolioProjectRequest,
+        BulkFeatureProjectsRequest, CreatePortfolioProjectRequest, ImportOutcome,
+        PortfolioImportResponse, PortfolioImportResult, PortfolioProject, PortfolioProjectQuery,
+        PortfolioProjectRecord, PortfolioProjectsResponse, PortfolioStats,
+        ReorderPortfolioProjectsRequest, UpdatePortfolioProjectRequest,
     },
     repositories::portfolio_repository::PortfolioRepositoryTrait,
 };
@@ -30,18 +36,44 @@ pub trait PortfolioServiceTrait: Send + Sync {
     ) -> Result<PortfolioProject>;
     async fn delete_project(&self, id: Uuid) -> Result<()>;
     async fn get_featured_projects(&self, limit: Option<u32>) -> Result<Vec<PortfolioProject>>;
+    async fn get_active_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>>;
     async fn get_portfolio_statistics(&self) -> Result<PortfolioStats>;
     async fn toggle_featured_status(&self, id: Uuid, featured: bool) -> Result<()>;
+    async fn reorder_projects(&self, request: ReorderPortfolioProjectsRequest) -> Result<()>;
+    async fn record_view(&self, id: Uuid, ip: &str) -> Result<()>;
+    async fn bulk_update_featured(&self, request: BulkFeatureProjectsRequest) -> Result<u64>;
+    async fn export_projects(&self) -> Result<Vec<PortfolioProjectRecord>>;
+    /// Upserts records by slug; invalid records are skipped and reported, not fatal
+    async fn import_projects(
+        &self,
+        records: Vec<serde_json::Value>,
+    ) -> Result<PortfolioImportResponse>;
 }
 
+// The homepage only has room for this many featured projects
+const MAX_FEATURED_PROJECTS: i64 = 10;
+
+// Repeat views from the same IP within this window are not counted
+const VIEW_DEDUP_WINDOW_SECONDS: u64 = 6 * 60 * 60;
+
+// Keeps a single import transaction reasonably short
+const MAX_IMPORT_RECORDS: usize = 500;
+
 #[derive(Clone)]
 pub struct PortfolioService {
     repository: Arc<dyn PortfolioRepositoryTrait>,
+    rate_limiter: Option<Arc<RedisRateLimiter>>,
 }
 
 impl PortfolioService {
-    pub fn new(repository: Arc<dyn PortfolioRepositoryTrait>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn PortfolioRepositoryTrait>,
+        rate_limiter: Option<Arc<RedisRateLimiter>>,
+    ) -> Self {
+        Self {
+            repository,
+            rate_limiter,
+        }
     }
 }
 
@@ -86,8 +118,18 @@ impl PortfolioServiceTrait for PortfolioService {
             ));
         }
 
-        // Business logic: Portfolio projects don't use slugs in this model
-        // This validation was for a different model structure
+        // Business logic: Auto-generate slug if empty
+        let mut request = request;
+        if request.slug.trim().is_empty() {
+            request.slug = Self::generate_slug(&request.title);
+        }
+        if request.slug.is_empty() {
+            // Titles made only of punctuation produce nothing usable
+            request.slug = "project".to_string();
+        }
+
+        // Business logic: Append a numeric suffix until the slug is unique
+        request.slug = self.unique_slug(&request.slug).await?;
 
         self.repository.create(request).await
     }
@@ -117,6 +159,15 @@ impl PortfolioServiceTrait for PortfolioService {
             ));
         }
 
+        // Business logic: Validate slug uniqueness (excluding current project)
+        if self
+            .repository
+            .check_slug_exists(&request.slug, Some(id))
+            .await?
+        {
+            return Err(AppError::Validation("Slug already exists".to_string()));
+        }
+
         self.repository.update(id, request).await
     }
 
@@ -145,6 +196,10 @@ impl PortfolioServiceTrait for PortfolioService {
         self.repository.get_featured(Some(limit)).await
     }
 
+    async fn get_active_slugs(&self) -> Result<Vec<(String, DateTime<Utc>)>> {
+        self.repository.get_active_slugs().await
+    }
+
     async fn get_portfolio_statistics(&self) -> Result<PortfolioStats> {
         self.repository.get_stats().await
     }
@@ -160,15 +215,262 @@ impl PortfolioServiceTrait for PortfolioService {
         // Business logic: Limit number of featured projects
         if featured {
             let stats = self.repository.get_stats().await?;
-            if stats.featured_projects >= 10 {
-                return Err(AppError::Validation(
-                    "Cannot have more than 10 featured projects".to_string(),
-                ));
+            if stats.featured_projects >= MAX_FEATURED_PROJECTS {
+                return Err(AppError::Validation(format!(
+                    "Cannot have more than {} featured projects",
+                    MAX_FEATURED_PROJECTS
+                )));
             }
         }
 
         self.repository.update_featured_status(id, featured).await
     }
+
+    async fn reorder_projects(&self, request: ReorderPortfolioProjectsRequest) -> Result<()> {
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = request.projects.iter().find(|p| !seen.insert(p.id)) {
+            return Err(AppError::Validation(format!(
+                "Portfolio project {} is listed more than once",
+                duplicate.id
+            )));
+        }
+
+        let orders = request
+            .projects
+            .into_iter()
+            .map(|p| (p.id, p.sort_order))
+            .collect();
+
+        self.repository.reorder(orders).await
+    }
+
+    async fn record_view(&self, id: Uuid, ip: &str) -> Result<()> {
+        // Business logic: Count each IP once per window, but never stop counting when Redis is down
+        if let Some(rate_limiter) = &self.rate_limiter {
+            match rate_limiter
+                .record_view("project", &id.to_string(), ip, VIEW_DEDUP_WINDOW_SECONDS)
+                .await
+            {
+                Ok(false) => return Ok(()),
+                Ok(true) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to deduplicate project view, counting anyway: {}", e);
+                }
+            }
+        }
+
+        self.repository.increment_view_count(id).await
+    }
+
+    async fn bulk_update_featured(&self, request: BulkFeatureProjectsRequest) -> Result<u64> {
+        request.validate()?;
+
+        let mut ids = request.ids;
+        ids.sort_unstable();
+        ids.dedup();
+
+        self.repository
+            .bulk_update_featured(&ids, request.featured, MAX_FEATURED_PROJECTS)
+            .await
+    }
+
+    async fn export_projects(&self) -> Result<Vec<PortfolioProjectRecord>> {
+        let projects = self.repository.find_all_for_export().await?;
+        Ok(projects
+            .into_iter()
+            .map(PortfolioProjectRecord::from)
+            .collect())
+    }
+
+    async fn import_projects(
+        &self,
+        records: Vec<serde_json::Value>,
+    ) -> Result<PortfolioImportResponse> {
+        if records.len() > MAX_IMPORT_RECORDS {
+            return Err(AppError::Validation(format!(
+                "Cannot import more than {} projects at once",
+                MAX_IMPORT_RECORDS
+            )));
+        }
+
+        // Business logic: Check every record up front, only valid ones reach the database
+        let mut results = Vec::with_capacity(records.len());
+        let mut valid = Vec::new();
+        let mut seen_slugs = HashSet::new();
+        for (index, value) in records.into_iter().enumerate() {
+            let slug = value
+                .get("slug")
+                .and_then(|slug| slug.as_str())
+                .map(str::to_string);
+
+            let checked = Self::check_import_record(value).and_then(|record| {
+                if seen_slugs.insert(record.slug.clone()) {
+                    Ok(record)
+                } else {
+                    Err("Duplicate slug in import".to_string())
+                }
+            });
+
+            match checked {
+                Ok(record) => {
+                    valid.push((results.len(), record));
+                    results.push(PortfolioImportResult {
+                        index,
+                        slug,
+                        outcome: ImportOutcome::Skipped,
+                        error: None,
+                    });
+                }
+                Err(error) => results.push(PortfolioImportResult {
+                    index,
+                    slug,
+                    outcome: ImportOutcome::Skipped,
+                    error: Some(error),
+                }),
+            }
+        }
+
+        let (positions, records): (Vec<usize>, Vec<PortfolioProjectRecord>) =
+            valid.into_iter().unzip();
+        let outcomes = self.repository.import(&records).await?;
+        for (position, outcome) in positions.into_iter().zip(outcomes) {
+            match outcome {
+                Ok(outcome) => results[position].outcome = outcome,
+                Err(e) => results[position].error = Some(e.to_string()),
+            }
+        }
+
+        let count = |outcome| results.iter().filter(|r| r.outcome == outcome).count();
+        Ok(PortfolioImportResponse {
+            created: count(ImportOutcome::Created),
+            updated: count(ImportOutcome::Updated),
+            skipped: count(ImportOutcome::Skipped),
+            results,
+        })
+    }
+}
+
+impl PortfolioService {
+    fn generate_slug(title: &str) -> String {
+        title
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<&str>>()
+            .join("-")
+    }
+
+    fn check_import_record(
+        value: serde_json::Value,
+    ) -> std::result::Result<PortfolioProjectRecord, String> {
+        let record: PortfolioProjectRecord =
+            serde_json::from_value(value).map_err(|e| e.to_string())?;
+
+        if let Err(errors) = record.validate() {
+            return Err(validation_error_summary(&errors));
+        }
+        if record.title.trim().is_empty() {
+            return Err("Project title cannot be empty".to_string());
+        }
+        if record.description.trim().is_empty() {
+            return Err("Project description cannot be empty".to_string());
+        }
+        // Same shape generate_slug produces, so exported slugs always round-trip
+        if Self::generate_slug(&record.slug) != record.slug {
+            return Err(
+                "Slug must contain only lowercase letters, numbers and hyphens".to_string(),
+            );
+        }
+
+        Ok(record)
+    }
+
+    async fn unique_slug(&self, slug: &str) -> Result<String> {
+        let mut candidate = slug.to_string();
+        let mut suffix = 2;
+
+        while self.repository.check_slug_exists(&candidate, None).await? {
+            candidate = format!("{}-{}", slug, suffix);
+            suffix += 1;
+        }
+
+        Ok(candidate)
+    }
 }
 
-impl PortfolioService {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_slug_strips_punctuation() {
+        assert_eq!(
+            PortfolioService::generate_slug("E-commerce Platform: v2.0!"),
+            "e-commerce-platform-v2-0"
+        );
+        assert_eq!(
+            PortfolioService::generate_slug("  Rust & WebAssembly -- Demo  "),
+            "rust-webassembly-demo"
+        );
+    }
+
+    #[test]
+    fn test_generate_slug_keeps_unicode_letters() {
+        assert_eq!(
+            PortfolioService::generate_slug("Café Ünïcode Résumé"),
+            "café-ünïcode-résumé"
+        );
+        assert_eq!(PortfolioService::generate_slug("東京 Guide"), "東京-guide");
+    }
+
+    fn exported_record() -> serde_json::Value {
+        serde_json::json!({
+            "title": "Rust API",
+            "slug": "rust-api",
+            "description": "A portfolio project",
+            "long_description": null,
+            "category": "Web",
+            "technologies": ["Rust"],
+            "live_url": null,
+            "github_url": null,
+            "image_url": null,
+            "featured": false,
+            "active": true,
+            "status": "completed",
+            "start_date": "2025-01-01",
+            "end_date": null,
+            "client": null,
+            "sort_order": 0
+        })
+    }
+
+    #[test]
+    fn test_check_import_record_accepts_exported_shape() {
+        let record = PortfolioService::check_import_record(exported_record()).unwrap();
+        assert_eq!(record.slug, "rust-api");
+
+        let mut unicode = exported_record();
+        unicode["slug"] = "café-guide".into();
+        assert!(PortfolioService::check_import_record(unicode).is_ok());
+    }
+
+    #[test]
+    fn test_check_import_record_reports_invalid_fields() {
+        let mut bad_url = exported_record();
+        bad_url["live_url"] = "not a url".into();
+        let error = PortfolioService::check_import_record(bad_url).unwrap_err();
+        assert!(error.starts_with("live_url:"), "{}", error);
+
+        let mut bad_slug = exported_record();
+        bad_slug["slug"] = "Rust API".into();
+        assert!(PortfolioService::check_import_record(bad_slug).is_err());
+
+        let mut missing = exported_record();
+        missing.as_object_mut().unwrap().remove("start_date");
+        assert!(PortfolioService::check_import_record(missing).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::utils::errors::AppError;
+use std::sync::Arc;
+type Result<T> = std::result::Result<T, AppError>;
+
+use crate::{
+    models::search::{
+        SearchQuery, SearchResponse, SearchResultKind, DEFAULT_SEARCH_LIMIT_PER_KIND,
+        MAX_SEARCH_LIMIT_PER_KIND, MAX_SEARCH_RESULTS,
+    },
+    repositories::search_repository::SearchRepositoryTrait,
+};
+
+const MIN_TERM_LENGTH: usize = 2;
+const MAX_TERM_LENGTH: usize = 100;
+
+#[async_trait::async_trait]
+pub trait SearchServiceTrait: Send + Sync {
+    /// Searches the given kinds in order; kinds left out (e.g. disabled features) are skipped
+    async fn search(
+        &self,
+        query: SearchQuery,
+        kinds: &[SearchResultKind],
+    ) -> Result<SearchResponse>;
+}
+
+#[derive(Clone)]
+pub struct SearchService {
+    repository: Arc<dyn SearchRepositoryTrait>,
+}
+
+impl SearchService {
+    pub fn new(repository: Arc<dyn SearchRepositoryTrait>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchServiceTrait for SearchService {
+    async fn search(
+        &self,
+        query: SearchQuery,
+        kinds: &[SearchResultKind],
+    ) -> Result<SearchResponse> {
+        let term = query.q.as_deref().map(str::trim).unwrap_or_default();
+        let length = term.chars().count();
+        if !(MIN_TERM_LENGTH..=MAX_TERM_LENGTH).contains(&length) {
+            return Err(AppError::Validation(format!(
+                "Search term must be between {} and {} characters",
+                MIN_TERM_LENGTH, MAX_TERM_LENGTH
+            )));
+        }
+
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT_PER_KIND)
+            .clamp(1, MAX_SEARCH_LIMIT_PER_KIND);
+
+        let mut results = Vec::new();
+        for kind in kinds {
+            let hits = match kind {
+                SearchResultKind::Post => self.repository.search_posts(term, limit).await?,
+                SearchResultKind::Portfolio => self.repository.search_projects(term, limit).await?,
+                SearchResultKind::Service => self.repository.search_services(term, limit).await?,
+            };
+            results.extend(hits);
+        }
+        results.truncate(MAX_SEARCH_RESULTS);
+
+        Ok(SearchResponse {
+            query: term.to_string(),
+            total: results.len(),
+            results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::search::SearchResult;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    // Returns `limit` hits of every kind for any term
+    struct StubRepository;
+
+    fn hits(kind: SearchResultKind, limit: u32) -> Vec<SearchResult> {
+        (0..limit)
+            .map(|i| SearchResult {
+                kind,
+                id: Uuid::new_v4(),
+                title: format!("Result {}", i),
+                slug: None,
+                summary: None,
+                updated_at: Utc::now(),
+            })
+            .collect()
+    }
+
+    #[async_trait::async_trait]
+    impl SearchRepositoryTrait for StubRepository {
+        async fn search_posts(&self, _term: &str, limit: u32) -> Result<Vec<SearchResult>> {
+            Ok(hits(SearchResultKind::Post, limit))
+        }
+
+        async fn search_projects(&self, _term: &str, limit: u32) -> Result<Vec<SearchResult>> {
+            Ok(hits(SearchResultKind::Portfolio, limit))
+        }
+
+        async fn search_services(&self, _term: &str, limit: u32) -> Result<Vec<SearchResult>> {
+            Ok(hits(SearchResultKind::Service, limit))
+        }
+    }
+
+    fn service() -> SearchService {
+        SearchService::new(Arc::new(StubRepository))
+    }
+
+    fn query(q: &str, limit: Option<u32>) -> SearchQuery {
+        SearchQuery {
+            q: Some(q.to_string()),
+            limit,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_short_terms() {
+        let result = service()
+            .search(query(" a ", None), &[SearchResultKind::Post])
+            .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_skips_kinds_left_out() {
+        let response = service()
+            .search(
+                query("rust", None),
+                &[SearchResultKind::Post, SearchResultKind::Service],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 2 * DEFAULT_SEARCH_LIMIT_PER_KIND as usize);
+        assert!(response
+            .results
+            .iter()
+            .all(|r| r.kind != SearchResultKind::Portfolio));
+    }
+
+    #[tokio::test]
+    async fn test_search_caps_per_kind_and_total_results() {
+        let response = service()
+            .search(
+                query("rust", Some(100)),
+                &[
+                    SearchResultKind::Post,
+                    SearchResultKind::Portfolio,
+                    SearchResultKind::Service,
+                ],
+            )
+            .await
+            .unwrap();
+
+        let posts = response
+            .results
+            .iter()
+            .filter(|r| r.kind == SearchResultKind::Post)
+            .count();
+        assert_eq!(posts, MAX_SEARCH_LIMIT_PER_KIND as usize);
+        assert_eq!(response.total, MAX_SEARCH_RESULTS);
+    }
+}
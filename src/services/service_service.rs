@@ -1,12 +1,13 @@
 use crate::utils::errors::AppError;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 type Result<T> = std::result::Result<T, AppError>;
 
 use crate::{
     models::service::{
-        CreateServiceRequest, Service, ServiceQuery, ServiceStats, ServicesResponse,
-        UpdateServiceRequest,
+        CreateServiceRequest, ReorderServicesRequest, Service, ServiceQuery, ServiceStats,
+        ServicesResponse, UpdateServiceRequest, PRICE_TYPES, SUPPORTED_CURRENCIES,
     },
     repositories::service_repository::ServiceRepositoryTrait,
 };
@@ -22,6 +23,7 @@ pub trait ServiceServiceTrait: Send + Sync {
     async fn get_service_statistics(&self) -> Result<ServiceStats>;
     async fn toggle_service_status(&self, id: Uuid, active: bool) -> Result<()>;
     async fn get_services_by_category(&self, category: &str) -> Result<Vec<Service>>;
+    async fn reorder_services(&self, request: ReorderServicesRequest) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -59,15 +61,25 @@ impl ServiceServiceTrait for ServiceService {
         // Business logic: Normalize category
         let mut request = request;
         request.category = self.normalize_category(&request.category);
+        request.price_currency = request
+            .price_currency
+            .map(|currency| currency.trim().to_uppercase());
+        self.validate_pricing(
+            request.price_amount,
+            request.price_currency.as_deref(),
+            request.price_type.as_deref(),
+        )?;
 
         self.repository.create(request).await
     }
 
     async fn update_service(&self, id: Uuid, request: UpdateServiceRequest) -> Result<Service> {
         // Business logic: Ensure service exists
-        if self.repository.find_by_id(id).await?.is_none() {
-            return Err(AppError::NotFound("Service not found".to_string()));
-        }
+        let existing = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Service not found".to_string()))?;
 
         // Business logic: Validate service data
         self.validate_service_request(&request.title, &request.description)?;
@@ -75,6 +87,33 @@ impl ServiceServiceTrait for ServiceService {
         // Business logic: Normalize category
         let mut request = request;
         request.category = self.normalize_category(&request.category);
+        request.price_currency = request
+            .price_currency
+            .map(|currency| currency.trim().to_uppercase());
+        if request.clear_price {
+            // Business logic: Clearing the price can't be combined with setting one
+            if request.price_amount.is_some()
+                || request.price_currency.is_some()
+                || request.price_type.is_some()
+            {
+                return Err(AppError::Validation(
+                    "Price fields must be omitted when clearing the price".to_string(),
+                ));
+            }
+        } else {
+            // Business logic: Omitted price fields keep their stored values, so check the result
+            self.validate_pricing(
+                request.price_amount.or(existing.price_amount),
+                request
+                    .price_currency
+                    .as_deref()
+                    .or(existing.price_currency.as_deref()),
+                request
+                    .price_type
+                    .as_deref()
+                    .or(existing.price_type.as_deref()),
+            )?;
+        }
 
         self.repository.update(id, request).await
     }
@@ -113,6 +152,24 @@ impl ServiceServiceTrait for ServiceService {
         let normalized_category = self.normalize_category(category);
         self.repository.get_by_category(&normalized_category).await
     }
+
+    async fn reorder_services(&self, request: ReorderServicesRequest) -> Result<()> {
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = request.services.iter().find(|s| !seen.insert(s.id)) {
+            return Err(AppError::Validation(format!(
+                "Service {} is listed more than once",
+                duplicate.id
+            )));
+        }
+
+        let orders = request
+            .services
+            .into_iter()
+            .map(|s| (s.id, s.display_order))
+            .collect();
+
+        self.repository.reorder(orders).await
+    }
 }
 
 impl ServiceService {
@@ -144,6 +201,53 @@ impl ServiceService {
         Ok(())
     }
 
+    fn validate_pricing(
+        &self,
+        amount: Option<i64>,
+        currency: Option<&str>,
+        price_type: Option<&str>,
+    ) -> Result<()> {
+        if let Some(currency) = currency {
+            if !SUPPORTED_CURRENCIES
+                .iter()
+                .any(|(code, _)| *code == currency)
+            {
+                return Err(AppError::Validation(format!(
+                    "Invalid currency '{}'. Must be one of: {}",
+                    currency,
+                    SUPPORTED_CURRENCIES
+                        .iter()
+                        .map(|(code, _)| *code)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+
+        if let Some(price_type) = price_type {
+            if !PRICE_TYPES.contains(&price_type) {
+                return Err(AppError::Validation(format!(
+                    "Invalid price type '{}'. Must be one of: {}",
+                    price_type,
+                    PRICE_TYPES.join(", ")
+                )));
+            }
+        }
+
+        match amount {
+            Some(amount) if amount < 0 => Err(AppError::Validation(
+                "Price amount cannot be negative".to_string(),
+            )),
+            Some(_) if currency.is_none() => Err(AppError::Validation(
+                "Price currency is required when a price amount is set".to_string(),
+            )),
+            None if currency.is_some() || price_type.is_some() => Err(AppError::Validation(
+                "Price amount is required when a currency or price type is set".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn normalize_category(&self, category: &str) -> String {
         category
             .trim()
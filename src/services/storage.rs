@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::utils::config::{FileUploadConfig, StorageBackendKind};
+
+/// Where uploaded files end up. Implementations return the public URL the
+/// stored object is reachable at, so an object store (e.g. S3) can be swapped
+/// in without touching the upload handler.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    async fn store(&self, key: &str, content_type: &str, data: &[u8]) -> Result<String>;
+    /// Removes a stored object; deleting a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Builds the backend selected by `file_upload.backend`.
+pub fn build_storage_backend(config: &FileUploadConfig) -> Result<Arc<dyn StorageBackend>> {
+    match config.backend {
+        StorageBackendKind::Local => Ok(Arc::new(LocalDiskBackend::new(
+            &config.directory,
+            &config.public_url,
+        ))),
+        #[cfg(feature = "s3")]
+        StorageBackendKind::S3 => Ok(Arc::new(S3Backend::new(&config.s3)?)),
+        #[cfg(not(feature = "s3"))]
+        StorageBackendKind::S3 => {
+            anyhow::bail!(
+                "file_upload.backend is s3 but the server was built without the \"s3\" feature"
+            )
+        }
+    }
+}
+
+/// Stores uploads on the local filesystem; the directory is expected to be
+/// served under `public_url`.
+pub struct LocalDiskBackend {
+    directory: PathBuf,
+    public_url: String,
+}
+
+impl LocalDiskBackend {
+    pub fn new(directory: impl Into<PathBuf>, public_url: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            public_url: public_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            anyhow::bail!("Invalid storage key: {}", key);
+        }
+
+        Ok(self.directory.join(relative))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalDiskBackend {
+    async fn store(&self, key: &str, _content_type: &str, data: &[u8]) -> Result<String> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .context("Failed to create upload directory")?;
+        }
+        tokio::fs::write(&path, data)
+            .await
+            .context("Failed to write uploaded file")?;
+
+        Ok(format!("{}/{}", self.public_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).context("Failed to delete uploaded file"),
+        }
+    }
+}
+
+/// Stores uploads in an S3 bucket or an S3-compatible store such as MinIO.
+#[cfg(feature = "s3")]
+pub struct S3Backend {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    public_url: Option<String>,
+    presign_expiry: std::time::Duration,
+}
+
+#[cfg(feature = "s3")]
+impl S3Backend {
+    pub fn new(config: &crate::utils::config::S3Config) -> Result<Self> {
+        use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+
+        let (Some(access_key_id), Some(secret_access_key)) =
+            (&config.access_key_id, &config.secret_access_key)
+        else {
+            anyhow::bail!("S3 storage requires storage credentials in .secret.yaml");
+        };
+
+        let mut builder = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(config.region.clone()))
+            .credentials_provider(Credentials::new(
+                access_key_id,
+                secret_access_key,
+                None,
+                None,
+                "portfolio-backend",
+            ))
+            .force_path_style(config.force_path_style);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        Ok(Self {
+            client: aws_sdk_s3::Client::from_conf(builder.build()),
+            bucket: config.bucket.clone(),
+            public_url: config
+                .public_url
+                .as_ref()
+                .map(|url| url.trim_end_matches('/').to_string()),
+            presign_expiry: std::time::Duration::from_secs(config.presign_expiry_seconds),
+        })
+    }
+}
+
+#[cfg(feature = "s3")]
+#[async_trait]
+impl StorageBackend for S3Backend {
+    async fn store(&self, key: &str, content_type: &str, data: &[u8]) -> Result<String> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(data.to_vec().into())
+            .send()
+            .await
+            .with_context(|| format!("Failed to upload {} to bucket {}", key, self.bucket))?;
+
+        if let Some(public_url) = &self.public_url {
+            return Ok(format!("{}/{}", public_url, key));
+        }
+
+        let presigned = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(aws_sdk_s3::presigning::PresigningConfig::expires_in(
+                self.presign_expiry,
+            )?)
+            .await
+            .with_context(|| format!("Failed to presign URL for {}", key))?;
+
+        Ok(presigned.uri().to_string())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("Failed to delete {} from bucket {}", key, self.bucket))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_disk_backend_rejects_path_traversal() {
+        let directory = std::env::temp_dir().join(format!("storage-{}", uuid::Uuid::new_v4()));
+        let backend = LocalDiskBackend::new(&directory, "/uploads");
+
+        assert!(backend
+            .store("../escape.png", "image/png", b"data")
+            .await
+            .is_err());
+        assert!(backend
+            .store("/etc/escape.png", "image/png", b"data")
+            .await
+            .is_err());
+
+        let url = backend
+            .store("2025/06/image.png", "image/png", b"data")
+            .await
+            .unwrap();
+        assert_eq!(url, "/uploads/2025/06/image.png");
+        assert_eq!(
+            tokio::fs::read(directory.join("2025/06/image.png"))
+                .await
+                .unwrap(),
+            b"data"
+        );
+
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[cfg(feature = "s3")]
+    fn s3_config(endpoint: String) -> crate::utils::config::S3Config {
+        crate::utils::config::S3Config {
+            bucket: "media".to_string(),
+            endpoint: Some(endpoint),
+            force_path_style: true,
+            access_key_id: Some("test-access-key".to_string()),
+            secret_access_key: Some("test-secret-key".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "s3")]
+    #[tokio::test]
+    async fn test_s3_backend_uploads_and_deletes_objects() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/media/2025/06/image.png"))
+            .and(header("content-type", "image/png"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/media/2025/06/image.png"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = S3Backend::new(&s3_config(server.uri())).unwrap();
+
+        let url = backend
+            .store("2025/06/image.png", "image/png", b"data")
+            .await
+            .unwrap();
+        assert!(url.starts_with(&format!("{}/media/2025/06/image.png?", server.uri())));
+        assert!(url.contains("X-Amz-Signature="));
+
+        backend.delete("2025/06/image.png").await.unwrap();
+    }
+
+    #[cfg(feature = "s3")]
+    #[tokio::test]
+    async fn test_s3_backend_uses_public_url_and_reports_failures() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut config = s3_config(server.uri());
+        config.public_url = Some("https://cdn.example.com/".to_string());
+        let backend = S3Backend::new(&config).unwrap();
+
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        assert_eq!(
+            backend
+                .store("2025/06/image.png", "image/png", b"data")
+                .await
+                .unwrap(),
+            "https://cdn.example.com/2025/06/image.png"
+        );
+
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        let err = backend
+            .store("2025/06/other.png", "image/png", b"data")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to upload 2025/06/other.png to bucket media"));
+    }
+
+    #[cfg(feature = "s3")]
+    #[test]
+    fn test_s3_backend_requires_credentials() {
+        let mut config = s3_config("http://localhost:9000".to_string());
+        config.secret_access_key = None;
+
+        assert!(S3Backend::new(&config).is_err());
+    }
+}
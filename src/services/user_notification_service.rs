@@ -1,8 +1,12 @@
 use crate::{
-    models::user_notification::{
-        MarkNotificationReadRequest, MarkNotificationsReadRequest, NotificationStats,
-        UpdateNotificationPreferenceRequest, UserNotificationPreference, UserNotificationRead,
-        UserNotificationsResponse,
+    models::{
+        audit_log::ResourceType,
+        user_notification::{
+            MarkNotificationReadRequest, MarkNotificationsReadRequest, NotificationFilter,
+            NotificationStats, UnreadNotificationSummary, UpdateNotificationPreferenceRequest,
+            UserNotificationPreference, UserNotificationRead, UserNotificationsResponse,
+            NOTIFICATION_TYPES,
+        },
     },
     repositories::UserNotificationRepository,
     utils::errors::AppError,
@@ -19,6 +23,7 @@ pub trait UserNotificationServiceTrait: Send + Sync {
         user_id: Uuid,
         limit: Option<i64>,
         offset: Option<i64>,
+        filter: NotificationFilter,
     ) -> Result<UserNotificationsResponse>;
     async fn mark_notification_read(
         &self,
@@ -33,6 +38,7 @@ pub trait UserNotificationServiceTrait: Send + Sync {
     async fn mark_all_notifications_read(&self, user_id: Uuid) -> Result<i64>;
     async fn get_notification_stats(&self, user_id: Uuid) -> Result<NotificationStats>;
     async fn get_unread_count(&self, user_id: Uuid) -> Result<i64>;
+    async fn get_unread_summary(&self, user_id: Uuid) -> Result<UnreadNotificationSummary>;
     async fn get_user_preferences(&self, user_id: Uuid) -> Result<Vec<UserNotificationPreference>>;
     async fn update_notification_preference(
         &self,
@@ -40,6 +46,7 @@ pub trait UserNotificationServiceTrait: Send + Sync {
         request: UpdateNotificationPreferenceRequest,
     ) -> Result<UserNotificationPreference>;
     async fn initialize_user_preferences(&self, user_id: Uuid) -> Result<()>;
+    async fn cleanup_old_read_notifications(&self) -> Result<i64>;
 }
 
 pub struct UserNotificationService {
@@ -52,36 +59,31 @@ impl UserNotificationService {
     }
 
     fn validate_notification_type(&self, notification_type: &str) -> Result<(), AppError> {
-        let valid_types = vec![
-            "login",
-            "logout",
-            "post_created",
-            "post_updated",
-            "post_published",
-            "portfolio_created",
-            "portfolio_updated",
-            "service_created",
-            "service_updated",
-            "comment_approved",
-            "comment_rejected",
-            "settings_updated",
-            "profile_updated",
-            "error",
-            "warning",
-            "system_alert",
-        ];
-
-        if !valid_types.contains(&notification_type) {
+        if !NOTIFICATION_TYPES.contains(&notification_type) {
             return Err(AppError::Validation(format!(
                 "Invalid notification type: {}. Valid types are: {}",
                 notification_type,
-                valid_types.join(", ")
+                NOTIFICATION_TYPES.join(", ")
             )));
         }
 
         Ok(())
     }
 
+    fn validate_filter(&self, filter: &NotificationFilter) -> Result<(), AppError> {
+        if let Some(action) = &filter.action {
+            self.validate_notification_type(action)?;
+        }
+
+        if let Some(resource_type) = &filter.resource_type {
+            resource_type
+                .parse::<ResourceType>()
+                .map_err(AppError::Validation)?;
+        }
+
+        Ok(())
+    }
+
     fn validate_delivery_method(&self, delivery_method: &str) -> Result<(), AppError> {
         let valid_methods = ["in_app", "email", "both"];
 
@@ -104,7 +106,10 @@ impl UserNotificationServiceTrait for UserNotificationService {
         user_id: Uuid,
         limit: Option<i64>,
         offset: Option<i64>,
+        filter: NotificationFilter,
     ) -> Result<UserNotificationsResponse> {
+        self.validate_filter(&filter)?;
+
         // Validate limits
         let limit = match limit {
             Some(l) if l > 100 => 100, // Cap at 100
@@ -113,12 +118,18 @@ impl UserNotificationServiceTrait for UserNotificationService {
             None => 20, // Default
         };
 
-        let offset = offset.unwrap_or(0);
+        let offset = offset.unwrap_or(0).max(0);
 
         // Get notifications with read status
         let notifications = self
             .repository
-            .get_notifications_with_read_status(user_id, Some(limit), Some(offset))
+            .get_notifications_with_read_status(user_id, Some(limit), Some(offset), &filter)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let total = self
+            .repository
+            .count_notifications(user_id, &filter)
             .await
             .map_err(|e| AppError::Internal(e.to_string()))?;
 
@@ -138,6 +149,11 @@ impl UserNotificationServiceTrait for UserNotificationService {
 
         Ok(UserNotificationsResponse {
             notifications,
+            total,
+            // Paging is offset-based; the page number is only informational
+            page: offset / limit + 1,
+            limit,
+            total_pages: (total + limit - 1) / limit,
             stats,
             preferences,
         })
@@ -198,6 +214,19 @@ impl UserNotificationServiceTrait for UserNotificationService {
             .map_err(|e| AppError::Internal(e.to_string()).into())
     }
 
+    async fn get_unread_summary(&self, user_id: Uuid) -> Result<UnreadNotificationSummary> {
+        let by_type = self
+            .repository
+            .get_unread_counts_by_type(user_id)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(UnreadNotificationSummary {
+            total_unread: by_type.iter().map(|count| count.unread).sum(),
+            by_type,
+        })
+    }
+
     async fn get_user_preferences(&self, user_id: Uuid) -> Result<Vec<UserNotificationPreference>> {
         self.repository
             .get_user_preferences(user_id)
@@ -232,4 +261,11 @@ impl UserNotificationServiceTrait for UserNotificationService {
             .await
             .map_err(|e| AppError::Internal(e.to_string()).into())
     }
+
+    async fn cleanup_old_read_notifications(&self) -> Result<i64> {
+        self.repository
+            .cleanup_old_read_notifications()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()).into())
+    }
 }
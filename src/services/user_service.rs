@@ -0,0 +1,108 @@
+use crate::utils::errors::AppError;
+use std::sync::Arc;
+use uuid::Uuid;
+type Result<T> = std::result::Result<T, AppError>;
+
+use crate::{
+    models::user::{User, UserQuery, UsersResponse, ADMIN_ROLE, USER_ROLES},
+    repositories::{
+        refresh_token_repository::RefreshTokenRepositoryTrait, user_repository::UserRepositoryTrait,
+    },
+};
+
+#[async_trait::async_trait]
+pub trait UserServiceTrait: Send + Sync {
+    async fn list_users(&self, query: UserQuery) -> Result<UsersResponse>;
+    async fn get_user(&self, id: Uuid) -> Result<User>;
+    async fn set_user_active(&self, id: Uuid, is_active: bool) -> Result<User>;
+    async fn change_user_role(&self, id: Uuid, role: String) -> Result<User>;
+}
+
+#[derive(Clone)]
+pub struct UserService {
+    repository: Arc<dyn UserRepositoryTrait>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+}
+
+impl UserService {
+    pub fn new(
+        repository: Arc<dyn UserRepositoryTrait>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            refresh_token_repository,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserServiceTrait for UserService {
+    async fn list_users(&self, query: UserQuery) -> Result<UsersResponse> {
+        if let Some(role) = &query.role {
+            validate_role(role)?;
+        }
+
+        self.repository.find_all(query).await
+    }
+
+    async fn get_user(&self, id: Uuid) -> Result<User> {
+        self.repository
+            .find_by_id_including_inactive(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn set_user_active(&self, id: Uuid, is_active: bool) -> Result<User> {
+        let user = self.get_user(id).await?;
+        if user.is_active == is_active {
+            return Ok(user);
+        }
+
+        // Business logic: The site must always keep at least one active admin; the
+        // repository checks this in the same transaction as the update
+        let user = self.repository.set_active(id, is_active).await?;
+
+        // Business logic: A deactivated user can't mint new access tokens either
+        if !is_active {
+            self.refresh_token_repository
+                .revoke_all_for_user(id)
+                .await?;
+        }
+
+        Ok(user)
+    }
+
+    async fn change_user_role(&self, id: Uuid, role: String) -> Result<User> {
+        validate_role(&role)?;
+
+        let user = self.get_user(id).await?;
+        if user.role == role {
+            return Ok(user);
+        }
+
+        let user = self.repository.update_role(id, &role).await?;
+
+        // Business logic: Access tokens carry the role, so a demoted user has to log in
+        // again; their current token keeps the old role until it expires
+        if role != ADMIN_ROLE {
+            self.refresh_token_repository
+                .revoke_all_for_user(id)
+                .await?;
+        }
+
+        Ok(user)
+    }
+}
+
+fn validate_role(role: &str) -> Result<()> {
+    if !USER_ROLES.contains(&role) {
+        return Err(AppError::Validation(format!(
+            "Invalid role '{}'. Must be one of: {}",
+            role,
+            USER_ROLES.join(", ")
+        )));
+    }
+
+    Ok(())
+}
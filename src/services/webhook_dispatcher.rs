@@ -0,0 +1,115 @@
+use reqwest::header::CONTENT_TYPE;
+use std::{sync::Arc, time::Duration};
+
+use crate::models::audit_log::AuditLog;
+use crate::services::admin_settings_service::AdminSettingsServiceTrait;
+use crate::utils::webhook::{
+    should_forward_action, sign_webhook_payload, WEBHOOK_SIGNATURE_HEADER,
+};
+
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Forwards audit log events to the webhook configured in notification settings.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    admin_settings_service: Arc<dyn AdminSettingsServiceTrait>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(admin_settings_service: Arc<dyn AdminSettingsServiceTrait>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            admin_settings_service,
+        }
+    }
+
+    /// Fire-and-forget: delivery runs on a spawned task so callers never wait on the endpoint
+    pub fn dispatch(&self, audit_log: AuditLog) {
+        let dispatcher = self.clone();
+
+        tokio::spawn(async move {
+            dispatcher.deliver(audit_log).await;
+        });
+    }
+
+    async fn deliver(&self, audit_log: AuditLog) {
+        let notifications = match self.admin_settings_service.get_all_settings().await {
+            Ok(settings) => settings.notifications,
+            Err(e) => {
+                tracing::warn!("Failed to load webhook settings: {}", e);
+                return;
+            }
+        };
+
+        let Some(url) = notifications
+            .webhook_url
+            .filter(|url| !url.trim().is_empty())
+        else {
+            return;
+        };
+
+        if !should_forward_action(&audit_log.action, &notifications.webhook_events) {
+            return;
+        }
+
+        let payload = match serde_json::to_vec(&audit_log) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize audit log for webhook: {}", e);
+                return;
+            }
+        };
+
+        let signature = notifications
+            .webhook_secret
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| sign_webhook_payload(&secret, &payload));
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request = self
+                .client
+                .post(&url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(payload.clone());
+            if let Some(ref signature) = signature {
+                request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => tracing::warn!(
+                    "Webhook delivery attempt {}/{} returned {}",
+                    attempt,
+                    MAX_ATTEMPTS,
+                    response.status()
+                ),
+                Err(e) => tracing::warn!(
+                    "Webhook delivery attempt {}/{} failed: {}",
+                    attempt,
+                    MAX_ATTEMPTS,
+                    e
+                ),
+            }
+
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        tracing::error!(
+            "Giving up on webhook delivery for audit log {} after {} attempts",
+            audit_log.id,
+            MAX_ATTEMPTS
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::{future::Future, time::Duration};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::info;
+
+/// Background jobs spawned at startup, tracked so shutdown can stop them
+/// between runs instead of killing them mid-write.
+#[derive(Clone, Default)]
+pub struct BackgroundTasks {
+    tracker: TaskTracker,
+    token: CancellationToken,
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancelled once shutdown starts.
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Spawns a task that is expected to return soon after the token is cancelled.
+    pub fn spawn<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let task = task(self.token.clone());
+        self.tracker.spawn(async move {
+            task.await;
+            info!("Background task {} stopped", name);
+        });
+    }
+
+    /// Runs `tick` every `period`, starting immediately. A run in progress is
+    /// allowed to finish; the next one is skipped once shutdown starts.
+    pub fn spawn_periodic<F, Fut>(&self, name: &'static str, period: Duration, mut tick: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn(name, move |token| async move {
+            let mut interval = tokio::time::interval(period);
+
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => tick().await,
+                }
+            }
+        });
+    }
+
+    /// Cancels every task and waits up to `timeout` for them to finish.
+    /// Returns false if some were still running when the timeout expired.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.token.cancel();
+        self.tracker.close();
+
+        tokio::time::timeout(timeout, self.tracker.wait())
+            .await
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn test_periodic_task_stops_promptly_on_shutdown() {
+        let tasks = BackgroundTasks::new();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = runs.clone();
+        tasks.spawn_periodic("test", Duration::from_secs(3600), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let started = std::time::Instant::now();
+        assert!(tasks.shutdown(Duration::from_secs(1)).await);
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_run_in_progress() {
+        let tasks = BackgroundTasks::new();
+        let finished = Arc::new(AtomicU32::new(0));
+
+        let counter = finished.clone();
+        tasks.spawn_periodic("test", Duration::from_secs(3600), move || {
+            let counter = counter.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(tasks.shutdown(Duration::from_secs(1)).await);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gives_up_after_timeout() {
+        let tasks = BackgroundTasks::new();
+        tasks.spawn("stuck", |_token| async {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        });
+
+        assert!(!tasks.shutdown(Duration::from_millis(50)).await);
+    }
+}
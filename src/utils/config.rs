@@ -1,15 +1,77 @@
 // use config::{Config, ConfigError, Environment, File};
 use anyhow::Result;
 use serde::Deserialize;
-use std::{env, fs};
+use std::{collections::HashMap, env, fs};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct DatabaseConfig {
     pub url: Option<String>,
+    #[serde(default = "default_db_max_connections")]
     pub max_connections: u32,
+    #[serde(default = "default_db_min_connections")]
     pub min_connections: u32,
-    pub connect_timeout: u64,
-    pub idle_timeout: u64,
+    // The old key names are still accepted so existing config files keep working
+    #[serde(
+        default = "default_db_acquire_timeout_seconds",
+        alias = "connect_timeout"
+    )]
+    pub acquire_timeout_seconds: u64,
+    #[serde(default = "default_db_idle_timeout_seconds", alias = "idle_timeout")]
+    pub idle_timeout_seconds: u64,
+    #[serde(default = "default_db_max_lifetime_seconds", alias = "max_lifetime")]
+    pub max_lifetime_seconds: u64,
+    // Postgres cancels statements running longer than this; 0 disables the timeout
+    #[serde(default = "default_db_statement_timeout_ms")]
+    pub statement_timeout_ms: u64,
+    // Instrumented queries slower than this are logged as warnings; 0 disables the log
+    #[serde(default = "default_db_slow_query_threshold_ms")]
+    pub slow_query_threshold_ms: u64,
+}
+
+impl DatabaseConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_connections == 0 {
+            anyhow::bail!("database.max_connections must be greater than 0");
+        }
+
+        if self.min_connections > self.max_connections {
+            anyhow::bail!(
+                "database.min_connections ({}) must not exceed database.max_connections ({})",
+                self.min_connections,
+                self.max_connections
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn default_db_max_connections() -> u32 {
+    10
+}
+
+fn default_db_min_connections() -> u32 {
+    1
+}
+
+fn default_db_acquire_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_db_idle_timeout_seconds() -> u64 {
+    600 // 10 minutes
+}
+
+fn default_db_max_lifetime_seconds() -> u64 {
+    1800 // 30 minutes
+}
+
+fn default_db_statement_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_db_slow_query_threshold_ms() -> u64 {
+    500
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -23,7 +85,79 @@ pub struct RedisConfig {
 pub struct AuthConfig {
     pub jwt_secret: Option<String>,
     pub token_expiry: i64,
+    #[serde(default = "default_refresh_token_expiry")]
+    pub refresh_token_expiry: i64,
     pub bcrypt_cost: u32,
+    #[serde(default)]
+    pub cookie: CookieConfig,
+}
+
+fn default_refresh_token_expiry() -> i64 {
+    7 * 24 * 60 * 60 // 7 days
+}
+
+// Attributes of the admin_token and refresh_token cookies
+#[derive(Debug, Deserialize, Clone)]
+pub struct CookieConfig {
+    // Only turn off for local development over plain HTTP
+    #[serde(default = "default_cookie_secure")]
+    pub secure: bool,
+    #[serde(default)]
+    pub same_site: SameSite,
+    #[serde(default)]
+    pub domain: Option<String>,
+    // Max-Age of the admin_token cookie in seconds; defaults to the access token lifetime
+    #[serde(default)]
+    pub max_age: Option<i64>,
+}
+
+fn default_cookie_secure() -> bool {
+    true
+}
+
+impl Default for CookieConfig {
+    fn default() -> Self {
+        Self {
+            secure: default_cookie_secure(),
+            same_site: SameSite::default(),
+            domain: None,
+            max_age: None,
+        }
+    }
+}
+
+impl CookieConfig {
+    pub fn validate(&self, production: bool) -> Result<()> {
+        if production && !self.secure {
+            anyhow::bail!("auth.cookie.secure cannot be disabled in production");
+        }
+
+        // Browsers drop SameSite=None cookies that aren't also Secure
+        if self.same_site == SameSite::None && !self.secure {
+            anyhow::bail!("auth.cookie.same_site \"none\" requires auth.cookie.secure");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SameSite {
+    #[default]
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -31,33 +165,182 @@ pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub workers: usize,
+    // Largest request body accepted by JSON routes; uploads use file_upload.max_size instead
+    #[serde(default = "default_max_body_size")]
+    pub max_body_size: usize,
+    // Largest body accepted by the bulk import routes, which skip the request timeout
+    #[serde(default = "default_max_import_body_size")]
+    pub max_import_body_size: usize,
+    // Requests still running after this many seconds are aborted with 504
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+    // max-age sent with Cache-Control on public read routes
+    #[serde(default = "default_public_cache_max_age_seconds")]
+    pub public_cache_max_age_seconds: u64,
+    // Honor X-Forwarded-For / X-Real-IP for the client IP. Only safe behind a reverse
+    // proxy that overwrites them; otherwise clients can spoof their address.
+    #[serde(default)]
+    pub trust_proxy_headers: bool,
+}
+
+fn default_max_body_size() -> usize {
+    1024 * 1024
+}
+
+fn default_max_import_body_size() -> usize {
+    16 * 1024 * 1024
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_public_cache_max_age_seconds() -> u64 {
+    60
+}
+
+impl ServerConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_body_size == 0 {
+            anyhow::bail!("server.max_body_size must be greater than 0");
+        }
+        if self.max_import_body_size == 0 {
+            anyhow::bail!("server.max_import_body_size must be greater than 0");
+        }
+        if self.request_timeout_seconds == 0 {
+            anyhow::bail!("server.request_timeout_seconds must be greater than 0");
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct SecurityConfig {
     pub rate_limit: RateLimitConfig,
     pub cors: CorsConfig,
+    #[serde(default)]
+    pub feature_check_fail_open: FeatureCheckFailOpen,
+}
+
+// Whether a feature counts as enabled when its admin setting can't be read.
+// Reads keep working through a settings outage; writes are refused.
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct FeatureCheckFailOpen {
+    #[serde(default = "default_feature_check_fail_open_reads")]
+    pub reads: bool,
+    #[serde(default)]
+    pub writes: bool,
+}
+
+impl Default for FeatureCheckFailOpen {
+    fn default() -> Self {
+        Self {
+            reads: default_feature_check_fail_open_reads(),
+            writes: false,
+        }
+    }
+}
+
+fn default_feature_check_fail_open_reads() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct RateLimitConfig {
+    // Requests allowed per IP and route within the window on public routes
     pub requests_per_minute: u64,
     pub burst_size: u32,
+    #[serde(default = "default_rate_limit_window_seconds")]
+    pub window_seconds: u64,
+    // Overrides keyed by route template, optionally prefixed with a method
+    // ("POST /api/v1/comments"). An override always wins over the default above;
+    // when several match a request the tightest one applies.
+    #[serde(default)]
+    pub routes: HashMap<String, RouteRateLimitConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RouteRateLimitConfig {
+    pub requests: u32,
+    #[serde(default = "default_rate_limit_window_seconds")]
+    pub window_seconds: u64,
+}
+
+fn default_rate_limit_window_seconds() -> u64 {
+    60
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct CorsConfig {
+    // Exact origins, e.g. "https://budhilaw.com". "*" is rejected because the admin
+    // panel authenticates with cookies, which browsers never send to a wildcard origin.
     pub allowed_origins: Vec<String>,
+    // Origins whose host is one of these domains or a subdomain of it also match, e.g.
+    // ".budhilaw.com"; the match is on whole labels, so "evilbudhilaw.com" doesn't count
+    #[serde(default)]
+    pub allowed_origin_suffixes: Vec<String>,
+    // Regexes matched against the whole origin, e.g. '^https://pr-\d+\.preview\.budhilaw\.com$'
+    #[serde(default)]
+    pub allowed_origin_patterns: Vec<String>,
     pub allowed_methods: Vec<String>,
     pub allowed_headers: Vec<String>,
     pub expose_headers: Vec<String>,
     pub max_age: u64,
 }
 
+impl CorsConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self
+            .allowed_origins
+            .iter()
+            .any(|origin| origin.trim() == "*")
+        {
+            anyhow::bail!(
+                "security.cors.allowed_origins cannot contain \"*\" because credentials are allowed; list the origins instead"
+            );
+        }
+
+        for pattern in &self.allowed_origin_patterns {
+            regex::Regex::new(pattern).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid security.cors.allowed_origin_patterns entry {:?}: {}",
+                    pattern,
+                    e
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    // One JSON object per line, for log aggregation
+    Json,
+    #[default]
+    Pretty,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            other => anyhow::bail!("expected \"json\" or \"pretty\", got {:?}", other),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct LoggingConfig {
     pub level: String,
-    pub format: String,
+    #[serde(default)]
+    pub format: LogFormat,
     pub enable_console: bool,
     pub enable_file: bool,
 }
@@ -68,6 +351,176 @@ pub struct PaginationConfig {
     pub max_limit: u32,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationConfig {
+    // Time of day (UTC, HH:MM) the daily digest goes out
+    #[serde(default = "default_digest_send_time")]
+    pub digest_send_time: String,
+    // Log digests instead of emailing them
+    #[serde(default)]
+    pub digest_dry_run: bool,
+}
+
+fn default_digest_send_time() -> String {
+    "08:00".to_string()
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            digest_send_time: default_digest_send_time(),
+            digest_dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackendKind {
+    #[default]
+    Local,
+    S3,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct S3Config {
+    #[serde(default)]
+    pub bucket: String,
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    // Custom endpoint for S3-compatible stores such as MinIO
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    // MinIO and most self-hosted stores need path-style addressing
+    #[serde(default)]
+    pub force_path_style: bool,
+    // Base URL objects are publicly served from; presigned URLs are returned when unset
+    #[serde(default)]
+    pub public_url: Option<String>,
+    #[serde(default = "default_s3_presign_expiry_seconds")]
+    pub presign_expiry_seconds: u64,
+    #[serde(skip)]
+    pub access_key_id: Option<String>,
+    #[serde(skip)]
+    pub secret_access_key: Option<String>,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_s3_presign_expiry_seconds() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            bucket: String::new(),
+            region: default_s3_region(),
+            endpoint: None,
+            force_path_style: false,
+            public_url: None,
+            presign_expiry_seconds: default_s3_presign_expiry_seconds(),
+            access_key_id: None,
+            secret_access_key: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FileUploadConfig {
+    #[serde(default)]
+    pub backend: StorageBackendKind,
+    // Maximum upload size in bytes
+    #[serde(default = "default_upload_max_size")]
+    pub max_size: usize,
+    #[serde(default = "default_upload_allowed_types")]
+    pub allowed_types: Vec<String>,
+    // Directory the local disk backend writes uploads to
+    #[serde(default = "default_upload_directory")]
+    pub directory: String,
+    // URL prefix uploads are served from
+    #[serde(default = "default_upload_public_url")]
+    pub public_url: String,
+    #[serde(default)]
+    pub s3: S3Config,
+}
+
+fn default_upload_max_size() -> usize {
+    10 * 1024 * 1024
+}
+
+// Uploads are served inline from our own origin, and an SVG can carry scripts
+pub const BLOCKED_UPLOAD_TYPES: &[&str] = &["image/svg+xml"];
+
+fn default_upload_allowed_types() -> Vec<String> {
+    ["image/jpeg", "image/png", "image/webp", "image/gif"]
+        .iter()
+        .map(|t| t.to_string())
+        .collect()
+}
+
+fn default_upload_directory() -> String {
+    "uploads".to_string()
+}
+
+fn default_upload_public_url() -> String {
+    "/uploads".to_string()
+}
+
+impl Default for FileUploadConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackendKind::default(),
+            max_size: default_upload_max_size(),
+            allowed_types: default_upload_allowed_types(),
+            directory: default_upload_directory(),
+            public_url: default_upload_public_url(),
+            s3: S3Config::default(),
+        }
+    }
+}
+
+impl FileUploadConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_size == 0 {
+            anyhow::bail!("file_upload.max_size must be greater than 0");
+        }
+        if self.allowed_types.is_empty() {
+            anyhow::bail!("file_upload.allowed_types must list at least one MIME type");
+        }
+        if let Some(blocked) = self
+            .allowed_types
+            .iter()
+            .find(|t| BLOCKED_UPLOAD_TYPES.contains(&t.to_ascii_lowercase().as_str()))
+        {
+            anyhow::bail!(
+                "file_upload.allowed_types must not include {}: it can run scripts when served",
+                blocked
+            );
+        }
+        if !self.public_url.starts_with('/') || self.public_url == "/" {
+            anyhow::bail!("file_upload.public_url must be a path such as \"/uploads\"");
+        }
+        if self.backend == StorageBackendKind::S3 {
+            if self.s3.bucket.trim().is_empty() {
+                anyhow::bail!("file_upload.s3.bucket is required when file_upload.backend is s3");
+            }
+            // SigV4 presigned URLs are valid for at most seven days
+            if !(1..=default_s3_presign_expiry_seconds()).contains(&self.s3.presign_expiry_seconds)
+            {
+                anyhow::bail!(
+                    "file_upload.s3.presign_expiry_seconds must be between 1 and {}",
+                    default_s3_presign_expiry_seconds()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub database: DatabaseConfig,
@@ -77,6 +530,10 @@ pub struct AppConfig {
     pub security: SecurityConfig,
     pub logging: LoggingConfig,
     pub pagination: PaginationConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub file_upload: FileUploadConfig,
 
     pub environment: String,
 }
@@ -86,6 +543,8 @@ pub struct SecretConfig {
     pub database: DatabaseSecrets,
     pub redis: RedisSecrets,
     pub auth: AuthSecrets,
+    #[serde(default)]
+    pub storage: Option<StorageSecrets>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -104,6 +563,12 @@ pub struct AuthSecrets {
     pub refresh_secret: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct StorageSecrets {
+    pub aws_access_key_id: String,
+    pub aws_secret_access_key: String,
+}
+
 impl AppConfig {
     pub fn from_yaml() -> Result<(Self, SecretConfig), anyhow::Error> {
         // Load main configuration from .config.yaml
@@ -139,10 +604,36 @@ impl AppConfig {
             app_config.logging.level = log_level;
         }
 
+        if let Ok(log_format) = env::var("LOG_FORMAT") {
+            app_config.logging.format = log_format
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid LOG_FORMAT value: {}", e))?;
+        }
+
+        if let Ok(dry_run) = env::var("DIGEST_DRY_RUN") {
+            app_config.notifications.digest_dry_run = dry_run
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid DIGEST_DRY_RUN value: {}", e))?;
+        }
+
+        app_config.server.validate()?;
+        app_config.database.validate()?;
+        app_config.security.cors.validate()?;
+        app_config.file_upload.validate()?;
+        app_config
+            .auth
+            .cookie
+            .validate(app_config.is_production())?;
+
         // Apply secrets to config
         app_config.database.url = Some(secret_config.database.url.clone());
         app_config.redis.url = Some(secret_config.redis.url.clone());
         app_config.auth.jwt_secret = Some(secret_config.auth.jwt_secret.clone());
+        if let Some(storage) = &secret_config.storage {
+            app_config.file_upload.s3.access_key_id = Some(storage.aws_access_key_id.clone());
+            app_config.file_upload.s3.secret_access_key =
+                Some(storage.aws_secret_access_key.clone());
+        }
 
         Ok((app_config, secret_config))
     }
@@ -179,3 +670,86 @@ impl AppConfig {
             .map(|s| s.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_config_parses_pool_settings() {
+        let config: DatabaseConfig = serde_yaml::from_str(
+            "max_connections: 20\nmin_connections: 2\nacquire_timeout_seconds: 5\nidle_timeout_seconds: 120\nmax_lifetime_seconds: 900\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.max_connections, 20);
+        assert_eq!(config.min_connections, 2);
+        assert_eq!(config.acquire_timeout_seconds, 5);
+        assert_eq!(config.idle_timeout_seconds, 120);
+        assert_eq!(config.max_lifetime_seconds, 900);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_log_format_parses_config_and_env_values() {
+        let config: LoggingConfig = serde_yaml::from_str(
+            "level: info\nformat: json\nenable_console: true\nenable_file: false\n",
+        )
+        .unwrap();
+        assert_eq!(config.format, LogFormat::Json);
+
+        assert_eq!(" Pretty ".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("logfmt".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_database_config_defaults_and_legacy_keys() {
+        let config: DatabaseConfig =
+            serde_yaml::from_str("connect_timeout: 15\nidle_timeout: 300\n").unwrap();
+
+        assert_eq!(config.max_connections, 10);
+        assert_eq!(config.min_connections, 1);
+        assert_eq!(config.acquire_timeout_seconds, 15);
+        assert_eq!(config.idle_timeout_seconds, 300);
+        assert_eq!(config.max_lifetime_seconds, 1800);
+    }
+
+    #[test]
+    fn test_database_config_rejects_min_above_max() {
+        let config: DatabaseConfig =
+            serde_yaml::from_str("max_connections: 5\nmin_connections: 10\n").unwrap();
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_cors_config_rejects_wildcard_and_bad_patterns() {
+        let config: CorsConfig = serde_yaml::from_str(
+            "allowed_origins: [\"https://budhilaw.com\"]\nallowed_origin_patterns: ['^https://pr-\\d+\\.budhilaw\\.com$']\nallowed_methods: []\nallowed_headers: []\nexpose_headers: []\nmax_age: 60\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config.allowed_origin_suffixes.is_empty());
+
+        let wildcard = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            ..config.clone()
+        };
+        assert!(wildcard.validate().is_err());
+
+        let bad_pattern = CorsConfig {
+            allowed_origin_patterns: vec!["(unclosed".to_string()],
+            ..config
+        };
+        assert!(bad_pattern.validate().is_err());
+    }
+
+    #[test]
+    fn test_file_upload_config_refuses_svg() {
+        let config: FileUploadConfig =
+            serde_yaml::from_str("allowed_types: [\"image/png\", \"Image/SVG+XML\"]\n").unwrap();
+
+        assert!(config.validate().is_err());
+        assert!(FileUploadConfig::default().validate().is_ok());
+    }
+}
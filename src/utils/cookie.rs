@@ -0,0 +1,96 @@
+use crate::utils::config::CookieConfig;
+
+/// Builds a `Set-Cookie` value using the configured Secure, SameSite and Domain attributes.
+/// Auth cookies are always HttpOnly.
+pub fn build_cookie(
+    config: &CookieConfig,
+    name: &str,
+    value: &str,
+    path: &str,
+    max_age: i64,
+) -> String {
+    let mut cookie = format!(
+        "{}={}; HttpOnly; SameSite={}; Path={}; Max-Age={}",
+        name,
+        value,
+        config.same_site.as_str(),
+        path,
+        max_age.max(0)
+    );
+
+    if config.secure {
+        cookie.push_str("; Secure");
+    }
+
+    if let Some(domain) = config.domain.as_deref().filter(|d| !d.trim().is_empty()) {
+        cookie.push_str("; Domain=");
+        cookie.push_str(domain.trim());
+    }
+
+    cookie
+}
+
+/// A cookie that expires immediately, with the same attributes it was set with
+pub fn clear_cookie(config: &CookieConfig, name: &str, path: &str) -> String {
+    build_cookie(config, name, "", path, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::config::SameSite;
+
+    #[test]
+    fn test_default_cookie_is_secure_and_strict() {
+        let cookie = build_cookie(&CookieConfig::default(), "admin_token", "abc", "/", 3600);
+
+        assert_eq!(
+            cookie,
+            "admin_token=abc; HttpOnly; SameSite=Strict; Path=/; Max-Age=3600; Secure"
+        );
+    }
+
+    #[test]
+    fn test_development_cookie_can_drop_secure() {
+        let config = CookieConfig {
+            secure: false,
+            same_site: SameSite::Lax,
+            ..CookieConfig::default()
+        };
+
+        assert_eq!(
+            build_cookie(&config, "admin_token", "abc", "/", 60),
+            "admin_token=abc; HttpOnly; SameSite=Lax; Path=/; Max-Age=60"
+        );
+    }
+
+    #[test]
+    fn test_cross_site_cookie_with_domain() {
+        let config = CookieConfig {
+            same_site: SameSite::None,
+            domain: Some("budhilaw.com".to_string()),
+            ..CookieConfig::default()
+        };
+
+        assert_eq!(
+            clear_cookie(&config, "refresh_token", "/api/v1/auth"),
+            "refresh_token=; HttpOnly; SameSite=None; Path=/api/v1/auth; Max-Age=0; Secure; Domain=budhilaw.com"
+        );
+    }
+
+    #[test]
+    fn test_cookie_config_validation() {
+        let insecure = CookieConfig {
+            secure: false,
+            ..CookieConfig::default()
+        };
+        assert!(insecure.validate(false).is_ok());
+        assert!(insecure.validate(true).is_err());
+
+        let cross_site_insecure = CookieConfig {
+            same_site: SameSite::None,
+            ..insecure
+        };
+        assert!(cross_site_insecure.validate(false).is_err());
+    }
+}
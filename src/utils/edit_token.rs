@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac_for(secret: &str, id: Uuid, created_at: DateTime<Utc>) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(id.as_bytes());
+    mac.update(created_at.timestamp_micros().to_string().as_bytes());
+    mac
+}
+
+/// Sign an edit token binding a resource id to its creation time. The signature alone
+/// doesn't make it one-time; the comment records when its token was spent
+pub fn sign_edit_token(secret: &str, id: Uuid, created_at: DateTime<Utc>) -> String {
+    hex::encode(mac_for(secret, id, created_at).finalize().into_bytes())
+}
+
+/// Verify an edit token in constant time
+pub fn verify_edit_token(secret: &str, id: Uuid, created_at: DateTime<Utc>, token: &str) -> bool {
+    match hex::decode(token) {
+        Ok(bytes) => mac_for(secret, id, created_at).verify_slice(&bytes).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_token_round_trip() {
+        let id = Uuid::new_v4();
+        let created_at = Utc::now();
+        let token = sign_edit_token("secret", id, created_at);
+
+        assert!(verify_edit_token("secret", id, created_at, &token));
+        assert!(!verify_edit_token("other-secret", id, created_at, &token));
+        assert!(!verify_edit_token(
+            "secret",
+            Uuid::new_v4(),
+            created_at,
+            &token
+        ));
+        assert!(!verify_edit_token("secret", id, created_at, "not-hex"));
+    }
+}
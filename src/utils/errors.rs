@@ -1,15 +1,22 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use std::collections::BTreeMap;
 use thiserror::Error;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::utils::request::current_request_id;
 
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
-    Database(#[from] sqlx::Error),
+    Database(#[source] sqlx::Error),
+
+    #[error("Database timeout: {0}")]
+    DatabaseTimeout(String),
 
     #[error("Migration error: {0}")]
     Migration(#[from] sqlx::migrate::MigrateError),
@@ -20,6 +27,9 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Validation error: {0}")]
+    ValidationErrors(ValidationErrors),
+
     #[error("Authentication failed: {0}")]
     Unauthorized(String),
 
@@ -35,6 +45,9 @@ pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
     #[error("Rate limit exceeded")]
     RateLimit,
 
@@ -49,10 +62,54 @@ pub enum AppError {
 
     #[error("External service error: {0}")]
     ExternalService(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    #[error("Gateway timeout: {0}")]
+    GatewayTimeout(String),
+}
+
+impl AppError {
+    /// Maps a unique-constraint violation to `Conflict` naming the duplicated field.
+    /// Any other database error is reported as an internal error with the given context.
+    pub fn from_unique_violation(err: sqlx::Error, resource: &str, context: &'static str) -> Self {
+        if let sqlx::Error::Database(db_err) = &err {
+            // 23505 is Postgres' unique_violation
+            if db_err.code().as_deref() == Some("23505") {
+                let field = db_err
+                    .constraint()
+                    .map(|constraint| unique_constraint_field(constraint, db_err.table()))
+                    .unwrap_or("value");
+
+                return AppError::Conflict(format!(
+                    "{} with this {} already exists",
+                    resource, field
+                ));
+            }
+        }
+
+        anyhow::Error::new(err).context(context).into()
+    }
+}
+
+// Postgres names column constraints `<table>_<column>_key`
+fn unique_constraint_field<'a>(constraint: &'a str, table: Option<&str>) -> &'a str {
+    let field = constraint.strip_suffix("_key").unwrap_or(constraint);
+
+    table
+        .and_then(|table| field.strip_prefix(table))
+        .and_then(|field| field.strip_prefix('_'))
+        .unwrap_or(field)
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let retry_after = match &self {
+            AppError::TooManyRequests { retry_after, .. } => *retry_after,
+            _ => None,
+        };
+
         let (status, error_code, message) = match &self {
             AppError::Database(_) => {
                 tracing::error!("Database error: {}", self);
@@ -62,6 +119,14 @@ impl IntoResponse for AppError {
                     "Internal server error".to_string(),
                 )
             }
+            AppError::DatabaseTimeout(_) => {
+                tracing::warn!("{}", self);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "DATABASE_TIMEOUT",
+                    "The database took too long to respond, please try again".to_string(),
+                )
+            }
             AppError::Migration(_) => {
                 tracing::error!("Migration error: {}", self);
                 (
@@ -79,11 +144,31 @@ impl IntoResponse for AppError {
                 )
             }
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
+            AppError::ValidationErrors(errors) => {
+                let mut body = json!({
+                    "error": {
+                        "code": "VALIDATION_ERROR",
+                        "message": "Validation failed",
+                        "fields": validation_error_fields(errors),
+                        "timestamp": chrono::Utc::now().to_rfc3339()
+                    }
+                });
+                if let Some(request_id) = current_request_id() {
+                    body["error"]["request_id"] = json!(request_id);
+                }
+
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response();
+            }
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg.clone()),
             AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, "FORBIDDEN", msg.clone()),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg.clone()),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.clone()),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg.clone()),
+            AppError::PayloadTooLarge(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PAYLOAD_TOO_LARGE",
+                msg.clone(),
+            ),
             AppError::RateLimit => (
                 StatusCode::TOO_MANY_REQUESTS,
                 "RATE_LIMIT",
@@ -113,46 +198,201 @@ impl IntoResponse for AppError {
                     "External service unavailable".to_string(),
                 )
             }
+            AppError::ServiceUnavailable(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "SERVICE_UNAVAILABLE",
+                msg.clone(),
+            ),
+            AppError::GatewayTimeout(msg) => {
+                tracing::warn!("{}", self);
+                (StatusCode::GATEWAY_TIMEOUT, "GATEWAY_TIMEOUT", msg.clone())
+            }
         };
 
-        let body = Json(json!({
+        let mut body = json!({
             "error": {
                 "code": error_code,
                 "message": message,
                 "timestamp": chrono::Utc::now().to_rfc3339()
             }
-        }));
+        });
+        if let Some(seconds) = retry_after {
+            body["error"]["retry_after"] = json!(seconds);
+        }
+        if let Some(request_id) = current_request_id() {
+            body["error"]["request_id"] = json!(request_id);
+        }
+
+        let mut response = (status, Json(body)).into_response();
+        if let Some(seconds) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+
+        response
+    }
+}
 
-        (status, body).into_response()
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        if is_timeout(&err) {
+            AppError::DatabaseTimeout(err.to_string())
+        } else {
+            AppError::Database(err)
+        }
     }
 }
 
 impl From<anyhow::Error> for AppError {
     fn from(err: anyhow::Error) -> Self {
-        AppError::Internal(err.to_string())
+        // Typed errors raised inside anyhow-based repositories keep their status code
+        let err = match err.downcast::<AppError>() {
+            Ok(app_err) => return app_err,
+            Err(err) => err,
+        };
+
+        // Repositories wrap sqlx errors in context, so look through it for timeouts
+        match err.downcast_ref::<sqlx::Error>() {
+            Some(sqlx_err) if is_timeout(sqlx_err) => {
+                AppError::DatabaseTimeout(format!("{:#}", err))
+            }
+            _ => AppError::Internal(err.to_string()),
+        }
     }
 }
 
-impl From<validator::ValidationErrors> for AppError {
-    fn from(err: validator::ValidationErrors) -> Self {
-        let errors: Vec<String> = err
-            .field_errors()
-            .into_iter()
-            .map(|(field, errors)| {
-                let messages: Vec<String> = errors
-                    .iter()
-                    .map(|error| {
-                        format!(
-                            "{}: {}",
-                            field,
-                            error.message.as_ref().unwrap_or(&"Invalid value".into())
-                        )
-                    })
-                    .collect();
-                messages.join(", ")
-            })
-            .collect();
-
-        AppError::Validation(errors.join("; "))
+/// Pool acquire timeouts and statements cancelled by `statement_timeout` (SQLSTATE 57014)
+fn is_timeout(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db_err) => db_err.code().as_deref() == Some("57014"),
+        _ => false,
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(err: ValidationErrors) -> Self {
+        AppError::ValidationErrors(err)
+    }
+}
+
+/// Flattens validation errors into `{"field": ["message", ...]}`.
+/// Nested structs and lists use dotted paths, e.g. `services[0].id`.
+pub fn validation_error_fields(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    let mut fields = BTreeMap::new();
+    collect_validation_errors(errors, None, &mut fields);
+    fields
+}
+
+/// One-line form of `validation_error_fields`, e.g. `slug: Slug is required; title: ...`.
+/// Used where errors are reported per item rather than as a 422 body.
+pub fn validation_error_summary(errors: &ValidationErrors) -> String {
+    validation_error_fields(errors)
+        .into_iter()
+        .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn collect_validation_errors(
+    errors: &ValidationErrors,
+    prefix: Option<&str>,
+    fields: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, field),
+            None => field.to_string(),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                fields
+                    .entry(path)
+                    .or_default()
+                    .extend(field_errors.iter().map(|error| match &error.message {
+                        Some(message) => message.to_string(),
+                        None => format!("Invalid value ({})", error.code),
+                    }));
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_validation_errors(nested, Some(&path), fields);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_validation_errors(
+                        nested,
+                        Some(&format!("{}[{}]", path, index)),
+                        fields,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_constraint_field() {
+        assert_eq!(
+            unique_constraint_field("users_username_key", Some("users")),
+            "username"
+        );
+        assert_eq!(
+            unique_constraint_field("portfolio_projects_slug_key", Some("portfolio_projects")),
+            "slug"
+        );
+        assert_eq!(
+            unique_constraint_field("users_email_key", None),
+            "users_email"
+        );
+    }
+
+    #[test]
+    fn test_conflict_maps_to_409() {
+        let response = AppError::Conflict("User with this username already exists".to_string())
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_database_timeouts_map_to_503() {
+        let response = AppError::from(sqlx::Error::PoolTimedOut).into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Repository errors arrive wrapped in anyhow context
+        let err = anyhow::Error::from(sqlx::Error::PoolTimedOut).context("Failed to fetch posts");
+        assert!(matches!(AppError::from(err), AppError::DatabaseTimeout(_)));
+
+        let err = anyhow::Error::from(sqlx::Error::RowNotFound).context("Failed to fetch posts");
+        assert!(matches!(AppError::from(err), AppError::Internal(_)));
+    }
+
+    #[test]
+    fn test_too_many_requests_sets_retry_after() {
+        let response = AppError::TooManyRequests {
+            message: "Too many requests".to_string(),
+            retry_after: Some(42),
+        }
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "42");
+    }
+
+    #[test]
+    fn test_other_errors_keep_context() {
+        let err = AppError::from_unique_violation(
+            sqlx::Error::RowNotFound,
+            "User",
+            "Failed to create user",
+        );
+
+        assert!(matches!(err, AppError::Internal(msg) if msg == "Failed to create user"));
     }
 }
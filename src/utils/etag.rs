@@ -0,0 +1,114 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Weak ETag for an entity, derived from its id and last modification time
+pub fn weak_etag(id: Uuid, updated_at: DateTime<Utc>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(id.as_bytes());
+    hasher.update(updated_at.timestamp_micros().to_string().as_bytes());
+    format!("W/\"{}\"", hex::encode(&hasher.finalize()[..16]))
+}
+
+/// Whether `If-None-Match` lists `etag` (or `*`), using weak comparison
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// Answers a conditional GET: `304 Not Modified` with no body when the client
+/// already has `etag`, otherwise `body` with the `ETag` header set.
+pub fn conditional_response(
+    headers: &HeaderMap,
+    etag: String,
+    body: impl IntoResponse,
+) -> Response {
+    let not_modified = if_none_match(headers, &etag);
+    let etag = HeaderValue::from_str(&etag).expect("ETag is ASCII");
+
+    if not_modified {
+        (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response()
+    } else {
+        ([(header::ETAG, etag)], body).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    fn request_headers(if_none_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(if_none_match).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_weak_etag_changes_with_updated_at() {
+        let id = Uuid::new_v4();
+        let updated_at = Utc::now();
+        let etag = weak_etag(id, updated_at);
+
+        assert!(etag.starts_with("W/\"") && etag.ends_with('"'));
+        assert_eq!(etag, weak_etag(id, updated_at));
+        assert_ne!(
+            etag,
+            weak_etag(id, updated_at + chrono::Duration::seconds(1))
+        );
+        assert_ne!(etag, weak_etag(Uuid::new_v4(), updated_at));
+    }
+
+    #[test]
+    fn test_if_none_match_compares_weakly_across_a_list() {
+        let etag = weak_etag(Uuid::new_v4(), Utc::now());
+        let strong = etag.trim_start_matches("W/");
+
+        assert!(if_none_match(&request_headers(&etag), &etag));
+        assert!(if_none_match(&request_headers(strong), &etag));
+        assert!(if_none_match(
+            &request_headers(&format!("W/\"other\", {}", etag)),
+            &etag
+        ));
+        assert!(if_none_match(&request_headers("*"), &etag));
+        assert!(!if_none_match(&HeaderMap::new(), &etag));
+    }
+
+    #[tokio::test]
+    async fn test_matching_if_none_match_returns_304_without_body() {
+        let etag = weak_etag(Uuid::new_v4(), Utc::now());
+
+        let response = conditional_response(&request_headers(&etag), etag.clone(), "body");
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_non_matching_if_none_match_returns_body_with_etag() {
+        let etag = weak_etag(Uuid::new_v4(), Utc::now());
+
+        let response = conditional_response(&request_headers("W/\"stale\""), etag.clone(), "body");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"body");
+    }
+}
@@ -0,0 +1,152 @@
+use std::io::{Cursor, Read};
+
+use crate::utils::errors::AppError;
+
+// Larger markdown files are almost certainly not blog posts
+const MAX_MARKDOWN_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Splits a markdown document into its YAML frontmatter and body.
+/// Returns `None` when the document doesn't open with a `---` block.
+pub fn split_frontmatter(source: &str) -> Option<(&str, &str)> {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    let rest = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let body = &rest[offset + line.len()..];
+            return Some((&rest[..offset], body.trim_start_matches(['\r', '\n'])));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// A markdown file's path and its contents, or why it couldn't be read
+pub type MarkdownFile = (String, Result<String, String>);
+
+/// Reads every `.md`/`.markdown` file from a zip archive, sorted by path.
+/// A file that can't be used is returned with the reason, so the rest can still be imported.
+pub fn read_markdown_archive(bytes: &[u8]) -> Result<Vec<MarkdownFile>, AppError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| AppError::Validation(format!("Invalid zip archive: {}", e)))?;
+
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let file = archive
+            .by_index(index)
+            .map_err(|e| AppError::Validation(format!("Invalid zip archive: {}", e)))?;
+
+        let name = file.name().to_string();
+        let lowercase = name.to_lowercase();
+        if file.is_dir()
+            || name.starts_with("__MACOSX/")
+            || !(lowercase.ends_with(".md") || lowercase.ends_with(".markdown"))
+        {
+            continue;
+        }
+
+        let contents = read_markdown_file(file);
+        files.push((name, contents));
+    }
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+fn read_markdown_file(file: impl Read) -> Result<String, String> {
+    // The declared size can't be trusted, so cap what is actually read
+    let mut contents = Vec::new();
+    file.take(MAX_MARKDOWN_FILE_BYTES + 1)
+        .read_to_end(&mut contents)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    if contents.len() as u64 > MAX_MARKDOWN_FILE_BYTES {
+        return Err(format!(
+            "File is larger than {} bytes",
+            MAX_MARKDOWN_FILE_BYTES
+        ));
+    }
+
+    String::from_utf8(contents).map_err(|_| "File is not valid UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_split_frontmatter() {
+        let (yaml, body) =
+            split_frontmatter("---\ntitle: Hello\ntags: [a, b]\n---\n\n# Heading\n").unwrap();
+        assert_eq!(yaml, "title: Hello\ntags: [a, b]\n");
+        assert_eq!(body, "# Heading\n");
+
+        let (yaml, body) = split_frontmatter("---\r\ntitle: Hi\r\n---\r\nBody").unwrap();
+        assert_eq!(yaml, "title: Hi\r\n");
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_split_frontmatter_requires_a_closed_block() {
+        assert!(split_frontmatter("# Just markdown").is_none());
+        assert!(split_frontmatter("---\ntitle: Never closed\n").is_none());
+    }
+
+    #[test]
+    fn test_read_markdown_archive_keeps_markdown_files() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default();
+        for (name, contents) in [
+            ("posts/b.md", "second"),
+            ("posts/a.markdown", "first"),
+            ("posts/cover.png", "not markdown"),
+            ("__MACOSX/posts/._a.md", "resource fork"),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let files = read_markdown_archive(&bytes).unwrap();
+        assert_eq!(
+            files,
+            [
+                ("posts/a.markdown".to_string(), Ok("first".to_string())),
+                ("posts/b.md".to_string(), Ok("second".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oversized_file_fails_on_its_own() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default();
+        writer.start_file("big.md", options).unwrap();
+        writer
+            .write_all(&vec![b'x'; MAX_MARKDOWN_FILE_BYTES as usize + 1])
+            .unwrap();
+        writer.start_file("small.md", options).unwrap();
+        writer.write_all(b"fits").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let files = read_markdown_archive(&bytes).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[0]
+            .1
+            .as_ref()
+            .is_err_and(|e| e.contains("larger than")));
+        assert_eq!(files[1].1, Ok("fits".to_string()));
+    }
+
+    #[test]
+    fn test_read_markdown_archive_rejects_non_zip() {
+        assert!(matches!(
+            read_markdown_archive(b"not a zip"),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,24 @@
+use md5::{Digest, Md5};
+
+/// Build a Gravatar URL from the canonical (trimmed, lowercased) email hash
+pub fn gravatar_url(email: &str) -> String {
+    let hash = Md5::digest(email.trim().to_lowercase().as_bytes());
+    format!(
+        "https://www.gravatar.com/avatar/{}?d=identicon",
+        hex::encode(hash)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gravatar_url_uses_canonical_hash() {
+        let expected =
+            "https://www.gravatar.com/avatar/0bc83cb571cd1c50ba6f3e8a78ef1346?d=identicon";
+
+        assert_eq!(gravatar_url("myemailaddress@example.com"), expected);
+        assert_eq!(gravatar_url("  MyEmailAddress@example.com "), expected);
+    }
+}
@@ -0,0 +1,30 @@
+use tracing::Subscriber;
+use tracing_subscriber::{
+    fmt::{self, time::ChronoUtc, MakeWriter},
+    registry::LookupSpan,
+    Layer,
+};
+
+use crate::utils::config::LogFormat;
+
+/// Formatting layer for the configured log format. JSON output carries the
+/// fields of the enclosing spans, so every line logged while handling a
+/// request includes its `request_id`.
+pub fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let timer = ChronoUtc::rfc_3339();
+
+    match format {
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_timer(timer)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Pretty => fmt::layer().with_timer(timer).with_writer(writer).boxed(),
+    }
+}
@@ -1,4 +1,18 @@
+pub mod background;
 pub mod config;
+pub mod cookie;
+pub mod cursor;
+pub mod edit_token;
 pub mod errors;
+pub mod etag;
+pub mod frontmatter;
+pub mod gravatar;
+pub mod logging;
 pub mod password;
+pub mod preview_token;
+pub mod request;
+pub mod response;
+pub mod sitemap;
+pub mod text;
 pub mod validation;
+pub mod webhook;
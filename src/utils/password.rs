@@ -3,6 +3,74 @@ use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
+use serde::{Deserialize, Serialize};
+
+const PASSWORD_SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?";
+
+/// Rules enforced by `validate_strength`, stored under `SecuritySettings`
+/// so admins can relax them without a redeploy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordPolicy {
+    #[serde(rename = "minLength")]
+    pub min_length: usize,
+    #[serde(rename = "requireUppercase")]
+    pub require_uppercase: bool,
+    #[serde(rename = "requireLowercase")]
+    pub require_lowercase: bool,
+    #[serde(rename = "requireDigit")]
+    pub require_digit: bool,
+    #[serde(rename = "requireSymbol")]
+    pub require_symbol: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: true,
+        }
+    }
+}
+
+/// Check a password against the policy, naming the first rule it fails.
+pub fn validate_strength(password: &str, policy: &PasswordPolicy) -> Result<(), AppError> {
+    if password.chars().count() < policy.min_length {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {} characters long",
+            policy.min_length
+        )));
+    }
+
+    if policy.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+        return Err(AppError::Validation(
+            "Password must contain at least one uppercase letter".to_string(),
+        ));
+    }
+
+    if policy.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+        return Err(AppError::Validation(
+            "Password must contain at least one lowercase letter".to_string(),
+        ));
+    }
+
+    if policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+        return Err(AppError::Validation(
+            "Password must contain at least one digit".to_string(),
+        ));
+    }
+
+    if policy.require_symbol && !password.chars().any(|c| PASSWORD_SYMBOLS.contains(c)) {
+        return Err(AppError::Validation(format!(
+            "Password must contain at least one symbol ({})",
+            PASSWORD_SYMBOLS
+        )));
+    }
+
+    Ok(())
+}
 
 #[derive(Clone, Default)]
 pub struct PasswordService;
@@ -56,3 +124,62 @@ impl PasswordService {
         criteria_met >= 3
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_fails_with(password: &str, expected: &str) {
+        match validate_strength(password, &PasswordPolicy::default()) {
+            Err(AppError::Validation(message)) => assert!(
+                message.contains(expected),
+                "expected '{}' in '{}'",
+                expected,
+                message
+            ),
+            other => panic!("expected validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_validate_strength_accepts_strong_password() {
+        assert!(validate_strength("Correct-Horse-9", &PasswordPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_strength_rejects_short_password() {
+        assert_fails_with("Sh0rt!pass", "at least 12 characters");
+    }
+
+    #[test]
+    fn test_validate_strength_rejects_missing_uppercase() {
+        assert_fails_with("correct-horse-9", "uppercase");
+    }
+
+    #[test]
+    fn test_validate_strength_rejects_missing_lowercase() {
+        assert_fails_with("CORRECT-HORSE-9", "lowercase");
+    }
+
+    #[test]
+    fn test_validate_strength_rejects_missing_digit() {
+        assert_fails_with("Correct-Horse-Battery", "digit");
+    }
+
+    #[test]
+    fn test_validate_strength_rejects_missing_symbol() {
+        assert_fails_with("CorrectHorse99", "symbol");
+    }
+
+    #[test]
+    fn test_validate_strength_respects_relaxed_policy() {
+        let policy = PasswordPolicy {
+            min_length: 8,
+            require_uppercase: false,
+            require_lowercase: true,
+            require_digit: false,
+            require_symbol: false,
+        };
+        assert!(validate_strength("lowercase", &policy).is_ok());
+    }
+}
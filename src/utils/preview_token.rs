@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac_for(secret: &str, post_id: Uuid, expires_at: i64) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    // Keeps preview tokens from being accepted anywhere else the secret is used
+    mac.update(b"post-preview:");
+    mac.update(post_id.as_bytes());
+    mac.update(expires_at.to_string().as_bytes());
+    mac
+}
+
+/// Sign a preview token for one post, valid until `expires_at`.
+/// The token is `<unix expiry>.<hex mac>`, so it carries its own expiry.
+pub fn sign_preview_token(secret: &str, post_id: Uuid, expires_at: DateTime<Utc>) -> String {
+    let expires_at = expires_at.timestamp();
+    let mac = mac_for(secret, post_id, expires_at).finalize().into_bytes();
+    format!("{}.{}", expires_at, hex::encode(mac))
+}
+
+/// Verify a preview token for `post_id` in constant time, rejecting expired tokens
+pub fn verify_preview_token(secret: &str, post_id: Uuid, token: &str, now: DateTime<Utc>) -> bool {
+    let Some((expires_at, signature)) = token.split_once('.') else {
+        return false;
+    };
+    let (Ok(expires_at), Ok(signature)) = (expires_at.parse::<i64>(), hex::decode(signature))
+    else {
+        return false;
+    };
+
+    now.timestamp() < expires_at
+        && mac_for(secret, post_id, expires_at)
+            .verify_slice(&signature)
+            .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_valid_preview_token() {
+        let post_id = Uuid::new_v4();
+        let now = Utc::now();
+        let token = sign_preview_token("secret", post_id, now + Duration::hours(1));
+
+        assert!(verify_preview_token("secret", post_id, &token, now));
+        assert!(!verify_preview_token("other-secret", post_id, &token, now));
+    }
+
+    #[test]
+    fn test_expired_preview_token() {
+        let post_id = Uuid::new_v4();
+        let now = Utc::now();
+        let token = sign_preview_token("secret", post_id, now + Duration::hours(1));
+
+        assert!(!verify_preview_token(
+            "secret",
+            post_id,
+            &token,
+            now + Duration::hours(2)
+        ));
+    }
+
+    #[test]
+    fn test_preview_token_for_another_post() {
+        let now = Utc::now();
+        let token = sign_preview_token("secret", Uuid::new_v4(), now + Duration::hours(1));
+
+        assert!(!verify_preview_token("secret", Uuid::new_v4(), &token, now));
+    }
+
+    #[test]
+    fn test_tampered_preview_token() {
+        let post_id = Uuid::new_v4();
+        let now = Utc::now();
+        let token = sign_preview_token("secret", post_id, now + Duration::hours(1));
+
+        // Pushing the expiry out invalidates the signature
+        let (_, signature) = token.split_once('.').unwrap();
+        let extended = format!("{}.{}", (now + Duration::days(30)).timestamp(), signature);
+        assert!(!verify_preview_token("secret", post_id, &extended, now));
+
+        assert!(!verify_preview_token("secret", post_id, "not-a-token", now));
+        assert!(!verify_preview_token("secret", post_id, "123.not-hex", now));
+    }
+}
@@ -0,0 +1,163 @@
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{future::Future, net::SocketAddr};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+pub const REAL_IP_HEADER: &str = "x-real-ip";
+
+/// ID of the request being handled, stored in request extensions by
+/// `request_id_middleware`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+// Reuse an upstream proxy's ID when it looks sane, otherwise mint one
+pub fn request_id_from_headers(headers: &HeaderMap) -> RequestId {
+    let upstream = headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= 128
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+
+    RequestId(
+        upstream
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+    )
+}
+
+/// Runs `future` with `request_id` visible to `current_request_id`.
+pub async fn scope_request_id<F: Future>(request_id: RequestId, future: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, future).await
+}
+
+// ID of the request handled by the current task, used when rendering error bodies
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.0.clone()).ok()
+}
+
+// Helper function to extract client IP. The forwarded headers are taken at face value;
+// `proxy_headers_middleware` removes them unless server.trust_proxy_headers is set.
+pub fn get_client_ip(headers: &HeaderMap, addr: Option<&SocketAddr>) -> String {
+    // Priority: X-Forwarded-For > X-Real-IP > actual connection IP > fallback to unknown
+    if let Some(forwarded) = headers.get(FORWARDED_FOR_HEADER) {
+        if let Ok(forwarded_str) = forwarded.to_str() {
+            if let Some(first_ip) = forwarded_str.split(',').next() {
+                return first_ip.trim().to_string();
+            }
+        }
+    }
+
+    if let Some(real_ip) = headers.get(REAL_IP_HEADER) {
+        if let Ok(ip_str) = real_ip.to_str() {
+            return ip_str.to_string();
+        }
+    }
+
+    // Use actual connection IP if available
+    if let Some(socket_addr) = addr {
+        return socket_addr.ip().to_string();
+    }
+
+    "unknown".to_string()
+}
+
+// Language tags from Accept-Language, most preferred first; wildcards and q=0 are dropped
+pub fn get_accept_languages(headers: &HeaderMap) -> Vec<String> {
+    let Some(header) = headers.get("accept-language").and_then(|h| h.to_str().ok()) else {
+        return Vec::new();
+    };
+
+    let mut languages: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let tag = params.next()?.trim();
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then(|| (tag.to_string(), quality))
+        })
+        .collect();
+
+    // Stable, so equally weighted tags keep their header order
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+    languages.into_iter().map(|(tag, _)| tag).collect()
+}
+
+// Helper function to extract user agent
+pub fn get_user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("user-agent")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+/// Keyed hash of a client IP for storage. The IPv4 space is small enough to brute-force a
+/// plain digest back to addresses, so the hash is an HMAC under a server secret.
+pub fn hash_client_ip(secret: &str, ip: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(ip.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_languages_are_ordered_by_quality() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "accept-language",
+            "fr;q=0.5, id-ID, en;q=0.8, *;q=0.1, de;q=0"
+                .parse()
+                .unwrap(),
+        );
+
+        assert_eq!(get_accept_languages(&headers), vec!["id-ID", "en", "fr"]);
+        assert!(get_accept_languages(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_request_id_reuses_only_sane_upstream_ids() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, "edge-1234.abc_DEF".parse().unwrap());
+        assert_eq!(request_id_from_headers(&headers).0, "edge-1234.abc_DEF");
+
+        headers.insert(REQUEST_ID_HEADER, "bad id\"><script>".parse().unwrap());
+        let generated = request_id_from_headers(&headers).0;
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    }
+
+    #[test]
+    fn test_client_ip_hash_depends_on_the_secret() {
+        use sha2::Digest;
+
+        let hash = hash_client_ip("secret", "203.0.113.7");
+        assert_eq!(hash, hash_client_ip("secret", "203.0.113.7"));
+        assert_ne!(hash, hash_client_ip("other-secret", "203.0.113.7"));
+        assert_ne!(hash, hex::encode(Sha256::digest(b"203.0.113.7")));
+    }
+
+    #[tokio::test]
+    async fn test_current_request_id_is_scoped() {
+        assert_eq!(current_request_id(), None);
+
+        let id =
+            scope_request_id(RequestId("abc".to_string()), async { current_request_id() }).await;
+        assert_eq!(id.as_deref(), Some("abc"));
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Success envelope returned by handlers: `{"success": true, "data": ..., "message": ...}`.
+/// Errors use the `{"error": {...}}` envelope from `AppError` instead.
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: T,
+    pub message: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn ok(data: T) -> Self {
+        Self {
+            success: true,
+            data,
+            message: None,
+        }
+    }
+
+    pub fn with_message(data: T, message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            data,
+            message: Some(message.into()),
+        }
+    }
+}
+
+impl ApiResponse<()> {
+    /// For actions with nothing to return; `data` serializes as null.
+    pub fn message(message: impl Into<String>) -> Self {
+        Self::with_message((), message)
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, http::StatusCode};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_api_response_serializes_envelope() {
+        assert_eq!(
+            serde_json::to_value(ApiResponse::ok(json!({ "id": 1 }))).unwrap(),
+            json!({ "success": true, "data": { "id": 1 }, "message": null })
+        );
+        assert_eq!(
+            serde_json::to_value(ApiResponse::with_message(vec![1, 2], "Loaded")).unwrap(),
+            json!({ "success": true, "data": [1, 2], "message": "Loaded" })
+        );
+        assert_eq!(
+            serde_json::to_value(ApiResponse::message("Post deleted successfully")).unwrap(),
+            json!({ "success": true, "data": null, "message": "Post deleted successfully" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_response_keeps_status_codes() {
+        let response = (StatusCode::CREATED, ApiResponse::ok("created")).into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"], "created");
+    }
+}
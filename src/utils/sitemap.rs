@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+pub struct SitemapBuilder {
+    base_url: String,
+    seen: HashSet<String>,
+    entries: Vec<String>,
+}
+
+impl SitemapBuilder {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            seen: HashSet::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a `<url>` entry; duplicate locations are skipped
+    pub fn add(
+        &mut self,
+        path: &str,
+        lastmod: Option<DateTime<Utc>>,
+        changefreq: &str,
+        priority: f32,
+    ) -> &mut Self {
+        let loc = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
+        if !self.seen.insert(loc.clone()) {
+            return self;
+        }
+
+        let mut entry = format!("  <url>\n    <loc>{}</loc>\n", escape_xml(&loc));
+        if let Some(lastmod) = lastmod {
+            entry.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                lastmod.format("%Y-%m-%d")
+            ));
+        }
+        entry.push_str(&format!(
+            "    <changefreq>{}</changefreq>\n    <priority>{:.1}</priority>\n  </url>\n",
+            changefreq, priority
+        ));
+
+        self.entries.push(entry);
+        self
+    }
+
+    pub fn build(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            xml.push_str(entry);
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_post_slugs_appear_once() {
+        let slugs = ["hello-world", "rust-tips", "hello-world", "axum-intro"];
+
+        let mut builder = SitemapBuilder::new("https://example.com/");
+        builder.add("/", None, "weekly", 1.0);
+        for slug in slugs {
+            builder.add(&format!("blog/{}", slug), Some(Utc::now()), "monthly", 0.7);
+        }
+        let xml = builder.build();
+
+        for slug in ["hello-world", "rust-tips", "axum-intro"] {
+            let loc = format!("<loc>https://example.com/blog/{}</loc>", slug);
+            assert_eq!(xml.matches(&loc).count(), 1);
+        }
+        assert!(xml.contains("<loc>https://example.com/</loc>"));
+    }
+
+    #[test]
+    fn test_loc_is_escaped() {
+        let mut builder = SitemapBuilder::new("https://example.com");
+        builder.add("search?q=a&b", None, "daily", 0.5);
+        assert!(builder.build().contains("search?q=a&amp;b"));
+    }
+}
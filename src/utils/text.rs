@@ -0,0 +1,166 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+const WORDS_PER_MINUTE: usize = 200;
+
+static CODE_BLOCK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)```.*?```|~~~.*?~~~").unwrap());
+
+static HTML_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+static IMAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[[^\]]*\]\([^)]*\)").unwrap());
+
+static LINK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+
+pub fn strip_markdown(content: &str) -> String {
+    let text = CODE_BLOCK_REGEX.replace_all(content, " ");
+    let text = HTML_TAG_REGEX.replace_all(&text, " ");
+    let text = IMAGE_REGEX.replace_all(&text, " ");
+    let text = LINK_REGEX.replace_all(&text, "$1");
+    text.to_string()
+}
+
+pub fn count_words(content: &str) -> usize {
+    strip_markdown(content)
+        .split_whitespace()
+        .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+        .count()
+}
+
+/// Plain-text teaser of at most `max_chars` characters, cut on a word boundary
+/// and ending with an ellipsis when the content had to be shortened.
+pub fn summarize(content: &str, max_chars: usize) -> String {
+    let clean_content = strip_markdown(content)
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || ".,!?".contains(*c))
+        .collect::<String>();
+
+    truncate_at_word(
+        &clean_content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        max_chars,
+    )
+}
+
+/// Shortens `text` to at most `max_chars` characters (not bytes), so multibyte
+/// UTF-8 is never split.
+pub fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    let kept = match kept.rfind(char::is_whitespace) {
+        Some(boundary) if boundary > 0 => &kept[..boundary],
+        _ => kept.as_str(),
+    };
+
+    format!(
+        "{}...",
+        kept.trim_end_matches(|c: char| c.is_whitespace() || ",.".contains(c))
+    )
+}
+
+pub fn reading_time_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+}
+
+/// `%term%` for an `ILIKE ... ESCAPE '\'` match, with the term's own `%`, `_` and `\`
+/// escaped so they match literally instead of acting as wildcards.
+pub fn contains_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words("# Hello World\n\nThis is **bold**."), 5);
+        assert_eq!(
+            count_words("See [the docs](https://example.com/docs) - now"),
+            4
+        );
+        assert_eq!(
+            count_words("Intro\n```rust\nfn main() { println!(\"hi\"); }\n```\nOutro"),
+            2
+        );
+        assert_eq!(count_words("<div class=\"note\">Two words</div>"), 2);
+    }
+
+    #[test]
+    fn test_summarize_cuts_on_word_boundary() {
+        assert_eq!(summarize("# Short\n\nAll of it.", 160), "Short All of it.");
+        assert_eq!(
+            summarize("The quick brown fox jumps over the lazy dog", 20),
+            "The quick brown..."
+        );
+    }
+
+    #[test]
+    fn test_summarize_handles_multibyte_content() {
+        let content = "Ünïcödé ✨ 日本語のテキスト 🚀 ".repeat(40);
+        let summary = summarize(&content, 160);
+
+        assert!(summary.chars().count() <= 160);
+        assert!(summary.ends_with("..."));
+        assert!(summary.starts_with("Ünïcödé 日本語のテキスト"));
+    }
+
+    #[test]
+    fn test_truncate_at_word_respects_multibyte_boundaries() {
+        // Byte 57 falls inside the emoji, which slicing by bytes would panic on
+        let title = format!("{}🚀 launch notes", "a".repeat(56));
+        assert!(!title.is_char_boundary(57));
+        assert_eq!(
+            truncate_at_word(&title, 60),
+            format!("{}🚀...", "a".repeat(56))
+        );
+
+        // Exactly at the limit in characters even though it is longer in bytes
+        let title = "日本語".repeat(20);
+        assert_eq!(truncate_at_word(&title, 60), title);
+
+        // No whitespace to break on, so the cut lands on a character
+        let title = "日本語".repeat(21);
+        assert_eq!(
+            truncate_at_word(&title, 60),
+            format!("{}...", "日本語".repeat(19))
+        );
+    }
+
+    #[test]
+    fn test_summarize_handles_cjk_at_description_boundary() {
+        let content = format!("{} 中文内容继续写下去", "x".repeat(155));
+        assert!(!content.is_char_boundary(157));
+
+        assert_eq!(summarize(&content, 160), format!("{}...", "x".repeat(155)));
+    }
+
+    #[test]
+    fn test_contains_pattern_escapes_wildcards() {
+        assert_eq!(contains_pattern("rust"), "%rust%");
+        assert_eq!(contains_pattern("100%_off\\"), "%100\\%\\_off\\\\%");
+    }
+
+    #[test]
+    fn test_reading_time() {
+        assert_eq!(reading_time_minutes(0), 1);
+        assert_eq!(reading_time_minutes(200), 1);
+        assert_eq!(reading_time_minutes(201), 2);
+        assert_eq!(reading_time_minutes(1000), 5);
+    }
+}
@@ -0,0 +1,57 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the payload signature, formatted as `sha256=<hex>`
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Sign a webhook body so receivers can verify it came from us
+pub fn sign_webhook_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// An empty event list forwards every action
+pub fn should_forward_action(action: &str, events: &[String]) -> bool {
+    events.is_empty() || events.iter().any(|event| event == action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_webhook_payload_matches_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_webhook_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_sign_webhook_payload_depends_on_secret_and_body() {
+        let signature = sign_webhook_payload("secret", b"{\"action\":\"login\"}");
+
+        assert_ne!(
+            signature,
+            sign_webhook_payload("other", b"{\"action\":\"login\"}")
+        );
+        assert_ne!(
+            signature,
+            sign_webhook_payload("secret", b"{\"action\":\"logout\"}")
+        );
+    }
+
+    #[test]
+    fn test_should_forward_action() {
+        let events = vec!["login_failed".to_string()];
+
+        assert!(should_forward_action("login_failed", &events));
+        assert!(!should_forward_action("login", &events));
+        assert!(should_forward_action("login", &[]));
+    }
+}
@@ -0,0 +1,94 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::middleware::rate_limiter::{api_rate_limit_middleware, RedisRateLimiter};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+#[tokio::test]
+#[ignore = "requires a Redis server via REDIS_URL"]
+async fn test_request_over_the_limit_is_blocked() {
+    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let rate_limiter =
+        Arc::new(RedisRateLimiter::new(&redis_url, 20, 300, 5, 900, 5, 24, 3, 60).unwrap());
+
+    let app = Router::new()
+        .route("/limited", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(
+            Some(rate_limiter),
+            api_rate_limit_middleware,
+        ));
+
+    // A fresh client IP per run keeps earlier runs from sharing the window
+    let client_ip = format!("203.0.113.{}", rand::random::<u8>());
+    let request = || {
+        Request::builder()
+            .uri("/limited")
+            .header("x-forwarded-for", &client_ip)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    for remaining in ["2", "1", "0"] {
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-ratelimit-remaining"], remaining);
+    }
+
+    let response = app.oneshot(request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+}
+
+#[tokio::test]
+#[ignore = "requires a Redis server via REDIS_URL"]
+async fn test_concurrent_requests_cannot_overshoot_the_limit() {
+    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let rate_limiter = RedisRateLimiter::new(&redis_url, 20, 300, 5, 900, 5, 24, 3, 60).unwrap();
+
+    let client_ip = format!("198.51.100.{}", rand::random::<u8>());
+    let results = futures::future::join_all(
+        (0..20).map(|_| rate_limiter.check_api_rate_limit(&client_ip, "GET", "/burst")),
+    )
+    .await;
+
+    let allowed = results
+        .into_iter()
+        .filter(|result| result.as_ref().unwrap().allowed)
+        .count();
+    assert_eq!(allowed, 3);
+}
+
+#[tokio::test]
+async fn test_requests_pass_without_redis() {
+    let app = Router::new()
+        .route("/limited", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(
+            None::<Arc<RedisRateLimiter>>,
+            api_rate_limit_middleware,
+        ));
+
+    for _ in 0..5 {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/limited")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
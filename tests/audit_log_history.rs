@@ -0,0 +1,56 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use portfolio_backend::handlers::audit_log::{get_audit_logs_by_resource, AuditLogState};
+use serde_json::Value;
+use tower::ServiceExt;
+
+// Path validation fails before the service touches the database
+async fn get_history(uri: &str) -> (StatusCode, Value) {
+    let pool = common::unused_pool();
+    let app = Router::new()
+        .route(
+            "/audit-logs/resource/:resource_type/:resource_id",
+            get(get_audit_logs_by_resource),
+        )
+        .with_state(AuditLogState {
+            audit_log_service: common::audit_log_service(&pool),
+        });
+
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_unknown_resource_type_is_rejected() {
+    let (status, body) = get_history(&format!(
+        "/audit-logs/resource/widget/{}",
+        uuid::Uuid::new_v4()
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid resource type 'widget'"));
+}
+
+#[tokio::test]
+async fn test_malformed_resource_id_is_rejected() {
+    let (status, body) = get_history("/audit-logs/resource/post/not-a-uuid").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["message"], "Invalid resource ID");
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use portfolio_backend::{
+    handlers::auth::{login, AuthState},
+    middleware::rate_limiter::{
+        clear_account_lock, get_account_lock, record_account_failure, record_auth_failure,
+        RedisRateLimiter,
+    },
+    utils::config::CookieConfig,
+    utils::errors::AppError,
+};
+use serde_json::{json, Value};
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn test_too_many_requests_reports_retry_after() {
+    let app = Router::new().route(
+        "/limited",
+        get(|| async {
+            Err::<(), _>(AppError::TooManyRequests {
+                message: "Too many requests".to_string(),
+                retry_after: Some(120),
+            })
+        }),
+    );
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/limited")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["code"], "TOO_MANY_REQUESTS");
+    assert_eq!(body["error"]["retry_after"], 120);
+}
+
+fn app(rate_limiter: Arc<RedisRateLimiter>) -> Router {
+    // Lockouts are decided before the database is queried
+    let pool = common::unused_pool();
+    let auth_service = common::auth_service(&pool);
+    Router::new()
+        .route("/login", post(login))
+        .with_state(AuthState {
+            auth_service,
+            audit_log_service: common::audit_log_service(&pool),
+            rate_limiter: Some(rate_limiter),
+            cookie_config: CookieConfig::default(),
+        })
+}
+
+fn login_request(username: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/login")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(
+            "192.0.2.10:4000".parse::<SocketAddr>().unwrap(),
+        ))
+        .body(Body::from(
+            json!({ "username": username, "password": "wrong-password" }).to_string(),
+        ))
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires a Redis server via REDIS_URL"]
+async fn test_login_lockout_sets_retry_after() {
+    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let rate_limiter =
+        Arc::new(RedisRateLimiter::new(&redis_url, 20, 300, 5, 900, 5, 24, 60, 60).unwrap());
+
+    // Failures spread over several IPs lock the username without auto-blocking an IP
+    let username = format!("locked_{}", uuid::Uuid::new_v4());
+    for _ in 0..5 {
+        let ip = format!("198.51.100.{}", rand::random::<u8>());
+        record_auth_failure(&rate_limiter, &ip, &username)
+            .await
+            .unwrap();
+    }
+
+    let response = app(rate_limiter)
+        .oneshot(login_request(&username))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "300");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["retry_after"], 300);
+}
+
+#[tokio::test]
+#[ignore = "requires a Redis server via REDIS_URL"]
+async fn test_account_lock_transitions() {
+    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let rate_limiter =
+        Arc::new(RedisRateLimiter::new(&redis_url, 20, 300, 5, 900, 5, 24, 60, 60).unwrap());
+    let username = format!("lockout_{}", uuid::Uuid::new_v4());
+
+    // Below the threshold the account stays open
+    for _ in 0..2 {
+        let lock = record_account_failure(&rate_limiter, &username, 3, 600)
+            .await
+            .unwrap();
+        assert_eq!(lock, None);
+    }
+    assert_eq!(
+        get_account_lock(&rate_limiter, &username).await.unwrap(),
+        None
+    );
+
+    // The failure that reaches max attempts locks it, whatever case the username is in
+    let lock = record_account_failure(&rate_limiter, &username.to_uppercase(), 3, 600)
+        .await
+        .unwrap();
+    assert_eq!(lock, Some(600));
+    let remaining = get_account_lock(&rate_limiter, &username).await.unwrap();
+    assert!(remaining.is_some_and(|seconds| seconds > 0 && seconds <= 600));
+
+    let response = app(rate_limiter.clone())
+        .oneshot(login_request(&username))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Account temporarily locked"));
+
+    // Successful login and password reset both clear the lock and the count
+    clear_account_lock(&rate_limiter, &username).await.unwrap();
+    assert_eq!(
+        get_account_lock(&rate_limiter, &username).await.unwrap(),
+        None
+    );
+    let lock = record_account_failure(&rate_limiter, &username, 3, 600)
+        .await
+        .unwrap();
+    assert_eq!(lock, None);
+}
@@ -0,0 +1,95 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::DefaultBodyLimit,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::post,
+    Json, Router,
+};
+use portfolio_backend::middleware::security::payload_too_large_middleware;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use tower_http::limit::RequestBodyLimitLayer;
+
+const LIMIT: usize = 1024;
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/posts",
+            post(|Json(body): Json<Value>| async move { Json(body) }),
+        )
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(LIMIT))
+        .layer(middleware::from_fn(payload_too_large_middleware))
+}
+
+fn oversized_json() -> String {
+    json!({ "content": "x".repeat(LIMIT * 2) }).to_string()
+}
+
+async fn assert_payload_too_large(response: axum::response::Response) {
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["code"], "PAYLOAD_TOO_LARGE");
+    assert_eq!(body["error"]["message"], "Request body is too large");
+}
+
+#[tokio::test]
+async fn test_oversized_body_with_content_length_returns_413() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/posts")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(oversized_json()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_payload_too_large(response).await;
+}
+
+#[tokio::test]
+async fn test_oversized_streamed_body_returns_413() {
+    let chunks = oversized_json()
+        .into_bytes()
+        .chunks(256)
+        .map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec()))
+        .collect::<Vec<_>>();
+
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/posts")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from_stream(futures::stream::iter(chunks)))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_payload_too_large(response).await;
+}
+
+#[tokio::test]
+async fn test_body_within_limit_is_accepted() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/posts")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(json!({ "title": "Hello" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,91 @@
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use portfolio_backend::middleware::security::{
+    no_store_middleware, public_cache_control, public_cache_middleware,
+};
+use tower::ServiceExt;
+
+fn app() -> Router {
+    let public_routes = Router::new()
+        .route(
+            "/posts",
+            get(|| async { "posts" }).post(|| async { StatusCode::CREATED }),
+        )
+        .route("/posts/missing", get(|| async { StatusCode::NOT_FOUND }))
+        .route(
+            "/posts/draft",
+            get(|| async {
+                (
+                    [(
+                        header::CACHE_CONTROL,
+                        HeaderValue::from_static("private, no-store"),
+                    )],
+                    "draft",
+                )
+                    .into_response()
+            }),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            public_cache_control(60),
+            public_cache_middleware,
+        ));
+
+    Router::new()
+        .route("/admin/stats", get(|| async { "stats" }))
+        .merge(public_routes)
+        .layer(middleware::from_fn(no_store_middleware))
+}
+
+async fn cache_control(method: &str, path: &str) -> String {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(path)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    response.headers()[header::CACHE_CONTROL]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_public_get_is_cacheable() {
+    assert_eq!(cache_control("GET", "/posts").await, "public, max-age=60");
+}
+
+#[tokio::test]
+async fn test_admin_get_is_no_store() {
+    assert_eq!(cache_control("GET", "/admin/stats").await, "no-store");
+}
+
+#[tokio::test]
+async fn test_public_write_and_error_are_no_store() {
+    assert_eq!(cache_control("POST", "/posts").await, "no-store");
+    assert_eq!(cache_control("GET", "/posts/missing").await, "no-store");
+}
+
+#[tokio::test]
+async fn test_draft_previews_are_never_public() {
+    // The handler's own header survives
+    assert_eq!(
+        cache_control("GET", "/posts/draft").await,
+        "private, no-store"
+    );
+    // Anything answered for a preview token stays out of shared caches
+    assert_eq!(
+        cache_control("GET", "/posts?preview_token=abc").await,
+        "no-store"
+    );
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use portfolio_backend::utils::errors::AppError;
+use portfolio_backend::{
+    models::comment::{CreateBanRequest, CreateCommentRequest, EditCommentRequest},
+    repositories::{AdminSettingsRepository, CommentRepository, CommentRepositoryTrait},
+    services::{
+        admin_settings_service::{AdminSettingsService, AdminSettingsServiceTrait},
+        comment_service::{CommentService, CommentServiceTrait},
+    },
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn comment_service(pool: &PgPool) -> CommentService {
+    CommentService::new(
+        Arc::new(CommentRepository::new(pool.clone())),
+        Arc::new(AdminSettingsService::new(Arc::new(
+            AdminSettingsRepository::new(pool.clone()),
+        ))),
+        common::audit_log_service(pool),
+        "test-secret".to_string(),
+        Default::default(),
+    )
+}
+
+fn request(post_id: Uuid, content: &str) -> CreateCommentRequest {
+    CreateCommentRequest {
+        post_id,
+        author_name: "Visitor".to_string(),
+        // Not a trusted domain, so only the IP's history can skip moderation
+        author_email: "visitor@example.org".to_string(),
+        content: content.to_string(),
+        parent_id: None,
+    }
+}
+
+// Earlier comments from `ip`, dated outside the comment rate limit windows
+async fn seed_history(pool: &PgPool, post_id: Uuid, ip: &str, statuses: &[&str]) {
+    let repository = CommentRepository::new(pool.clone());
+    for status in statuses {
+        repository
+            .create_with_status(
+                request(post_id, "An earlier comment"),
+                Some(ip.to_string()),
+                None,
+                status.to_string(),
+            )
+            .await
+            .unwrap();
+    }
+
+    sqlx::query(
+        "UPDATE comments SET created_at = NOW() - INTERVAL '2 hours' WHERE ip_address = $1::inet",
+    )
+    .bind(ip)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn post_from(service: &CommentService, post_id: Uuid, ip: &str, content: &str) -> String {
+    service
+        .create_comment(request(post_id, content), Some(ip.to_string()), None)
+        .await
+        .unwrap()
+        .status
+}
+
+async fn insert_post(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO posts (title, slug, content, category) VALUES ('Post', 'post', 'Content', 'General') RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_ip_history_escalates_moderation(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let service = comment_service(&pool);
+    let content = "Thanks for writing this up, very helpful";
+
+    // Unknown IPs go to the moderation queue
+    assert_eq!(
+        post_from(&service, post_id, "203.0.113.1", content).await,
+        "pending"
+    );
+
+    // An approved history is enough to skip the queue, but keyword rules still apply
+    seed_history(&pool, post_id, "203.0.113.2", &["approved"]).await;
+    assert_eq!(
+        post_from(&service, post_id, "203.0.113.2", content).await,
+        "approved"
+    );
+    assert_eq!(
+        post_from(
+            &service,
+            post_id,
+            "203.0.113.2",
+            "I think I found a bug here"
+        )
+        .await,
+        "pending"
+    );
+
+    // A single spam comment spoils the history without escalating to spam
+    seed_history(&pool, post_id, "203.0.113.3", &["approved", "spam"]).await;
+    assert_eq!(
+        post_from(&service, post_id, "203.0.113.3", content).await,
+        "pending"
+    );
+
+    // Repeated spam from the same IP marks new comments as spam outright
+    seed_history(&pool, post_id, "203.0.113.4", &["spam", "spam"]).await;
+    assert_eq!(
+        post_from(&service, post_id, "203.0.113.4", content).await,
+        "spam"
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_banned_commenters_are_blocked_until_expiry(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let service = comment_service(&pool);
+    let content = "Thanks for writing this up, very helpful";
+
+    let ban = service
+        .ban_commenter(
+            CreateBanRequest {
+                email: Some("Visitor@Example.org".to_string()),
+                ip_address: None,
+                reason: Some("Repeated abuse".to_string()),
+                expires_at: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ban.email.as_deref(), Some("visitor@example.org"));
+
+    let result = service
+        .create_comment(
+            request(post_id, content),
+            Some("203.0.113.5".to_string()),
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+    // Lifting the ban lets the commenter back in
+    service.unban_commenter(ban.id).await.unwrap();
+    assert!(service
+        .create_comment(
+            request(post_id, content),
+            Some("203.0.113.5".to_string()),
+            None
+        )
+        .await
+        .is_ok());
+
+    // An IP ban whose expiry has passed no longer applies
+    CommentRepository::new(pool.clone())
+        .ban_commenter(
+            CreateBanRequest {
+                email: None,
+                ip_address: Some("203.0.113.6".to_string()),
+                reason: None,
+                expires_at: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(service
+        .create_comment(
+            request(post_id, content),
+            Some("203.0.113.6".to_string()),
+            None
+        )
+        .await
+        .is_ok());
+    assert_eq!(service.list_bans().await.unwrap().len(), 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_comment_length_limits_follow_feature_settings(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let service = comment_service(&pool);
+    let settings = AdminSettingsService::new(Arc::new(AdminSettingsRepository::new(pool.clone())));
+
+    // "Nice" is below the default minimum of 5 characters
+    let result = service
+        .create_comment(
+            request(post_id, "Nice"),
+            Some("203.0.113.7".to_string()),
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    let mut features = settings.get_all_settings().await.unwrap().features;
+    features.comment_min_length = 2;
+    features.comment_max_length = 20;
+    settings
+        .update_feature_settings(features, None)
+        .await
+        .unwrap();
+
+    assert!(service
+        .create_comment(
+            request(post_id, "Nice"),
+            Some("203.0.113.8".to_string()),
+            None
+        )
+        .await
+        .is_ok());
+    let result = service
+        .create_comment(
+            request(post_id, "This comment is now far too long"),
+            Some("203.0.113.9".to_string()),
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    // Limits are counted in characters, not bytes
+    assert!(service
+        .create_comment(
+            request(post_id, "Ünïcödé ümläüts ✓✓✓"),
+            Some("203.0.113.10".to_string()),
+            None
+        )
+        .await
+        .is_ok());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_edit_token_can_only_be_used_once(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let service = comment_service(&pool);
+    let comment = service
+        .create_comment(
+            request(post_id, "Thanks for writing this up, very helpfull"),
+            Some("203.0.113.30".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+    let edit_token = service.create_edit_token(&comment);
+    let edit = |content: &str| EditCommentRequest {
+        edit_token: edit_token.clone(),
+        content: content.to_string(),
+    };
+
+    let edited = service
+        .edit_comment(comment.id, edit("Thanks for writing this up, very helpful"))
+        .await
+        .unwrap();
+    assert_eq!(edited.content, "Thanks for writing this up, very helpful");
+
+    // Replaying the same token is refused and leaves the edited content alone
+    assert!(matches!(
+        service
+            .edit_comment(comment.id, edit("Replayed edit"))
+            .await,
+        Err(AppError::Forbidden(_))
+    ));
+    let stored: String = sqlx::query_scalar("SELECT content FROM comments WHERE id = $1")
+        .bind(comment.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, "Thanks for writing this up, very helpful");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_threaded_comments_hide_commenter_details(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let repository = CommentRepository::new(pool.clone());
+    let parent = repository
+        .create_with_status(
+            request(post_id, "Top-level comment"),
+            Some("203.0.113.40".to_string()),
+            Some("commenter-agent/1.0".to_string()),
+            "approved".to_string(),
+        )
+        .await
+        .unwrap();
+    repository
+        .create_with_status(
+            CreateCommentRequest {
+                parent_id: Some(parent.id),
+                ..request(post_id, "A reply")
+            },
+            Some("203.0.113.41".to_string()),
+            Some("commenter-agent/1.0".to_string()),
+            "approved".to_string(),
+        )
+        .await
+        .unwrap();
+
+    let threads = comment_service(&pool)
+        .get_threaded_comments(post_id)
+        .await
+        .unwrap();
+    assert_eq!(threads.len(), 1);
+    assert_eq!(threads[0].replies.len(), 1);
+
+    let body = serde_json::to_string(&threads).unwrap();
+    for leaked in ["visitor@example.org", "203.0.113.4", "commenter-agent"] {
+        assert!(!body.contains(leaked), "{} leaked into {}", leaked, body);
+    }
+}
@@ -0,0 +1,141 @@
+// Helpers shared by the integration tests; each test binary uses a different subset
+#![allow(dead_code)]
+
+use portfolio_backend::{
+    handlers::admin_settings::AdminSettingsState,
+    middleware::maintenance::MaintenanceCache,
+    repositories::{
+        AdminSettingsRepository, ApiKeyRepository, AuditLogRepository, PasswordResetRepository,
+        RefreshTokenRepository, UserRepository,
+    },
+    services::{
+        admin_settings_service::{AdminSettingsService, AdminSettingsServiceTrait},
+        api_key_service::ApiKeyService,
+        audit_log_service::AuditLogService,
+        auth_service::{AuthService, Claims},
+        email_service::{EmailService, EmailServiceTrait},
+        notification_tester::NotificationTester,
+    },
+    utils::password::PasswordService,
+};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const JWT_SECRET: &str = "test-secret";
+
+// For handlers that fail or answer before any query runs
+pub fn unused_pool() -> PgPool {
+    PgPoolOptions::new()
+        .connect_lazy("postgres://localhost/unused")
+        .unwrap()
+}
+
+pub fn admin_settings_service(pool: &PgPool) -> Arc<dyn AdminSettingsServiceTrait> {
+    Arc::new(AdminSettingsService::new(Arc::new(
+        AdminSettingsRepository::new(pool.clone()),
+    )))
+}
+
+pub fn audit_log_service(pool: &PgPool) -> Arc<AuditLogService> {
+    Arc::new(AuditLogService::new(
+        Arc::new(AuditLogRepository::new(pool.clone())),
+        None,
+        None,
+        None,
+    ))
+}
+
+pub fn admin_settings_state(pool: &PgPool) -> AdminSettingsState {
+    AdminSettingsState {
+        admin_settings_service: admin_settings_service(pool),
+        audit_log_service: audit_log_service(pool),
+        rate_limiter: None,
+        notification_tester: Arc::new(NotificationTester::default()),
+        maintenance_cache: MaintenanceCache::new(),
+    }
+}
+
+// One-hour access tokens and one-day refresh tokens
+pub fn auth_service(pool: &PgPool) -> AuthService {
+    auth_service_with_expiry(pool, 3600, 86400)
+}
+
+pub fn auth_service_with_expiry(
+    pool: &PgPool,
+    token_expiry: i64,
+    refresh_token_expiry: i64,
+) -> AuthService {
+    let email_service = Arc::new(EmailService::new(admin_settings_service(pool)));
+    auth_service_with_email(pool, email_service, token_expiry, refresh_token_expiry)
+}
+
+pub fn auth_service_with_email(
+    pool: &PgPool,
+    email_service: Arc<dyn EmailServiceTrait>,
+    token_expiry: i64,
+    refresh_token_expiry: i64,
+) -> AuthService {
+    AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(PasswordResetRepository::new(pool.clone())),
+        Arc::new(RefreshTokenRepository::new(pool.clone())),
+        Arc::new(ApiKeyService::new(Arc::new(ApiKeyRepository::new(
+            pool.clone(),
+        )))),
+        admin_settings_service(pool),
+        email_service,
+        JWT_SECRET.to_string(),
+        token_expiry,
+        refresh_token_expiry,
+    )
+}
+
+// Claims as auth_middleware would attach them for an admin
+pub fn admin_claims(user_id: Uuid) -> Claims {
+    Claims {
+        sub: user_id.to_string(),
+        username: "admin".to_string(),
+        role: "admin".to_string(),
+        jti: Uuid::new_v4().to_string(),
+        exp: 0,
+        iat: 0,
+    }
+}
+
+pub async fn insert_user(pool: &PgPool, username: &str, role: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO users (username, email, password_hash, role) VALUES ($1, $2, 'hash', $3) RETURNING id",
+    )
+    .bind(username)
+    .bind(format!("{}@example.com", username))
+    .bind(role)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+// For tests that log in, so the stored hash has to come from a real password
+pub async fn insert_user_with_password(pool: &PgPool, username: &str, password: &str) -> Uuid {
+    let password_hash = PasswordService::new().hash_password(password).unwrap();
+    sqlx::query_scalar(
+        "INSERT INTO users (username, email, password_hash) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(username)
+    .bind(format!("{}@example.com", username))
+    .bind(password_hash)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+pub async fn insert_audit_log(pool: &PgPool, action: &str, minutes_ago: i32) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO audit_logs (action, resource_type, created_at) VALUES ($1, 'post', NOW() - make_interval(mins => $2)) RETURNING id",
+    )
+    .bind(action)
+    .bind(minutes_ago)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
@@ -0,0 +1,136 @@
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    middleware::security::create_cors_layer,
+    utils::config::{CorsConfig, RateLimitConfig, SecurityConfig},
+};
+use std::collections::HashMap;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    app_with_suffix(".budhilaw.com")
+}
+
+fn app_with_suffix(suffix: &str) -> Router {
+    let config = SecurityConfig {
+        rate_limit: RateLimitConfig {
+            requests_per_minute: 100,
+            burst_size: 10,
+            window_seconds: 60,
+            routes: HashMap::new(),
+        },
+        cors: CorsConfig {
+            allowed_origins: vec!["https://budhilaw.com".to_string()],
+            allowed_origin_suffixes: vec![suffix.to_string()],
+            allowed_origin_patterns: vec![r"^http://localhost:\d+$".to_string()],
+            allowed_methods: vec!["GET".to_string(), "PUT".to_string()],
+            allowed_headers: vec!["Content-Type".to_string()],
+            expose_headers: vec![],
+            max_age: 600,
+        },
+        feature_check_fail_open: Default::default(),
+    };
+
+    Router::new()
+        .route("/api/v1/posts", get(|| async { "posts" }))
+        .layer(create_cors_layer(&config))
+}
+
+async fn preflight(origin: &str) -> axum::response::Response {
+    preflight_to(app(), origin).await
+}
+
+async fn preflight_to(app: Router, origin: &str) -> axum::response::Response {
+    app.oneshot(
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/v1/posts")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PUT")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_allowed_origins_are_echoed_with_credentials() {
+    for origin in [
+        "https://budhilaw.com",
+        "https://admin.budhilaw.com",
+        "http://localhost:5173",
+    ] {
+        let response = preflight(origin).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], origin);
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert!(headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap()
+            .contains("PUT"));
+    }
+}
+
+#[tokio::test]
+async fn test_disallowed_origins_get_no_allow_origin_header() {
+    for origin in [
+        "https://evil.example",
+        "https://evilbudhilaw.com",
+        "http://localhost.evil.example:3000",
+    ] {
+        let response = preflight(origin).await;
+
+        assert!(
+            !response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            "{} should not be allowed",
+            origin
+        );
+    }
+
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/posts")
+                .header(header::ORIGIN, "https://evil.example")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(!response
+        .headers()
+        .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn test_suffixes_match_whole_domain_labels() {
+    // With or without the leading dot, a suffix covers the domain and its subdomains only
+    for suffix in ["budhilaw.com", ".budhilaw.com"] {
+        for (origin, allowed) in [
+            ("https://blog.budhilaw.com", true),
+            ("https://a.b.budhilaw.com:8443", true),
+            ("https://budhilaw.com.evil.example", false),
+            ("https://evilbudhilaw.com", false),
+        ] {
+            let response = preflight_to(app_with_suffix(suffix), origin).await;
+            assert_eq!(
+                response
+                    .headers()
+                    .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+                allowed,
+                "{} with suffix {}",
+                origin,
+                suffix
+            );
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    middleware::feature_flags::{feature_flag_middleware, Feature, FeatureFlagCache},
+    models::admin_settings::FeatureSettings,
+    utils::config::FeatureCheckFailOpen,
+};
+use tower::ServiceExt;
+
+fn app(cache: FeatureFlagCache) -> Router {
+    let post_public_routes = Router::new()
+        .route("/published", get(|| async { "published" }))
+        .route("/", get(|| async { "posts" }))
+        .route_layer(middleware::from_fn_with_state(
+            cache.gate(Feature::Blog),
+            feature_flag_middleware,
+        ));
+
+    Router::new()
+        .nest("/api/v1/posts", post_public_routes)
+        .route(
+            "/api/v1/admin/settings/features",
+            get(|| async { "features" }),
+        )
+}
+
+async fn get_status(app: Router, uri: &str) -> (StatusCode, String) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_disabling_blog_hides_public_post_routes() {
+    let cache = FeatureFlagCache::new();
+
+    let (status, _) = get_status(app(cache.clone()), "/api/v1/posts/published").await;
+    assert_eq!(status, StatusCode::OK);
+
+    cache.set(FeatureSettings {
+        blog_enabled: false,
+        ..FeatureSettings::default()
+    });
+
+    let (status, body) = get_status(app(cache.clone()), "/api/v1/posts/published").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body.contains("Blog is currently disabled"));
+
+    // Admin routes stay reachable so the blog can be turned back on
+    let (status, _) = get_status(app(cache), "/api/v1/admin/settings/features").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_disabling_search_rejects_search_queries() {
+    let cache = FeatureFlagCache::new();
+    cache.set(FeatureSettings {
+        search_enabled: false,
+        ..FeatureSettings::default()
+    });
+
+    let (status, _) = get_status(app(cache.clone()), "/api/v1/posts?search=rust").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = get_status(app(cache), "/api/v1/posts?page=2&search=").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_disabling_search_hides_search_route() {
+    let cache = FeatureFlagCache::new();
+    cache.set(FeatureSettings {
+        search_enabled: false,
+        ..FeatureSettings::default()
+    });
+
+    let app = Router::new().route(
+        "/api/v1/search",
+        get(|| async { "results" }).route_layer(middleware::from_fn_with_state(
+            cache.gate(Feature::Search),
+            feature_flag_middleware,
+        )),
+    );
+
+    let (status, body) = get_status(app, "/api/v1/search?q=rust").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body.contains("Search is currently disabled"));
+}
+
+fn portfolio_app(cache: FeatureFlagCache) -> Router {
+    Router::new()
+        .route(
+            "/api/v1/portfolio/:id/views",
+            get(|| async { "views" }).post(|| async { "recorded" }),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            cache.gate(Feature::Portfolio),
+            feature_flag_middleware,
+        ))
+}
+
+async fn send(app: Router, method: &str) -> StatusCode {
+    app.oneshot(
+        Request::builder()
+            .method(method)
+            .uri("/api/v1/portfolio/1/views")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test]
+async fn test_unloaded_flags_fail_open_for_reads_and_closed_for_writes() {
+    // Nothing has been loaded yet, as when admin settings can't be read
+    let cache = FeatureFlagCache::new().with_fail_open(FeatureCheckFailOpen::default());
+
+    assert_eq!(
+        send(portfolio_app(cache.clone()), "GET").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(portfolio_app(cache.clone()), "POST").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    // Once the flags load, writes go through again
+    cache.set(FeatureSettings::default());
+    assert_eq!(
+        send(portfolio_app(cache.clone()), "POST").await,
+        StatusCode::OK
+    );
+
+    // And a failed refresh puts the gate back on the policy
+    cache.invalidate();
+    assert_eq!(
+        send(portfolio_app(cache), "POST").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn test_unloaded_flags_can_fail_closed_for_reads() {
+    let cache = FeatureFlagCache::new().with_fail_open(FeatureCheckFailOpen {
+        reads: false,
+        writes: false,
+    });
+    assert_eq!(
+        send(portfolio_app(cache), "GET").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    let cache = FeatureFlagCache::new().with_fail_open(FeatureCheckFailOpen {
+        reads: true,
+        writes: true,
+    });
+    assert_eq!(send(portfolio_app(cache), "POST").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_stale_flags_still_hide_disabled_features_when_failing_open() {
+    let cache = FeatureFlagCache::new();
+    cache.set(FeatureSettings {
+        portfolio_enabled: false,
+        ..FeatureSettings::default()
+    });
+    cache.invalidate();
+
+    assert_eq!(
+        send(portfolio_app(cache), "GET").await,
+        StatusCode::NOT_FOUND
+    );
+}
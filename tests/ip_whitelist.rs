@@ -0,0 +1,62 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use portfolio_backend::middleware::auth::admin_only;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+// Stands in for a content-admin group, guarded the way create_app guards it
+fn portfolio_app(pool: &PgPool) -> Router {
+    admin_only(
+        Router::new().route("/portfolio", get(|| async { "projects" })),
+        common::auth_service(pool),
+        common::admin_settings_service(pool),
+    )
+}
+
+async fn status_from(app: Router, ip: [u8; 4]) -> StatusCode {
+    app.oneshot(
+        Request::builder()
+            .uri("/portfolio")
+            .extension(ConnectInfo(SocketAddr::from((ip, 4000))))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_content_admin_routes_enforce_ip_whitelist(pool: PgPool) {
+    let admin_settings_service = common::admin_settings_service(&pool);
+    let mut security = admin_settings_service
+        .get_all_settings()
+        .await
+        .unwrap()
+        .security;
+    security.ip_whitelist = vec!["10.0.0.0/8".to_string()];
+    admin_settings_service
+        .update_security_settings(security, None)
+        .await
+        .unwrap();
+
+    // Blocked before authentication, so no token is needed to see the 403
+    assert_eq!(
+        status_from(portfolio_app(&pool), [192, 0, 2, 1]).await,
+        StatusCode::FORBIDDEN
+    );
+    // A whitelisted address moves on to the token check
+    assert_eq!(
+        status_from(portfolio_app(&pool), [10, 1, 2, 3]).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
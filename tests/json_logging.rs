@@ -0,0 +1,87 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    middleware::security::request_id_middleware,
+    utils::{config::LogFormat, logging::fmt_layer},
+};
+use serde_json::Value;
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+use tower::ServiceExt;
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl CapturedLogs {
+    fn lines(&self) -> Vec<Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_json_logs_carry_request_id_and_rfc3339_timestamp() {
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::registry().with(fmt_layer(LogFormat::Json, logs.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = Router::new()
+        .route(
+            "/",
+            get(|| async {
+                tracing::info!("handling request");
+                "ok"
+            }),
+        )
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let response = app
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let request_id = response.headers()["x-request-id"].to_str().unwrap();
+
+    let lines = logs.lines();
+    let line = lines
+        .iter()
+        .find(|line| line["fields"]["message"] == "handling request")
+        .expect("handler log line was not captured");
+
+    assert_eq!(line["span"]["request_id"], request_id);
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(line["timestamp"].as_str().unwrap()).is_ok(),
+        "timestamp is not RFC3339: {}",
+        line["timestamp"]
+    );
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use portfolio_backend::{
+    handlers::auth::{login, refresh_token, AuthState},
+    utils::config::CookieConfig,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+fn app(pool: PgPool) -> Router {
+    let auth_service = common::auth_service(&pool);
+
+    Router::new()
+        .route("/login", post(login))
+        .route("/refresh", post(refresh_token))
+        .with_state(AuthState {
+            auth_service,
+            audit_log_service: common::audit_log_service(&pool),
+            rate_limiter: None,
+            cookie_config: CookieConfig::default(),
+        })
+}
+
+async fn refresh_with(
+    pool: &PgPool,
+    header_token: Option<&str>,
+    body: Value,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/refresh")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+    if let Some(token) = header_token {
+        request = request.header("x-refresh-token", token);
+    }
+
+    let response = app(pool.clone())
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn login_as(pool: &PgPool, auth_mode: Option<&str>) -> (StatusCode, usize, Value) {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/login")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+    if let Some(mode) = auth_mode {
+        request = request.header("x-auth-mode", mode);
+    }
+
+    let response = app(pool.clone())
+        .oneshot(
+            request
+                .body(Body::from(
+                    json!({ "username": "token_admin", "password": "correct-horse-battery" })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let cookies = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .count();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, cookies, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_login_returns_tokens_only_when_requested(pool: PgPool) {
+    common::insert_user_with_password(&pool, "token_admin", "correct-horse-battery").await;
+
+    // Default stays cookie-only
+    let (status, cookies, body) = login_as(&pool, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cookies, 2);
+    assert_eq!(body["data"]["user"]["username"], "token_admin");
+    assert!(body["data"].get("token").is_none());
+    assert!(body["data"].get("refresh_token").is_none());
+
+    let (status, cookies, body) = login_as(&pool, Some("token")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cookies, 2);
+    assert!(body["data"]["token"]
+        .as_str()
+        .is_some_and(|t| !t.is_empty()));
+    assert!(body["data"]["refresh_token"].is_string());
+    assert!(body["data"]["refresh_expires_at"].is_string());
+
+    // Unknown modes fall back to cookies only
+    let (_, _, body) = login_as(&pool, Some("cookie")).await;
+    assert!(body["data"].get("token").is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_refresh_accepts_token_from_body_or_header(pool: PgPool) {
+    common::insert_user_with_password(&pool, "token_admin", "correct-horse-battery").await;
+    let (_, _, login) = login_as(&pool, Some("token")).await;
+    let first = login["data"]["refresh_token"].as_str().unwrap().to_string();
+
+    let (status, body) = refresh_with(&pool, None, json!({ "refresh_token": first })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["token"].is_string());
+    let second = body["data"]["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(first, second);
+
+    let (status, body) = refresh_with(&pool, Some(&second), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["refresh_token"].is_string());
+
+    // Rotation spent the earlier tokens, and a request carrying none is refused
+    let (status, _) = refresh_with(&pool, Some(&second), json!({})).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = refresh_with(&pool, None, json!({})).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use portfolio_backend::{
+    handlers::admin_settings::{
+        get_maintenance_mode, reset_settings, set_maintenance_mode, AdminSettingsState,
+    },
+    middleware::maintenance::{maintenance_middleware, MaintenanceCache, MaintenanceStatus},
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::{collections::HashMap, net::SocketAddr};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn public_app(cache: MaintenanceCache) -> Router {
+    let public_routes = Router::new()
+        .route("/posts", get(|| async { "posts" }))
+        .route_layer(middleware::from_fn_with_state(
+            cache,
+            maintenance_middleware,
+        ));
+
+    Router::new()
+        .nest("/api/v1", public_routes)
+        .route("/api/v1/auth/login", get(|| async { "login" }))
+}
+
+async fn get_status(app: Router, uri: &str) -> (StatusCode, String) {
+    send_public(
+        app,
+        Request::builder().uri(uri).body(Body::empty()).unwrap(),
+    )
+    .await
+}
+
+async fn send_public(app: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_toggling_maintenance_mode_blocks_public_routes() {
+    let cache = MaintenanceCache::new();
+
+    let (status, _) = get_status(public_app(cache.clone()), "/api/v1/posts").await;
+    assert_eq!(status, StatusCode::OK);
+
+    cache.set(MaintenanceStatus {
+        enabled: true,
+        message: "Back in five minutes".to_string(),
+        ..MaintenanceStatus::default()
+    });
+
+    let (status, body) = get_status(public_app(cache.clone()), "/api/v1/posts").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body.contains("Back in five minutes"));
+
+    // Login stays reachable so admins can turn maintenance off again
+    let (status, _) = get_status(public_app(cache.clone()), "/api/v1/auth/login").await;
+    assert_eq!(status, StatusCode::OK);
+
+    cache.set(MaintenanceStatus::default());
+
+    let (status, _) = get_status(public_app(cache), "/api/v1/posts").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_maintenance_message_follows_accept_language() {
+    let cache = MaintenanceCache::new();
+    cache.set(MaintenanceStatus {
+        enabled: true,
+        message: "Back in five minutes".to_string(),
+        messages: HashMap::from([("id".to_string(), "Kembali dalam lima menit".to_string())]),
+    });
+
+    let localized = Request::builder()
+        .uri("/api/v1/posts")
+        .header("accept-language", "id-ID,id;q=0.9,en;q=0.8")
+        .body(Body::empty())
+        .unwrap();
+    let (status, body) = send_public(public_app(cache.clone()), localized).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body.contains("Kembali dalam lima menit"));
+
+    // No translation for French, so the default message is used
+    let missing = Request::builder()
+        .uri("/api/v1/posts")
+        .header("accept-language", "fr-FR")
+        .body(Body::empty())
+        .unwrap();
+    let (status, body) = send_public(public_app(cache), missing).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body.contains("Back in five minutes"));
+}
+
+async fn send(app: &Router, request: Request<Body>) -> Value {
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn set_request(user_id: Uuid, payload: Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri("/maintenance-mode")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+        .extension(common::admin_claims(user_id))
+        .body(Body::from(payload.to_string()))
+        .unwrap()
+}
+
+fn get_request() -> Request<Body> {
+    Request::builder()
+        .uri("/maintenance-mode")
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn settings_app(pool: &PgPool, maintenance_cache: MaintenanceCache) -> Router {
+    Router::new()
+        .route(
+            "/maintenance-mode",
+            get(get_maintenance_mode).put(set_maintenance_mode),
+        )
+        .route("/reset", post(reset_settings))
+        .with_state(AdminSettingsState {
+            maintenance_cache,
+            ..common::admin_settings_state(pool)
+        })
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_toggle_maintenance_mode(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "maintenance_admin", "admin").await;
+
+    let maintenance_cache = MaintenanceCache::new();
+    let app = settings_app(&pool, maintenance_cache.clone());
+
+    send(
+        &app,
+        set_request(
+            user_id,
+            json!({ "enabled": true, "message": "Back in ten minutes" }),
+        ),
+    )
+    .await;
+
+    let status = send(&app, get_request()).await;
+    assert_eq!(status["maintenance_mode"], true);
+    assert_eq!(status["maintenance_message"], "Back in ten minutes");
+
+    // Public routes see the change without waiting for the background refresh
+    let cached = maintenance_cache.get();
+    assert!(cached.enabled);
+    assert_eq!(cached.message, "Back in ten minutes");
+
+    let audit_entries: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM audit_logs WHERE action = 'settings_updated'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(audit_entries, 1);
+
+    // Turning it off keeps the message for next time
+    send(&app, set_request(user_id, json!({ "enabled": false }))).await;
+
+    let status = send(&app, get_request()).await;
+    assert_eq!(status["maintenance_mode"], false);
+    assert!(!maintenance_cache.get().enabled);
+    assert_eq!(maintenance_cache.get().message, "Back in ten minutes");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_reset_settings_clears_cached_maintenance_mode(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "maintenance_admin", "admin").await;
+
+    let maintenance_cache = MaintenanceCache::new();
+    let app = settings_app(&pool, maintenance_cache.clone());
+
+    send(&app, set_request(user_id, json!({ "enabled": true }))).await;
+    assert!(maintenance_cache.get().enabled);
+
+    let reset = Request::builder()
+        .method("POST")
+        .uri("/reset")
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+        .extension(common::admin_claims(user_id))
+        .body(Body::empty())
+        .unwrap();
+    send(&app, reset).await;
+
+    // The defaults turn maintenance off without waiting for the background refresh
+    assert!(!maintenance_cache.get().enabled);
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use portfolio_backend::{
+    models::user_notification::{MarkNotificationsReadRequest, NotificationFilter},
+    repositories::UserNotificationRepository,
+    services::user_notification_service::{UserNotificationService, UserNotificationServiceTrait},
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+fn unread_only() -> NotificationFilter {
+    NotificationFilter {
+        unread_only: true,
+        ..Default::default()
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_unread_only_excludes_read_notifications(pool: PgPool) {
+    let service =
+        UserNotificationService::new(Arc::new(UserNotificationRepository::new(pool.clone())));
+    let user_id = common::insert_user(&pool, "reader", "user").await;
+    sqlx::query("DELETE FROM audit_logs")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut ids = Vec::new();
+    for minutes_ago in 0..25 {
+        ids.push(common::insert_audit_log(&pool, "post_created", minutes_ago).await);
+    }
+    // The three newest are read
+    service
+        .mark_notifications_read(
+            user_id,
+            MarkNotificationsReadRequest {
+                audit_log_ids: ids[..3].to_vec(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let all = service
+        .get_user_notifications(user_id, Some(10), Some(0), NotificationFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(all.total, 25);
+    assert_eq!(all.total_pages, 3);
+    assert_eq!(all.notifications.len(), 10);
+    assert!(all.notifications[0].read);
+
+    let unread = service
+        .get_user_notifications(user_id, Some(10), Some(20), unread_only())
+        .await
+        .unwrap();
+    assert_eq!(unread.total, 22);
+    assert_eq!(unread.page, 3);
+    assert_eq!(unread.total_pages, 3);
+    assert_eq!(unread.notifications.len(), 2);
+    assert!(unread.notifications.iter().all(|n| !n.read));
+
+    let first_unread = service
+        .get_user_notifications(user_id, Some(10), Some(0), unread_only())
+        .await
+        .unwrap();
+    assert_eq!(first_unread.notifications[0].id, ids[3]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_filter_by_action_and_unread_summary(pool: PgPool) {
+    let service =
+        UserNotificationService::new(Arc::new(UserNotificationRepository::new(pool.clone())));
+    let user_id = common::insert_user(&pool, "reader", "user").await;
+    sqlx::query("DELETE FROM audit_logs")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let login = common::insert_audit_log(&pool, "login", 0).await;
+    for minutes_ago in 1..4 {
+        common::insert_audit_log(&pool, "comment_created", minutes_ago).await;
+    }
+    common::insert_audit_log(&pool, "post_created", 5).await;
+    // Audit actions that aren't notification types stay out of the summary
+    common::insert_audit_log(&pool, "bulk_delete", 6).await;
+    service
+        .mark_notifications_read(
+            user_id,
+            MarkNotificationsReadRequest {
+                audit_log_ids: vec![login],
+            },
+        )
+        .await
+        .unwrap();
+
+    let comments = service
+        .get_user_notifications(
+            user_id,
+            None,
+            None,
+            NotificationFilter {
+                action: Some("comment_created".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(comments.total, 3);
+    assert!(comments
+        .notifications
+        .iter()
+        .all(|n| n.action == "comment_created"));
+
+    // Actions outside the notification types are rejected
+    let invalid = service
+        .get_user_notifications(
+            user_id,
+            None,
+            None,
+            NotificationFilter {
+                action: Some("drop_table".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(invalid.is_err());
+
+    let summary = service.get_unread_summary(user_id).await.unwrap();
+    assert_eq!(summary.total_unread, 4);
+    let by_type: Vec<_> = summary
+        .by_type
+        .iter()
+        .map(|count| (count.notification_type.as_str(), count.unread))
+        .collect();
+    assert_eq!(by_type, [("comment_created", 3), ("post_created", 1)]);
+}
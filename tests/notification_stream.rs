@@ -0,0 +1,162 @@
+mod common;
+
+use axum::{
+    extract::Request,
+    middleware::{self, Next},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use portfolio_backend::{
+    handlers::user_notification::{notifications_ws, UserNotificationState},
+    models::audit_log::AuditLog,
+    repositories::{AuditLogRepository, UserNotificationRepository},
+    services::{
+        audit_log_service::AuditLogService, auth_service::Claims,
+        notification_hub::NotificationHub, user_notification_service::UserNotificationService,
+    },
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::net::TcpListener;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use uuid::Uuid;
+
+fn audit_log(action: &str) -> AuditLog {
+    AuditLog {
+        id: Uuid::new_v4(),
+        user_id: None,
+        user_name: Some("admin".to_string()),
+        action: action.to_string(),
+        resource_type: "post".to_string(),
+        resource_id: None,
+        resource_title: Some("Hello".to_string()),
+        details: None,
+        old_values: None,
+        new_values: None,
+        ip_address: None,
+        user_agent: None,
+        success: true,
+        error_message: None,
+        created_at: Utc::now(),
+    }
+}
+
+// Serves the socket on a real port; claims are attached as auth_middleware would
+async fn serve(pool: PgPool, hub: NotificationHub) -> SocketAddr {
+    let state = UserNotificationState {
+        user_notification_service: Arc::new(UserNotificationService::new(Arc::new(
+            UserNotificationRepository::new(pool),
+        ))),
+        notification_hub: hub,
+    };
+    let claims = Claims {
+        sub: Uuid::new_v4().to_string(),
+        username: "admin".to_string(),
+        role: "admin".to_string(),
+        jti: Uuid::new_v4().to_string(),
+        exp: Utc::now().timestamp() + 3600,
+        iat: Utc::now().timestamp(),
+    };
+    let app = Router::new()
+        .route("/ws", get(notifications_ws))
+        .with_state(state)
+        .layer(middleware::from_fn(
+            move |mut request: Request, next: Next| {
+                let claims = claims.clone();
+                async move {
+                    request.extensions_mut().insert(claims);
+                    next.run(request).await
+                }
+            },
+        ));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_connected_client_receives_new_notification(pool: PgPool) {
+    let hub = NotificationHub::new();
+    let addr = serve(pool.clone(), hub.clone()).await;
+    let audit_log_service = AuditLogService::new(
+        Arc::new(AuditLogRepository::new(pool)),
+        None,
+        None,
+        Some(hub),
+    );
+
+    let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+
+    let created = audit_log_service
+        .log_admin_action(
+            None,
+            Some("admin".to_string()),
+            "post_created",
+            "post",
+            None,
+            Some("Hello".to_string()),
+            None,
+            None,
+            None,
+            true,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+        .await
+        .expect("notification was not delivered")
+        .unwrap()
+        .unwrap();
+    let notification: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+
+    assert_eq!(notification["id"], created.id.to_string());
+    assert_eq!(notification["action"], "post_created");
+    assert_eq!(notification["read"], false);
+}
+
+#[tokio::test]
+async fn test_socket_answers_client_pings() {
+    // The socket handler never touches the database
+    let pool = common::unused_pool();
+    let addr = serve(pool, NotificationHub::new()).await;
+
+    let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+    socket
+        .send(Message::Ping(b"keepalive".to_vec()))
+        .await
+        .unwrap();
+
+    let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+        .await
+        .expect("ping was not answered")
+        .unwrap()
+        .unwrap();
+    assert_eq!(message, Message::Pong(b"keepalive".to_vec()));
+}
+
+#[tokio::test]
+async fn test_hub_drops_channels_without_listeners() {
+    let hub = NotificationHub::new();
+    let user_id = Uuid::new_v4();
+
+    let mut receiver = hub.subscribe(user_id);
+    hub.publish(user_id, audit_log("login").into());
+    assert_eq!(receiver.recv().await.unwrap().action, "login");
+
+    drop(receiver);
+    hub.publish(user_id, audit_log("logout").into());
+
+    // A new subscriber doesn't see notifications sent while nobody was connected
+    let mut receiver = hub.subscribe(user_id);
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use async_trait::async_trait;
+use portfolio_backend::{services::email_service::EmailServiceTrait, utils::errors::AppError};
+use sqlx::PgPool;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+
+// Stands in for an SMTP server that takes a while to accept the message
+struct SlowEmailService {
+    sent: mpsc::UnboundedSender<String>,
+}
+
+#[async_trait]
+impl EmailServiceTrait for SlowEmailService {
+    async fn send_email(&self, to: &str, _subject: &str, _body: &str) -> Result<(), AppError> {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let _ = self.sent.send(to.to_string());
+        Ok(())
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_known_email_is_answered_without_waiting_on_delivery(pool: PgPool) {
+    common::insert_user(&pool, "forgetful", "admin").await;
+    let (sent, mut delivered) = mpsc::unbounded_channel();
+    let service =
+        common::auth_service_with_email(&pool, Arc::new(SlowEmailService { sent }), 3600, 604800);
+
+    let started = Instant::now();
+    let unknown = service
+        .request_password_reset("nobody@example.com")
+        .await
+        .unwrap();
+    assert!(unknown.is_none());
+
+    let known = service
+        .request_password_reset("forgetful@example.com")
+        .await
+        .unwrap();
+    assert!(known.is_some());
+
+    // Both answers come back before the slow mail server has accepted anything
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    let recipient = tokio::time::timeout(Duration::from_secs(5), delivered.recv())
+        .await
+        .expect("reset email was not sent")
+        .unwrap();
+    assert_eq!(recipient, "forgetful@example.com");
+
+    let tokens: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM password_reset_tokens")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(tokens, 1);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    handlers::portfolio::{get_project_by_slug, PortfolioState},
+    repositories::PortfolioRepository,
+    services::portfolio_service::PortfolioService,
+};
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+
+fn slug_request(slug: &str) -> Request<Body> {
+    Request::builder()
+        .uri(format!("/portfolio/slug/{}", slug))
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_public_slug_counts_views_of_active_projects(pool: PgPool) {
+    sqlx::query(
+        r#"
+        INSERT INTO portfolio_projects (title, slug, description, category, active, start_date)
+        VALUES ('Live', 'live', 'Shown publicly', 'Web', true, '2025-01-01'),
+               ('Hidden', 'hidden', 'Not shown publicly', 'Web', false, '2025-01-01')
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Without Redis every view is counted
+    let app = Router::new()
+        .route("/portfolio/slug/:slug", get(get_project_by_slug))
+        .with_state(PortfolioState {
+            portfolio_service: Arc::new(PortfolioService::new(
+                Arc::new(PortfolioRepository::new(pool.clone())),
+                None,
+            )),
+            audit_log_service: common::audit_log_service(&pool),
+        });
+
+    for _ in 0..2 {
+        let response = app.clone().oneshot(slug_request("live")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app.oneshot(slug_request("hidden")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let views: Vec<(String, i32)> =
+        sqlx::query_as("SELECT slug, view_count FROM portfolio_projects ORDER BY slug")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        views,
+        vec![("hidden".to_string(), 0), ("live".to_string(), 2)]
+    );
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::NaiveDate;
+use portfolio_backend::{
+    handlers::post::{import_posts, PostState},
+    repositories::PostRepository,
+    services::blog_service::BlogService,
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::{
+    io::{Cursor, Write},
+    net::SocketAddr,
+    sync::Arc,
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const BODY: &str = "This body is comfortably long enough to pass the post content validation.";
+
+fn app(pool: PgPool) -> Router {
+    Router::new()
+        .route("/posts/import", post(import_posts))
+        .with_state(PostState {
+            blog_service: Arc::new(BlogService::new(
+                Arc::new(PostRepository::new(pool.clone())),
+                None,
+                "test-secret".to_string(),
+            )),
+            audit_log_service: common::audit_log_service(&pool),
+        })
+}
+
+fn markdown_archive(title: &str) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    let files = [
+        (
+            "posts/first.md",
+            format!(
+                "---\ntitle: {}\nslug: first-post\ntags: [rust, migration]\ndate: 2023-05-01\ncategories: [Programming]\n---\n\n{}\n",
+                title, BODY
+            ),
+        ),
+        (
+            // No slug in the frontmatter, so it comes from the title
+            "posts/second.md",
+            format!("---\ntitle: Second Imported Post\ndraft: true\n---\n{}\n", BODY),
+        ),
+        (
+            "posts/broken.md",
+            format!("---\ntitle: Broken Post\ndate: yesterday\n---\n{}\n", BODY),
+        ),
+    ];
+    for (name, contents) in files {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+async fn import(app: Router, mode: &str, archive: Vec<u8>) -> Value {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/posts/import?mode={}", mode))
+                .header(header::CONTENT_TYPE, "application/zip")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(Uuid::new_v4()))
+                .body(Body::from(archive))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice::<Value>(&body).unwrap()["data"].clone()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_import_markdown_with_frontmatter(pool: PgPool) {
+    let data = import(app(pool.clone()), "skip", markdown_archive("First Post")).await;
+    assert_eq!(data["created"], 2);
+    assert_eq!(data["skipped"], 0);
+    assert_eq!(data["failed"], 1);
+
+    // Files are processed in path order
+    let results = data["results"].as_array().unwrap();
+    assert_eq!(results[0]["source"], "posts/broken.md");
+    assert_eq!(results[0]["outcome"], "failed");
+    assert!(results[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid date"));
+    assert_eq!(results[2]["slug"], "second-imported-post");
+
+    let (title, tags, category, published, published_at): (
+        String,
+        Vec<String>,
+        String,
+        bool,
+        Option<chrono::DateTime<chrono::Utc>>,
+    ) = sqlx::query_as(
+        "SELECT title, tags, category, published, published_at FROM posts WHERE slug = 'first-post'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(title, "First Post");
+    assert_eq!(tags, ["rust", "migration"]);
+    assert_eq!(category, "Programming");
+    assert!(published);
+    assert_eq!(
+        published_at.unwrap().date_naive(),
+        NaiveDate::from_ymd_opt(2023, 5, 1).unwrap()
+    );
+
+    let draft: bool =
+        sqlx::query_scalar("SELECT published FROM posts WHERE slug = 'second-imported-post'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!draft);
+
+    // Existing slugs are left alone in skip mode and replaced in overwrite mode
+    let data = import(app(pool.clone()), "skip", markdown_archive("Renamed Post")).await;
+    assert_eq!(data["created"], 0);
+    assert_eq!(data["skipped"], 2);
+    assert_eq!(data["failed"], 1);
+
+    let data = import(
+        app(pool.clone()),
+        "overwrite",
+        markdown_archive("Renamed Post"),
+    )
+    .await;
+    assert_eq!(data["updated"], 2);
+
+    let title: String = sqlx::query_scalar("SELECT title FROM posts WHERE slug = 'first-post'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(title, "Renamed Post");
+}
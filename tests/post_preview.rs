@@ -0,0 +1,148 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    response::Response,
+    routing::{get, post},
+    Router,
+};
+use chrono::{Duration, Utc};
+use portfolio_backend::{
+    handlers::post::{create_preview_link, get_all_posts, get_post, get_post_by_slug, PostState},
+    repositories::PostRepository,
+    services::blog_service::BlogService,
+    utils::preview_token::sign_preview_token,
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const SECRET: &str = "test-secret";
+
+fn app(pool: PgPool) -> Router {
+    Router::new()
+        .route("/posts", get(get_all_posts))
+        .route("/posts/:id", get(get_post))
+        .route("/posts/slug/:slug", get(get_post_by_slug))
+        .route("/posts/:id/preview-link", post(create_preview_link))
+        .with_state(PostState {
+            blog_service: Arc::new(BlogService::new(
+                Arc::new(PostRepository::new(pool.clone())),
+                None,
+                SECRET.to_string(),
+            )),
+            audit_log_service: common::audit_log_service(&pool),
+        })
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn insert_draft(pool: &PgPool, slug: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO posts (title, slug, content, category, published) VALUES ('Draft post', $1, 'Content that is comfortably long enough to pass post validation', 'Programming', false) RETURNING id",
+    )
+    .bind(slug)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_draft_requires_a_valid_preview_token(pool: PgPool) {
+    let draft_id = insert_draft(&pool, "secret-draft").await;
+    let other_id = insert_draft(&pool, "other-draft").await;
+    let app = app(pool);
+
+    // Guessing the slug is no longer enough
+    for uri in [
+        "/posts/slug/secret-draft",
+        "/posts/slug/secret-draft?preview=true",
+    ] {
+        assert_eq!(send(&app, "GET", uri).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    let response = send(&app, "POST", &format!("/posts/{}/preview-link", draft_id)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let link: Value = serde_json::from_slice(&body).unwrap();
+    let path = link["data"]["path"].as_str().unwrap();
+    let uri = path.trim_start_matches("/api/v1");
+
+    let response = send(&app, "GET", uri).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "private, no-store"
+    );
+
+    // A token signed for another draft doesn't open this one
+    let token = link["data"]["token"].as_str().unwrap();
+    let uri = format!("/posts/slug/other-draft?preview_token={}", token);
+    assert_eq!(
+        send(&app, "GET", &uri).await.status(),
+        StatusCode::NOT_FOUND
+    );
+
+    let expired = sign_preview_token(SECRET, other_id, Utc::now() - Duration::minutes(1));
+    let uri = format!("/posts/slug/other-draft?preview_token={}", expired);
+    assert_eq!(
+        send(&app, "GET", &uri).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_drafts_are_hidden_from_public_id_and_list_routes(pool: PgPool) {
+    let draft_id = insert_draft(&pool, "hidden-draft").await;
+    let app = app(pool);
+
+    let uri = format!("/posts/{}", draft_id);
+    assert_eq!(
+        send(&app, "GET", &uri).await.status(),
+        StatusCode::NOT_FOUND
+    );
+
+    for uri in ["/posts", "/posts?published=false"] {
+        let response = send(&app, "GET", uri).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["data"]["posts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|post| post["id"] != draft_id.to_string()));
+    }
+
+    // A preview link opens the draft by id too, without shared caching
+    let token = sign_preview_token(SECRET, draft_id, Utc::now() + Duration::hours(1));
+    let uri = format!("/posts/{}?preview_token={}", draft_id, token);
+    let response = send(&app, "GET", &uri).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "private, no-store"
+    );
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::put,
+    Router,
+};
+use portfolio_backend::{
+    handlers::post::{update_post, update_published_status, PostState},
+    repositories::PostRepository,
+    services::blog_service::BlogService,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// Long enough to pass both post validation and the publish readiness check
+const CONTENT: &str = "Content that is comfortably long enough to pass post validation and also the minimum length required for publishing";
+
+fn update_request(id: Uuid, title: &str, version: i32) -> Request<Body> {
+    let payload = json!({
+        "title": title,
+        "slug": "versioned-post",
+        "content": CONTENT,
+        "category": "Programming",
+        "tags": [],
+        "version": version
+    });
+
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/posts/{}", id))
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+        .extension(common::admin_claims(Uuid::new_v4()))
+        .body(Body::from(payload.to_string()))
+        .unwrap()
+}
+
+fn publish_request(id: Uuid) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/posts/{}/publish", id))
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(common::admin_claims(Uuid::new_v4()))
+        .body(Body::from(json!({ "published": true }).to_string()))
+        .unwrap()
+}
+
+async fn insert_post(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO posts (title, slug, content, category) VALUES ('Versioned post', 'versioned-post', $1, 'Programming') RETURNING id",
+    )
+    .bind(CONTENT)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+fn app(pool: &PgPool) -> Router {
+    Router::new()
+        .route("/posts/:id", put(update_post))
+        .route("/posts/:id/publish", put(update_published_status))
+        .with_state(PostState {
+            blog_service: Arc::new(BlogService::new(
+                Arc::new(PostRepository::new(pool.clone())),
+                None,
+                "test-secret".to_string(),
+            )),
+            audit_log_service: common::audit_log_service(pool),
+        })
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_stale_post_update_is_rejected(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let app = app(&pool);
+
+    // Both admins read version 1; the first save wins
+    let response = app
+        .clone()
+        .oneshot(update_request(post_id, "First edit", 1))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(update_request(post_id, "Second edit", 1))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let (title, version): (String, i32) =
+        sqlx::query_as("SELECT title, version FROM posts WHERE id = $1")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(title, "First edit");
+    assert_eq!(version, 2);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_stale_update_after_publish_is_rejected(pool: PgPool) {
+    let post_id = insert_post(&pool).await;
+    let app = app(&pool);
+
+    // The editor loaded version 1, then someone published the post
+    let response = app.clone().oneshot(publish_request(post_id)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(update_request(post_id, "Stale edit", 1))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let (title, published, version): (String, bool, i32) =
+        sqlx::query_as("SELECT title, published, version FROM posts WHERE id = $1")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(title, "Versioned post");
+    assert!(published);
+    assert_eq!(version, 2);
+}
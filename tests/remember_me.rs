@@ -0,0 +1,107 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use portfolio_backend::{
+    handlers::auth::{login, AuthState},
+    utils::config::CookieConfig,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+const REFRESH_TOKEN_EXPIRY: i64 = 86400;
+
+fn app(pool: PgPool) -> Router {
+    let auth_service = common::auth_service_with_expiry(&pool, 3600, REFRESH_TOKEN_EXPIRY);
+
+    Router::new()
+        .route("/login", post(login))
+        .with_state(AuthState {
+            auth_service,
+            audit_log_service: common::audit_log_service(&pool),
+            rate_limiter: None,
+            cookie_config: CookieConfig::default(),
+        })
+}
+
+// Logs in and returns the Max-Age of the refresh token cookie
+async fn refresh_cookie_max_age(pool: &PgPool, body: Value) -> i64 {
+    let response = app(pool.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let cookie = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|cookie| cookie.to_str().unwrap())
+        .find(|cookie| cookie.starts_with("refresh_token="))
+        .unwrap()
+        .to_string();
+
+    cookie
+        .split("; ")
+        .find_map(|attribute| attribute.strip_prefix("Max-Age="))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_remember_me_extends_refresh_cookie(pool: PgPool) {
+    common::insert_user_with_password(&pool, "remember_admin", "correct-horse-battery").await;
+
+    let standard = refresh_cookie_max_age(
+        &pool,
+        json!({ "username": "remember_admin", "password": "correct-horse-battery" }),
+    )
+    .await;
+    let remembered = refresh_cookie_max_age(
+        &pool,
+        json!({
+            "username": "remember_admin",
+            "password": "correct-horse-battery",
+            "remember_me": true
+        }),
+    )
+    .await;
+
+    // Allow a second of drift between issuing the token and building the cookie
+    assert!((REFRESH_TOKEN_EXPIRY - 1..=REFRESH_TOKEN_EXPIRY).contains(&standard));
+    assert!((30 * 86400 - 1..=30 * 86400).contains(&remembered));
+
+    let remembered_sessions: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM refresh_tokens WHERE remember_me")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(remembered_sessions, 1);
+
+    let details: Vec<String> = sqlx::query_scalar(
+        "SELECT details FROM audit_logs WHERE action = 'login' ORDER BY created_at",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(details.len(), 2);
+    assert!(details[0].ends_with("(standard session)"));
+    assert!(details[1].ends_with("(remembered session)"));
+}
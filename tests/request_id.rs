@@ -0,0 +1,81 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Extension, Router,
+};
+use portfolio_backend::{
+    middleware::security::request_id_middleware,
+    utils::{errors::AppError, request::RequestId},
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/missing",
+            get(|| async { Err::<(), _>(AppError::NotFound("Post not found".to_string())) }),
+        )
+        .route(
+            "/echo",
+            get(|Extension(request_id): Extension<RequestId>| async move { request_id.0 }),
+        )
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, String, Vec<u8>) {
+    let response = app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let header = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec();
+    (status, header, body)
+}
+
+#[tokio::test]
+async fn test_error_body_request_id_matches_header() {
+    let (status, header, body) = send(
+        Request::builder()
+            .uri("/missing")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["request_id"], header.as_str());
+    assert!(uuid::Uuid::parse_str(&header).is_ok());
+}
+
+#[tokio::test]
+async fn test_request_id_is_exposed_to_handlers() {
+    let (status, header, body) =
+        send(Request::builder().uri("/echo").body(Body::empty()).unwrap()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(String::from_utf8(body).unwrap(), header);
+}
+
+#[tokio::test]
+async fn test_upstream_request_id_round_trips() {
+    let (_, header, body) = send(
+        Request::builder()
+            .uri("/missing")
+            .header("x-request-id", "edge-42")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(header, "edge-42");
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["request_id"], "edge-42");
+}
@@ -0,0 +1,54 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::middleware::security::request_timeout_middleware;
+use serde_json::Value;
+use std::time::Duration;
+use tower::ServiceExt;
+use tower_http::timeout::TimeoutLayer;
+
+async fn slow() -> &'static str {
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    "done"
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/slow", get(slow))
+        .route("/fast", get(|| async { "done" }))
+        .layer(TimeoutLayer::new(Duration::from_millis(50)))
+        .layer(middleware::from_fn(request_timeout_middleware))
+        // Routes added after the layer, like the notification stream, are not timed
+        .route("/exempt", get(slow))
+}
+
+async fn get_path(path: &str) -> axum::response::Response {
+    app()
+        .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_slow_request_returns_504_envelope() {
+    let response = get_path("/slow").await;
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["code"], "GATEWAY_TIMEOUT");
+}
+
+#[tokio::test]
+async fn test_fast_request_is_unaffected() {
+    assert_eq!(get_path("/fast").await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_exempt_route_is_not_timed_out() {
+    assert_eq!(get_path("/exempt").await.status(), StatusCode::OK);
+}
@@ -0,0 +1,74 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    middleware::auth::require_role, models::user::ADMIN_ROLE, services::auth_service::Claims,
+};
+use serde_json::json;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// Stands in for an admin-only group; claims are attached as auth_middleware would
+fn admin_app() -> Router {
+    Router::new()
+        .route("/admin/settings", get(|| async { "settings" }))
+        .route_layer(middleware::from_fn_with_state(ADMIN_ROLE, require_role))
+}
+
+fn claims(role: &str) -> Claims {
+    Claims {
+        sub: "00000000-0000-0000-0000-000000000001".to_string(),
+        username: "someone".to_string(),
+        role: role.to_string(),
+        jti: Uuid::new_v4().to_string(),
+        exp: 0,
+        iat: 0,
+    }
+}
+
+async fn status_for(claims: Option<Claims>) -> StatusCode {
+    let mut request = Request::builder().uri("/admin/settings");
+    if let Some(claims) = claims {
+        request = request.extension(claims);
+    }
+
+    admin_app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_user_token_is_rejected_from_admin_route() {
+    assert_eq!(
+        status_for(Some(claims("user"))).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(status_for(Some(claims("admin"))).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_missing_claims_are_unauthorized() {
+    assert_eq!(status_for(None).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_tokens_without_a_role_are_treated_as_users() {
+    // Shape of a token payload issued before role and jti were added
+    let claims: Claims = serde_json::from_value(json!({
+        "sub": "00000000-0000-0000-0000-000000000001",
+        "username": "someone",
+        "exp": 0,
+        "iat": 0,
+    }))
+    .unwrap();
+
+    assert_eq!(claims.role, "user");
+    assert_eq!(claims.jti, "");
+    assert_eq!(status_for(Some(claims)).await, StatusCode::FORBIDDEN);
+}
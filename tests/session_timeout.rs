@@ -0,0 +1,65 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    middleware::auth::auth_middleware,
+    repositories::{user_repository::UserRepository, user_repository::UserRepositoryTrait},
+    services::auth_service::AuthService,
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn get_me(auth_service: AuthService, token: &str) -> StatusCode {
+    let app = Router::new()
+        .route("/me", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(
+            auth_service,
+            auth_middleware,
+        ));
+
+    app.oneshot(
+        Request::builder()
+            .uri("/me")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+// Default session timeout is 60 minutes
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_idle_session_is_rejected(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "session_user", "user").await;
+    let user_repository = UserRepository::new(pool.clone());
+    let auth_service = common::auth_service_with_expiry(&pool, 86400, 604800);
+
+    user_repository.update_last_login(user_id).await.unwrap();
+    let user = user_repository.find_by_id(user_id).await.unwrap().unwrap();
+    let (token, _) = auth_service
+        .generate_token(&user, chrono::Utc::now() + chrono::Duration::days(1))
+        .await
+        .unwrap();
+
+    assert_eq!(get_me(auth_service.clone(), &token).await, StatusCode::OK);
+
+    // Age all activity past the timeout; the token's exp is still in the future
+    sqlx::query(
+        "UPDATE users SET last_login = NOW() - INTERVAL '2 hours', last_activity_at = NOW() - INTERVAL '2 hours' WHERE id = $1",
+    )
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(get_me(auth_service, &token).await, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::{delete, get, post},
+    Router,
+};
+use portfolio_backend::{
+    handlers::auth::{list_sessions, login, revoke_other_sessions, revoke_session, AuthState},
+    utils::config::CookieConfig,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn app(pool: PgPool) -> Router {
+    let auth_service = common::auth_service(&pool);
+
+    Router::new()
+        .route("/login", post(login))
+        .route(
+            "/sessions",
+            get(list_sessions).delete(revoke_other_sessions),
+        )
+        .route("/sessions/:id", delete(revoke_session))
+        .with_state(AuthState {
+            auth_service,
+            audit_log_service: common::audit_log_service(&pool),
+            rate_limiter: None,
+            cookie_config: CookieConfig::default(),
+        })
+}
+
+// Logs in from the given browser and returns the refresh token
+async fn login_from(pool: &PgPool, user_agent: &str) -> String {
+    let response = app(pool.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::USER_AGENT, user_agent)
+                .header("x-auth-mode", "token")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .body(Body::from(
+                    json!({ "username": "session_admin", "password": "correct-horse-battery" })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    body["data"]["refresh_token"].as_str().unwrap().to_string()
+}
+
+fn session_request(method: &str, uri: &str, user_id: Uuid, refresh_token: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::COOKIE, format!("refresh_token={}", refresh_token))
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+        .extension(common::admin_claims(user_id))
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn list(pool: &PgPool, user_id: Uuid, refresh_token: &str) -> Vec<Value> {
+    let response = app(pool.clone())
+        .oneshot(session_request("GET", "/sessions", user_id, refresh_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    body["data"].as_array().unwrap().clone()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_revoke_all_but_current_session(pool: PgPool) {
+    let user_id =
+        common::insert_user_with_password(&pool, "session_admin", "correct-horse-battery").await;
+
+    let current = login_from(&pool, "Firefox on Linux").await;
+    login_from(&pool, "Safari on iPhone").await;
+    login_from(&pool, "Chrome on Windows").await;
+
+    let sessions = list(&pool, user_id, &current).await;
+    assert_eq!(sessions.len(), 3);
+    let current_sessions: Vec<_> = sessions.iter().filter(|s| s["current"] == true).collect();
+    assert_eq!(current_sessions.len(), 1);
+    assert_eq!(current_sessions[0]["user_agent"], "Firefox on Linux");
+    assert_eq!(current_sessions[0]["ip_address"], "127.0.0.1");
+
+    let response = app(pool.clone())
+        .oneshot(session_request("DELETE", "/sessions", user_id, &current))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["revoked"], 2);
+
+    let sessions = list(&pool, user_id, &current).await;
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["current"], true);
+
+    // Revoking the current session by id also clears this client's cookies
+    let uri = format!("/sessions/{}", sessions[0]["id"].as_str().unwrap());
+    let response = app(pool.clone())
+        .oneshot(session_request("DELETE", &uri, user_id, &current))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let cleared: Vec<_> = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|cookie| cookie.to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(cleared.len(), 2);
+    assert!(cleared.iter().all(|cookie| cookie.contains("Max-Age=0")));
+
+    assert!(list(&pool, user_id, &current).await.is_empty());
+
+    let response = app(pool.clone())
+        .oneshot(session_request("DELETE", &uri, user_id, &current))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
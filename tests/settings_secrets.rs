@@ -0,0 +1,95 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use portfolio_backend::{
+    handlers::admin_settings::{get_settings, update_settings},
+    models::admin_settings::{NotificationSettings, MASKED_SECRET},
+    repositories::AdminSettingsRepository,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+const SMTP_PASSWORD: &str = "smtp-password-do-not-leak";
+const TELEGRAM_TOKEN: &str = "123456:telegram-token-do-not-leak";
+
+fn app(pool: &PgPool) -> Router {
+    Router::new()
+        .route("/admin/settings", get(get_settings).put(update_settings))
+        .with_state(common::admin_settings_state(pool))
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_settings_responses_mask_secrets(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "secrets_admin", "admin").await;
+
+    let repository = AdminSettingsRepository::new(pool.clone());
+    repository
+        .update_notification_settings(
+            NotificationSettings {
+                smtp_host: Some("smtp.example.com".to_string()),
+                smtp_username: Some("admin@example.com".to_string()),
+                smtp_password: Some(SMTP_PASSWORD.to_string()),
+                telegram_bot_token: Some(TELEGRAM_TOKEN.to_string()),
+                ..NotificationSettings::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let (status, body) = send(
+        app(&pool),
+        Request::builder()
+            .uri("/admin/settings")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains(SMTP_PASSWORD));
+    assert!(!body.contains(TELEGRAM_TOKEN));
+
+    // Send the masked settings straight back with only the host changed
+    let mut notifications = serde_json::from_str::<Value>(&body).unwrap()["notifications"].clone();
+    assert_eq!(notifications["smtpPassword"], MASKED_SECRET);
+    notifications["smtpHost"] = json!("mail.example.com");
+
+    let (status, body) = send(
+        app(&pool),
+        Request::builder()
+            .method("PUT")
+            .uri("/admin/settings")
+            .header(header::CONTENT_TYPE, "application/json")
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+            .extension(common::admin_claims(user_id))
+            .body(Body::from(
+                json!({ "notifications": notifications }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains(SMTP_PASSWORD));
+
+    let stored = repository.get_all_settings().await.unwrap().notifications;
+    assert_eq!(stored.smtp_host.as_deref(), Some("mail.example.com"));
+    assert_eq!(stored.smtp_password.as_deref(), Some(SMTP_PASSWORD));
+    assert_eq!(stored.telegram_bot_token.as_deref(), Some(TELEGRAM_TOKEN));
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::{post, put},
+    Router,
+};
+use portfolio_backend::{
+    handlers::{
+        admin_settings::{reset_settings, update_settings},
+        service::{update_service, ServiceState},
+    },
+    middleware::security::proxy_headers_middleware,
+    repositories::ServiceRepository,
+    services::service_service::ServiceService,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_update_logs_full_snapshots(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "audit_admin", "admin").await;
+    let service_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO services (title, description, features, category) VALUES ('Consulting', 'Technical advice for teams', '{}', 'business') RETURNING id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = Router::new()
+        .route("/services/:id", put(update_service))
+        .with_state(ServiceState {
+            service_service: Arc::new(ServiceService::new(Arc::new(ServiceRepository::new(
+                pool.clone(),
+            )))),
+            audit_log_service: common::audit_log_service(&pool),
+        });
+
+    let payload = json!({
+        "title": "Architecture Consulting",
+        "description": "Technical advice for teams",
+        "features": [],
+        "category": "Business",
+        "version": 1
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/services/{}", service_id))
+                .header(header::CONTENT_TYPE, "application/json")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(user_id))
+                .body(Body::from(payload.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (action, old_values, new_values): (String, Value, Value) = sqlx::query_as(
+        "SELECT action, old_values, new_values FROM audit_logs WHERE resource_type = 'service' AND resource_id = $1",
+    )
+    .bind(service_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(action, "service_updated");
+    // Both sides are full snapshots, not just the changed fields
+    assert_eq!(old_values["title"], "Consulting");
+    assert_eq!(new_values["title"], "Architecture Consulting");
+    for snapshot in [&old_values, &new_values] {
+        assert_eq!(snapshot["id"], json!(service_id));
+        assert_eq!(snapshot["description"], "Technical advice for teams");
+        assert!(snapshot.get("updated_at").is_some());
+    }
+}
+
+// Records the IP audited for a settings update sent with a forwarded header, as
+// create_app layers proxy_headers_middleware around every route
+async fn audited_settings_ip(pool: &PgPool, trust_proxy_headers: bool) -> String {
+    let user_id = common::insert_user(pool, "settings_admin", "admin").await;
+
+    let app = Router::new()
+        .route("/admin/settings", put(update_settings))
+        .with_state(common::admin_settings_state(pool))
+        .layer(middleware::from_fn_with_state(
+            trust_proxy_headers,
+            proxy_headers_middleware,
+        ));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/admin/settings")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+                .header(header::USER_AGENT, "settings-test/1.0")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(user_id))
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (action, ip_address, user_agent): (String, String, String) = sqlx::query_as(
+        "SELECT action, host(ip_address), user_agent FROM audit_logs WHERE resource_type = 'settings' AND user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    assert_eq!(action, "settings_updated");
+    assert_eq!(user_agent, "settings-test/1.0");
+    ip_address
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_settings_update_records_forwarded_ip_behind_trusted_proxy(pool: PgPool) {
+    assert_eq!(audited_settings_ip(&pool, true).await, "203.0.113.7");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_settings_update_ignores_forwarded_ip_by_default(pool: PgPool) {
+    // Without a trusted proxy the header is client-supplied, so the connection wins
+    assert_eq!(audited_settings_ip(&pool, false).await, "127.0.0.1");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_settings_reset_is_audited(pool: PgPool) {
+    let user_id = common::insert_user(&pool, "reset_admin", "admin").await;
+
+    let app = Router::new()
+        .route("/admin/settings/reset", post(reset_settings))
+        .with_state(common::admin_settings_state(&pool));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/settings/reset")
+                .header(header::USER_AGENT, "settings-test/1.0")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(user_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (action, details, ip_address): (String, String, String) = sqlx::query_as(
+        "SELECT action, details, host(ip_address) FROM audit_logs WHERE resource_type = 'settings' AND user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(action, "settings_updated");
+    assert_eq!(details, "All settings reset to defaults");
+    assert_eq!(ip_address, "127.0.0.1");
+}
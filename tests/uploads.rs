@@ -0,0 +1,134 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::{delete, post},
+    Router,
+};
+use portfolio_backend::{
+    handlers::upload::{delete_file, upload_file, UploadState},
+    services::storage::LocalDiskBackend,
+};
+use serde_json::Value;
+use std::{path::PathBuf, sync::Arc};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const BOUNDARY: &str = "upload-test-boundary";
+
+fn app(directory: &PathBuf) -> Router {
+    Router::new()
+        .route("/uploads", post(upload_file))
+        .route("/uploads/*key", delete(delete_file))
+        .with_state(UploadState {
+            storage: Arc::new(LocalDiskBackend::new(directory, "/uploads")),
+            max_size: 1024,
+            allowed_types: vec!["image/png".to_string(), "image/jpeg".to_string()],
+        })
+}
+
+fn upload_request(content_type: &str, data: &[u8]) -> Request<Body> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"image\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    Request::builder()
+        .method("POST")
+        .uri("/uploads")
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn temp_directory() -> PathBuf {
+    std::env::temp_dir().join(format!("uploads-{}", Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_upload_rejects_oversized_file() {
+    let directory = temp_directory();
+
+    let (status, body) = send(app(&directory), upload_request("image/png", &[0u8; 2048])).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert!(!directory.exists());
+}
+
+#[tokio::test]
+async fn test_upload_rejects_disallowed_type() {
+    let directory = temp_directory();
+
+    let (status, body) = send(
+        app(&directory),
+        upload_request("application/x-msdownload", b"MZ"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert!(!directory.exists());
+}
+
+#[tokio::test]
+async fn test_upload_rejects_svg_even_when_configured() {
+    let directory = temp_directory();
+    let app = Router::new()
+        .route("/uploads", post(upload_file))
+        .with_state(UploadState {
+            storage: Arc::new(LocalDiskBackend::new(&directory, "/uploads")),
+            max_size: 1024,
+            allowed_types: vec!["image/svg+xml".to_string()],
+        });
+
+    let (status, body) = send(
+        app,
+        upload_request(
+            "image/svg+xml",
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"><script>alert(1)</script></svg>",
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert!(!directory.exists());
+}
+
+#[tokio::test]
+async fn test_upload_stores_allowed_file() {
+    let directory = temp_directory();
+
+    let (status, body) = send(app(&directory), upload_request("image/png", b"png-bytes")).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    let key = body["key"].as_str().unwrap();
+    assert!(key.ends_with(".png"));
+    assert_eq!(body["url"], format!("/uploads/{}", key));
+    assert_eq!(body["size"], 9);
+    assert_eq!(std::fs::read(directory.join(key)).unwrap(), b"png-bytes");
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/uploads/{}", key))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _) = send(app(&directory), request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(!directory.join(key).exists());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
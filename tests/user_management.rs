@@ -0,0 +1,127 @@
+mod common;
+
+use portfolio_backend::{
+    models::user::UserQuery,
+    repositories::{RefreshTokenRepository, UserRepository, UserRepositoryTrait},
+    services::user_service::{UserService, UserServiceTrait},
+    utils::errors::AppError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+fn user_service(pool: &PgPool) -> UserService {
+    UserService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(RefreshTokenRepository::new(pool.clone())),
+    )
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_last_active_admin_cannot_be_deactivated_or_demoted(pool: PgPool) {
+    let service = user_service(&pool);
+    let first = common::insert_user(&pool, "first_admin", "admin").await;
+    let second = common::insert_user(&pool, "second_admin", "admin").await;
+    let member = common::insert_user(&pool, "plain_user", "user").await;
+
+    // With two admins, one of them can go
+    let user = service.set_user_active(second, false).await.unwrap();
+    assert!(!user.is_active);
+
+    // The remaining admin is protected both ways
+    assert!(matches!(
+        service.set_user_active(first, false).await,
+        Err(AppError::Conflict(_))
+    ));
+    assert!(matches!(
+        service.change_user_role(first, "user".to_string()).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    // Non-admins aren't affected by the guard
+    assert!(
+        !service
+            .set_user_active(member, false)
+            .await
+            .unwrap()
+            .is_active
+    );
+
+    // Promoting someone else frees the original admin to step down
+    service
+        .change_user_role(member, "admin".to_string())
+        .await
+        .unwrap();
+    service.set_user_active(member, true).await.unwrap();
+    let user = service
+        .change_user_role(first, "user".to_string())
+        .await
+        .unwrap();
+    assert_eq!(user.role, "user");
+
+    // Deactivated accounts no longer resolve for login or token checks
+    let users = UserRepository::new(pool.clone());
+    assert!(users.find_by_id(second).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_unknown_role_is_rejected(pool: PgPool) {
+    let service = user_service(&pool);
+    let id = common::insert_user(&pool, "someone", "user").await;
+
+    assert!(matches!(
+        service.change_user_role(id, "superuser".to_string()).await,
+        Err(AppError::Validation(_))
+    ));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_concurrent_demotions_keep_one_admin(pool: PgPool) {
+    let service = user_service(&pool);
+    let first = common::insert_user(&pool, "first_admin", "admin").await;
+    let second = common::insert_user(&pool, "second_admin", "admin").await;
+
+    // Each request alone would be allowed; together only one may go through
+    let (first_result, second_result) = tokio::join!(
+        service.change_user_role(first, "user".to_string()),
+        service.set_user_active(second, false),
+    );
+    assert_eq!(
+        [first_result.is_ok(), second_result.is_ok()]
+            .iter()
+            .filter(|ok| **ok)
+            .count(),
+        1
+    );
+
+    let admins: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE role = 'admin' AND is_active = true")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(admins, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+#[ignore = "requires a Postgres database via DATABASE_URL"]
+async fn test_search_treats_wildcards_literally(pool: PgPool) {
+    let service = user_service(&pool);
+    common::insert_user(&pool, "snake_case", "user").await;
+    common::insert_user(&pool, "snakexcase", "user").await;
+
+    let search = |term: &str| UserQuery {
+        page: None,
+        limit: None,
+        role: None,
+        is_active: None,
+        search: Some(term.to_string()),
+    };
+
+    // "_" would match any character and "%" anything at all if left unescaped
+    let users = service.list_users(search("snake_")).await.unwrap();
+    assert_eq!(users.total, 1);
+    assert_eq!(users.users[0].username, "snake_case");
+    assert_eq!(service.list_users(search("%")).await.unwrap().total, 0);
+}
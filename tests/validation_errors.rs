@@ -0,0 +1,140 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::{delete, post, put},
+    Router,
+};
+use portfolio_backend::{
+    handlers::post::{bulk_delete_posts, bulk_update_featured, create_post, PostState},
+    repositories::PostRepository,
+    services::blog_service::BlogService,
+};
+use serde_json::{json, Value};
+use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceExt;
+
+// Validation fails before the services touch the database
+fn post_state() -> PostState {
+    let pool = common::unused_pool();
+
+    PostState {
+        blog_service: Arc::new(BlogService::new(
+            Arc::new(PostRepository::new(pool.clone())),
+            None,
+            "test-secret".to_string(),
+        )),
+        audit_log_service: common::audit_log_service(&pool),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_post_returns_field_keyed_errors() {
+    let state = post_state();
+    let app = Router::new()
+        .route("/posts", post(create_post))
+        .with_state(state);
+
+    let payload = json!({
+        "title": "",
+        "slug": "",
+        "content": "",
+        "category": "Programming",
+        "tags": [],
+        "featured_image": "not a url"
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/posts")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(payload.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    let error = &body["error"];
+
+    assert_eq!(error["code"], "VALIDATION_ERROR");
+    assert_eq!(
+        error["fields"]["title"],
+        json!(["Title is required and must be less than 255 characters"])
+    );
+    assert_eq!(error["fields"]["content"], json!(["Content is required"]));
+    assert_eq!(
+        error["fields"]["featured_image"],
+        json!(["Featured image must be a valid URL"])
+    );
+    assert!(error["fields"].get("category").is_none());
+}
+
+#[tokio::test]
+async fn test_empty_bulk_delete_is_rejected() {
+    let app = Router::new()
+        .route("/posts/bulk", delete(bulk_delete_posts))
+        .with_state(post_state());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/posts/bulk")
+                .header(header::CONTENT_TYPE, "application/json")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(uuid::Uuid::new_v4()))
+                .body(Body::from(json!({ "ids": [] }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["error"]["fields"]["ids"],
+        json!(["Between 1 and 100 post IDs are required"])
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_bulk_featured_is_rejected() {
+    let app = Router::new()
+        .route("/posts/bulk-featured", put(bulk_update_featured))
+        .with_state(post_state());
+
+    let ids: Vec<uuid::Uuid> = (0..101).map(|_| uuid::Uuid::new_v4()).collect();
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/posts/bulk-featured")
+                .header(header::CONTENT_TYPE, "application/json")
+                .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                .extension(common::admin_claims(uuid::Uuid::new_v4()))
+                .body(Body::from(
+                    json!({ "ids": ids, "featured": true }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["error"]["fields"]["ids"],
+        json!(["Between 1 and 100 post IDs are required"])
+    );
+}